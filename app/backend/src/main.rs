@@ -4,6 +4,7 @@ use std::sync::Arc;
 
 use auth_app::{app as auth_app, AuthService, PostgresStore};
 use auth_password_app::{app as auth_password_app, AuthPasswordService};
+use auth_siwe_app::{app as auth_siwe_app, AuthSiweService};
 use axum::{routing::get, Extension, Json, Router};
 use axum_extra::extract::cookie::{Key, SameSite};
 use base64::Engine;
@@ -13,7 +14,12 @@ use log::info;
 use serde_json::json;
 use tower_http::cors::CorsLayer;
 use tower_sessions::{Expiry, SessionManagerLayer};
-use wallet_app::{app as wallet_app, WalletService};
+use wallet_app::{
+    app as wallet_app, BalanceService, ChainWatcher, ContactService, ContractService,
+    CustodyService, EnsService, FileKeyVault, KeyVault, NotificationService, PaymentRequestService,
+    PolicyService, RelayService, ScheduledPaymentService, SystemClock, TransactionService,
+    TreasuryService, WalletEvents, WalletService,
+};
 
 async fn healthcheck() -> Json<serde_json::Value> {
     Json(json!({
@@ -72,17 +78,175 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
 
     let auth_service = Arc::new(AuthService::new(db.clone()));
     let auth_password_service = Arc::new(AuthPasswordService::new(db.clone()));
+    // Sign-in messages have to name the site's domain and the wallet's chain
+    let siwe_domain = std::env::var("SIWE_DOMAIN").unwrap_or_else(|_| "localhost:8080".into());
+    let siwe_chain_id = std::env::var("SIWE_CHAIN_ID")
+        .ok()
+        .and_then(|chain_id| chain_id.parse().ok())
+        .unwrap_or(31337);
+    let siwe_nonce_lifetime = std::env::var("SIWE_NONCE_LIFETIME_MINUTES")
+        .ok()
+        .and_then(|minutes| minutes.parse().ok())
+        .unwrap_or(10);
+    let auth_siwe_service = Arc::new(AuthSiweService::new(
+        db.clone(),
+        siwe_domain,
+        siwe_chain_id,
+        chrono::Duration::minutes(siwe_nonce_lifetime),
+    ));
+    let _nonce_purge_task = tokio::task::spawn(
+        auth_siwe_service
+            .clone()
+            .continuously_purge_expired_nonces(tokio::time::Duration::from_secs(60)),
+    );
     let wallet_service = Arc::new(WalletService::new(db.clone()));
+    let contact_service = Arc::new(ContactService::new(db.clone()));
+    let contract_service = Arc::new(ContractService::new(db.clone()));
+    let payment_request_service = Arc::new(PaymentRequestService::new(db.clone()));
+    let transaction_service = Arc::new(TransactionService::new(db.clone()));
+    let policy_cooling_off_hours = std::env::var("WALLET_POLICY_COOLING_OFF_HOURS")
+        .ok()
+        .and_then(|hours| hours.parse().ok())
+        .unwrap_or(24);
+    let policy_service = Arc::new(PolicyService::new(
+        db.clone(),
+        chrono::Duration::hours(policy_cooling_off_hours),
+    ));
+
+    let rpc_url = std::env::var("ETH_RPC_URL").unwrap_or_else(|_| "http://localhost:8545".into());
+    let balance_sync_interval = std::env::var("BALANCE_SYNC_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(30);
+    let balance_snapshot_interval = std::env::var("BALANCE_SNAPSHOT_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(3600);
+    let block_poll_interval = std::env::var("BLOCK_POLL_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(5);
+    let scheduled_payment_interval = std::env::var("SCHEDULED_PAYMENT_INTERVAL_SECONDS")
+        .ok()
+        .and_then(|seconds| seconds.parse().ok())
+        .unwrap_or(60);
+    let ens_registry = std::env::var("ENS_REGISTRY_ADDRESS").ok().map(|address| {
+        address
+            .parse()
+            .expect("Failed to parse ENS_REGISTRY_ADDRESS")
+    });
+    let ens_service =
+        Arc::new(EnsService::new(&rpc_url, ens_registry).expect("Failed to parse ETH_RPC_URL"));
+    let wallet_events = Arc::new(WalletEvents::new());
+    let notification_service = Arc::new(NotificationService::new(db.clone()));
+    let balance_service = Arc::new(
+        BalanceService::new(db.clone(), &rpc_url, wallet_events.clone())
+            .expect("Failed to parse ETH_RPC_URL"),
+    );
+    let _balance_sync_task = tokio::task::spawn(
+        balance_service
+            .clone()
+            .continuously_sync(tokio::time::Duration::from_secs(balance_sync_interval)),
+    );
+    let _balance_snapshot_task = tokio::task::spawn(
+        balance_service
+            .clone()
+            .continuously_snapshot(tokio::time::Duration::from_secs(balance_snapshot_interval)),
+    );
+    let chain_watcher = Arc::new(
+        ChainWatcher::new(
+            db.clone(),
+            &rpc_url,
+            balance_service.clone(),
+            notification_service.clone(),
+            payment_request_service.clone(),
+            transaction_service.clone(),
+            wallet_events.clone(),
+        )
+        .expect("Failed to parse ETH_RPC_URL"),
+    );
+    let relay_service = Arc::new(
+        RelayService::new(
+            &rpc_url,
+            chain_watcher.clone(),
+            policy_service.clone(),
+            transaction_service.clone(),
+            wallet_events.clone(),
+        )
+        .expect("Failed to parse ETH_RPC_URL"),
+    );
+    // Custody is only offered when a master key is configured
+    let key_vault = std::env::var("WALLET_CUSTODY_MASTER_KEY_FILE")
+        .ok()
+        .map(|path| {
+            Arc::new(
+                FileKeyVault::from_file(&path)
+                    .expect("Failed to read WALLET_CUSTODY_MASTER_KEY_FILE"),
+            ) as Arc<dyn KeyVault>
+        });
+    let custody_service = Arc::new(
+        CustodyService::new(
+            db.clone(),
+            key_vault,
+            &rpc_url,
+            chain_watcher.clone(),
+            relay_service.clone(),
+        )
+        .expect("Failed to parse ETH_RPC_URL"),
+    );
+    let treasury_service = Arc::new(
+        TreasuryService::new(db.clone(), &rpc_url, custody_service.clone())
+            .expect("Failed to parse ETH_RPC_URL"),
+    );
+    let _treasury_reconcile_task = tokio::task::spawn(
+        treasury_service
+            .clone()
+            .continuously_reconcile(tokio::time::Duration::from_secs(60)),
+    );
+    let scheduled_payment_service = Arc::new(
+        ScheduledPaymentService::new(
+            db.clone(),
+            &rpc_url,
+            custody_service.clone(),
+            wallet_events.clone(),
+            Arc::new(SystemClock),
+        )
+        .expect("Failed to parse ETH_RPC_URL"),
+    );
+    let _scheduled_payment_task = tokio::task::spawn(
+        scheduled_payment_service
+            .clone()
+            .continuously_run(tokio::time::Duration::from_secs(scheduled_payment_interval)),
+    );
+    let _chain_watch_task = tokio::task::spawn(
+        chain_watcher.continuously_watch(tokio::time::Duration::from_secs(block_poll_interval)),
+    );
+
     let app = Router::new()
         .route("/api/v1/health", get(healthcheck))
         .nest("/api", auth_app())
         .nest("/api", auth_password_app())
+        .nest("/api", auth_siwe_app())
         .merge(wallet_app())
         .layer(CorsLayer::very_permissive())
         .layer(session_layer)
         .layer(Extension(auth_service))
         .layer(Extension(auth_password_service))
+        .layer(Extension(auth_siwe_service))
         .layer(Extension(wallet_service))
+        .layer(Extension(contact_service))
+        .layer(Extension(contract_service))
+        .layer(Extension(payment_request_service))
+        .layer(Extension(transaction_service))
+        .layer(Extension(policy_service))
+        .layer(Extension(relay_service))
+        .layer(Extension(custody_service))
+        .layer(Extension(treasury_service))
+        .layer(Extension(scheduled_payment_service))
+        .layer(Extension(ens_service))
+        .layer(Extension(balance_service))
+        .layer(Extension(wallet_events))
+        .layer(Extension(notification_service))
         .layer(Extension(db));
 
     let addr = "0.0.0.0:8000";
@@ -1,6 +1,9 @@
 use app_config::Logo;
+use auth_screens::store::AuthStore;
+use wallet_screens::components::notifications::PaymentToasts;
 use wonopui::*;
 use yew::prelude::*;
+use yewdux::prelude::*;
 
 use crate::{
     components::{topbar::AppTopbar, usermenu::UserMenu},
@@ -42,6 +45,9 @@ pub fn sidebar_header_with_logo() -> Html {
 
 #[function_component(PrimaryMainMenu)]
 pub fn primary_main_menu() -> Html {
+    let (auth, _) = use_store::<AuthStore>();
+    let is_staff = auth.has_role("staff") || auth.has_role("admin");
+
     html! {
         <SidebarColumn
             header={html!{
@@ -73,6 +79,33 @@ pub fn primary_main_menu() -> Html {
                           <path stroke-linecap="round" stroke-linejoin="round" d="M3.75 12h16.5m-16.5 3.75h16.5M3.75 19.5h16.5M5.625 4.5h12.75a1.875 1.875 0 0 1 0 3.75H5.625a1.875 1.875 0 0 1 0-3.75Z" />
                     </svg>
                 }} />
+                <SidebarLink<AppRoute> to={AppRoute::Contacts} label={"Contacts"} icon={html!{
+                    <svg class={classes!("w-5", "h-5")} fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 20h5v-2a3 3 0 00-5.356-1.857M17 20H7m10 0v-2c0-.656-.126-1.283-.356-1.857M7 20H2v-2a3 3 0 015.356-1.857M7 20v-2c0-.656.126-1.283.356-1.857m0 0a5.002 5.002 0 019.288 0M15 7a3 3 0 11-6 0 3 3 0 016 0zm6 3a2 2 0 11-4 0 2 2 0 014 0zM7 10a2 2 0 11-4 0 2 2 0 014 0z"/>
+                    </svg>
+                }} />
+                <SidebarLink<AppRoute> to={AppRoute::Contracts} label={"Contracts"} icon={html!{
+                    <svg class={classes!("w-5", "h-5")} fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M10 20l4-16m4 4l4 4-4 4M6 16l-4-4 4-4"/>
+                    </svg>
+                }} />
+                <SidebarLink<AppRoute> to={AppRoute::Scheduled} label={"Scheduled"} icon={html!{
+                    <svg class={classes!("w-5", "h-5")} fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M12 8v4l3 3m6-3a9 9 0 11-18 0 9 9 0 0118 0z"/>
+                    </svg>
+                }} />
+                <SidebarLink<AppRoute> to={AppRoute::Sign} label={"Sign"} icon={html!{
+                    <svg class={classes!("w-5", "h-5")} fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                        <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M15.232 5.232l3.536 3.536m-2.036-5.036a2.5 2.5 0 113.536 3.536L6.5 21.036H3v-3.572L16.732 3.732z"/>
+                    </svg>
+                }} />
+                if is_staff {
+                    <SidebarLink<AppRoute> to={AppRoute::Treasury} label={"Treasury"} icon={html!{
+                        <svg class={classes!("w-5", "h-5")} fill="none" stroke="currentColor" viewBox="0 0 24 24">
+                            <path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M8 14v3m4-3v3m4-3v3M3 21h18M3 10h18M3 7l9-4 9 4M4 10h16v11H4V10z"/>
+                        </svg>
+                    }} />
+                }
             </SidebarMenu>
         </SidebarColumn>
     }
@@ -142,6 +175,7 @@ pub fn app_layout(props: &LayoutProps) -> Html {
 
             direction={LayoutDirection::Vertical}
         >
+            <PaymentToasts />
             <Container variant={ContainerVariant::Responsive}>
                 {for props.children.iter()}
             </Container>
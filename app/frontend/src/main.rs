@@ -6,11 +6,17 @@ mod components {
 
 use app_config::AppRoute;
 use auth_password_screens::components::{Login, Signup, ThankYouForSigningUp};
-use auth_screens::{components::LoginRequired, store::AuthStore};
+use auth_screens::{
+    components::{LoginRequired, PrivilegesRequired},
+    store::AuthStore,
+};
+use auth_siwe_screens::SiweLogin;
 use components::layout::AppLayout;
 use wallet_screens::{
     components::{
-        dashboard::Dashboard, setup::Setup, transactions::Transactions, transfer::Transfer,
+        contacts::Contacts, contracts::Contracts, dashboard::Dashboard,
+        scheduled::ScheduledPayments, setup::Setup, sign::Sign, transactions::Transactions,
+        transfer::Transfer, treasury::Treasury,
     },
     store::WalletStore,
 };
@@ -60,7 +66,51 @@ fn app() -> Html {
                         </AppLayout>
                     </LoginRequired<AppRoute>>
                 },
-                AppRoute::Login => html! { <Login /> },
+                AppRoute::Contacts => html! {
+                    <LoginRequired<AppRoute> fallback={AppRoute::Login}>
+                        <AppLayout>
+                            <Contacts />
+                        </AppLayout>
+                    </LoginRequired<AppRoute>>
+                },
+                AppRoute::Contracts => html! {
+                    <LoginRequired<AppRoute> fallback={AppRoute::Login}>
+                        <AppLayout>
+                            <Contracts />
+                        </AppLayout>
+                    </LoginRequired<AppRoute>>
+                },
+                AppRoute::Scheduled => html! {
+                    <LoginRequired<AppRoute> fallback={AppRoute::Login}>
+                        <AppLayout>
+                            <ScheduledPayments />
+                        </AppLayout>
+                    </LoginRequired<AppRoute>>
+                },
+                AppRoute::Treasury => html! {
+                    <LoginRequired<AppRoute> fallback={AppRoute::Login}>
+                        <PrivilegesRequired<AppRoute>
+                            accepted_roles={vec!["staff".to_string(), "admin".to_string()]}
+                            fallback={AppRoute::Login}
+                        >
+                            <AppLayout>
+                                <Treasury is_admin={auth.has_role("admin")} />
+                            </AppLayout>
+                        </PrivilegesRequired<AppRoute>>
+                    </LoginRequired<AppRoute>>
+                },
+                AppRoute::Sign => html! {
+                    <LoginRequired<AppRoute> fallback={AppRoute::Login}>
+                        <AppLayout>
+                            <Sign />
+                        </AppLayout>
+                    </LoginRequired<AppRoute>>
+                },
+                AppRoute::Login => html! {
+                    <Login>
+                        <SiweLogin />
+                    </Login>
+                },
                 AppRoute::Signup => html! { <Signup /> },
                 AppRoute::ThankYouForSigningUp => {
                     html! { <ThankYouForSigningUp /> }
@@ -123,6 +123,60 @@ impl ErrorResponse {
         }
     }
 
+    #[cfg(feature = "backend")]
+    pub fn conflict(message: &str) -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: message.to_string(),
+            status_code: StatusCode::CONFLICT,
+        }
+    }
+
+    #[cfg(not(feature = "backend"))]
+    pub fn conflict(message: &str) -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: message.to_string(),
+            status_code: 409,
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    pub fn forbidden(message: &str) -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: message.to_string(),
+            status_code: StatusCode::FORBIDDEN,
+        }
+    }
+
+    #[cfg(not(feature = "backend"))]
+    pub fn forbidden(message: &str) -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: message.to_string(),
+            status_code: 403,
+        }
+    }
+
+    #[cfg(feature = "backend")]
+    pub fn reauthentication_required() -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: "Please log in again to confirm this change.".to_string(),
+            status_code: StatusCode::UNAUTHORIZED,
+        }
+    }
+
+    #[cfg(not(feature = "backend"))]
+    pub fn reauthentication_required() -> Self {
+        Self {
+            status: "fail".to_string(),
+            message: "Please log in again to confirm this change.".to_string(),
+            status_code: 401,
+        }
+    }
+
     #[cfg(feature = "backend")]
     pub fn timeout(message: &str) -> Self {
         Self {
@@ -23,8 +23,21 @@ pub enum AppRoute {
     Dashboard,
     #[at("/transfer")]
     Transfer,
+    /// Filters and the page are kept in the query string, see
+    /// `wallet_api::TransactionQuery`.
     #[at("/transactions")]
     Transactions,
+    #[at("/contacts")]
+    Contacts,
+    #[at("/contracts")]
+    Contracts,
+    #[at("/scheduled")]
+    Scheduled,
+    /// Staff only.
+    #[at("/treasury")]
+    Treasury,
+    #[at("/sign")]
+    Sign,
     #[at("/signup")]
     Signup,
     #[at("/login")]
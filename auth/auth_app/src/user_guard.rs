@@ -7,6 +7,7 @@ use axum::{
     response::{IntoResponse, Response},
     Json,
 };
+use chrono::{DateTime, Duration, Utc};
 use log::info;
 use serde::{Deserialize, Serialize};
 use tower_sessions::Session;
@@ -16,6 +17,7 @@ use crate::user_session::UserSession;
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UserAuthenticatedGuard {
     pub user: User,
+    pub login_time: DateTime<Utc>,
 }
 
 #[async_trait]
@@ -47,7 +49,10 @@ where
         info!("user_session: {:?}", user_session);
         match user_session {
             Some(user_session) => match user_session.user {
-                Some(user) => Ok(UserAuthenticatedGuard { user }),
+                Some(user) => Ok(UserAuthenticatedGuard {
+                    user,
+                    login_time: user_session.login_time,
+                }),
                 None => {
                     info!("UserAuthenticatedGuard::from_request_parts: user_session.user is None");
                     Err((
@@ -92,6 +97,16 @@ impl UserAuthenticatedGuard {
         }
     }
 
+    /// Guards sensitive operations by requiring that the user logged in within
+    /// `max_age`, so a stolen or long-lived session alone is not enough.
+    pub fn require_recent_login(&self, max_age: Duration) -> Result<(), Response> {
+        if Utc::now() - self.login_time <= max_age {
+            Ok(())
+        } else {
+            Err(ErrorResponse::reauthentication_required().into_response())
+        }
+    }
+
     pub fn require_staff(&self) -> Result<(), Response> {
         println!("UserAuthenticatedGuard::require_staff");
         println!(
@@ -108,4 +123,16 @@ impl UserAuthenticatedGuard {
                 .into_response())
         }
     }
+
+    pub fn require_admin(&self) -> Result<(), Response> {
+        if self.user.role == "admin" {
+            Ok(())
+        } else {
+            Err((
+                StatusCode::FORBIDDEN,
+                Json(ErrorResponse::insufficient_permissions()),
+            )
+                .into_response())
+        }
+    }
 }
@@ -75,8 +75,15 @@ where
     });
 }
 
+#[derive(Properties, PartialEq)]
+pub struct LoginProps {
+    /// Other ways to log in, shown below the password form.
+    #[prop_or_default]
+    pub children: Children,
+}
+
 #[function_component(Login)]
-pub fn login() -> Html {
+pub fn login(props: &LoginProps) -> Html {
     let (auth, dispatch) = use_store::<AuthStore>();
     let navigator = use_navigator().unwrap();
     let generic_error = use_state(|| None::<String>);
@@ -131,6 +138,8 @@ pub fn login() -> Html {
                     error={(*generic_error).clone()}
                 />
 
+                { props.children.clone() }
+
                 <div class="px-8 pb-8 pt-2 text-center">
                     <p class="text-zinc-600 dark:text-zinc-400">
                         { "Don't have a wallet? " }
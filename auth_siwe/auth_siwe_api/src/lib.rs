@@ -0,0 +1,43 @@
+//! Types for Sign-In With Ethereum (EIP-4361).
+
+mod message;
+
+use auth_api::User;
+use chrono::{DateTime, Utc};
+pub use message::{SiweMessage, SiweMessageError};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+/// A nonce to sign in with, and the domain and chain the signed message has
+/// to name for the server to accept it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SiweNonce {
+    pub nonce: String,
+    pub domain: String,
+    pub chain_id: u64,
+    pub expires_at: DateTime<Utc>,
+}
+
+/// A signed EIP-4361 message, used both to log in and to link an address.
+#[derive(Validate, Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSiweSignIn {
+    #[validate(length(min = 1, max = 4096, message = "Message is invalid"))]
+    pub message: String,
+    #[validate(length(min = 130, max = 132, message = "Signature is invalid"))]
+    pub signature: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthSiweLoginResponse {
+    pub status: String,
+    pub data: User,
+}
+
+/// An address that can be used to sign in to a user's account.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct SiweAddress {
+    pub address: String,
+    pub user_id: uuid::Uuid,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,171 @@
+//! EIP-4361 messages, in the text form wallets show and sign.
+
+use std::{fmt, str::FromStr};
+
+use chrono::{DateTime, SecondsFormat, Utc};
+
+const PREAMBLE: &str = " wants you to sign in with your Ethereum account:";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum SiweMessageError {
+    #[error("Message is missing {0}")]
+    Missing(&'static str),
+    #[error("Invalid {0} in message")]
+    Invalid(&'static str),
+    #[error("Unexpected line in message: {0}")]
+    UnexpectedLine(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SiweMessage {
+    /// Authority of the site asking for the signature, e.g. `example.com`.
+    pub domain: String,
+    /// EIP-55 checksummed address of the signer.
+    pub address: String,
+    pub statement: Option<String>,
+    pub uri: String,
+    pub version: String,
+    pub chain_id: u64,
+    pub nonce: String,
+    pub issued_at: DateTime<Utc>,
+    pub expiration_time: Option<DateTime<Utc>>,
+    pub not_before: Option<DateTime<Utc>>,
+    pub request_id: Option<String>,
+    pub resources: Vec<String>,
+}
+
+impl SiweMessage {
+    pub const VERSION: &'static str = "1";
+
+    /// Whether the message may be used at `now`.
+    pub fn is_valid_at(&self, now: DateTime<Utc>) -> bool {
+        self.expiration_time.is_none_or(|expiry| now < expiry)
+            && self.not_before.is_none_or(|not_before| now >= not_before)
+    }
+}
+
+fn timestamp(time: &DateTime<Utc>) -> String {
+    time.to_rfc3339_opts(SecondsFormat::Millis, true)
+}
+
+fn parse_timestamp(value: &str, field: &'static str) -> Result<DateTime<Utc>, SiweMessageError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc))
+        .map_err(|_| SiweMessageError::Invalid(field))
+}
+
+impl fmt::Display for SiweMessage {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}{}", self.domain, PREAMBLE)?;
+        writeln!(f, "{}", self.address)?;
+        writeln!(f)?;
+        if let Some(statement) = &self.statement {
+            writeln!(f, "{}", statement)?;
+        }
+        writeln!(f)?;
+        writeln!(f, "URI: {}", self.uri)?;
+        writeln!(f, "Version: {}", self.version)?;
+        writeln!(f, "Chain ID: {}", self.chain_id)?;
+        writeln!(f, "Nonce: {}", self.nonce)?;
+        write!(f, "Issued At: {}", timestamp(&self.issued_at))?;
+        if let Some(expiration_time) = &self.expiration_time {
+            write!(f, "\nExpiration Time: {}", timestamp(expiration_time))?;
+        }
+        if let Some(not_before) = &self.not_before {
+            write!(f, "\nNot Before: {}", timestamp(not_before))?;
+        }
+        if let Some(request_id) = &self.request_id {
+            write!(f, "\nRequest ID: {}", request_id)?;
+        }
+        if !self.resources.is_empty() {
+            write!(f, "\nResources:")?;
+            for resource in &self.resources {
+                write!(f, "\n- {}", resource)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for SiweMessage {
+    type Err = SiweMessageError;
+
+    fn from_str(message: &str) -> Result<Self, Self::Err> {
+        let mut lines = message.split('\n').peekable();
+
+        let domain = lines
+            .next()
+            .and_then(|line| line.strip_suffix(PREAMBLE))
+            .ok_or(SiweMessageError::Missing("domain"))?
+            .to_string();
+        let address = lines
+            .next()
+            .filter(|address| address.starts_with("0x") && address.len() == 42)
+            .ok_or(SiweMessageError::Invalid("address"))?
+            .to_string();
+        if lines.next() != Some("") {
+            return Err(SiweMessageError::Missing("blank line after address"));
+        }
+        let statement = match lines.next() {
+            Some("") => None,
+            Some(statement) if !statement.starts_with("URI: ") => {
+                if lines.next() != Some("") {
+                    return Err(SiweMessageError::Missing("blank line after statement"));
+                }
+                Some(statement.to_string())
+            }
+            _ => return Err(SiweMessageError::Missing("blank line before URI")),
+        };
+
+        let mut field = |prefix: &str| -> Option<String> {
+            let value = lines.peek()?.strip_prefix(prefix)?.to_string();
+            lines.next();
+            Some(value)
+        };
+        let uri = field("URI: ").ok_or(SiweMessageError::Missing("URI"))?;
+        let version = field("Version: ").ok_or(SiweMessageError::Missing("version"))?;
+        let chain_id = field("Chain ID: ")
+            .ok_or(SiweMessageError::Missing("chain ID"))?
+            .parse()
+            .map_err(|_| SiweMessageError::Invalid("chain ID"))?;
+        let nonce = field("Nonce: ").ok_or(SiweMessageError::Missing("nonce"))?;
+        if nonce.len() < 8 || !nonce.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(SiweMessageError::Invalid("nonce"));
+        }
+        let issued_at = parse_timestamp(
+            &field("Issued At: ").ok_or(SiweMessageError::Missing("issued at"))?,
+            "issued at",
+        )?;
+        let expiration_time = field("Expiration Time: ")
+            .map(|value| parse_timestamp(&value, "expiration time"))
+            .transpose()?;
+        let not_before = field("Not Before: ")
+            .map(|value| parse_timestamp(&value, "not before"))
+            .transpose()?;
+        let request_id = field("Request ID: ");
+        let mut resources = Vec::new();
+        if field("Resources:").is_some() {
+            while let Some(resource) = field("- ") {
+                resources.push(resource);
+            }
+        }
+        if let Some(line) = lines.next() {
+            return Err(SiweMessageError::UnexpectedLine(line.to_string()));
+        }
+
+        Ok(Self {
+            domain,
+            address,
+            statement,
+            uri,
+            version,
+            chain_id,
+            nonce,
+            issued_at,
+            expiration_time,
+            not_before,
+            request_id,
+            resources,
+        })
+    }
+}
@@ -0,0 +1,110 @@
+use std::sync::Arc;
+
+use app_config::ErrorResponse;
+use auth_app::{AuthService, UserAuthenticatedGuard};
+use auth_siwe_api::{AuthSiweLoginResponse, AuthSiweSignIn, SiweAddress, SiweNonce};
+use axum::{
+    extract::Path,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::{delete, get, post},
+    Extension, Json, Router,
+};
+use chrono::Duration;
+use tower_sessions::Session;
+use validator::Validate;
+
+use crate::AuthSiweService;
+
+/// Linking an address adds a way into the account, so it needs a fresh login.
+const LINK_MAX_LOGIN_AGE_MINUTES: i64 = 5;
+
+async fn nonce_handler(
+    Extension(siwe_service): Extension<Arc<AuthSiweService>>,
+) -> Result<Json<SiweNonce>, ErrorResponse> {
+    Ok(Json(siwe_service.issue_nonce().await?))
+}
+
+async fn login_siwe_handler(
+    Extension(auth_service): Extension<Arc<AuthService>>,
+    Extension(siwe_service): Extension<Arc<AuthSiweService>>,
+    session: Session,
+    Json(body): Json<AuthSiweSignIn>,
+) -> Result<Json<AuthSiweLoginResponse>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in login_siwe_handler: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    let address = siwe_service
+        .verify(&body)
+        .await
+        .map_err(|e| e.into_response())?;
+    let user_id = siwe_service
+        .user_for_address(&address)
+        .await
+        .map_err(|e| e.into_response())?;
+    let user = auth_service
+        .login(user_id, &session)
+        .await
+        .map_err(|e| e.into_response())?;
+
+    Ok(Json(AuthSiweLoginResponse {
+        status: "success".to_string(),
+        data: user,
+    }))
+}
+
+async fn list_addresses_handler(
+    Extension(siwe_service): Extension<Arc<AuthSiweService>>,
+    guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<SiweAddress>>, ErrorResponse> {
+    Ok(Json(siwe_service.addresses(guard.user.id).await?))
+}
+
+async fn link_address_handler(
+    Extension(siwe_service): Extension<Arc<AuthSiweService>>,
+    guard: UserAuthenticatedGuard,
+    Json(body): Json<AuthSiweSignIn>,
+) -> Result<Json<SiweAddress>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in link_address_handler: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+    guard.require_recent_login(Duration::minutes(LINK_MAX_LOGIN_AGE_MINUTES))?;
+
+    let address = siwe_service
+        .verify(&body)
+        .await
+        .map_err(|e| e.into_response())?;
+    siwe_service
+        .link_address(guard.user.id, &address)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn unlink_address_handler(
+    Extension(siwe_service): Extension<Arc<AuthSiweService>>,
+    guard: UserAuthenticatedGuard,
+    Path(address): Path<String>,
+) -> Result<StatusCode, ErrorResponse> {
+    siwe_service
+        .unlink_address(guard.user.id, &address)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+}
+
+pub fn app() -> Router {
+    Router::new()
+        .route("/v1/auth/siwe/nonce", post(nonce_handler))
+        .route("/v1/auth/siwe/login", post(login_siwe_handler))
+        .route(
+            "/v1/auth/siwe/addresses",
+            get(list_addresses_handler).post(link_address_handler),
+        )
+        .route(
+            "/v1/auth/siwe/addresses/:address",
+            delete(unlink_address_handler),
+        )
+}
@@ -0,0 +1,8 @@
+//! Sign-In With Ethereum: password-less login for users who linked an
+//! address to their account.
+
+mod handlers;
+mod services;
+
+pub use handlers::app;
+pub use services::AuthSiweService;
@@ -0,0 +1,219 @@
+use std::{str::FromStr, sync::Arc};
+
+use app_config::ErrorResponse;
+use auth_siwe_api::{AuthSiweSignIn, SiweAddress, SiweMessage, SiweNonce};
+use chrono::{DateTime, Duration, Utc};
+use ethers::{
+    types::{Address, Signature},
+    utils::to_checksum,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+
+/// Most nonces that may be valid at once. Anyone can ask for a nonce, so this
+/// bounds how large the table can grow between purges.
+const MAX_OUTSTANDING_NONCES: i64 = 10_000;
+
+/// How far the wallet's clock may be ahead of or behind the server's when it
+/// dates a message.
+const MAX_CLOCK_SKEW_SECONDS: i64 = 300;
+
+#[derive(Debug)]
+pub struct AuthSiweService {
+    pool: PgPool,
+    /// The domain messages have to be for, e.g. `wallet.example.com`.
+    domain: String,
+    chain_id: u64,
+    nonce_lifetime: Duration,
+}
+
+impl AuthSiweService {
+    pub fn new(pool: PgPool, domain: String, chain_id: u64, nonce_lifetime: Duration) -> Self {
+        Self {
+            pool,
+            domain,
+            chain_id,
+            nonce_lifetime,
+        }
+    }
+
+    /// Issues a nonce for a sign-in message, unless too many are outstanding.
+    pub async fn issue_nonce(&self) -> Result<SiweNonce, ErrorResponse> {
+        let nonce = Uuid::new_v4().simple().to_string();
+        let expires_at = Utc::now() + self.nonce_lifetime;
+        let issued = sqlx::query(
+            "INSERT INTO auth_siwe_nonces (nonce, expires_at)
+             SELECT $1, $2
+             WHERE (SELECT COUNT(*) FROM auth_siwe_nonces WHERE expires_at > CURRENT_TIMESTAMP) < $3",
+        )
+        .bind(&nonce)
+        .bind(expires_at)
+        .bind(MAX_OUTSTANDING_NONCES)
+        .execute(&self.pool)
+        .await
+        .map_err(|_| ErrorResponse::internal_error())?;
+        if issued.rows_affected() == 0 {
+            log::warn!(
+                "Refused a sign-in nonce, as {} are outstanding",
+                MAX_OUTSTANDING_NONCES
+            );
+            return Err(ErrorResponse::site_is_overloaded());
+        }
+
+        Ok(SiweNonce {
+            nonce,
+            domain: self.domain.clone(),
+            chain_id: self.chain_id,
+            expires_at,
+        })
+    }
+
+    /// Deletes the nonces that expired unused. Nonces are only looked up
+    /// while they are valid, so these can go.
+    async fn purge_expired_nonces(&self) -> Result<u64, sqlx::Error> {
+        sqlx::query("DELETE FROM auth_siwe_nonces WHERE expires_at <= CURRENT_TIMESTAMP")
+            .execute(&self.pool)
+            .await
+            .map(|result| result.rows_affected())
+    }
+
+    pub async fn continuously_purge_expired_nonces(self: Arc<Self>, period: std::time::Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.purge_expired_nonces().await {
+                log::error!("Error purging expired sign-in nonces: {:?}", e);
+            }
+        }
+    }
+
+    /// Checks a signed message and returns the checksummed address that signed
+    /// it. The message's nonce is used up, so it cannot be replayed, and it must
+    /// have been issued between its nonce and now.
+    pub async fn verify(&self, sign_in: &AuthSiweSignIn) -> Result<String, ErrorResponse> {
+        let message = SiweMessage::from_str(&sign_in.message)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+
+        if message.domain != self.domain {
+            log::error!("Sign-in message for another domain: {}", message.domain);
+            return Err(ErrorResponse::bad_request("Message is for another domain"));
+        }
+        if message.version != SiweMessage::VERSION {
+            return Err(ErrorResponse::bad_request("Unsupported message version"));
+        }
+        if message.chain_id != self.chain_id {
+            return Err(ErrorResponse::bad_request("Message is for another chain"));
+        }
+        let now = Utc::now();
+        let skew = Duration::seconds(MAX_CLOCK_SKEW_SECONDS);
+        if !message.is_valid_at(now) {
+            return Err(ErrorResponse::bad_request("Message has expired"));
+        }
+        if message.issued_at > now + skew {
+            return Err(ErrorResponse::bad_request(
+                "Message is issued in the future",
+            ));
+        }
+
+        let address = Address::from_str(&message.address)
+            .map_err(|_| ErrorResponse::bad_request("Invalid address in message"))?;
+        let checksummed = to_checksum(&address, None);
+        if message.address != checksummed {
+            return Err(ErrorResponse::bad_request(
+                "Address in message is not checksummed",
+            ));
+        }
+        let signature = Signature::from_str(&sign_in.signature)
+            .map_err(|_| ErrorResponse::bad_request("Invalid signature"))?;
+        if signature.verify(sign_in.message.as_str(), address).is_err() {
+            log::error!("Sign-in message not signed by {}", checksummed);
+            return Err(ErrorResponse::bad_request("Invalid signature"));
+        }
+
+        let nonce_expires_at = sqlx::query_scalar::<_, DateTime<Utc>>(
+            "DELETE FROM auth_siwe_nonces WHERE nonce = $1 AND expires_at > CURRENT_TIMESTAMP
+             RETURNING expires_at",
+        )
+        .bind(&message.nonce)
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|_| ErrorResponse::internal_error())?
+        .ok_or_else(|| ErrorResponse::bad_request("Invalid or expired nonce"))?;
+        // Issued by this server's clock, like the expiry
+        let nonce_issued_at = nonce_expires_at - self.nonce_lifetime;
+        if message.issued_at < nonce_issued_at - skew {
+            return Err(ErrorResponse::bad_request(
+                "Message is issued before its nonce",
+            ));
+        }
+
+        Ok(checksummed)
+    }
+
+    pub async fn user_for_address(&self, address: &str) -> Result<Uuid, ErrorResponse> {
+        sqlx::query_scalar::<_, Uuid>("SELECT user_id FROM auth_siwe_addresses WHERE address = $1")
+            .bind(address)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|_| ErrorResponse::internal_error())?
+            .ok_or_else(|| {
+                ErrorResponse::bad_request(
+                    "This address is not linked to an account. Log in with your password and link it first.",
+                )
+            })
+    }
+
+    pub async fn addresses(&self, user_id: Uuid) -> Result<Vec<SiweAddress>, ErrorResponse> {
+        sqlx::query_as::<_, SiweAddress>(
+            "SELECT * FROM auth_siwe_addresses WHERE user_id = $1 ORDER BY created_at",
+        )
+        .bind(user_id)
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|_| ErrorResponse::internal_error())
+    }
+
+    /// Lets `address` sign in to the user's account. Linking an address twice
+    /// is fine, but an address only ever belongs to one account.
+    pub async fn link_address(
+        &self,
+        user_id: Uuid,
+        address: &str,
+    ) -> Result<SiweAddress, ErrorResponse> {
+        let linked = sqlx::query_as::<_, SiweAddress>(
+            "INSERT INTO auth_siwe_addresses (address, user_id) VALUES ($1, $2)
+             ON CONFLICT (address) DO UPDATE SET address = EXCLUDED.address
+             RETURNING *",
+        )
+        .bind(address)
+        .bind(user_id)
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Failed to link address: {:#?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        if linked.user_id != user_id {
+            return Err(ErrorResponse::conflict(
+                "This address is linked to another account",
+            ));
+        }
+        Ok(linked)
+    }
+
+    pub async fn unlink_address(&self, user_id: Uuid, address: &str) -> Result<(), ErrorResponse> {
+        let result =
+            sqlx::query("DELETE FROM auth_siwe_addresses WHERE user_id = $1 AND address = $2")
+                .bind(user_id)
+                .bind(address)
+                .execute(&self.pool)
+                .await
+                .map_err(|_| ErrorResponse::internal_error())?;
+
+        if result.rows_affected() == 0 {
+            return Err(ErrorResponse::not_found("Address not found"));
+        }
+        Ok(())
+    }
+}
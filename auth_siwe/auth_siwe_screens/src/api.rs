@@ -0,0 +1,141 @@
+//! Client for the `/v1/auth/siwe` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::{get_base_url, ErrorResponse};
+use auth_api::User;
+use auth_siwe_api::{AuthSiweSignIn, SiweAddress, SiweMessage, SiweNonce};
+use chrono::Utc;
+use ethers::{types::Address, utils::to_checksum};
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+
+/// The message `address` signs to sign in with `nonce`.
+pub fn sign_in_message(nonce: &SiweNonce, address: Address, uri: String) -> SiweMessage {
+    SiweMessage {
+        domain: nonce.domain.clone(),
+        address: to_checksum(&address, None),
+        statement: Some("Sign in to your wallet.".to_string()),
+        uri,
+        version: SiweMessage::VERSION.to_string(),
+        chain_id: nonce.chain_id,
+        nonce: nonce.nonce.clone(),
+        issued_at: Utc::now(),
+        expiration_time: Some(nonce.expires_at),
+        not_before: None,
+        request_id: None,
+        resources: Vec::new(),
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn read<T: serde::de::DeserializeOwned>(
+    res: Response,
+    fallback: &str,
+) -> Result<T, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+    Err(rejection(res, fallback).await)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn rejection(res: Response, fallback: &str) -> anyhow::Error {
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    anyhow::anyhow!(message)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_nonce() -> Result<SiweNonce, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn get_nonce() -> Result<SiweNonce, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/v1/auth/siwe/nonce", get_base_url()))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to get a sign-in nonce").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn login(_sign_in: &AuthSiweSignIn) -> Result<User, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn login(sign_in: &AuthSiweSignIn) -> Result<User, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/v1/auth/siwe/login", get_base_url()))
+        .json(sign_in)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read::<auth_siwe_api::AuthSiweLoginResponse>(res, "Failed to sign in")
+        .await
+        .map(|response| response.data)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_addresses() -> Result<Vec<SiweAddress>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_addresses() -> Result<Vec<SiweAddress>, anyhow::Error> {
+    let res = Client::new()
+        .get(format!("{}/v1/auth/siwe/addresses", get_base_url()))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load sign-in addresses").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn link_address(_sign_in: &AuthSiweSignIn) -> Result<SiweAddress, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn link_address(sign_in: &AuthSiweSignIn) -> Result<SiweAddress, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/v1/auth/siwe/addresses", get_base_url()))
+        .json(sign_in)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to link address").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn unlink_address(_address: &str) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn unlink_address(address: &str) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .delete(format!(
+            "{}/v1/auth/siwe/addresses/{}",
+            get_base_url(),
+            address
+        ))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(rejection(res, "Failed to unlink address").await)
+    }
+}
@@ -0,0 +1,3 @@
+mod siwe_login;
+
+pub use siwe_login::SiweLogin;
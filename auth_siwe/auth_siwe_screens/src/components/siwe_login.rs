@@ -0,0 +1,76 @@
+use auth_screens::store::{actions::AuthAction, state::AuthStore};
+use auth_siwe_api::AuthSiweSignIn;
+use wonopui::{Alert, AlertType, Button, ButtonVariant};
+use yew::{platform::spawn_local, prelude::*};
+use yewdux::prelude::*;
+
+use crate::{api, ethereum};
+
+/// Signs the user in with the browser wallet holding an address they linked
+/// to their account.
+async fn sign_in() -> Result<auth_api::User, anyhow::Error> {
+    let address = ethereum::request_account().await?;
+    let nonce = api::get_nonce().await?;
+    let origin = web_sys::window()
+        .and_then(|window| window.location().origin().ok())
+        .unwrap_or_default();
+    let message = api::sign_in_message(&nonce, address, origin).to_string();
+    let signature = ethereum::personal_sign(&message, address).await?;
+
+    api::login(&AuthSiweSignIn { message, signature }).await
+}
+
+/// "Sign in with Ethereum", as an alternative to the password form.
+#[function_component(SiweLogin)]
+pub fn siwe_login() -> Html {
+    let (_, dispatch) = use_store::<AuthStore>();
+    let error = use_state(|| None::<String>);
+    let loading = use_state(|| false);
+
+    let onclick = {
+        let error = error.clone();
+        let loading = loading.clone();
+        Callback::from(move |_| {
+            let dispatch = dispatch.clone();
+            let error = error.clone();
+            let loading = loading.clone();
+            loading.set(true);
+            error.set(None);
+            spawn_local(async move {
+                match sign_in().await {
+                    Ok(user) => dispatch.apply(AuthAction::SetUser(Some(user))),
+                    Err(e) => error.set(Some(e.to_string())),
+                }
+                loading.set(false);
+            });
+        })
+    };
+
+    html! {
+        <div class="px-8 pb-4 space-y-4 max-w-md mx-auto">
+            <div class="flex items-center gap-4 text-sm text-zinc-500 dark:text-zinc-400">
+                <div class="flex-1 border-t border-zinc-200 dark:border-zinc-700"></div>
+                { "or" }
+                <div class="flex-1 border-t border-zinc-200 dark:border-zinc-700"></div>
+            </div>
+            if let Some(error) = (*error).clone() {
+                <Alert alert_type={AlertType::Error}>
+                    { error }
+                </Alert>
+            }
+            <Button
+                variant={ButtonVariant::Secondary}
+                disabled={*loading}
+                onclick={onclick}
+                class="w-full py-3.5 font-medium rounded-lg border-2 border-zinc-200 dark:border-zinc-700 text-zinc-700 dark:text-zinc-300 hover:border-blue-400 transition-all duration-200"
+            >
+                if *loading {
+                    <span class="inline-block animate-spin mr-2 text-xl">{"⟳"}</span>
+                    { "Waiting for your wallet..." }
+                } else {
+                    { "Sign in with Ethereum" }
+                }
+            </Button>
+        </div>
+    }
+}
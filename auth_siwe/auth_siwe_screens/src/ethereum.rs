@@ -0,0 +1,62 @@
+//! The Ethereum provider browser wallets such as MetaMask inject as
+//! `window.ethereum`.
+
+use std::str::FromStr;
+
+use ethers::{types::Address, utils::hex};
+use js_sys::{Array, Function, Object, Promise, Reflect};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+
+fn error_message(error: JsValue) -> anyhow::Error {
+    let message = Reflect::get(&error, &"message".into())
+        .ok()
+        .and_then(|message| message.as_string())
+        .unwrap_or_else(|| "The wallet rejected the request".to_string());
+    anyhow::anyhow!(message)
+}
+
+/// Sends a JSON-RPC request to the injected provider.
+async fn request(method: &str, params: Array) -> Result<JsValue, anyhow::Error> {
+    let provider = web_sys::window()
+        .and_then(|window| Reflect::get(&window, &"ethereum".into()).ok())
+        .filter(|provider| !provider.is_undefined() && !provider.is_null())
+        .ok_or_else(|| anyhow::anyhow!("No Ethereum wallet found in this browser"))?;
+    let request = Reflect::get(&provider, &"request".into())
+        .map_err(error_message)?
+        .dyn_into::<Function>()
+        .map_err(|_| anyhow::anyhow!("The browser wallet is not supported"))?;
+
+    let args = Object::new();
+    Reflect::set(&args, &"method".into(), &method.into()).map_err(error_message)?;
+    Reflect::set(&args, &"params".into(), &params).map_err(error_message)?;
+    let promise = request
+        .call1(&provider, &args)
+        .map_err(error_message)?
+        .dyn_into::<Promise>()
+        .map_err(|_| anyhow::anyhow!("The browser wallet is not supported"))?;
+
+    JsFuture::from(promise).await.map_err(error_message)
+}
+
+/// Asks the wallet for access, and returns the account the user picked.
+pub async fn request_account() -> Result<Address, anyhow::Error> {
+    let accounts = request("eth_requestAccounts", Array::new()).await?;
+    let account = Array::from(&accounts)
+        .get(0)
+        .as_string()
+        .ok_or_else(|| anyhow::anyhow!("The wallet did not share an account"))?;
+    Address::from_str(&account).map_err(|e| anyhow::anyhow!("Invalid account: {}", e))
+}
+
+/// Has the wallet sign `message` with `personal_sign`.
+pub async fn personal_sign(message: &str, address: Address) -> Result<String, anyhow::Error> {
+    let params = Array::of2(
+        &format!("0x{}", hex::encode(message)).into(),
+        &format!("{:?}", address).into(),
+    );
+    request("personal_sign", params)
+        .await?
+        .as_string()
+        .ok_or_else(|| anyhow::anyhow!("The wallet did not return a signature"))
+}
@@ -0,0 +1,5 @@
+pub mod api;
+pub mod components;
+mod ethereum;
+
+pub use components::SiweLogin;
@@ -0,0 +1,3 @@
+-- Recovery phrase of generated wallets, encrypted like the private key.
+-- Wallets imported from a raw key or keystore file have no phrase.
+ALTER TABLE wallets ADD COLUMN encrypted_mnemonic TEXT;
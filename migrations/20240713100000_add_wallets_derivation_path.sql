@@ -0,0 +1,3 @@
+-- BIP-32 path the key was derived at, e.g. m/44'/60'/0'/0/0.
+-- NULL for wallets imported from a raw key or keystore file.
+ALTER TABLE wallets ADD COLUMN derivation_path VARCHAR(64);
@@ -0,0 +1,30 @@
+-- A user's wallet can hold several accounts. The wallets row keeps the
+-- recovery phrase and the first account; every account, including the first,
+-- has a row here.
+CREATE TABLE IF NOT EXISTS
+    "wallet_accounts" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        label VARCHAR(64) NOT NULL,
+        derivation_index INTEGER CHECK (derivation_index >= 0),
+        encrypted_private_key TEXT NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        balance NUMERIC(28, 18) NOT NULL DEFAULT 0,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        updated_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (user_id, address)
+    );
+
+CREATE INDEX wallet_accounts_user_id_idx ON wallet_accounts (user_id);
+CREATE INDEX wallet_accounts_address_idx ON wallet_accounts (address);
+
+INSERT INTO wallet_accounts (user_id, label, derivation_index, encrypted_private_key, address, balance)
+SELECT
+    user_id,
+    'Account 1',
+    substring(derivation_path FROM '([0-9]+)''?$')::INTEGER,
+    encrypted_private_key,
+    address,
+    balance
+FROM wallets;
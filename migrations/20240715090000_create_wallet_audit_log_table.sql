@@ -0,0 +1,16 @@
+-- Append-only record of changes to the keys stored for a user.
+CREATE TABLE IF NOT EXISTS
+    "wallet_audit_log" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        event VARCHAR(32) NOT NULL CHECK (
+            event IN ('wallet_created', 'wallet_key_rotated', 'account_key_rotated')
+        ),
+        account_id UUID,
+        previous_address VARCHAR(42),
+        address VARCHAR(42) NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX wallet_audit_log_user_id_idx ON wallet_audit_log (user_id, created_at);
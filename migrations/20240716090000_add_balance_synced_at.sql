@@ -0,0 +1,4 @@
+-- When the balance was last read from the chain by the balance service.
+-- NULL until the first sync.
+ALTER TABLE wallets ADD COLUMN balance_synced_at TIMESTAMPTZ;
+ALTER TABLE wallet_accounts ADD COLUMN balance_synced_at TIMESTAMPTZ;
@@ -0,0 +1,18 @@
+-- In-app notifications, e.g. for received payments. A notification is unread
+-- until read_at is set.
+CREATE TABLE IF NOT EXISTS
+    "wallet_notifications" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        kind VARCHAR(32) NOT NULL CHECK (kind IN ('incoming_transfer')),
+        address VARCHAR(42) NOT NULL,
+        counterparty VARCHAR(42),
+        amount NUMERIC(28, 18),
+        tx_hash VARCHAR(66),
+        read_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (user_id, kind, tx_hash)
+    );
+
+CREATE INDEX wallet_notifications_user_id_idx ON wallet_notifications (user_id, created_at);
@@ -0,0 +1,17 @@
+-- A user's address book. Addresses are stored with their EIP-55 checksum, so
+-- the same recipient cannot be saved twice on one network.
+CREATE TABLE IF NOT EXISTS
+    "wallet_contacts" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        name VARCHAR(64) NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        network VARCHAR(32) NOT NULL DEFAULT 'ethereum',
+        note TEXT,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        updated_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (user_id, network, address)
+    );
+
+CREATE INDEX wallet_contacts_user_id_idx ON wallet_contacts (user_id, name);
@@ -0,0 +1,21 @@
+-- Payments users asked for through a shared link or QR code. A request stays
+-- open until a transfer of the requested amount to its address is seen.
+CREATE TABLE IF NOT EXISTS
+    "wallet_payment_requests" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        token VARCHAR(42),
+        token_decimals SMALLINT NOT NULL DEFAULT 18,
+        chain_id BIGINT,
+        amount NUMERIC(28, 18),
+        memo VARCHAR(200),
+        status VARCHAR(16) NOT NULL DEFAULT 'open' CHECK (status IN ('open', 'paid', 'cancelled')),
+        paid_tx_hash VARCHAR(66),
+        paid_at TIMESTAMPTZ,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX wallet_payment_requests_user_id_idx ON wallet_payment_requests (user_id, created_at);
+CREATE INDEX wallet_payment_requests_open_idx ON wallet_payment_requests (LOWER(address)) WHERE status = 'open';
@@ -0,0 +1,21 @@
+-- Transactions seen on chain that involve a user's accounts, recorded by the
+-- chain watcher. A transfer between two accounts of the same user has a row
+-- for each side.
+CREATE TABLE IF NOT EXISTS
+    "wallet_transactions" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        tx_hash VARCHAR(66) NOT NULL,
+        direction VARCHAR(8) NOT NULL CHECK (direction IN ('in', 'out')),
+        counterparty VARCHAR(42),
+        amount NUMERIC(28, 18) NOT NULL,
+        fee NUMERIC(28, 18),
+        block_number BIGINT NOT NULL,
+        block_time TIMESTAMPTZ NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (user_id, address, tx_hash, direction)
+    );
+
+CREATE INDEX wallet_transactions_user_id_idx ON wallet_transactions (user_id, block_time);
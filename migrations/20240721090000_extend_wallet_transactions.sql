@@ -0,0 +1,26 @@
+-- Transactions keep both sides, their status and what they cost, and sends
+-- are recorded by the app before they are mined.
+ALTER TABLE wallet_transactions
+    ADD COLUMN sender VARCHAR(42),
+    ADD COLUMN recipient VARCHAR(42),
+    ADD COLUMN status VARCHAR(16) NOT NULL DEFAULT 'confirmed' CHECK (status IN ('pending', 'confirmed', 'failed', 'replaced')),
+    ADD COLUMN gas_used BIGINT,
+    ADD COLUMN nonce BIGINT,
+    ADD COLUMN chain_id BIGINT,
+    ADD COLUMN error TEXT,
+    ALTER COLUMN tx_hash DROP NOT NULL,
+    ALTER COLUMN block_number DROP NOT NULL,
+    ALTER COLUMN block_time DROP NOT NULL;
+
+UPDATE wallet_transactions
+SET
+    sender = CASE WHEN direction = 'out' THEN address ELSE counterparty END,
+    recipient = CASE WHEN direction = 'out' THEN counterparty ELSE address END;
+
+ALTER TABLE wallet_transactions
+    ALTER COLUMN sender SET NOT NULL,
+    ALTER COLUMN status DROP DEFAULT,
+    DROP COLUMN counterparty;
+
+-- Sends waiting to be mined are looked up by nonce when one replaces another
+CREATE INDEX wallet_transactions_nonce_idx ON wallet_transactions (LOWER(address), nonce) WHERE status = 'pending';
@@ -0,0 +1,14 @@
+-- ERC-20 transfers are recorded in units of their token, whose contract is
+-- in `token`. Ether transactions have no token. Token amounts can be far
+-- larger than ether amounts, hence the unconstrained precision.
+ALTER TABLE wallet_transactions
+    ADD COLUMN token VARCHAR(42),
+    ALTER COLUMN amount TYPE NUMERIC;
+
+-- Transaction history is paged newest first
+DROP INDEX IF EXISTS wallet_transactions_user_id_idx;
+CREATE INDEX wallet_transactions_user_time_idx ON wallet_transactions (
+    user_id,
+    (COALESCE(block_time, created_at)) DESC,
+    id DESC
+);
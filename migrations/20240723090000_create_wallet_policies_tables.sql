@@ -0,0 +1,38 @@
+-- Spending policies in force. Users without a row have no limits.
+CREATE TABLE IF NOT EXISTS
+    "wallet_spending_policies" (
+        user_id UUID NOT NULL PRIMARY KEY,
+        daily_limit NUMERIC(28, 18),
+        per_transaction_limit NUMERIC(28, 18),
+        allowlist_only BOOLEAN NOT NULL DEFAULT FALSE,
+        recipient_delay_hours INTEGER NOT NULL DEFAULT 0,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+-- A requested policy change, which replaces the policy once its cooling-off
+-- period has passed.
+CREATE TABLE IF NOT EXISTS
+    "wallet_policy_changes" (
+        user_id UUID NOT NULL PRIMARY KEY,
+        daily_limit NUMERIC(28, 18),
+        per_transaction_limit NUMERIC(28, 18),
+        allowlist_only BOOLEAN NOT NULL,
+        recipient_delay_hours INTEGER NOT NULL,
+        requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        effective_at TIMESTAMPTZ NOT NULL,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE TABLE IF NOT EXISTS
+    "wallet_allowed_recipients" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        label VARCHAR(64),
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        active_at TIMESTAMPTZ NOT NULL,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE UNIQUE INDEX wallet_allowed_recipients_address_idx ON wallet_allowed_recipients (user_id, LOWER(address));
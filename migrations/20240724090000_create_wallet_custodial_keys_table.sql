@@ -0,0 +1,28 @@
+-- Accounts whose private key the server holds, for deployments that run the
+-- wallet custodially. Their key never reaches the browser, so
+-- encrypted_private_key is left empty.
+ALTER TABLE wallet_accounts ADD COLUMN custodial BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- The handle the key vault returned for each custodial account's key. With the
+-- file vault it is the key encrypted under the master key.
+CREATE TABLE IF NOT EXISTS
+    "wallet_custodial_keys" (
+        account_id UUID NOT NULL PRIMARY KEY,
+        user_id UUID NOT NULL,
+        key_handle BYTEA NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (account_id) REFERENCES wallet_accounts(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+-- Custodial accounts are created with a key the server generated, which is
+-- recorded like the other key changes.
+ALTER TABLE wallet_audit_log DROP CONSTRAINT wallet_audit_log_event_check;
+ALTER TABLE wallet_audit_log ADD CONSTRAINT wallet_audit_log_event_check CHECK (
+    event IN (
+        'wallet_created',
+        'wallet_key_rotated',
+        'account_key_rotated',
+        'custodial_account_created'
+    )
+);
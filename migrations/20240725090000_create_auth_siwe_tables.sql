@@ -0,0 +1,18 @@
+-- Nonces issued for Sign-In With Ethereum messages. Each is used once.
+CREATE TABLE IF NOT EXISTS
+    "auth_siwe_nonces" (
+        nonce VARCHAR(64) NOT NULL PRIMARY KEY,
+        expires_at TIMESTAMPTZ NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
+
+-- Addresses whose signature logs in to a user's account, checksummed.
+CREATE TABLE IF NOT EXISTS
+    "auth_siwe_addresses" (
+        address VARCHAR(42) NOT NULL PRIMARY KEY,
+        user_id UUID NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX IF NOT EXISTS auth_siwe_addresses_user_id_idx ON auth_siwe_addresses (user_id);
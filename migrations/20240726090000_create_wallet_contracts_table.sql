@@ -0,0 +1,17 @@
+-- Contracts a user saved to call from the wallet, with the ABI JSON their
+-- forms are generated from.
+CREATE TABLE IF NOT EXISTS
+    "wallet_contracts" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        name VARCHAR(64) NOT NULL,
+        address VARCHAR(42) NOT NULL,
+        network VARCHAR(32) NOT NULL DEFAULT 'ethereum',
+        abi TEXT NOT NULL,
+        created_at TIMESTAMPTZ DEFAULT NOW(),
+        updated_at TIMESTAMPTZ DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (user_id, network, address)
+    );
+
+CREATE INDEX wallet_contracts_user_id_idx ON wallet_contracts (user_id, name);
@@ -0,0 +1,88 @@
+-- Shared treasury wallets. The key is held in the custody key vault, and a
+-- send only happens once `threshold` of the designated staff signers approved
+-- it.
+CREATE TABLE IF NOT EXISTS
+    "wallet_treasuries" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        name VARCHAR(64) NOT NULL,
+        address VARCHAR(42) NOT NULL UNIQUE,
+        key_handle BYTEA NOT NULL,
+        threshold INTEGER NOT NULL CHECK (threshold >= 1),
+        created_by UUID,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (created_by) REFERENCES auth_users(id) ON DELETE SET NULL
+    );
+
+CREATE TABLE IF NOT EXISTS
+    "wallet_treasury_signers" (
+        treasury_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (treasury_id, user_id),
+        FOREIGN KEY (treasury_id) REFERENCES wallet_treasuries(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX wallet_treasury_signers_user_id_idx ON wallet_treasury_signers (user_id);
+
+-- A proposed send. It is approved once enough signers approved it, and
+-- rejected once too many rejected it for the threshold to still be reached.
+-- Only approved proposals can be executed.
+CREATE TABLE IF NOT EXISTS
+    "wallet_treasury_proposals" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        treasury_id UUID NOT NULL,
+        proposed_by UUID,
+        recipient VARCHAR(42) NOT NULL,
+        amount NUMERIC(28, 18) NOT NULL CHECK (amount > 0),
+        memo TEXT,
+        status VARCHAR(16) NOT NULL DEFAULT 'pending' CHECK (
+            status IN ('pending', 'approved', 'rejected', 'executed', 'failed')
+        ),
+        tx_hash VARCHAR(66),
+        error TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (treasury_id) REFERENCES wallet_treasuries(id) ON DELETE CASCADE,
+        FOREIGN KEY (proposed_by) REFERENCES auth_users(id) ON DELETE SET NULL
+    );
+
+CREATE INDEX wallet_treasury_proposals_treasury_id_idx ON wallet_treasury_proposals (treasury_id, created_at);
+
+CREATE TABLE IF NOT EXISTS
+    "wallet_treasury_votes" (
+        proposal_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        approved BOOLEAN NOT NULL,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        PRIMARY KEY (proposal_id, user_id),
+        FOREIGN KEY (proposal_id) REFERENCES wallet_treasury_proposals(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE
+    );
+
+-- Append-only record of everything done with a treasury. Entries outlive the
+-- users who made them.
+CREATE TABLE IF NOT EXISTS
+    "wallet_treasury_audit_log" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        treasury_id UUID NOT NULL,
+        proposal_id UUID,
+        user_id UUID,
+        event VARCHAR(32) NOT NULL CHECK (
+            event IN (
+                'treasury_created',
+                'proposal_created',
+                'proposal_approved',
+                'proposal_rejected',
+                'proposal_executed',
+                'proposal_failed'
+            )
+        ),
+        detail TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (treasury_id) REFERENCES wallet_treasuries(id) ON DELETE CASCADE,
+        FOREIGN KEY (proposal_id) REFERENCES wallet_treasury_proposals(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE SET NULL
+    );
+
+CREATE INDEX wallet_treasury_audit_log_treasury_id_idx ON wallet_treasury_audit_log (treasury_id, created_at);
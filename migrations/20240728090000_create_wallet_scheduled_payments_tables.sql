@@ -0,0 +1,48 @@
+-- Payments made on the user's behalf, once or on a cron schedule. The
+-- scheduler picks up active payments whose next_run_at has passed.
+CREATE TABLE IF NOT EXISTS
+    "wallet_scheduled_payments" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        user_id UUID NOT NULL,
+        account_id UUID NOT NULL,
+        recipient VARCHAR(42) NOT NULL,
+        token VARCHAR(42),
+        token_decimals SMALLINT NOT NULL DEFAULT 18,
+        amount NUMERIC NOT NULL CHECK (amount > 0),
+        memo VARCHAR(200),
+        schedule VARCHAR(100),
+        next_run_at TIMESTAMPTZ,
+        status VARCHAR(16) NOT NULL DEFAULT 'active' CHECK (status IN ('active', 'completed', 'cancelled')),
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        FOREIGN KEY (account_id) REFERENCES wallet_accounts(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX wallet_scheduled_payments_user_id_idx ON wallet_scheduled_payments (user_id, created_at);
+CREATE INDEX wallet_scheduled_payments_due_idx ON wallet_scheduled_payments (next_run_at) WHERE status = 'active';
+
+-- Every time a payment came due, with what was paid. Runs from accounts the
+-- server holds no key for wait for the user to sign them in the app.
+CREATE TABLE IF NOT EXISTS
+    "wallet_scheduled_payment_runs" (
+        id UUID NOT NULL PRIMARY KEY DEFAULT (uuid_generate_v4()),
+        scheduled_payment_id UUID NOT NULL,
+        user_id UUID NOT NULL,
+        account_id UUID NOT NULL,
+        recipient VARCHAR(42) NOT NULL,
+        token VARCHAR(42),
+        token_decimals SMALLINT NOT NULL,
+        amount NUMERIC NOT NULL,
+        due_at TIMESTAMPTZ NOT NULL,
+        status VARCHAR(24) NOT NULL CHECK (status IN ('awaiting_approval', 'sending', 'submitted', 'failed', 'skipped')),
+        tx_hash VARCHAR(66),
+        error TEXT,
+        created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (scheduled_payment_id) REFERENCES wallet_scheduled_payments(id) ON DELETE CASCADE,
+        FOREIGN KEY (user_id) REFERENCES auth_users(id) ON DELETE CASCADE,
+        UNIQUE (scheduled_payment_id, due_at)
+    );
+
+CREATE INDEX wallet_scheduled_payment_runs_user_id_idx ON wallet_scheduled_payment_runs (user_id, due_at DESC);
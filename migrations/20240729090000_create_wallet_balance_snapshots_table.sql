@@ -0,0 +1,12 @@
+-- Balances of every account over time, for the balance history chart. One is
+-- taken on an interval, and another whenever a sync sees the balance change.
+CREATE TABLE IF NOT EXISTS
+    "wallet_balance_snapshots" (
+        id BIGSERIAL PRIMARY KEY,
+        account_id UUID NOT NULL,
+        balance NUMERIC(28, 18) NOT NULL,
+        taken_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+        FOREIGN KEY (account_id) REFERENCES wallet_accounts(id) ON DELETE CASCADE
+    );
+
+CREATE INDEX wallet_balance_snapshots_account_id_idx ON wallet_balance_snapshots (account_id, taken_at);
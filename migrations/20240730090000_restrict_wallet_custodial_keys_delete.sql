@@ -0,0 +1,5 @@
+-- The server holds the only copy of a custodial account's key, so the account
+-- row must not take the key with it when deleted.
+ALTER TABLE wallet_custodial_keys DROP CONSTRAINT wallet_custodial_keys_account_id_fkey;
+ALTER TABLE wallet_custodial_keys ADD CONSTRAINT wallet_custodial_keys_account_id_fkey
+    FOREIGN KEY (account_id) REFERENCES wallet_accounts(id) ON DELETE RESTRICT;
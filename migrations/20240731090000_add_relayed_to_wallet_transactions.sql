@@ -0,0 +1,4 @@
+-- Sends that went through the relay were checked against the spending policy,
+-- so what the app reports afterwards must not change their status. Only the
+-- chain watcher settles them.
+ALTER TABLE wallet_transactions ADD COLUMN relayed BOOLEAN NOT NULL DEFAULT FALSE;
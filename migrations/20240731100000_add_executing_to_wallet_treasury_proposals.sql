@@ -0,0 +1,7 @@
+-- A proposal is marked executing, with the hash of its send, before the send
+-- is broadcast, so that it cannot be executed twice and the send can be traced
+-- should the server stop before the outcome is recorded.
+ALTER TABLE wallet_treasury_proposals DROP CONSTRAINT wallet_treasury_proposals_status_check;
+ALTER TABLE wallet_treasury_proposals ADD CONSTRAINT wallet_treasury_proposals_status_check CHECK (
+    status IN ('pending', 'approved', 'rejected', 'executing', 'executed', 'failed')
+);
@@ -0,0 +1,8 @@
+-- Incoming token transfers are notified in units of their token, whose
+-- contract is in `token` and whose symbol, if the contract has one, is in
+-- `token_symbol`. Token amounts can be far larger than ether amounts, hence
+-- the unconstrained precision.
+ALTER TABLE wallet_notifications
+    ADD COLUMN token VARCHAR(42),
+    ADD COLUMN token_symbol VARCHAR(32),
+    ALTER COLUMN amount TYPE NUMERIC;
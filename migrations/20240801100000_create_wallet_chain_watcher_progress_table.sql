@@ -0,0 +1,9 @@
+-- The last block the chain watcher processed on each chain, so that after a
+-- restart it carries on from there instead of skipping the blocks mined while
+-- it was down.
+CREATE TABLE IF NOT EXISTS
+    "wallet_chain_watcher_progress" (
+        chain_id BIGINT NOT NULL PRIMARY KEY,
+        last_block BIGINT NOT NULL,
+        updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+    );
@@ -0,0 +1,111 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::address::validate_address;
+
+/// An address held by a user's wallet. Accounts derived from the wallet's
+/// recovery phrase record their BIP-44 address index.
+#[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct Account {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub label: String,
+    pub derivation_index: Option<i32>,
+    pub encrypted_private_key: String,
+    pub address: String,
+    pub balance: Decimal,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+    pub balance_synced_at: Option<DateTime<Utc>>,
+    /// The server holds the key and signs for the account, so
+    /// `encrypted_private_key` is empty.
+    #[serde(default)]
+    pub custodial: bool,
+}
+
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateAccount {
+    #[validate(length(min = 1, max = 64, message = "Label must be 1 to 64 characters"))]
+    pub label: String,
+    #[validate(range(min = 0, message = "Derivation index must not be negative"))]
+    pub derivation_index: Option<i32>,
+    #[validate(length(min = 1, message = "Encrypted private key is required"))]
+    pub encrypted_private_key: String,
+    #[validate(length(equal = 42, message = "Address is invalid"))]
+    pub address: String,
+    /// `personal_sign` signature of [`CreateAccount::message`] by the
+    /// account's key, proving that the user adding the address holds it.
+    #[validate(length(min = 1, message = "Signature is required"))]
+    pub signature: String,
+}
+
+impl CreateAccount {
+    /// What the account's key signs to add the account to the wallet of
+    /// `user_id`. It names the user, so that the signature cannot be used to
+    /// add the address to anyone else's wallet.
+    pub fn message(user_id: Uuid, address: &str, encrypted_private_key: &str) -> String {
+        format!(
+            "Add account {} to the wallet of user {}\n\n{}",
+            address.to_lowercase(),
+            user_id,
+            encrypted_private_key
+        )
+    }
+}
+
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub struct UpdateAccount {
+    #[validate(length(min = 1, max = 64, message = "Label must be 1 to 64 characters"))]
+    pub label: Option<String>,
+}
+
+/// Body of `POST /api/v1/wallet/custody/accounts`. The server creates the key.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateCustodialAccount {
+    #[validate(length(min = 1, max = 64, message = "Label must be 1 to 64 characters"))]
+    pub label: String,
+}
+
+/// Body of `POST /api/v1/wallet/custody/send`, a send the server signs for a
+/// custodial account.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustodialSend {
+    pub account_id: Uuid,
+    #[validate(custom(function = "validate_address", message = "Recipient is invalid"))]
+    pub recipient: String,
+    /// In ether.
+    pub amount: Decimal,
+}
+
+/// Response of `GET /api/v1/wallet/custody`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct CustodyStatus {
+    /// Whether the server is set up to hold keys.
+    pub enabled: bool,
+}
+
+/// Body of `POST /api/v1/wallet/accounts/:id/rotate-key`, used when the key is
+/// re-encrypted, e.g. after an upgrade to a newer keystore format.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RotateAccountKey {
+    #[validate(length(min = 1, message = "Encrypted private key is required"))]
+    pub encrypted_private_key: String,
+}
+
+/// Body of `POST /api/v1/wallet/accounts/:id/upgrade-key`, which replaces a key
+/// in the legacy format with the same key in the current keystore format.
+/// Unlike a rotation it needs no recent login, as the app upgrades keys
+/// whenever it unlocks one.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct UpgradeAccountKey {
+    #[validate(length(min = 1, message = "Encrypted private key is required"))]
+    pub encrypted_private_key: String,
+    /// `personal_sign` signature of `encrypted_private_key` by the account's
+    /// key, proving that the upgraded keystore holds the same key.
+    #[validate(length(min = 1, message = "Signature is required"))]
+    pub signature: String,
+}
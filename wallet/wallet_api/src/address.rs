@@ -0,0 +1,55 @@
+//! Parsing of addresses typed or pasted by users, with the EIP-55 mixed-case
+//! checksum that catches most typos.
+
+use ethers::{types::Address, utils::to_checksum};
+use validator::ValidationError;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AddressError {
+    #[error("Enter an address")]
+    Empty,
+    #[error("Address must be 0x followed by 40 hexadecimal characters")]
+    Invalid,
+    #[error("Address checksum does not match, please check it for typos")]
+    Checksum,
+}
+
+/// Parses a hex address. Mixed-case input has to match its EIP-55 checksum;
+/// all lowercase or all uppercase input carries no checksum and is accepted.
+pub fn parse_address(input: &str) -> Result<Address, AddressError> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(AddressError::Empty);
+    }
+    let hex = input
+        .strip_prefix("0x")
+        .filter(|hex| hex.len() == 40 && hex.chars().all(|c| c.is_ascii_hexdigit()))
+        .ok_or(AddressError::Invalid)?;
+    let address = hex.parse::<Address>().map_err(|_| AddressError::Invalid)?;
+
+    let is_mixed_case =
+        hex.chars().any(|c| c.is_ascii_lowercase()) && hex.chars().any(|c| c.is_ascii_uppercase());
+    if is_mixed_case && checksum_address(&address) != input {
+        return Err(AddressError::Checksum);
+    }
+
+    Ok(address)
+}
+
+/// Formats an address with its EIP-55 checksum, e.g.
+/// `0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed`.
+pub fn checksum_address(address: &Address) -> String {
+    to_checksum(address, None)
+}
+
+/// Whether two addresses are the same, ignoring case.
+pub fn same_address(a: &str, b: &str) -> bool {
+    a.trim().eq_ignore_ascii_case(b.trim())
+}
+
+/// `validator` check for address fields.
+pub fn validate_address(address: &str) -> Result<(), ValidationError> {
+    parse_address(address)
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("address"))
+}
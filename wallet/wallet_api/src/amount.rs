@@ -0,0 +1,458 @@
+//! Conversions between on-chain amounts, which are integers in the token's
+//! smallest unit (wei for ether), and the decimal amounts users see and type.
+//!
+//! All conversions are exact: they either succeed without losing precision or
+//! return an [`AmountError`].
+
+use ethers::types::U256;
+use rust_decimal::{Decimal, RoundingStrategy};
+
+/// Decimals of ether, used unless a wallet says otherwise.
+pub const ETHER_DECIMALS: i16 = 18;
+
+/// The largest number of decimals a [`Decimal`] can represent.
+const MAX_DECIMALS: i16 = 28;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum AmountError {
+    #[error("Enter an amount")]
+    Empty,
+    #[error("Amount is not a valid number")]
+    Invalid,
+    #[error("Amount must not be negative")]
+    Negative,
+    #[error("Amount can have at most {0} decimal places")]
+    TooManyDecimals(i16),
+    #[error("Amount is too large")]
+    Overflow,
+    #[error("Tokens with {0} decimals are not supported")]
+    UnsupportedDecimals(i16),
+}
+
+/// How numbers are written in a locale.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NumberLocale {
+    pub decimal_separator: char,
+    pub group_separator: char,
+}
+
+impl Default for NumberLocale {
+    fn default() -> Self {
+        Self::EN
+    }
+}
+
+impl NumberLocale {
+    /// `1,234.5`
+    pub const EN: Self = Self {
+        decimal_separator: '.',
+        group_separator: ',',
+    };
+    /// `1.234,5`
+    pub const DE: Self = Self {
+        decimal_separator: ',',
+        group_separator: '.',
+    };
+    /// `1 234,5`, grouped with a narrow no-break space.
+    pub const FR: Self = Self {
+        decimal_separator: ',',
+        group_separator: '\u{202f}',
+    };
+    /// `1'234.5`
+    pub const CH: Self = Self {
+        decimal_separator: '.',
+        group_separator: '\'',
+    };
+
+    /// Picks the conventions for a BCP 47 language tag such as `en-US` or
+    /// `de`. Unknown languages fall back to English.
+    pub fn from_language_tag(tag: &str) -> Self {
+        let mut parts = tag.split(['-', '_']);
+        let language = parts.next().unwrap_or_default().to_ascii_lowercase();
+        let region = parts.next().unwrap_or_default().to_ascii_uppercase();
+
+        match (language.as_str(), region.as_str()) {
+            ("de" | "fr" | "it", "CH") => Self::CH,
+            ("fr" | "nb" | "no" | "sv" | "fi" | "cs" | "sk" | "pl" | "ru" | "uk", _) => Self::FR,
+            (
+                "de" | "da" | "nl" | "es" | "it" | "pt" | "id" | "tr" | "el" | "ro" | "hr" | "sl"
+                | "sr",
+                _,
+            ) => Self::DE,
+            _ => Self::EN,
+        }
+    }
+}
+
+fn check_decimals(decimals: i16) -> Result<u32, AmountError> {
+    if (0..=MAX_DECIMALS).contains(&decimals) {
+        Ok(decimals as u32)
+    } else {
+        Err(AmountError::UnsupportedDecimals(decimals))
+    }
+}
+
+fn pow10(exponent: u32) -> U256 {
+    U256::exp10(exponent as usize)
+}
+
+/// Converts an integer amount in the smallest unit to a decimal amount, e.g.
+/// `1_500_000_000_000_000_000` wei with 18 decimals to `1.5`.
+pub fn to_decimal(amount: U256, decimals: i16) -> Result<Decimal, AmountError> {
+    let mut scale = check_decimals(decimals)?;
+    let mut mantissa = amount;
+    // A Decimal holds 96 bits; trailing zeros can be traded for scale
+    let max_mantissa = U256::from(u128::MAX >> 32);
+    while mantissa > max_mantissa {
+        if scale == 0 || !(mantissa % 10).is_zero() {
+            return Err(AmountError::Overflow);
+        }
+        mantissa /= 10;
+        scale -= 1;
+    }
+
+    Ok(Decimal::from_i128_with_scale(mantissa.as_u128() as i128, scale).normalize())
+}
+
+/// Converts a decimal amount to an integer amount in the smallest unit. Fails
+/// instead of rounding when the amount is more precise than the token.
+pub fn from_decimal(amount: Decimal, decimals: i16) -> Result<U256, AmountError> {
+    let target_scale = check_decimals(decimals)?;
+    if amount.is_sign_negative() && !amount.is_zero() {
+        return Err(AmountError::Negative);
+    }
+
+    let amount = amount.normalize();
+    if amount.scale() > target_scale {
+        return Err(AmountError::TooManyDecimals(decimals));
+    }
+
+    U256::from(amount.mantissa().unsigned_abs())
+        .checked_mul(pow10(target_scale - amount.scale()))
+        .ok_or(AmountError::Overflow)
+}
+
+/// Groups the integer digits and joins them with the fraction digits.
+fn localize(integer: &str, fraction: &str, locale: NumberLocale) -> String {
+    let mut formatted = String::with_capacity(integer.len() * 4 / 3 + fraction.len() + 1);
+    for (i, digit) in integer.chars().enumerate() {
+        if i > 0 && (integer.len() - i).is_multiple_of(3) {
+            formatted.push(locale.group_separator);
+        }
+        formatted.push(digit);
+    }
+    if !fraction.is_empty() {
+        formatted.push(locale.decimal_separator);
+        formatted.push_str(fraction);
+    }
+    formatted
+}
+
+/// Formats an integer amount in the smallest unit with exactly
+/// `fraction_digits` decimal places, rounding half away from zero.
+pub fn format_amount(
+    amount: U256,
+    decimals: i16,
+    fraction_digits: u32,
+    locale: NumberLocale,
+) -> Result<String, AmountError> {
+    let decimals = check_decimals(decimals)?;
+    let rounded = if fraction_digits < decimals {
+        let unit = pow10(decimals - fraction_digits);
+        // Only overflows within half a unit of U256::MAX
+        amount.checked_add(unit / 2).ok_or(AmountError::Overflow)? / unit
+    } else {
+        amount
+            .checked_mul(pow10(fraction_digits - decimals))
+            .ok_or(AmountError::Overflow)?
+    };
+
+    let digits = format!(
+        "{:0>width$}",
+        rounded.to_string(),
+        width = fraction_digits as usize + 1
+    );
+    let (integer, fraction) = digits.split_at(digits.len() - fraction_digits as usize);
+    Ok(localize(integer, fraction, locale))
+}
+
+/// Formats an integer amount in the smallest unit with all significant decimal
+/// places, e.g. `1.5` rather than `1.500000000000000000`.
+pub fn format_amount_exact(
+    amount: U256,
+    decimals: i16,
+    locale: NumberLocale,
+) -> Result<String, AmountError> {
+    let decimals = check_decimals(decimals)?;
+    let digits = format!(
+        "{:0>width$}",
+        amount.to_string(),
+        width = decimals as usize + 1
+    );
+    let (integer, fraction) = digits.split_at(digits.len() - decimals as usize);
+    Ok(localize(integer, fraction.trim_end_matches('0'), locale))
+}
+
+/// Formats a decimal amount with exactly `fraction_digits` decimal places,
+/// rounding half away from zero.
+pub fn format_decimal(amount: Decimal, fraction_digits: u32, locale: NumberLocale) -> String {
+    let rounded =
+        amount.round_dp_with_strategy(fraction_digits, RoundingStrategy::MidpointAwayFromZero);
+    let digits = format!("{:.*}", fraction_digits as usize, rounded.abs());
+    let (integer, fraction) = digits.split_once('.').unwrap_or((&digits, ""));
+    let sign = if rounded.is_sign_negative() && !rounded.is_zero() {
+        "-"
+    } else {
+        ""
+    };
+    format!("{}{}", sign, localize(integer, fraction, locale))
+}
+
+/// Parses an amount typed by the user into an integer amount in the smallest
+/// unit. Spaces and correctly placed group separators are ignored.
+pub fn parse_amount(input: &str, decimals: i16, locale: NumberLocale) -> Result<U256, AmountError> {
+    let target_scale = check_decimals(decimals)?;
+    let input = input.trim();
+    if input.is_empty() {
+        return Err(AmountError::Empty);
+    }
+    if input.starts_with('-') {
+        return Err(AmountError::Negative);
+    }
+
+    let compact: String = input.chars().filter(|c| !c.is_whitespace()).collect();
+    let (integer, fraction) = match compact.split_once(locale.decimal_separator) {
+        Some((integer, fraction)) => (integer, fraction),
+        None => (compact.as_str(), ""),
+    };
+    // Reject misplaced group separators, so that `1.5` typed with German
+    // conventions is not read as 15
+    let groups: Vec<&str> = integer.split(locale.group_separator).collect();
+    if groups.len() > 1
+        && (groups[0].is_empty()
+            || groups[0].len() > 3
+            || groups[1..].iter().any(|group| group.len() != 3))
+    {
+        return Err(AmountError::Invalid);
+    }
+    let integer = groups.concat();
+
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (integer.is_empty() && fraction.is_empty()) || !is_digits(&integer) || !is_digits(fraction) {
+        return Err(AmountError::Invalid);
+    }
+
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.len() > target_scale as usize {
+        return Err(AmountError::TooManyDecimals(decimals));
+    }
+
+    let digits = format!(
+        "{}{:0<width$}",
+        integer,
+        fraction,
+        width = target_scale as usize
+    );
+    U256::from_dec_str(&digits).map_err(|_| AmountError::Overflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    const LOCALES: [NumberLocale; 4] = [
+        NumberLocale::EN,
+        NumberLocale::DE,
+        NumberLocale::FR,
+        NumberLocale::CH,
+    ];
+
+    fn any_u256() -> impl Strategy<Value = U256> {
+        any::<[u64; 4]>().prop_map(U256)
+    }
+
+    fn any_locale() -> impl Strategy<Value = NumberLocale> {
+        prop::sample::select(LOCALES.to_vec())
+    }
+
+    /// Amounts that fit a [`Decimal`] at any scale.
+    fn decimal_sized() -> impl Strategy<Value = U256> {
+        (0..=u128::MAX >> 32).prop_map(U256::from)
+    }
+
+    #[test]
+    fn round_trips_bounds() {
+        for decimals in [0, ETHER_DECIMALS, MAX_DECIMALS] {
+            for amount in [U256::zero(), U256::one()] {
+                let decimal = to_decimal(amount, decimals).unwrap();
+                assert_eq!(from_decimal(decimal, decimals), Ok(amount));
+            }
+            // Its last digit is not zero, so it cannot be traded for scale
+            assert_eq!(to_decimal(U256::MAX, decimals), Err(AmountError::Overflow));
+        }
+        assert_eq!(
+            to_decimal(U256::one(), ETHER_DECIMALS).unwrap().to_string(),
+            "0.000000000000000001"
+        );
+        assert_eq!(
+            parse_amount(&U256::MAX.to_string(), 0, NumberLocale::EN),
+            Ok(U256::MAX)
+        );
+    }
+
+    #[test]
+    fn rejects_unsupported_decimals() {
+        for decimals in [-1, MAX_DECIMALS + 1] {
+            assert_eq!(
+                to_decimal(U256::one(), decimals),
+                Err(AmountError::UnsupportedDecimals(decimals))
+            );
+            assert_eq!(
+                parse_amount("1", decimals, NumberLocale::EN),
+                Err(AmountError::UnsupportedDecimals(decimals))
+            );
+        }
+    }
+
+    #[test]
+    fn rounds_half_away_from_zero() {
+        let amount = U256::from(1_005);
+        assert_eq!(
+            format_amount(amount, 3, 2, NumberLocale::EN),
+            Ok("1.01".to_string())
+        );
+        assert_eq!(
+            format_amount(U256::from(1_004), 3, 2, NumberLocale::EN),
+            Ok("1.00".to_string())
+        );
+        assert_eq!(
+            format_amount_exact(amount, 3, NumberLocale::EN),
+            Ok("1.005".to_string())
+        );
+        assert_eq!(
+            format_amount(U256::MAX, 1, 0, NumberLocale::EN),
+            Err(AmountError::Overflow)
+        );
+    }
+
+    #[test]
+    fn writes_locale_separators() {
+        let amount = U256::from(12_345_675);
+        let formatted = |locale| format_amount_exact(amount, 1, locale).unwrap();
+        assert_eq!(formatted(NumberLocale::EN), "1,234,567.5");
+        assert_eq!(formatted(NumberLocale::DE), "1.234.567,5");
+        assert_eq!(formatted(NumberLocale::FR), "1\u{202f}234\u{202f}567,5");
+        assert_eq!(formatted(NumberLocale::CH), "1'234'567.5");
+    }
+
+    #[test]
+    fn picks_locale_from_language_tag() {
+        assert_eq!(NumberLocale::from_language_tag("en-US"), NumberLocale::EN);
+        assert_eq!(NumberLocale::from_language_tag("de"), NumberLocale::DE);
+        assert_eq!(NumberLocale::from_language_tag("de-CH"), NumberLocale::CH);
+        assert_eq!(NumberLocale::from_language_tag("fr_ch"), NumberLocale::CH);
+        assert_eq!(NumberLocale::from_language_tag("fr-FR"), NumberLocale::FR);
+        assert_eq!(NumberLocale::from_language_tag("ja"), NumberLocale::EN);
+        assert_eq!(NumberLocale::from_language_tag(""), NumberLocale::EN);
+    }
+
+    #[test]
+    fn rejects_misplaced_group_separators() {
+        // `1.5` in German would otherwise be read as 15
+        assert_eq!(
+            parse_amount("1.5", 0, NumberLocale::DE),
+            Err(AmountError::Invalid)
+        );
+        assert_eq!(parse_amount("1,5", 1, NumberLocale::DE), Ok(U256::from(15)));
+        assert_eq!(
+            parse_amount("12,34", 0, NumberLocale::EN),
+            Err(AmountError::Invalid)
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn decimal_round_trips(amount in any_u256(), decimals in 0..=MAX_DECIMALS) {
+            if let Ok(decimal) = to_decimal(amount, decimals) {
+                prop_assert_eq!(from_decimal(decimal, decimals), Ok(amount));
+            }
+        }
+
+        #[test]
+        fn decimal_sized_amounts_convert(amount in decimal_sized(), decimals in 0..=MAX_DECIMALS) {
+            let decimal = to_decimal(amount, decimals).unwrap();
+            prop_assert_eq!(from_decimal(decimal, decimals), Ok(amount));
+        }
+
+        #[test]
+        fn formatted_amounts_parse_back(
+            amount in any_u256(),
+            decimals in 0..=MAX_DECIMALS,
+            locale in any_locale(),
+        ) {
+            let formatted = format_amount_exact(amount, decimals, locale).unwrap();
+            prop_assert_eq!(parse_amount(&formatted, decimals, locale), Ok(amount));
+        }
+
+        #[test]
+        fn rejects_more_decimals_than_the_token(
+            integer in any::<u64>(),
+            decimals in 0..MAX_DECIMALS,
+            digits in prop::collection::vec(0..10u8, 0..4),
+            last in 1..10u8,
+        ) {
+            let fraction: String = std::iter::repeat_n('0', decimals as usize)
+                .chain(digits.iter().chain([&last]).map(|digit| (b'0' + digit) as char))
+                .collect();
+            let input = format!("{}.{}", integer, fraction);
+            prop_assert_eq!(
+                parse_amount(&input, decimals, NumberLocale::EN),
+                Err(AmountError::TooManyDecimals(decimals))
+            );
+        }
+
+        #[test]
+        fn rejects_overflow(extra in 1..30usize, decimals in 0..=MAX_DECIMALS) {
+            // Every amount with more integer digits than U256::MAX overflows
+            let integer = format!("1{}", "0".repeat(U256::MAX.to_string().len() - 1 + extra));
+            prop_assert_eq!(
+                parse_amount(&integer, decimals, NumberLocale::EN),
+                Err(AmountError::Overflow)
+            );
+            let above_max = U256::MAX.to_string() + "0";
+            prop_assert_eq!(
+                parse_amount(&above_max, 0, NumberLocale::EN),
+                Err(AmountError::Overflow)
+            );
+        }
+
+        #[test]
+        fn rounds_like_decimal_formatting(
+            amount in any::<u64>(),
+            decimals in 0..=MAX_DECIMALS,
+            fraction_digits in 0..=8u32,
+            locale in any_locale(),
+        ) {
+            // Small enough for the rounded Decimal to keep every digit
+            let amount = U256::from(amount);
+            let decimal = to_decimal(amount, decimals).unwrap();
+            prop_assert_eq!(
+                format_amount(amount, decimals, fraction_digits, locale).unwrap(),
+                format_decimal(decimal, fraction_digits, locale)
+            );
+        }
+
+        #[test]
+        fn exact_formatting_drops_only_trailing_zeros(
+            amount in any_u256(),
+            decimals in 1..=MAX_DECIMALS,
+        ) {
+            let locale = NumberLocale::EN;
+            let padded = format_amount(amount, decimals, decimals as u32, locale).unwrap();
+            let exact = format_amount_exact(amount, decimals, locale).unwrap();
+            prop_assert_eq!(padded.trim_end_matches('0').trim_end_matches('.'), exact);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::address::validate_address;
+
+/// Network contacts are saved for unless another one is given.
+pub const DEFAULT_NETWORK: &str = "ethereum";
+
+fn default_network() -> String {
+    DEFAULT_NETWORK.to_string()
+}
+
+/// A saved recipient in a user's address book. The address is stored with its
+/// EIP-55 checksum.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct Contact {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub address: String,
+    pub network: String,
+    pub note: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateContact {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1 to 64 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[serde(default = "default_network")]
+    #[validate(length(min = 1, max = 32, message = "Network must be 1 to 32 characters"))]
+    pub network: String,
+    #[validate(length(max = 500, message = "Note must be at most 500 characters"))]
+    pub note: Option<String>,
+}
+
+/// Body of `PATCH /api/v1/wallet/contacts/:id`. Fields left out are kept; an
+/// empty note removes it.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct UpdateContact {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1 to 64 characters"))]
+    pub name: Option<String>,
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: Option<String>,
+    #[validate(length(min = 1, max = 32, message = "Network must be 1 to 32 characters"))]
+    pub network: Option<String>,
+    #[validate(length(max = 500, message = "Note must be at most 500 characters"))]
+    pub note: Option<String>,
+}
@@ -0,0 +1,57 @@
+use chrono::{DateTime, Utc};
+use ethers::abi::Abi;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::{address::validate_address, contact::DEFAULT_NETWORK};
+
+/// Largest ABI JSON a contract can be saved with.
+pub const MAX_ABI_LENGTH: usize = 100_000;
+
+fn default_network() -> String {
+    DEFAULT_NETWORK.to_string()
+}
+
+/// Parses ABI JSON as produced by compilers and block explorers.
+pub fn parse_abi(json: &str) -> Result<Abi, serde_json::Error> {
+    serde_json::from_str(json)
+}
+
+/// `validator` check for ABI fields.
+pub fn validate_abi(json: &str) -> Result<(), ValidationError> {
+    parse_abi(json)
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("abi"))
+}
+
+/// A contract the user saved to call from the wallet. The address is stored
+/// with its EIP-55 checksum and the ABI as the JSON it was saved with.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct SavedContract {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub name: String,
+    pub address: String,
+    pub network: String,
+    pub abi: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateSavedContract {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1 to 64 characters"))]
+    pub name: String,
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[serde(default = "default_network")]
+    #[validate(length(min = 1, max = 32, message = "Network must be 1 to 32 characters"))]
+    pub network: String,
+    #[validate(
+        length(max = "MAX_ABI_LENGTH", message = "ABI is too long"),
+        custom(function = "validate_abi", message = "ABI is not valid JSON")
+    )]
+    pub abi: String,
+}
@@ -0,0 +1,30 @@
+use serde::{Deserialize, Serialize};
+
+/// Addresses looked up at most per reverse resolution request.
+pub const MAX_LOOKUP_ADDRESSES: usize = 25;
+
+/// An ENS name and the address it resolves to. Returned by
+/// `GET /api/v1/wallet/ens/resolve` and, for reverse resolution, by
+/// `GET /api/v1/wallet/ens/lookup`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnsResolution {
+    pub name: String,
+    pub address: String,
+}
+
+/// Whether user input looks like an ENS name, such as `vitalik.eth`, rather
+/// than a hex address.
+pub fn is_ens_name(input: &str) -> bool {
+    let input = input.trim();
+    !input.starts_with("0x")
+        && input.contains('.')
+        && input
+            .split('.')
+            .all(|label| !label.is_empty() && !label.chars().any(char::is_whitespace))
+}
+
+/// Normalizes a name for lookup. Only case is folded; names that need full
+/// UTS-46 normalization are passed on as typed.
+pub fn normalize_name(name: &str) -> String {
+    name.trim().to_lowercase()
+}
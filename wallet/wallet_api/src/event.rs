@@ -0,0 +1,29 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    notification::Notification, payment_request::PaymentRequest,
+    scheduled_payment::ScheduledPaymentRun, transaction::Transaction, wallet::WalletBalance,
+};
+
+/// Pushed to the wallet's owner over `GET /api/v1/wallet/events`. Addresses and
+/// hashes are lowercase hex.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum WalletEvent {
+    BalanceChanged(WalletBalance),
+    /// A transaction of one of the user's accounts was recorded or changed
+    /// status, e.g. an incoming transfer was mined or a send was replaced.
+    TransactionRecorded(Transaction),
+    /// A block included a transaction sent from one of the user's accounts.
+    TransactionConfirmed {
+        address: String,
+        tx_hash: String,
+        block_number: u64,
+    },
+    /// A transfer settled one of the user's open payment requests.
+    PaymentRequestPaid(PaymentRequest),
+    /// A notification was raised, e.g. for an incoming transfer.
+    Notification(Notification),
+    /// A scheduled payment came due, or one of its runs was sent.
+    ScheduledPaymentRun(ScheduledPaymentRun),
+}
@@ -1,5 +1,44 @@
-pub mod wallet;
+pub mod account;
+pub mod address;
+pub mod amount;
+pub mod contact;
+pub mod contract;
+pub mod ens;
+pub mod event;
+pub mod notification;
+pub mod payment_request;
+pub mod policy;
+pub mod scheduled_payment;
 pub mod transaction;
+pub mod treasury;
+pub mod wallet;
 
-pub use wallet::{Wallet, WalletError};
-pub use transaction::Transaction;
\ No newline at end of file
+pub use account::{
+    Account, CreateAccount, CreateCustodialAccount, CustodialSend, CustodyStatus, RotateAccountKey,
+    UpdateAccount, UpgradeAccountKey,
+};
+pub use contact::{Contact, CreateContact, UpdateContact};
+pub use contract::{CreateSavedContract, SavedContract};
+pub use ens::EnsResolution;
+pub use event::WalletEvent;
+pub use notification::Notification;
+pub use payment_request::{CreatePaymentRequest, PaymentRequest, PaymentUri};
+pub use policy::{
+    AllowedRecipient, CreateAllowedRecipient, PendingPolicyChange, PolicyOverview, SpendCheck,
+    SpendingPolicy,
+};
+pub use scheduled_payment::{
+    CreateScheduledPayment, ScheduledPayment, ScheduledPaymentRun, SubmitScheduledPaymentRun,
+};
+pub use transaction::{
+    BroadcastResult, BroadcastTransaction, ExportFormat, ExportedTransaction, RecordTransaction,
+    Transaction, TransactionExportQuery, TransactionPage, TransactionQuery,
+};
+pub use treasury::{
+    CreateTreasury, CreateTreasuryProposal, Treasury, TreasuryAuditEntry, TreasuryOverview,
+    TreasuryProposal, TreasurySigner,
+};
+pub use wallet::{
+    BalanceHistory, BalanceHistoryQuery, BalancePeriod, BalancePoint, CreateWallet,
+    RotateWalletKey, Wallet, WalletBalance, WalletError,
+};
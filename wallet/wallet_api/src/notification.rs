@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+/// An in-app notification. Only `incoming_transfer` is raised for now, with the
+/// receiving `address`, the sender as `counterparty` and the `amount` in ether,
+/// or for token transfers in units of the `token`, named by `token_symbol`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct Notification {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub kind: String,
+    pub address: String,
+    pub counterparty: Option<String>,
+    pub amount: Option<Decimal>,
+    pub token: Option<String>,
+    pub token_symbol: Option<String>,
+    pub tx_hash: Option<String>,
+    pub read_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl Notification {
+    pub const INCOMING_TRANSFER: &'static str = "incoming_transfer";
+
+    pub fn is_read(&self) -> bool {
+        self.read_at.is_some()
+    }
+}
@@ -0,0 +1,259 @@
+//! Payment requests and their encoding as EIP-681 `ethereum:` URIs.
+//!
+//! Ether requests are encoded as `ethereum:<address>[@<chain id>]?value=<wei>`,
+//! token requests as a call to the token's `transfer` function:
+//! `ethereum:<token>[@<chain id>]/transfer?address=<address>&uint256=<amount>`.
+//! The memo travels in the `message` parameter, which wallets that do not know
+//! it ignore.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use url::{form_urlencoded, Url};
+use uuid::Uuid;
+use validator::Validate;
+
+use crate::{
+    address::{checksum_address, parse_address, validate_address},
+    amount::{from_decimal, ETHER_DECIMALS},
+};
+
+const SCHEME: &str = "ethereum";
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PaymentUriError {
+    #[error("Not an ethereum: payment link")]
+    Scheme,
+    #[error("Payment link has an invalid address")]
+    Address,
+    #[error("Payment link has an invalid chain id")]
+    ChainId,
+    #[error("Payment link has an invalid amount")]
+    Amount,
+    #[error("Payment link calls an unsupported function: {0}")]
+    Function(String),
+}
+
+/// The contents of an EIP-681 payment link.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub struct PaymentUri {
+    /// Address to pay. For token requests, the ENS name or address given as
+    /// the recipient.
+    pub address: String,
+    pub chain_id: Option<u64>,
+    /// Token contract, `None` for ether.
+    pub token: Option<String>,
+    /// Amount in the token's smallest unit.
+    pub amount: Option<U256>,
+    pub memo: Option<String>,
+}
+
+impl PaymentUri {
+    pub fn to_uri(&self) -> String {
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        let target = match &self.token {
+            Some(token) => {
+                query.append_pair("address", &self.address);
+                if let Some(amount) = self.amount {
+                    query.append_pair("uint256", &amount.to_string());
+                }
+                token
+            }
+            None => {
+                if let Some(amount) = self.amount {
+                    query.append_pair("value", &amount.to_string());
+                }
+                &self.address
+            }
+        };
+        if let Some(memo) = &self.memo {
+            query.append_pair("message", memo);
+        }
+
+        let mut uri = format!("{}:{}", SCHEME, target);
+        if let Some(chain_id) = self.chain_id {
+            uri.push_str(&format!("@{}", chain_id));
+        }
+        if self.token.is_some() {
+            uri.push_str("/transfer");
+        }
+        // Spaces are encoded as %20 rather than +, which not every wallet reads
+        // as a space. A literal + is already escaped as %2B.
+        let query = query.finish().replace('+', "%20");
+        if !query.is_empty() {
+            uri.push('?');
+            uri.push_str(&query);
+        }
+        uri
+    }
+}
+
+/// Parses an EIP-681 number: an integer, optionally with a decimal fraction
+/// and exponent such as `2.014e18`, that has to come out whole.
+fn parse_number(input: &str) -> Result<U256, PaymentUriError> {
+    let (mantissa, exponent) = match input.split_once(['e', 'E']) {
+        Some((mantissa, exponent)) => (
+            mantissa,
+            exponent
+                .parse::<u32>()
+                .map_err(|_| PaymentUriError::Amount)?,
+        ),
+        None => (input, 0),
+    };
+    let (integer, fraction) = mantissa.split_once('.').unwrap_or((mantissa, ""));
+    let fraction = fraction.trim_end_matches('0');
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .chars()
+            .chain(fraction.chars())
+            .all(|c| c.is_ascii_digit())
+        || fraction.len() > exponent as usize
+    {
+        return Err(PaymentUriError::Amount);
+    }
+
+    let digits = format!(
+        "{}{}{}",
+        integer,
+        fraction,
+        "0".repeat(exponent as usize - fraction.len())
+    );
+    U256::from_dec_str(&digits).map_err(|_| PaymentUriError::Amount)
+}
+
+/// Checks a target address, accepting ENS names as EIP-681 does.
+fn parse_target(target: &str) -> Result<String, PaymentUriError> {
+    if target.starts_with("0x") {
+        parse_address(target)
+            .map(|address| checksum_address(&address))
+            .map_err(|_| PaymentUriError::Address)
+    } else if crate::ens::is_ens_name(target) {
+        Ok(target.to_string())
+    } else {
+        Err(PaymentUriError::Address)
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = PaymentUriError;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let url = Url::parse(input.trim()).map_err(|_| PaymentUriError::Scheme)?;
+        if url.scheme() != SCHEME {
+            return Err(PaymentUriError::Scheme);
+        }
+
+        let path = url.path();
+        let path = path.strip_prefix("pay-").unwrap_or(path);
+        let (target, function) = match path.split_once('/') {
+            Some((target, function)) => (target, Some(function)),
+            None => (path, None),
+        };
+        let (target, chain_id) = match target.split_once('@') {
+            Some((target, chain_id)) => (
+                target,
+                Some(
+                    chain_id
+                        .parse::<u64>()
+                        .map_err(|_| PaymentUriError::ChainId)?,
+                ),
+            ),
+            None => (target, None),
+        };
+        let target = parse_target(target)?;
+
+        let mut request = PaymentUri {
+            chain_id,
+            ..Default::default()
+        };
+        for (key, value) in url.query_pairs() {
+            match key.as_ref() {
+                "value" if function.is_none() => request.amount = Some(parse_number(&value)?),
+                "uint256" => request.amount = Some(parse_number(&value)?),
+                "address" => request.address = parse_target(&value)?,
+                "message" => {
+                    request.memo = Some(value.into_owned()).filter(|memo| !memo.is_empty())
+                }
+                _ => {}
+            }
+        }
+
+        match function {
+            None => request.address = target,
+            Some("transfer") => {
+                if request.address.is_empty() {
+                    return Err(PaymentUriError::Address);
+                }
+                request.token = Some(target);
+            }
+            Some(function) => return Err(PaymentUriError::Function(function.to_string())),
+        }
+        Ok(request)
+    }
+}
+
+/// A payment the user asked for. It is `open` until a matching transfer to
+/// `address` is seen on chain, or the user cancels it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct PaymentRequest {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub address: String,
+    pub token: Option<String>,
+    pub token_decimals: i16,
+    pub chain_id: Option<i64>,
+    pub amount: Option<Decimal>,
+    pub memo: Option<String>,
+    pub status: String,
+    pub paid_tx_hash: Option<String>,
+    pub paid_at: Option<DateTime<Utc>>,
+    pub created_at: Option<DateTime<Utc>>,
+}
+
+impl PaymentRequest {
+    pub const OPEN: &'static str = "open";
+    pub const PAID: &'static str = "paid";
+    pub const CANCELLED: &'static str = "cancelled";
+
+    pub fn is_open(&self) -> bool {
+        self.status == Self::OPEN
+    }
+
+    pub fn to_uri(&self) -> PaymentUri {
+        PaymentUri {
+            address: self.address.clone(),
+            chain_id: self
+                .chain_id
+                .and_then(|chain_id| u64::try_from(chain_id).ok()),
+            token: self.token.clone(),
+            amount: self
+                .amount
+                .and_then(|amount| from_decimal(amount, self.token_decimals).ok()),
+            memo: self.memo.clone(),
+        }
+    }
+}
+
+fn default_token_decimals() -> i16 {
+    ETHER_DECIMALS
+}
+
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreatePaymentRequest {
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[validate(custom(function = "validate_address", message = "Token address is invalid"))]
+    pub token: Option<String>,
+    #[serde(default = "default_token_decimals")]
+    #[validate(range(min = 0, max = 28, message = "Token decimals must be 0 to 28"))]
+    pub token_decimals: i16,
+    #[validate(range(min = 1, message = "Chain id must be positive"))]
+    pub chain_id: Option<i64>,
+    pub amount: Option<Decimal>,
+    #[validate(length(max = 200, message = "Memo must be at most 200 characters"))]
+    pub memo: Option<String>,
+}
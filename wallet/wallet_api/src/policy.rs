@@ -0,0 +1,117 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::address::validate_address;
+
+fn validate_limit(limit: &Decimal) -> Result<(), ValidationError> {
+    if limit.is_sign_negative() {
+        Err(ValidationError::new("limit"))
+    } else {
+        Ok(())
+    }
+}
+
+/// Limits on what a user's accounts may send, checked before every send.
+/// Amounts are in ether, and `None` means no limit. While a limit is set,
+/// token transfers and other contract calls are refused, as they cannot be
+/// counted in ether. Sends between the user's own accounts are not
+/// restricted to the allow-list.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct SpendingPolicy {
+    /// Most that may be sent in any 24 hours.
+    #[validate(custom(
+        function = "validate_limit",
+        message = "Daily limit must not be negative"
+    ))]
+    pub daily_limit: Option<Decimal>,
+    #[validate(custom(
+        function = "validate_limit",
+        message = "Per-transaction limit must not be negative"
+    ))]
+    pub per_transaction_limit: Option<Decimal>,
+    /// Only allow-listed recipients whose time-lock has passed may be paid.
+    pub allowlist_only: bool,
+    /// Hours after being allow-listed before a recipient can be paid.
+    #[validate(range(min = 0, max = 744, message = "Time-lock must be 0 to 744 hours"))]
+    pub recipient_delay_hours: i32,
+}
+
+/// A policy change waiting out its cooling-off period. Until `effective_at`
+/// the current policy stays in force, and the change can still be cancelled.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PendingPolicyChange {
+    pub daily_limit: Option<Decimal>,
+    pub per_transaction_limit: Option<Decimal>,
+    pub allowlist_only: bool,
+    pub recipient_delay_hours: i32,
+    pub requested_at: DateTime<Utc>,
+    pub effective_at: DateTime<Utc>,
+}
+
+impl PendingPolicyChange {
+    pub fn policy(&self) -> SpendingPolicy {
+        SpendingPolicy {
+            daily_limit: self.daily_limit,
+            per_transaction_limit: self.per_transaction_limit,
+            allowlist_only: self.allowlist_only,
+            recipient_delay_hours: self.recipient_delay_hours,
+        }
+    }
+}
+
+/// A recipient on a user's allow-list. It can be paid from `active_at`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct AllowedRecipient {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub address: String,
+    pub label: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub active_at: DateTime<Utc>,
+}
+
+impl AllowedRecipient {
+    pub fn is_active(&self, now: DateTime<Utc>) -> bool {
+        self.active_at <= now
+    }
+}
+
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateAllowedRecipient {
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[validate(length(max = 64, message = "Label must be at most 64 characters"))]
+    pub label: Option<String>,
+}
+
+/// Response of `GET /api/v1/wallet/policy`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, Default)]
+pub struct PolicyOverview {
+    pub policy: SpendingPolicy,
+    pub pending: Option<PendingPolicyChange>,
+    pub recipients: Vec<AllowedRecipient>,
+    /// Ether sent in the last 24 hours, counting pending sends.
+    pub spent_today: Decimal,
+}
+
+/// Body of `POST /api/v1/wallet/policy/check`, which a send must pass before
+/// it is signed.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SpendCheck {
+    /// The user's account the send is from.
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[validate(custom(function = "validate_address", message = "Recipient is invalid"))]
+    pub recipient: String,
+    /// In ether.
+    #[validate(custom(function = "validate_limit", message = "Amount must not be negative"))]
+    pub amount: Decimal,
+    /// The send carries calldata, like a token transfer, and can move value
+    /// that `amount` does not show.
+    #[serde(default)]
+    pub contract_call: bool,
+}
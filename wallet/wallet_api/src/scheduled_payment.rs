@@ -0,0 +1,325 @@
+//! Payments the server makes on the user's behalf, once at a set time or
+//! again and again on a cron-like schedule.
+//!
+//! Schedules are five field cron expressions in UTC, `minute hour day month
+//! weekday`, such as `0 9 1 * *` for 9:00 on the first of every month, or one
+//! of the shorthands `@daily`, `@weekly`, `@monthly` and `@yearly`. Weekdays
+//! are numbered as in standard cron, 1 for Monday and 0 or 7 for Sunday, or
+//! named, like `MON`.
+
+use std::str::FromStr;
+
+use chrono::{DateTime, Duration, Utc};
+use cron::Schedule;
+use ethers::{
+    abi::{self, Token},
+    types::{Address, Bytes, U256},
+    utils::id,
+};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::{
+    address::{parse_address, validate_address, AddressError},
+    amount::{from_decimal, AmountError, ETHER_DECIMALS},
+};
+
+/// Runs of a recurring payment must be at least this far apart.
+pub const MIN_SCHEDULE_INTERVAL_MINUTES: i64 = 60;
+
+/// How many upcoming runs are checked against the minimum interval.
+const CHECKED_RUNS: usize = 24;
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ScheduleError {
+    #[error("Schedule is not a valid cron expression")]
+    Invalid,
+    #[error("Schedule never runs")]
+    NeverRuns,
+    #[error("Runs must be at least an hour apart")]
+    TooFrequent,
+}
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum PaymentError {
+    #[error(transparent)]
+    Address(#[from] AddressError),
+    #[error(transparent)]
+    Amount(#[from] AmountError),
+}
+
+/// Weekdays in the order of standard cron, which numbers them from 0 for
+/// Sunday.
+const WEEKDAYS: [&str; 7] = ["SUN", "MON", "TUE", "WED", "THU", "FRI", "SAT"];
+
+/// Rewrites a standard cron weekday field with day names. The cron crate
+/// numbers days from 1 for Sunday, so it would read `1` as Sunday rather than
+/// Monday.
+fn weekdays_as_names(field: &str) -> Result<String, ScheduleError> {
+    if field == "*" || field == "?" {
+        return Ok(field.to_string());
+    }
+    let day = |value: &str| {
+        WEEKDAYS
+            .iter()
+            .position(|name| name.eq_ignore_ascii_case(value))
+            .or_else(|| value.parse().ok().filter(|day| *day <= 7))
+            .ok_or(ScheduleError::Invalid)
+    };
+
+    let mut days = [false; 7];
+    for item in field.split(',') {
+        let (range, step) = match item.split_once('/') {
+            Some((range, step)) => (
+                range,
+                step.parse()
+                    .ok()
+                    .filter(|step| *step > 0)
+                    .ok_or(ScheduleError::Invalid)?,
+            ),
+            None => (item, 1),
+        };
+        let (first, last) = match range.split_once('-') {
+            _ if range == "*" => (0, 6),
+            Some((first, last)) => (day(first)?, day(last)?),
+            // `2/3` steps from Tuesday to the end of the week
+            None if step > 1 => (day(range)?, 6),
+            None => (day(range)?, day(range)?),
+        };
+        if first > last {
+            return Err(ScheduleError::Invalid);
+        }
+        for day in (first..=last).step_by(step) {
+            days[day % 7] = true;
+        }
+    }
+    Ok(WEEKDAYS
+        .iter()
+        .zip(days)
+        .filter(|(_, included)| *included)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Parses a schedule, see the module documentation.
+pub fn parse_schedule(expression: &str) -> Result<Schedule, ScheduleError> {
+    let expression = expression.trim();
+    let fields: Vec<&str> = expression.split_whitespace().collect();
+    // The cron crate counts seconds too, which payments have no use for
+    let expression = if expression.starts_with('@') {
+        expression.to_string()
+    } else if let [minute, hour, day, month, weekday] = fields[..] {
+        format!(
+            "0 {} {} {} {} {}",
+            minute,
+            hour,
+            day,
+            month,
+            weekdays_as_names(weekday)?
+        )
+    } else {
+        return Err(ScheduleError::Invalid);
+    };
+    let schedule = Schedule::from_str(&expression).map_err(|_| ScheduleError::Invalid)?;
+
+    let runs: Vec<_> = schedule.upcoming(Utc).take(CHECKED_RUNS).collect();
+    if runs.is_empty() {
+        return Err(ScheduleError::NeverRuns);
+    }
+    if runs
+        .windows(2)
+        .any(|runs| runs[1] - runs[0] < Duration::minutes(MIN_SCHEDULE_INTERVAL_MINUTES))
+    {
+        return Err(ScheduleError::TooFrequent);
+    }
+    Ok(schedule)
+}
+
+/// First run of `schedule` at or after `from`.
+pub fn first_run(schedule: &Schedule, from: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    schedule.after(&(from - Duration::seconds(1))).next()
+}
+
+fn validate_schedule(expression: &str) -> Result<(), ValidationError> {
+    parse_schedule(expression)
+        .map(|_| ())
+        .map_err(|_| ValidationError::new("schedule"))
+}
+
+fn validate_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if amount.is_sign_positive() && !amount.is_zero() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("amount"))
+    }
+}
+
+fn default_token_decimals() -> i16 {
+    ETHER_DECIMALS
+}
+
+/// A payment from one of the user's accounts. Its `next_run_at` is cleared
+/// once it is `completed` or `cancelled`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct ScheduledPayment {
+    pub id: Uuid,
+    pub user_id: Uuid,
+    pub account_id: Uuid,
+    pub recipient: String,
+    /// Contract of the token to pay in, `None` for ether.
+    pub token: Option<String>,
+    pub token_decimals: i16,
+    /// In ether, or in units of `token`.
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    /// `None` for a one-off payment.
+    pub schedule: Option<String>,
+    pub next_run_at: Option<DateTime<Utc>>,
+    pub status: String,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ScheduledPayment {
+    pub const ACTIVE: &'static str = "active";
+    /// A one-off payment that ran.
+    pub const COMPLETED: &'static str = "completed";
+    pub const CANCELLED: &'static str = "cancelled";
+}
+
+/// Body of `POST /api/v1/wallet/scheduled-payments`. A one-off payment runs at
+/// `run_at`. A recurring one runs on `schedule`, from `run_at` when given.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateScheduledPayment {
+    pub account_id: Uuid,
+    #[validate(custom(function = "validate_address", message = "Recipient is invalid"))]
+    pub recipient: String,
+    #[validate(custom(function = "validate_address", message = "Token address is invalid"))]
+    pub token: Option<String>,
+    #[serde(default = "default_token_decimals")]
+    #[validate(range(min = 0, max = 28, message = "Token decimals must be 0 to 28"))]
+    pub token_decimals: i16,
+    #[validate(custom(function = "validate_amount", message = "Amount must be positive"))]
+    pub amount: Decimal,
+    #[validate(length(max = 200, message = "Memo must be at most 200 characters"))]
+    pub memo: Option<String>,
+    #[validate(custom(function = "validate_schedule", message = "Schedule is invalid"))]
+    pub schedule: Option<String>,
+    pub run_at: Option<DateTime<Utc>>,
+}
+
+/// A time a scheduled payment came due. The payment's details are copied, so
+/// the run shows what was paid. Sends from custodial accounts are signed by
+/// the server right away; the others wait for the user to approve them in
+/// the app, which signs them.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct ScheduledPaymentRun {
+    pub id: Uuid,
+    pub scheduled_payment_id: Uuid,
+    pub account_id: Uuid,
+    pub recipient: String,
+    pub token: Option<String>,
+    pub token_decimals: i16,
+    pub amount: Decimal,
+    pub due_at: DateTime<Utc>,
+    pub status: String,
+    pub tx_hash: Option<String>,
+    /// Why the send could not be made.
+    pub error: Option<String>,
+    /// Status of the recorded transaction, see [`crate::Transaction`].
+    pub transaction_status: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl ScheduledPaymentRun {
+    /// Waiting for the user to sign the send.
+    pub const AWAITING_APPROVAL: &'static str = "awaiting_approval";
+    /// Being signed and sent by the server.
+    pub const SENDING: &'static str = "sending";
+    /// Sent, with the outcome in `transaction_status`.
+    pub const SUBMITTED: &'static str = "submitted";
+    pub const FAILED: &'static str = "failed";
+    /// The user chose not to make the send.
+    pub const SKIPPED: &'static str = "skipped";
+
+    pub fn is_awaiting_approval(&self) -> bool {
+        self.status == Self::AWAITING_APPROVAL
+    }
+
+    /// Recipient, value and data of the transaction that makes the payment:
+    /// a transfer of ether, or a call to the token's `transfer` function.
+    pub fn transaction(&self) -> Result<(Address, U256, Bytes), PaymentError> {
+        let recipient = parse_address(&self.recipient)?;
+        let amount = from_decimal(self.amount, self.token_decimals)?;
+        Ok(match &self.token {
+            Some(token) => {
+                let mut data = id("transfer(address,uint256)").to_vec();
+                data.extend(abi::encode(&[
+                    Token::Address(recipient),
+                    Token::Uint(amount),
+                ]));
+                (parse_address(token)?, U256::zero(), data.into())
+            }
+            None => (recipient, amount, Bytes::new()),
+        })
+    }
+}
+
+/// Body of `POST /api/v1/wallet/scheduled-payment-runs/:id/submit`, with which
+/// the app reports the send it signed for a run.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct SubmitScheduledPaymentRun {
+    #[validate(length(equal = 66, message = "Transaction hash is invalid"))]
+    pub tx_hash: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{Datelike, TimeZone, Weekday};
+
+    use super::*;
+
+    /// Weekdays of the first week of runs, from Monday 2024-07-01.
+    fn weekdays(expression: &str) -> Vec<Weekday> {
+        let schedule = parse_schedule(expression).unwrap();
+        let from = Utc.with_ymd_and_hms(2024, 7, 1, 0, 0, 0).unwrap();
+        schedule
+            .after(&from)
+            .take_while(|run| *run < from + Duration::days(7))
+            .map(|run| run.weekday())
+            .collect()
+    }
+
+    #[test]
+    fn numbers_weekdays_from_sunday_as_zero() {
+        use Weekday::*;
+        assert_eq!(weekdays("0 9 * * 1"), [Mon]);
+        assert_eq!(weekdays("0 9 * * 0"), [Sun]);
+        assert_eq!(weekdays("0 9 * * 7"), [Sun]);
+        assert_eq!(weekdays("0 9 * * 1-5"), [Mon, Tue, Wed, Thu, Fri]);
+        assert_eq!(weekdays("0 9 * * 5-7"), [Fri, Sat, Sun]);
+        assert_eq!(weekdays("0 9 * * 1,3"), [Mon, Wed]);
+        assert_eq!(weekdays("0 9 * * */2"), [Tue, Thu, Sat, Sun]);
+        assert_eq!(weekdays("0 9 * * 2/3"), [Tue, Fri]);
+        assert_eq!(weekdays("0 9 * * mon,FRI"), [Mon, Fri]);
+        assert_eq!(weekdays("0 9 * * *").len(), 7);
+    }
+
+    #[test]
+    fn rejects_invalid_weekdays() {
+        for weekday in ["8", "3-1", "*/0", "MONDAY", ""] {
+            assert_eq!(
+                parse_schedule(&format!("0 9 * * {}", weekday)),
+                Err(ScheduleError::Invalid),
+                "{}",
+                weekday
+            );
+        }
+    }
+}
@@ -1,13 +1,313 @@
-use chrono::{DateTime, Utc};
-use ethers::types::U256;
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
 
-// Define Transaction struct
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+use crate::address::validate_address;
+
+/// A transaction sent from or received by one of the user's accounts. A
+/// transfer between two accounts of the same user has a row for each side.
+/// Amounts are in ether, or in units of the token for token transfers. Fees
+/// are in ether.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
 pub struct Transaction {
-    pub id: String,
-    pub amount: U256,
+    pub id: Uuid,
+    pub user_id: Uuid,
+    /// The user's account.
+    pub address: String,
+    /// `None` for sends that failed before they were broadcast.
+    pub tx_hash: Option<String>,
+    pub direction: String,
+    pub sender: String,
+    /// `None` for contract creations.
+    pub recipient: Option<String>,
+    pub amount: Decimal,
+    /// Contract of the transferred token, `None` for ether.
+    pub token: Option<String>,
+    pub status: String,
+    pub gas_used: Option<i64>,
+    /// Fee the user paid, `None` for incoming transactions.
+    pub fee: Option<Decimal>,
+    pub nonce: Option<i64>,
+    pub block_number: Option<i64>,
+    pub block_time: Option<DateTime<Utc>>,
+    pub chain_id: Option<i64>,
+    pub error: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    /// Sent through the backend's relay. Only the chain watcher changes the
+    /// status of such a send, never the app.
+    #[serde(default)]
+    pub relayed: bool,
+}
+
+impl Transaction {
+    pub const IN: &'static str = "in";
+    pub const OUT: &'static str = "out";
+
+    pub const PENDING: &'static str = "pending";
+    pub const CONFIRMED: &'static str = "confirmed";
+    pub const FAILED: &'static str = "failed";
+    /// Another transaction with the same nonce was mined instead.
+    pub const REPLACED: &'static str = "replaced";
+
+    pub fn is_outgoing(&self) -> bool {
+        self.direction == Self::OUT
+    }
+
+    /// The other side: the recipient of outgoing transactions, the sender of
+    /// incoming ones.
+    pub fn counterparty(&self) -> Option<&str> {
+        if self.is_outgoing() {
+            self.recipient.as_deref()
+        } else {
+            Some(&self.sender)
+        }
+    }
+
+    /// When the transaction was mined, or recorded if it has not been.
+    pub fn date(&self) -> DateTime<Utc> {
+        self.block_time.or(self.created_at).unwrap_or_default()
+    }
+
+    /// Position of the transaction in the history, for paging from it.
+    pub fn cursor(&self) -> String {
+        format!("{}_{}", self.date().timestamp_micros(), self.id)
+    }
+}
+
+/// Reads a cursor made by [`Transaction::cursor`].
+pub fn parse_cursor(cursor: &str) -> Option<(DateTime<Utc>, Uuid)> {
+    let (micros, id) = cursor.split_once('_')?;
+    Some((
+        DateTime::from_timestamp_micros(micros.parse().ok()?)?,
+        id.parse().ok()?,
+    ))
+}
+
+fn validate_direction(direction: &str) -> Result<(), ValidationError> {
+    match direction {
+        Transaction::IN | Transaction::OUT => Ok(()),
+        _ => Err(ValidationError::new("direction")),
+    }
+}
+
+fn validate_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        Transaction::PENDING
+        | Transaction::CONFIRMED
+        | Transaction::FAILED
+        | Transaction::REPLACED => Ok(()),
+        _ => Err(ValidationError::new("status")),
+    }
+}
+
+/// Counterparties are searched for by whole or partial hex address.
+fn validate_counterparty(counterparty: &str) -> Result<(), ValidationError> {
+    let digits = counterparty.strip_prefix("0x").unwrap_or(counterparty);
+    if !digits.is_empty() && digits.len() <= 40 && digits.chars().all(|c| c.is_ascii_hexdigit()) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("counterparty"))
+    }
+}
+
+fn validate_token_filter(token: &str) -> Result<(), ValidationError> {
+    if token == TransactionQuery::ETHER {
+        Ok(())
+    } else {
+        validate_address(token)
+    }
+}
+
+/// Query of `GET /api/v1/wallet/transactions`, which the transactions screen
+/// also keeps in its URL. Dates are inclusive and in UTC. A page starts
+/// `after` or ends `before` the cursor of a transaction on another page.
+#[derive(Validate, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TransactionQuery {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+    #[validate(custom(function = "validate_direction", message = "Direction is invalid"))]
+    pub direction: Option<String>,
+    #[validate(custom(function = "validate_status", message = "Status is invalid"))]
+    pub status: Option<String>,
+    /// Whole or partial address of the other side.
+    #[validate(custom(
+        function = "validate_counterparty",
+        message = "Counterparty must be a hex address or part of one"
+    ))]
+    pub counterparty: Option<String>,
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    /// Contract of a token, or [`TransactionQuery::ETHER`].
+    #[validate(custom(function = "validate_token_filter", message = "Token is invalid"))]
+    pub token: Option<String>,
+    pub after: Option<String>,
+    pub before: Option<String>,
+    #[validate(range(min = 1, max = 100, message = "Limit must be 1 to 100"))]
+    pub limit: Option<i64>,
+}
+
+impl TransactionQuery {
+    /// Token filter for ether transactions.
+    pub const ETHER: &'static str = "eth";
+    pub const DEFAULT_LIMIT: i64 = 20;
+
+    pub fn limit(&self) -> i64 {
+        self.limit.unwrap_or(Self::DEFAULT_LIMIT)
+    }
+
+    /// The first page with the same filters.
+    pub fn first_page(&self) -> Self {
+        Self {
+            after: None,
+            before: None,
+            ..self.clone()
+        }
+    }
+
+    /// Whether the query filters out any transactions.
+    pub fn is_filtered(&self) -> bool {
+        *self
+            != Self {
+                after: self.after.clone(),
+                before: self.before.clone(),
+                limit: self.limit,
+                ..Default::default()
+            }
+    }
+}
+
+/// A page of transactions, newest first, with cursors to the pages around it.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TransactionPage {
+    pub transactions: Vec<Transaction>,
+    /// Transactions on all pages.
+    pub total: i64,
+    /// Cursor for the older transactions, `None` on the last page.
+    pub next: Option<String>,
+    /// Cursor for the newer transactions, `None` on the first page.
+    pub previous: Option<String>,
+}
+
+fn validate_send_status(status: &str) -> Result<(), ValidationError> {
+    match status {
+        Transaction::PENDING | Transaction::CONFIRMED | Transaction::FAILED => Ok(()),
+        _ => Err(ValidationError::new("status")),
+    }
+}
+
+/// Body of `POST /api/v1/wallet/transactions`, with which the app records a
+/// send from one of the user's accounts as it progresses. What the chain
+/// watcher later sees takes precedence.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct RecordTransaction {
+    #[validate(custom(function = "validate_address", message = "Address is invalid"))]
+    pub address: String,
+    #[validate(length(equal = 66, message = "Transaction hash is invalid"))]
+    pub tx_hash: Option<String>,
+    #[validate(custom(function = "validate_address", message = "Recipient is invalid"))]
     pub recipient: String,
-    pub date: DateTime<Utc>,
+    pub amount: Decimal,
+    #[validate(custom(function = "validate_send_status", message = "Status is invalid"))]
+    pub status: String,
+    #[validate(range(min = 0, message = "Nonce is invalid"))]
+    pub nonce: Option<i64>,
+    #[validate(range(min = 0, message = "Block number is invalid"))]
+    pub block_number: Option<i64>,
+    #[validate(range(min = 1, message = "Chain id must be positive"))]
+    pub chain_id: Option<i64>,
+    #[validate(length(max = 500, message = "Error must be at most 500 characters"))]
+    pub error: Option<String>,
+}
+
+/// Body of `POST /api/v1/wallet/transactions/broadcast`, with which the app
+/// hands a transaction it signed to the server to relay to the node.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BroadcastTransaction {
+    /// Hex of the signed transaction, as for `eth_sendRawTransaction`.
+    #[validate(length(min = 1, max = 262144, message = "Transaction is invalid"))]
+    pub raw_transaction: String,
+}
+
+/// Response of `POST /api/v1/wallet/transactions/broadcast`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BroadcastResult {
+    pub tx_hash: String,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv; charset=utf-8",
+            ExportFormat::Json => "application/json",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Query of `GET /api/v1/wallet/transactions/export`. Both dates are
+/// inclusive and in UTC.
+#[derive(Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct TransactionExportQuery {
+    #[serde(default)]
+    pub format: ExportFormat,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl TransactionExportQuery {
+    /// Statements cover at most this many transactions. Longer periods are
+    /// refused rather than cut short, so a statement is never silently
+    /// incomplete.
+    pub const MAX_ROWS: i64 = 100_000;
+}
+
+/// A row of a transaction statement.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExportedTransaction {
+    pub hash: String,
+    pub account: String,
+    pub direction: String,
+    pub status: String,
+    pub counterparty: Option<String>,
+    /// In ether, or in units of `token`.
+    pub amount: Decimal,
+    pub token: Option<String>,
+    /// In ether.
+    pub fee: Option<Decimal>,
+    pub block_number: i64,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl From<Transaction> for ExportedTransaction {
+    fn from(tx: Transaction) -> Self {
+        Self {
+            counterparty: tx.counterparty().map(str::to_string),
+            timestamp: tx.date(),
+            hash: tx.tx_hash.unwrap_or_default(),
+            account: tx.address,
+            direction: tx.direction,
+            status: tx.status,
+            amount: tx.amount.normalize(),
+            token: tx.token,
+            fee: tx.fee.map(|fee| fee.normalize()),
+            block_number: tx.block_number.unwrap_or_default(),
+        }
+    }
 }
@@ -0,0 +1,132 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+use validator::{Validate, ValidationError};
+
+use crate::address::validate_address;
+
+/// Most signers a treasury can have.
+pub const MAX_TREASURY_SIGNERS: usize = 20;
+
+fn validate_amount(amount: &Decimal) -> Result<(), ValidationError> {
+    if amount.is_sign_positive() && !amount.is_zero() {
+        Ok(())
+    } else {
+        Err(ValidationError::new("amount"))
+    }
+}
+
+/// A shared wallet run by staff. Its key is held by the server, which only
+/// signs a send once `threshold` of its signers approved it.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct Treasury {
+    pub id: Uuid,
+    pub name: String,
+    pub address: String,
+    pub threshold: i32,
+    pub created_by: Option<Uuid>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A staff member who can propose and vote on a treasury's sends.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct TreasurySigner {
+    pub user_id: Uuid,
+    pub username: String,
+    pub name: Option<String>,
+}
+
+/// Response of `GET /api/v1/wallet/treasuries`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TreasuryOverview {
+    pub treasury: Treasury,
+    pub signers: Vec<TreasurySigner>,
+}
+
+/// Body of `POST /api/v1/wallet/treasuries`, which only admins may use.
+/// Signers are given by username and must be staff.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateTreasury {
+    #[validate(length(min = 1, max = 64, message = "Name must be 1 to 64 characters"))]
+    pub name: String,
+    #[validate(range(min = 1, message = "At least one approval must be required"))]
+    pub threshold: i32,
+    #[validate(length(
+        min = 1,
+        max = "MAX_TREASURY_SIGNERS",
+        message = "A treasury needs 1 to 20 signers"
+    ))]
+    pub signers: Vec<String>,
+}
+
+/// A send from a treasury, waiting for, or done with, its signers' votes.
+/// Amounts are in ether.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct TreasuryProposal {
+    pub id: Uuid,
+    pub treasury_id: Uuid,
+    pub proposed_by: Option<Uuid>,
+    pub recipient: String,
+    pub amount: Decimal,
+    pub memo: Option<String>,
+    pub status: String,
+    /// Signers who approved.
+    pub approvals: Vec<Uuid>,
+    /// Signers who rejected.
+    pub rejections: Vec<Uuid>,
+    /// Set once the send was executed.
+    pub tx_hash: Option<String>,
+    /// Why the node refused the send.
+    pub error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+impl TreasuryProposal {
+    /// Open for votes.
+    pub const PENDING: &'static str = "pending";
+    /// Enough signers approved, so it can be executed.
+    pub const APPROVED: &'static str = "approved";
+    /// Too many signers rejected for it to be approved.
+    pub const REJECTED: &'static str = "rejected";
+    /// Its send is being broadcast, or the node could not be reached to tell
+    /// whether it was, and `tx_hash` is already set.
+    pub const EXECUTING: &'static str = "executing";
+    pub const EXECUTED: &'static str = "executed";
+    /// The node refused the send.
+    pub const FAILED: &'static str = "failed";
+
+    pub fn has_voted(&self, user_id: Uuid) -> bool {
+        self.approvals.contains(&user_id) || self.rejections.contains(&user_id)
+    }
+}
+
+/// Body of `POST /api/v1/wallet/treasuries/:id/proposals`.
+#[derive(Validate, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CreateTreasuryProposal {
+    #[validate(custom(function = "validate_address", message = "Recipient is invalid"))]
+    pub recipient: String,
+    /// In ether.
+    #[validate(custom(function = "validate_amount", message = "Amount must be positive"))]
+    pub amount: Decimal,
+    #[validate(length(max = 500, message = "Memo must be at most 500 characters"))]
+    pub memo: Option<String>,
+}
+
+/// An entry of a treasury's audit trail.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
+pub struct TreasuryAuditEntry {
+    pub id: Uuid,
+    pub treasury_id: Uuid,
+    pub proposal_id: Option<Uuid>,
+    /// `None` once the user was deleted.
+    pub username: Option<String>,
+    pub event: String,
+    pub detail: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
@@ -1,7 +1,8 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
+use validator::Validate;
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
 #[cfg_attr(feature = "backend", derive(sqlx::FromRow))]
@@ -9,12 +10,134 @@ pub struct Wallet {
     pub id: Uuid,
     pub user_id: Uuid,
     pub encrypted_private_key: String,
+    pub encrypted_mnemonic: Option<String>,
+    pub derivation_path: Option<String>,
     pub address: String,
     pub balance: Decimal,
     pub salt: Vec<u8>,
     pub token_decimals: i16,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub balance_synced_at: Option<DateTime<Utc>>,
+}
+
+/// Response of `GET /api/v1/wallet/balance`. `synced_at` is when the balance
+/// was last read from the chain.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WalletBalance {
+    pub address: String,
+    pub balance: Decimal,
+    pub token_decimals: i16,
+    pub synced_at: Option<DateTime<Utc>>,
+}
+
+/// How far back `GET /api/v1/wallet/balance/history` goes.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BalancePeriod {
+    Day,
+    #[default]
+    Week,
+    Month,
+    Year,
+}
+
+impl BalancePeriod {
+    pub const ALL: [Self; 4] = [Self::Day, Self::Week, Self::Month, Self::Year];
+
+    pub fn duration(&self) -> Duration {
+        match self {
+            Self::Day => Duration::days(1),
+            Self::Week => Duration::weeks(1),
+            Self::Month => Duration::days(30),
+            Self::Year => Duration::days(365),
+        }
+    }
+
+    /// History is downsampled to one point per bucket this wide, so every
+    /// period comes to about a hundred points.
+    pub fn bucket(&self) -> Duration {
+        match self {
+            Self::Day => Duration::minutes(15),
+            Self::Week => Duration::hours(2),
+            Self::Month => Duration::hours(8),
+            Self::Year => Duration::days(4),
+        }
+    }
+}
+
+/// Query of `GET /api/v1/wallet/balance/history`. The wallet's primary account
+/// when `address` is not given.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct BalanceHistoryQuery {
+    pub address: Option<String>,
+    #[serde(default)]
+    pub period: BalancePeriod,
+}
+
+/// The last balance recorded in a bucket, with when it was recorded.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BalancePoint {
+    pub at: DateTime<Utc>,
+    pub balance: Decimal,
+}
+
+/// Response of `GET /api/v1/wallet/balance/history`, oldest point first. The
+/// last point is the current balance.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct BalanceHistory {
+    pub address: String,
+    pub period: BalancePeriod,
+    pub token_decimals: i16,
+    pub points: Vec<BalancePoint>,
+}
+
+/// Body of `POST /api/v1/wallet`. The balance, salt and token decimals are
+/// owned by the server and cannot be set by the client.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CreateWallet {
+    #[validate(length(min = 1, message = "Encrypted private key is required"))]
+    pub encrypted_private_key: String,
+    pub encrypted_mnemonic: Option<String>,
+    #[validate(length(max = 64, message = "Derivation path is too long"))]
+    pub derivation_path: Option<String>,
+    #[validate(length(equal = 42, message = "Address is invalid"))]
+    pub address: String,
+}
+
+/// Body of `POST /api/v1/wallet/rotate-key`. Replaces the wallet's key and,
+/// when the address changes, the accounts derived from the previous key.
+#[derive(Validate, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RotateWalletKey {
+    #[validate(length(min = 1, message = "Encrypted private key is required"))]
+    pub encrypted_private_key: String,
+    pub encrypted_mnemonic: Option<String>,
+    #[validate(length(max = 64, message = "Derivation path is too long"))]
+    pub derivation_path: Option<String>,
+    #[validate(length(equal = 42, message = "Address is invalid"))]
+    pub address: String,
+}
+
+impl From<&Wallet> for CreateWallet {
+    fn from(wallet: &Wallet) -> Self {
+        Self {
+            encrypted_private_key: wallet.encrypted_private_key.clone(),
+            encrypted_mnemonic: wallet.encrypted_mnemonic.clone(),
+            derivation_path: wallet.derivation_path.clone(),
+            address: wallet.address.clone(),
+        }
+    }
+}
+
+impl From<&Wallet> for RotateWalletKey {
+    fn from(wallet: &Wallet) -> Self {
+        Self {
+            encrypted_private_key: wallet.encrypted_private_key.clone(),
+            encrypted_mnemonic: wallet.encrypted_mnemonic.clone(),
+            derivation_path: wallet.derivation_path.clone(),
+            address: wallet.address.clone(),
+        }
+    }
 }
 
 #[derive(Clone, PartialEq, Serialize, Deserialize)]
@@ -0,0 +1,325 @@
+use std::{str::FromStr, sync::Arc, time::Duration};
+
+use app_config::ErrorResponse;
+use chrono::Utc;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::Address,
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{
+    amount::{to_decimal, ETHER_DECIMALS},
+    BalanceHistory, BalanceHistoryQuery, BalancePoint, WalletBalance, WalletEvent,
+};
+
+use crate::WalletEvents;
+
+/// Balances read more recently than this are served from the database.
+const MAX_BALANCE_AGE_SECONDS: i64 = 60;
+/// An explicit refresh is ignored when the balance was synced this recently, so
+/// that many open tabs refreshing at once only cost one RPC call.
+const MIN_REFRESH_INTERVAL_SECONDS: i64 = 5;
+
+/// Keeps `wallets.balance` and `wallet_accounts.balance` in sync with the chain,
+/// and records their history in `wallet_balance_snapshots`.
+#[derive(Debug)]
+pub struct BalanceService {
+    pool: PgPool,
+    provider: Provider<Http>,
+    events: Arc<WalletEvents>,
+}
+
+impl BalanceService {
+    pub fn new(
+        pool: PgPool,
+        rpc_url: &str,
+        events: Arc<WalletEvents>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            pool,
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            events,
+        })
+    }
+
+    /// Returns the balance of one of the user's accounts, the wallet's primary
+    /// account when `address` is not given. The balance is read from the chain
+    /// when the stored one is stale, or on `refresh`.
+    pub async fn get_balance(
+        &self,
+        user_id: Uuid,
+        address: Option<String>,
+        refresh: bool,
+    ) -> Result<WalletBalance, ErrorResponse> {
+        let cached = sqlx::query_as!(
+            WalletBalance,
+            r#"
+            SELECT a.address, a.balance, w.token_decimals, a.balance_synced_at AS synced_at
+            FROM wallet_accounts a
+            JOIN wallets w ON w.user_id = a.user_id
+            WHERE a.user_id = $1 AND a.address = COALESCE($2, w.address)
+            "#,
+            user_id,
+            address
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error fetching balance: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Account not found"))?;
+
+        let max_age = if refresh {
+            MIN_REFRESH_INTERVAL_SECONDS
+        } else {
+            MAX_BALANCE_AGE_SECONDS
+        };
+        let is_fresh = cached
+            .synced_at
+            .is_some_and(|synced_at| Utc::now() - synced_at < chrono::Duration::seconds(max_age));
+        if is_fresh {
+            return Ok(cached);
+        }
+
+        match self.sync_address(&cached.address).await {
+            Ok(balance) => Ok(WalletBalance {
+                balance,
+                synced_at: Some(Utc::now()),
+                ..cached
+            }),
+            // A stale balance is still useful, the timestamp tells its age
+            Err(e) if cached.synced_at.is_some() => {
+                log::warn!("Serving stale balance for {}: {:?}", cached.address, e);
+                Ok(cached)
+            }
+            Err(e) => {
+                log::error!("Error syncing balance for {}: {:?}", cached.address, e);
+                Err(ErrorResponse::origin_is_unreachable())
+            }
+        }
+    }
+
+    /// Returns the downsampled balance history of one of the user's accounts,
+    /// the wallet's primary account when no address is given.
+    pub async fn get_history(
+        &self,
+        user_id: Uuid,
+        query: BalanceHistoryQuery,
+    ) -> Result<BalanceHistory, ErrorResponse> {
+        let internal_error = |e: sqlx::Error| {
+            log::error!("Error fetching balance history: {:?}", e);
+            ErrorResponse::internal_error()
+        };
+
+        let account = sqlx::query!(
+            r#"
+            SELECT a.id, a.address, a.balance, a.balance_synced_at, w.token_decimals
+            FROM wallet_accounts a
+            JOIN wallets w ON w.user_id = a.user_id
+            WHERE a.user_id = $1 AND a.address = COALESCE($2, w.address)
+            "#,
+            user_id,
+            query.address
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(internal_error)?
+        .ok_or_else(|| ErrorResponse::not_found("Account not found"))?;
+
+        let since = Utc::now() - query.period.duration();
+        // The balance the period started with, shown at its start
+        let opening = sqlx::query_scalar!(
+            r#"
+            SELECT balance
+            FROM wallet_balance_snapshots
+            WHERE account_id = $1 AND taken_at < $2
+            ORDER BY taken_at DESC
+            LIMIT 1
+            "#,
+            account.id,
+            since
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(internal_error)?;
+        let recorded = sqlx::query_as!(
+            BalancePoint,
+            r#"
+            SELECT DISTINCT ON (date_bin(make_interval(secs => $3), taken_at, TIMESTAMPTZ 'epoch'))
+                taken_at AS at, balance
+            FROM wallet_balance_snapshots
+            WHERE account_id = $1 AND taken_at >= $2
+            ORDER BY date_bin(make_interval(secs => $3), taken_at, TIMESTAMPTZ 'epoch'), taken_at DESC
+            "#,
+            account.id,
+            since,
+            query.period.bucket().num_seconds() as f64
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(internal_error)?;
+
+        let mut points: Vec<_> = opening
+            .map(|balance| BalancePoint { at: since, balance })
+            .into_iter()
+            .chain(recorded)
+            .collect();
+        if let Some(synced_at) = account.balance_synced_at {
+            if points.last().is_none_or(|last| last.at < synced_at) {
+                points.push(BalancePoint {
+                    at: synced_at,
+                    balance: account.balance,
+                });
+            }
+        }
+
+        Ok(BalanceHistory {
+            address: account.address,
+            period: query.period,
+            token_decimals: account.token_decimals,
+            points,
+        })
+    }
+
+    /// Reads the balance of `address` from the chain and stores it on every
+    /// wallet and account holding that address. Owners of accounts whose
+    /// balance changed are notified, and a snapshot of the new balance is
+    /// taken.
+    pub async fn sync_address(&self, address: &str) -> Result<Decimal, anyhow::Error> {
+        let wei = self
+            .provider
+            .get_balance(Address::from_str(address)?, None)
+            .await?;
+        let balance = to_decimal(wei, ETHER_DECIMALS)?;
+
+        let mut tx = self.pool.begin().await?;
+        sqlx::query!(
+            r#"
+            UPDATE wallets
+            SET balance = $2, balance_synced_at = NOW()
+            WHERE LOWER(address) = LOWER($1)
+            "#,
+            address,
+            balance
+        )
+        .execute(&mut *tx)
+        .await?;
+        let changed = sqlx::query!(
+            r#"
+            WITH previous AS (
+                SELECT id, balance
+                FROM wallet_accounts
+                WHERE LOWER(address) = LOWER($1)
+                FOR UPDATE
+            )
+            UPDATE wallet_accounts a
+            SET balance = $2, balance_synced_at = NOW()
+            FROM previous p
+            WHERE a.id = p.id
+            RETURNING
+                a.id,
+                a.user_id,
+                a.address,
+                a.balance_synced_at,
+                p.balance AS previous_balance,
+                (SELECT token_decimals FROM wallets w WHERE w.user_id = a.user_id) AS "token_decimals!"
+            "#,
+            address,
+            balance
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+        let changed: Vec<_> = changed
+            .into_iter()
+            .filter(|row| row.previous_balance != balance)
+            .collect();
+        sqlx::query!(
+            r#"
+            INSERT INTO wallet_balance_snapshots (account_id, balance)
+            SELECT UNNEST($1::UUID[]), $2
+            "#,
+            &changed.iter().map(|row| row.id).collect::<Vec<_>>(),
+            balance
+        )
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        for row in changed {
+            self.events.publish(
+                row.user_id,
+                WalletEvent::BalanceChanged(WalletBalance {
+                    address: row.address,
+                    balance,
+                    token_decimals: row.token_decimals,
+                    synced_at: row.balance_synced_at,
+                }),
+            );
+        }
+
+        Ok(balance)
+    }
+
+    /// Syncs every account whose balance is older than the maximum age.
+    pub async fn sync_stale(&self) -> Result<(), anyhow::Error> {
+        let addresses = sqlx::query_scalar!(
+            r#"
+            SELECT DISTINCT LOWER(address) AS "address!"
+            FROM wallet_accounts
+            WHERE balance_synced_at IS NULL
+                OR balance_synced_at < NOW() - make_interval(secs => $1)
+            "#,
+            MAX_BALANCE_AGE_SECONDS as f64
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for address in addresses {
+            if let Err(e) = self.sync_address(&address).await {
+                log::error!("Error syncing balance for {}: {:?}", address, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Records the current balance of every account that was synced at least
+    /// once, so the history has a point per interval even when nothing moves.
+    pub async fn snapshot_balances(&self) -> Result<(), anyhow::Error> {
+        sqlx::query!(
+            r#"
+            INSERT INTO wallet_balance_snapshots (account_id, balance)
+            SELECT id, balance
+            FROM wallet_accounts
+            WHERE balance_synced_at IS NOT NULL
+            "#
+        )
+        .execute(&self.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    pub async fn continuously_snapshot(self: Arc<Self>, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.snapshot_balances().await {
+                log::error!("Error taking balance snapshots: {:?}", e);
+            }
+        }
+    }
+
+    pub async fn continuously_sync(self: Arc<Self>, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.sync_stale().await {
+                log::error!("Error syncing balances: {:?}", e);
+            }
+        }
+    }
+}
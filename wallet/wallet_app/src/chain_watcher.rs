@@ -0,0 +1,534 @@
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use chrono::{DateTime, Utc};
+use ethers::{
+    abi::{self, ParamType, Token},
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, Transaction, TransactionReceipt,
+        TransactionRequest, U256,
+    },
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{
+    amount::{to_decimal, ETHER_DECIMALS},
+    Transaction as WalletTransaction, WalletEvent,
+};
+
+use crate::{
+    transactions::NewTransaction, BalanceService, IncomingTransfer, NotificationService,
+    PaymentRequestService, TransactionService, WalletEvents,
+};
+
+/// Most blocks processed in one poll. A watcher further behind, e.g. after
+/// downtime, catches up over the following polls.
+const MAX_BLOCKS_PER_POLL: u64 = 100;
+
+/// Selector of the ERC-20 `transfer(address,uint256)` function.
+const ERC20_TRANSFER_SELECTOR: [u8; 4] = [0xa9, 0x05, 0x9c, 0xbb];
+
+/// Selector of the ERC-20 `decimals()` function.
+const ERC20_DECIMALS_SELECTOR: [u8; 4] = [0x31, 0x3c, 0xe5, 0x67];
+
+/// Selector of the ERC-20 `symbol()` function.
+const ERC20_SYMBOL_SELECTOR: [u8; 4] = [0x95, 0xd8, 0x9b, 0x41];
+
+/// Longest token symbol kept, as stored with notifications.
+const MAX_SYMBOL_LENGTH: usize = 32;
+
+/// An ERC-20 `transfer` call, with the amount in units of the token.
+pub(crate) struct TokenTransfer {
+    pub token: String,
+    pub recipient: String,
+    pub amount: Decimal,
+}
+
+/// The recipient and amount of an ERC-20 `transfer` call.
+fn decode_token_transfer(tx: &Transaction) -> Option<(Address, U256)> {
+    let input = tx.input.as_ref();
+    if input.len() != 68 || input[..4] != ERC20_TRANSFER_SELECTOR {
+        return None;
+    }
+    Some((
+        Address::from_slice(&input[16..36]),
+        U256::from_big_endian(&input[36..68]),
+    ))
+}
+
+/// The symbol returned by an ERC-20 `symbol()` call. Some early tokens return
+/// it as `bytes32` rather than `string`.
+fn decode_token_symbol(output: &[u8]) -> Option<String> {
+    let symbol = match abi::decode(&[ParamType::String], output) {
+        Ok(tokens) => match tokens.into_iter().next() {
+            Some(Token::String(symbol)) => symbol,
+            _ => return None,
+        },
+        Err(_) if output.len() == 32 => String::from_utf8(output.to_vec())
+            .ok()?
+            .trim_end_matches('\0')
+            .to_string(),
+        Err(_) => return None,
+    };
+    let symbol = symbol.trim();
+    (!symbol.is_empty() && symbol.chars().count() <= MAX_SYMBOL_LENGTH).then(|| symbol.to_string())
+}
+
+/// Polls the node for new blocks and notifies users about transactions that
+/// involve their accounts.
+#[derive(Debug)]
+pub struct ChainWatcher {
+    pool: PgPool,
+    provider: Provider<Http>,
+    balances: Arc<BalanceService>,
+    notifications: Arc<NotificationService>,
+    payment_requests: Arc<PaymentRequestService>,
+    transactions: Arc<TransactionService>,
+    events: Arc<WalletEvents>,
+    chain_id: Mutex<Option<u64>>,
+    /// Decimals of the tokens seen, `None` for contracts that do not tell.
+    token_decimals: Mutex<HashMap<Address, Option<i16>>>,
+    /// Symbols of the tokens seen, `None` for contracts that do not tell.
+    token_symbols: Mutex<HashMap<Address, Option<String>>>,
+    /// The last block processed, once read from or saved to the database.
+    last_block: Mutex<Option<u64>>,
+}
+
+impl ChainWatcher {
+    pub fn new(
+        pool: PgPool,
+        rpc_url: &str,
+        balances: Arc<BalanceService>,
+        notifications: Arc<NotificationService>,
+        payment_requests: Arc<PaymentRequestService>,
+        transactions: Arc<TransactionService>,
+        events: Arc<WalletEvents>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            pool,
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            balances,
+            notifications,
+            payment_requests,
+            transactions,
+            events,
+            chain_id: Mutex::new(None),
+            token_decimals: Mutex::new(HashMap::new()),
+            token_symbols: Mutex::new(HashMap::new()),
+            last_block: Mutex::new(None),
+        })
+    }
+
+    /// Users holding each account address, keyed by lowercase address.
+    async fn account_owners(&self) -> Result<HashMap<String, Vec<Uuid>>, anyhow::Error> {
+        let rows =
+            sqlx::query!(r#"SELECT user_id, LOWER(address) AS "address!" FROM wallet_accounts"#)
+                .fetch_all(&self.pool)
+                .await?;
+
+        let mut owners: HashMap<String, Vec<Uuid>> = HashMap::new();
+        for row in rows {
+            owners.entry(row.address).or_default().push(row.user_id);
+        }
+        Ok(owners)
+    }
+
+    pub(crate) async fn chain_id(&self) -> Result<u64, anyhow::Error> {
+        if let Some(chain_id) = *self.chain_id.lock().unwrap() {
+            return Ok(chain_id);
+        }
+        let chain_id = self.provider.get_chainid().await?.as_u64();
+        *self.chain_id.lock().unwrap() = Some(chain_id);
+        Ok(chain_id)
+    }
+
+    /// Decimals of `token`, asked from the contract once.
+    async fn token_decimals(&self, token: Address) -> Option<i16> {
+        if let Some(decimals) = self.token_decimals.lock().unwrap().get(&token) {
+            return *decimals;
+        }
+        let call: TypedTransaction = TransactionRequest::new()
+            .to(token)
+            .data(ERC20_DECIMALS_SELECTOR.to_vec())
+            .into();
+        let decimals = match self.provider.call(&call, None).await {
+            Ok(output) if output.len() == 32 => {
+                let decimals = U256::from_big_endian(&output);
+                (decimals <= 28.into()).then(|| decimals.as_u32() as i16)
+            }
+            Ok(_) => None,
+            // Not cached, as the node may just be unavailable
+            Err(e) => {
+                log::warn!("Error reading decimals of token {:?}: {:?}", token, e);
+                return None;
+            }
+        };
+        self.token_decimals.lock().unwrap().insert(token, decimals);
+        decimals
+    }
+
+    /// Symbol of `token`, asked from the contract once.
+    async fn token_symbol(&self, token: Address) -> Option<String> {
+        if let Some(symbol) = self.token_symbols.lock().unwrap().get(&token) {
+            return symbol.clone();
+        }
+        let call: TypedTransaction = TransactionRequest::new()
+            .to(token)
+            .data(ERC20_SYMBOL_SELECTOR.to_vec())
+            .into();
+        let symbol = match self.provider.call(&call, None).await {
+            Ok(output) => decode_token_symbol(&output),
+            // Not cached, as the node may just be unavailable
+            Err(e) => {
+                log::warn!("Error reading symbol of token {:?}: {:?}", token, e);
+                return None;
+            }
+        };
+        self.token_symbols
+            .lock()
+            .unwrap()
+            .insert(token, symbol.clone());
+        symbol
+    }
+
+    /// The token transfer `tx` makes, if it is one of a token with known
+    /// decimals.
+    pub(crate) async fn token_transfer(&self, tx: &Transaction) -> Option<TokenTransfer> {
+        let token = tx.to?;
+        let (recipient, amount) = decode_token_transfer(tx)?;
+        let decimals = self.token_decimals(token).await?;
+        Some(TokenTransfer {
+            token: format!("{:?}", token),
+            recipient: format!("{:?}", recipient),
+            amount: to_decimal(amount, decimals).ok()?,
+        })
+    }
+
+    /// Settles the open payment request, if any, that a transfer pays.
+    async fn settle_payment_request(
+        &self,
+        address: &str,
+        token: Option<&str>,
+        amount: U256,
+        tx_hash: &str,
+    ) -> Result<(), anyhow::Error> {
+        let chain_id = self.chain_id().await?;
+        if let Some(request) = self
+            .payment_requests
+            .mark_paid(address, token, chain_id, amount, tx_hash)
+            .await?
+        {
+            self.events
+                .publish(request.user_id, WalletEvent::PaymentRequestPaid(request));
+        }
+        Ok(())
+    }
+
+    /// Fee the sender of `tx` paid, from its receipt.
+    fn fee_paid(tx: &Transaction, receipt: &TransactionReceipt) -> Option<U256> {
+        let gas_price = receipt.effective_gas_price.or(tx.gas_price)?;
+        receipt.gas_used.map(|gas_used| gas_used * gas_price)
+    }
+
+    /// Records a transaction and tells its owner about it.
+    async fn record(&self, tx: NewTransaction<'_>) -> Result<(), anyhow::Error> {
+        let recorded = self.transactions.record(tx).await?;
+        self.events
+            .publish(recorded.user_id, WalletEvent::TransactionRecorded(recorded));
+        Ok(())
+    }
+
+    /// The last block processed on this chain, as stored by earlier polls,
+    /// including those of earlier runs of the server.
+    async fn last_block(&self) -> Result<Option<u64>, anyhow::Error> {
+        if let Some(last_block) = *self.last_block.lock().unwrap() {
+            return Ok(Some(last_block));
+        }
+        let chain_id = self.chain_id().await? as i64;
+        let last_block = sqlx::query_scalar!(
+            "SELECT last_block FROM wallet_chain_watcher_progress WHERE chain_id = $1",
+            chain_id
+        )
+        .fetch_optional(&self.pool)
+        .await?
+        .map(|last_block| last_block as u64);
+        *self.last_block.lock().unwrap() = last_block;
+        Ok(last_block)
+    }
+
+    async fn save_last_block(&self, last_block: u64) -> Result<(), anyhow::Error> {
+        let chain_id = self.chain_id().await? as i64;
+        sqlx::query!(
+            r#"
+            INSERT INTO wallet_chain_watcher_progress (chain_id, last_block)
+            VALUES ($1, $2)
+            ON CONFLICT (chain_id) DO UPDATE
+            SET last_block = EXCLUDED.last_block, updated_at = NOW()
+            "#,
+            chain_id,
+            last_block as i64
+        )
+        .execute(&self.pool)
+        .await?;
+        *self.last_block.lock().unwrap() = Some(last_block);
+        Ok(())
+    }
+
+    /// Processes the blocks mined since the last poll, at most
+    /// `MAX_BLOCKS_PER_POLL` of them. The very first poll on a chain only
+    /// records the current block. Progress is saved after each block, so a
+    /// poll that fails, or a server that stopped, carries on from the block
+    /// after the last one processed.
+    pub async fn poll(&self) -> Result<(), anyhow::Error> {
+        let latest = self.provider.get_block_number().await?.as_u64();
+        let Some(last_block) = self.last_block().await? else {
+            return self.save_last_block(latest).await;
+        };
+        if latest <= last_block {
+            return Ok(());
+        }
+
+        let owners = self.account_owners().await?;
+        let mut touched = HashSet::new();
+        let last = latest.min(last_block + MAX_BLOCKS_PER_POLL);
+        for number in (last_block + 1)..=last {
+            // The node may not serve a block it just announced yet
+            let Some(block) = self.provider.get_block_with_txs(number).await? else {
+                break;
+            };
+            let block_time = DateTime::<Utc>::from_timestamp(block.timestamp.as_u64() as i64, 0)
+                .unwrap_or_else(Utc::now);
+
+            for tx in block.transactions {
+                let tx_hash = format!("{:?}", tx.hash);
+                let from = format!("{:?}", tx.from);
+
+                if let Some(users) = owners.get(&from) {
+                    // Token transfers are recorded in units of the token
+                    let token_transfer = self.token_transfer(&tx).await;
+                    let (recipient, amount, token) = match &token_transfer {
+                        Some(transfer) => (
+                            Some(transfer.recipient.clone()),
+                            transfer.amount,
+                            Some(transfer.token.as_str()),
+                        ),
+                        None => (
+                            tx.to.map(|to| format!("{:?}", to)),
+                            to_decimal(tx.value, ETHER_DECIMALS)?,
+                            None,
+                        ),
+                    };
+                    let receipt = self.provider.get_transaction_receipt(tx.hash).await?;
+                    let succeeded = receipt
+                        .as_ref()
+                        .is_none_or(|receipt| receipt.status != Some(0.into()));
+                    let fee = match receipt
+                        .as_ref()
+                        .and_then(|receipt| Self::fee_paid(&tx, receipt))
+                    {
+                        Some(fee) => Some(to_decimal(fee, ETHER_DECIMALS)?),
+                        None => None,
+                    };
+                    let gas_used = receipt
+                        .as_ref()
+                        .and_then(|receipt| receipt.gas_used)
+                        .map(|gas_used| gas_used.as_u64() as i64);
+                    let nonce = tx.nonce.as_u64() as i64;
+                    let chain_id = self.chain_id().await? as i64;
+                    for user_id in users {
+                        self.record(NewTransaction {
+                            user_id: *user_id,
+                            address: &from,
+                            tx_hash: Some(&tx_hash),
+                            direction: WalletTransaction::OUT,
+                            sender: &from,
+                            recipient: recipient.as_deref(),
+                            amount,
+                            token,
+                            status: if succeeded {
+                                WalletTransaction::CONFIRMED
+                            } else {
+                                WalletTransaction::FAILED
+                            },
+                            gas_used,
+                            fee,
+                            nonce: Some(nonce),
+                            block_number: Some(number as i64),
+                            block_time: Some(block_time),
+                            chain_id: Some(chain_id),
+                            error: (!succeeded).then_some("Transaction reverted"),
+                            observed: true,
+                            relayed: false,
+                        })
+                        .await?;
+                        self.events.publish(
+                            *user_id,
+                            WalletEvent::TransactionConfirmed {
+                                address: from.clone(),
+                                tx_hash: tx_hash.clone(),
+                                block_number: number,
+                            },
+                        );
+                    }
+                    // Sends that used the same nonce can never be mined now
+                    for replaced in self
+                        .transactions
+                        .mark_replaced(&from, nonce, &tx_hash)
+                        .await?
+                    {
+                        self.events
+                            .publish(replaced.user_id, WalletEvent::TransactionRecorded(replaced));
+                    }
+                    touched.insert(from.clone());
+                }
+
+                let Some(to) = tx.to.map(|to| format!("{:?}", to)) else {
+                    continue;
+                };
+
+                if let Some((recipient, amount)) = decode_token_transfer(&tx) {
+                    let recipient = format!("{:?}", recipient);
+                    if let Some(users) = owners.get(&recipient) {
+                        // Only the receipt tells whether the token call reverted
+                        let succeeded = self
+                            .provider
+                            .get_transaction_receipt(tx.hash)
+                            .await?
+                            .is_some_and(|receipt| receipt.status == Some(1.into()));
+                        if succeeded {
+                            if let Some(transfer) = self.token_transfer(&tx).await {
+                                let chain_id = self.chain_id().await? as i64;
+                                let symbol = match tx.to {
+                                    Some(token) => self.token_symbol(token).await,
+                                    None => None,
+                                };
+                                for user_id in users {
+                                    self.record(NewTransaction {
+                                        user_id: *user_id,
+                                        address: &recipient,
+                                        tx_hash: Some(&tx_hash),
+                                        direction: WalletTransaction::IN,
+                                        sender: &from,
+                                        recipient: Some(&recipient),
+                                        amount: transfer.amount,
+                                        token: Some(&transfer.token),
+                                        status: WalletTransaction::CONFIRMED,
+                                        block_number: Some(number as i64),
+                                        block_time: Some(block_time),
+                                        chain_id: Some(chain_id),
+                                        observed: true,
+                                        ..Default::default()
+                                    })
+                                    .await?;
+                                    if owners
+                                        .get(&from)
+                                        .is_some_and(|senders| senders.contains(user_id))
+                                    {
+                                        continue;
+                                    }
+                                    if let Some(notification) = self
+                                        .notifications
+                                        .notify_incoming_transfer(
+                                            *user_id,
+                                            IncomingTransfer {
+                                                address: &recipient,
+                                                counterparty: &from,
+                                                amount: transfer.amount,
+                                                token: Some(&transfer.token),
+                                                token_symbol: symbol.as_deref(),
+                                                tx_hash: &tx_hash,
+                                            },
+                                        )
+                                        .await?
+                                    {
+                                        self.events.publish(
+                                            *user_id,
+                                            WalletEvent::Notification(notification),
+                                        );
+                                    }
+                                }
+                            }
+                            self.settle_payment_request(&recipient, Some(&to), amount, &tx_hash)
+                                .await?;
+                        }
+                    }
+                }
+
+                if let Some(users) = owners.get(&to).filter(|_| !tx.value.is_zero()) {
+                    let amount = to_decimal(tx.value, ETHER_DECIMALS)?;
+                    let chain_id = self.chain_id().await? as i64;
+                    for user_id in users {
+                        self.record(NewTransaction {
+                            user_id: *user_id,
+                            address: &to,
+                            tx_hash: Some(&tx_hash),
+                            direction: WalletTransaction::IN,
+                            sender: &from,
+                            recipient: Some(&to),
+                            amount,
+                            status: WalletTransaction::CONFIRMED,
+                            block_number: Some(number as i64),
+                            block_time: Some(block_time),
+                            chain_id: Some(chain_id),
+                            observed: true,
+                            ..Default::default()
+                        })
+                        .await?;
+                        // Transfers between the user's own accounts are not news
+                        if owners
+                            .get(&from)
+                            .is_some_and(|senders| senders.contains(user_id))
+                        {
+                            continue;
+                        }
+                        if let Some(notification) = self
+                            .notifications
+                            .notify_incoming_transfer(
+                                *user_id,
+                                IncomingTransfer {
+                                    address: &to,
+                                    counterparty: &from,
+                                    amount,
+                                    tx_hash: &tx_hash,
+                                    ..Default::default()
+                                },
+                            )
+                            .await?
+                        {
+                            self.events
+                                .publish(*user_id, WalletEvent::Notification(notification));
+                        }
+                    }
+                    self.settle_payment_request(&to, None, tx.value, &tx_hash)
+                        .await?;
+                    touched.insert(to);
+                }
+            }
+
+            self.save_last_block(number).await?;
+        }
+
+        for address in touched {
+            if let Err(e) = self.balances.sync_address(&address).await {
+                log::error!("Error syncing balance for {}: {:?}", address, e);
+            }
+        }
+
+        Ok(())
+    }
+
+    pub async fn continuously_watch(self: Arc<Self>, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.poll().await {
+                log::error!("Error polling for new blocks: {:?}", e);
+            }
+        }
+    }
+}
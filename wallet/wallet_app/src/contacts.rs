@@ -0,0 +1,148 @@
+use app_config::ErrorResponse;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    Contact, CreateContact, UpdateContact,
+};
+
+#[derive(Debug)]
+pub struct ContactService {
+    pool: PgPool,
+}
+
+/// Normalizes an address to its checksummed form. The request bodies are
+/// validated before, so this only fails for unvalidated input.
+pub(crate) fn checksummed(address: &str) -> Result<String, ErrorResponse> {
+    parse_address(address)
+        .map(|address| checksum_address(&address))
+        .map_err(|e| ErrorResponse::bad_request(&e.to_string()))
+}
+
+impl ContactService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_contacts(&self, user_id: Uuid) -> Result<Vec<Contact>, ErrorResponse> {
+        sqlx::query_as!(
+            Contact,
+            "SELECT * FROM wallet_contacts WHERE user_id = $1 ORDER BY LOWER(name), created_at",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error listing contacts: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    pub async fn get_contact(&self, user_id: Uuid, id: Uuid) -> Result<Contact, ErrorResponse> {
+        sqlx::query_as!(
+            Contact,
+            "SELECT * FROM wallet_contacts WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error fetching contact: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Contact not found"))
+    }
+
+    pub async fn create_contact(
+        &self,
+        user_id: Uuid,
+        contact: CreateContact,
+    ) -> Result<Contact, ErrorResponse> {
+        let address = checksummed(&contact.address)?;
+        let note = contact.note.filter(|note| !note.trim().is_empty());
+
+        sqlx::query_as!(
+            Contact,
+            r#"
+            INSERT INTO wallet_contacts (user_id, name, address, network, note)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, network, address) DO NOTHING
+            RETURNING *
+            "#,
+            user_id,
+            contact.name.trim(),
+            address,
+            contact.network,
+            note
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error creating contact: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::conflict("A contact with this address already exists"))
+    }
+
+    pub async fn update_contact(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        contact: UpdateContact,
+    ) -> Result<Contact, ErrorResponse> {
+        let address = contact.address.as_deref().map(checksummed).transpose()?;
+
+        sqlx::query_as!(
+            Contact,
+            r#"
+            UPDATE wallet_contacts
+            SET
+                name = COALESCE($3, name),
+                address = COALESCE($4, address),
+                network = COALESCE($5, network),
+                note = CASE WHEN $6::TEXT IS NULL THEN note ELSE NULLIF(TRIM($6), '') END,
+                updated_at = NOW()
+            WHERE user_id = $1 AND id = $2
+            RETURNING *
+            "#,
+            user_id,
+            id,
+            contact.name.as_deref().map(str::trim),
+            address,
+            contact.network,
+            contact.note
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e) if e.constraint().is_some() => {
+                ErrorResponse::conflict("A contact with this address already exists")
+            }
+            e => {
+                log::error!("Error updating contact: {:?}", e);
+                ErrorResponse::internal_error()
+            }
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Contact not found"))
+    }
+
+    pub async fn delete_contact(&self, user_id: Uuid, id: Uuid) -> Result<(), ErrorResponse> {
+        let result = sqlx::query!(
+            "DELETE FROM wallet_contacts WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error deleting contact: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(ErrorResponse::not_found("Contact not found"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,82 @@
+use app_config::ErrorResponse;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{CreateSavedContract, SavedContract};
+
+use crate::contacts::checksummed;
+
+#[derive(Debug)]
+pub struct ContractService {
+    pool: PgPool,
+}
+
+impl ContractService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_contracts(&self, user_id: Uuid) -> Result<Vec<SavedContract>, ErrorResponse> {
+        sqlx::query_as!(
+            SavedContract,
+            "SELECT * FROM wallet_contracts WHERE user_id = $1 ORDER BY LOWER(name), created_at",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error listing contracts: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    /// Saves a contract. Saving an address again replaces its name and ABI,
+    /// so that an outdated ABI can be corrected.
+    pub async fn save_contract(
+        &self,
+        user_id: Uuid,
+        contract: CreateSavedContract,
+    ) -> Result<SavedContract, ErrorResponse> {
+        let address = checksummed(&contract.address)?;
+
+        sqlx::query_as!(
+            SavedContract,
+            r#"
+            INSERT INTO wallet_contracts (user_id, name, address, network, abi)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, network, address)
+            DO UPDATE SET name = EXCLUDED.name, abi = EXCLUDED.abi, updated_at = NOW()
+            RETURNING *
+            "#,
+            user_id,
+            contract.name.trim(),
+            address,
+            contract.network,
+            contract.abi
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error saving contract: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    pub async fn delete_contract(&self, user_id: Uuid, id: Uuid) -> Result<(), ErrorResponse> {
+        let result = sqlx::query!(
+            "DELETE FROM wallet_contracts WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error deleting contract: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        if result.rows_affected() == 0 {
+            return Err(ErrorResponse::not_found("Contract not found"));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,264 @@
+use std::{future::Future, sync::Arc};
+
+use app_config::ErrorResponse;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::{
+        transaction::eip2718::TypedTransaction, Address, BlockNumber, Bytes, TransactionRequest,
+        U256,
+    },
+};
+use sqlx::PgPool;
+use tokio::sync::Mutex;
+use uuid::Uuid;
+use wallet_api::{
+    address::parse_address,
+    amount::{from_decimal, ETHER_DECIMALS},
+    Account, BroadcastResult, CreateCustodialAccount, CustodialSend, CustodyStatus,
+};
+
+use crate::{services::audit, ChainWatcher, KeyVault, RelayService};
+
+/// A key in the vault, with the account or treasury it belongs to and the
+/// address it sends from.
+pub(crate) struct VaultKey<'a> {
+    pub owner: Uuid,
+    pub handle: &'a [u8],
+    pub address: Address,
+}
+
+/// Signs for accounts whose keys the server holds, in deployments that run
+/// the wallet custodially. Without a key vault configured, custody is off and
+/// every request is refused.
+#[derive(Debug)]
+pub struct CustodyService {
+    pool: PgPool,
+    vault: Option<Arc<dyn KeyVault>>,
+    provider: Provider<Http>,
+    watcher: Arc<ChainWatcher>,
+    relay: Arc<RelayService>,
+    /// Held from taking a nonce until the send reaches the node, so that
+    /// concurrent sends from one account do not get the same nonce.
+    sending: Mutex<()>,
+}
+
+fn internal_error(action: &str, e: impl std::fmt::Debug) -> ErrorResponse {
+    log::error!("Error {}: {:?}", action, e);
+    ErrorResponse::internal_error()
+}
+
+impl CustodyService {
+    pub fn new(
+        pool: PgPool,
+        vault: Option<Arc<dyn KeyVault>>,
+        rpc_url: &str,
+        watcher: Arc<ChainWatcher>,
+        relay: Arc<RelayService>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            pool,
+            vault,
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            watcher,
+            relay,
+            sending: Mutex::new(()),
+        })
+    }
+
+    pub fn status(&self) -> CustodyStatus {
+        CustodyStatus {
+            enabled: self.vault.is_some(),
+        }
+    }
+
+    fn vault(&self) -> Result<&Arc<dyn KeyVault>, ErrorResponse> {
+        self.vault
+            .as_ref()
+            .ok_or_else(|| ErrorResponse::bad_request("Custody is not enabled on this server"))
+    }
+
+    /// Generates a key in the vault for `owner`, the account or treasury it
+    /// is for, returning its handle and address.
+    pub(crate) async fn create_key(&self, owner: Uuid) -> Result<(Vec<u8>, String), ErrorResponse> {
+        let (handle, address) = self
+            .vault()?
+            .create_key(owner)
+            .await
+            .map_err(|e| internal_error("creating custodial key", e))?;
+        Ok((handle, format!("{:?}", address)))
+    }
+
+    /// Creates an account with a key held in the vault.
+    pub async fn create_account(
+        &self,
+        user_id: Uuid,
+        account: CreateCustodialAccount,
+    ) -> Result<Account, ErrorResponse> {
+        self.vault()?;
+        // Accounts belong to a wallet, so one has to be set up first
+        sqlx::query!("SELECT id FROM wallets WHERE user_id = $1", user_id)
+            .fetch_optional(&self.pool)
+            .await
+            .map_err(|e| internal_error("fetching wallet", e))?
+            .ok_or_else(|| ErrorResponse::not_found("Wallet not found"))?;
+
+        let account_id = Uuid::new_v4();
+        let (handle, address) = self.create_key(account_id).await?;
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        let created = sqlx::query_as!(
+            Account,
+            r#"
+            INSERT INTO wallet_accounts (id, user_id, label, encrypted_private_key, address, custodial)
+            VALUES ($1, $2, $3, '', $4, TRUE)
+            RETURNING *
+            "#,
+            account_id,
+            user_id,
+            account.label,
+            address
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal_error("creating custodial account", e))?;
+        sqlx::query!(
+            r#"
+            INSERT INTO wallet_custodial_keys (account_id, user_id, key_handle)
+            VALUES ($1, $2, $3)
+            "#,
+            created.id,
+            user_id,
+            handle
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal_error("storing custodial key", e))?;
+        audit(
+            &mut tx,
+            user_id,
+            "custodial_account_created",
+            Some(created.id),
+            None,
+            &created.address,
+        )
+        .await?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing custodial account", e))?;
+
+        Ok(created)
+    }
+
+    /// Builds, signs and relays a send from a custodial account. The relay
+    /// holds it to the user's spending policy before it reaches the node.
+    pub async fn send(
+        &self,
+        user_id: Uuid,
+        send: CustodialSend,
+    ) -> Result<BroadcastResult, ErrorResponse> {
+        let to = parse_address(&send.recipient)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let value = from_decimal(send.amount, ETHER_DECIMALS)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+
+        self.send_from_account(user_id, send.account_id, to, value, Bytes::new())
+            .await
+    }
+
+    /// Signs and relays a transaction from a custodial account of the user,
+    /// such as a token transfer when `data` is given.
+    pub(crate) async fn send_from_account(
+        &self,
+        user_id: Uuid,
+        account_id: Uuid,
+        to: Address,
+        value: U256,
+        data: Bytes,
+    ) -> Result<BroadcastResult, ErrorResponse> {
+        self.vault()?;
+        let key = sqlx::query!(
+            r#"
+            SELECT a.address, k.key_handle
+            FROM wallet_custodial_keys k
+            JOIN wallet_accounts a ON a.id = k.account_id
+            WHERE k.account_id = $1 AND k.user_id = $2
+            "#,
+            account_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching custodial key", e))?
+        .ok_or_else(|| ErrorResponse::not_found("Custodial account not found"))?;
+
+        let from = parse_address(&key.address).map_err(|e| internal_error("parsing address", e))?;
+        let key = VaultKey {
+            owner: account_id,
+            handle: &key.key_handle,
+            address: from,
+        };
+        self.sign_and_submit(key, to, value, data, |raw| async move {
+            self.relay.broadcast(user_id, &raw.to_string()).await
+        })
+        .await
+    }
+
+    /// Builds a transaction from the address of a vault key, signs it and
+    /// hands it to `submit`. Sends are serialized until `submit` returns, so that
+    /// concurrent sends do not get the same nonce.
+    pub(crate) async fn sign_and_submit<F, Fut, T>(
+        &self,
+        key: VaultKey<'_>,
+        to: Address,
+        value: U256,
+        data: Bytes,
+        submit: F,
+    ) -> Result<T, ErrorResponse>
+    where
+        F: FnOnce(Bytes) -> Fut,
+        Fut: Future<Output = Result<T, ErrorResponse>>,
+    {
+        let vault = self.vault()?;
+        let chain_id = self
+            .watcher
+            .chain_id()
+            .await
+            .map_err(|e| internal_error("reading chain id", e))?;
+
+        let _sending = self.sending.lock().await;
+        let nonce = self
+            .provider
+            .get_transaction_count(key.address, Some(BlockNumber::Pending.into()))
+            .await
+            .map_err(|e| internal_error("reading nonce", e))?;
+        let gas_price = self
+            .provider
+            .get_gas_price()
+            .await
+            .map_err(|e| internal_error("reading gas price", e))?;
+        let mut tx: TypedTransaction = TransactionRequest::new()
+            .from(key.address)
+            .to(to)
+            .value(value)
+            .data(data)
+            .nonce(nonce)
+            .gas_price(gas_price)
+            .chain_id(chain_id)
+            .into();
+        let gas = self.provider.estimate_gas(&tx, None).await.map_err(|e| {
+            log::error!("Error estimating gas: {:?}", e);
+            ErrorResponse::bad_request("The transaction would fail, e.g. for lack of funds")
+        })?;
+        tx.set_gas(gas);
+
+        let signature = vault
+            .sign_transaction(key.owner, key.handle, &tx)
+            .await
+            .map_err(|e| internal_error("signing custodial transaction", e))?;
+        submit(tx.rlp_signed(&signature)).await
+    }
+}
@@ -0,0 +1,102 @@
+use std::{
+    collections::HashMap,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+use app_config::ErrorResponse;
+use ethers::{
+    providers::{Http, Middleware, Provider, ProviderError},
+    types::Address,
+};
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    ens::{is_ens_name, normalize_name},
+    EnsResolution,
+};
+
+/// How long a reverse lookup, including one that found no name, is reused.
+const REVERSE_LOOKUP_TTL: Duration = Duration::from_secs(10 * 60);
+
+/// Resolves ENS names through the registry at `ENS_REGISTRY_ADDRESS`, or the
+/// mainnet registry when none is configured.
+#[derive(Debug)]
+pub struct EnsService {
+    provider: Provider<Http>,
+    reverse_cache: Mutex<HashMap<Address, (Option<String>, Instant)>>,
+}
+
+impl EnsService {
+    pub fn new(rpc_url: &str, registry: Option<Address>) -> Result<Self, anyhow::Error> {
+        let provider = Provider::<Http>::try_from(rpc_url)?;
+        Ok(Self {
+            provider: match registry {
+                Some(registry) => provider.ens(registry),
+                None => provider,
+            },
+            reverse_cache: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub async fn resolve(&self, name: &str) -> Result<EnsResolution, ErrorResponse> {
+        let name = normalize_name(name);
+        if !is_ens_name(&name) {
+            return Err(ErrorResponse::bad_request("Not an ENS name"));
+        }
+
+        match self.provider.resolve_name(&name).await {
+            Ok(address) if !address.is_zero() => Ok(EnsResolution {
+                name,
+                address: checksum_address(&address),
+            }),
+            Ok(_) | Err(ProviderError::EnsError(_)) => Err(ErrorResponse::not_found(
+                "Name does not resolve to an address",
+            )),
+            Err(e) => {
+                log::error!("Error resolving {}: {:?}", name, e);
+                Err(ErrorResponse::origin_is_unreachable())
+            }
+        }
+    }
+
+    /// Finds the primary names of `addresses`. Addresses without a name, or
+    /// whose name does not resolve back to them, are left out.
+    pub async fn lookup(&self, addresses: &[String]) -> Result<Vec<EnsResolution>, ErrorResponse> {
+        let mut resolutions = Vec::new();
+        for address in addresses {
+            let address =
+                parse_address(address).map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+            if let Some(name) = self.lookup_address(address).await {
+                resolutions.push(EnsResolution {
+                    name,
+                    address: checksum_address(&address),
+                });
+            }
+        }
+        Ok(resolutions)
+    }
+
+    async fn lookup_address(&self, address: Address) -> Option<String> {
+        if let Some((name, looked_up_at)) = self.reverse_cache.lock().unwrap().get(&address) {
+            if looked_up_at.elapsed() < REVERSE_LOOKUP_TTL {
+                return name.clone();
+            }
+        }
+
+        let name = match self.provider.lookup_address(address).await {
+            Ok(name) => Some(name),
+            Err(ProviderError::EnsError(_) | ProviderError::EnsNotOwned(_)) => None,
+            Err(e) => {
+                // Not cached, so that the lookup is retried once the node is back
+                log::warn!("Error looking up ENS name of {:?}: {:?}", address, e);
+                return None;
+            }
+        };
+
+        self.reverse_cache
+            .lock()
+            .unwrap()
+            .insert(address, (name.clone(), Instant::now()));
+        name
+    }
+}
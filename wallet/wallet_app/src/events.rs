@@ -0,0 +1,34 @@
+use tokio::sync::broadcast;
+use uuid::Uuid;
+use wallet_api::WalletEvent;
+
+/// Events a slow subscriber may fall behind by before it starts missing some.
+const EVENT_BUFFER: usize = 256;
+
+/// Fans wallet events out to the users' open event streams.
+#[derive(Debug)]
+pub struct WalletEvents {
+    sender: broadcast::Sender<(Uuid, WalletEvent)>,
+}
+
+impl Default for WalletEvents {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WalletEvents {
+    pub fn new() -> Self {
+        let (sender, _) = broadcast::channel(EVENT_BUFFER);
+        Self { sender }
+    }
+
+    pub fn publish(&self, user_id: Uuid, event: WalletEvent) {
+        // Sending only fails when nobody is listening
+        let _ = self.sender.send((user_id, event));
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<(Uuid, WalletEvent)> {
+        self.sender.subscribe()
+    }
+}
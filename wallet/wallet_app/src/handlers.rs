@@ -1,13 +1,49 @@
-use std::sync::Arc;
+use std::{convert::Infallible, sync::Arc};
 
+use app_config::ErrorResponse;
 use auth_app::user_guard::UserAuthenticatedGuard;
 use axum::{
-    response::{IntoResponse, Response},
+    extract::{Path, Query},
+    http::{header, StatusCode},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
     Extension, Json, Router,
 };
-use wallet_api::Wallet;
+use chrono::Duration;
+use serde::Deserialize;
+use tokio_stream::{wrappers::BroadcastStream, Stream, StreamExt};
+use uuid::Uuid;
+use validator::Validate;
+use wallet_api::{
+    ens::MAX_LOOKUP_ADDRESSES, Account, AllowedRecipient, BalanceHistory, BalanceHistoryQuery,
+    BroadcastResult, BroadcastTransaction, Contact, CreateAccount, CreateAllowedRecipient,
+    CreateContact, CreateCustodialAccount, CreatePaymentRequest, CreateSavedContract,
+    CreateScheduledPayment, CreateTreasury, CreateTreasuryProposal, CreateWallet, CustodialSend,
+    CustodyStatus, EnsResolution, ExportFormat, Notification, PaymentRequest, PolicyOverview,
+    RecordTransaction, RotateAccountKey, RotateWalletKey, SavedContract, ScheduledPayment,
+    ScheduledPaymentRun, SpendCheck, SpendingPolicy, SubmitScheduledPaymentRun, Transaction,
+    TransactionExportQuery, TransactionPage, TransactionQuery, TreasuryAuditEntry,
+    TreasuryOverview, TreasuryProposal, UpdateAccount, UpdateContact, UpgradeAccountKey, Wallet,
+    WalletBalance,
+};
+
+use crate::{
+    transactions::to_csv, BalanceService, ContactService, ContractService, CustodyService,
+    EnsService, NotificationService, PaymentRequestService, PolicyService, RelayService,
+    ScheduledPaymentService, TransactionService, TreasuryService, WalletEvents, WalletService,
+};
+
+/// Replacing a stored key requires the user to have logged in this recently.
+const KEY_ROTATION_MAX_LOGIN_AGE_MINUTES: i64 = 5;
+
+/// Changing the spending policy or its allow-list requires the user to have
+/// logged in this recently.
+const POLICY_CHANGE_MAX_LOGIN_AGE_MINUTES: i64 = 5;
 
-use crate::WalletService;
+/// Creating a treasury requires the admin to have logged in this recently.
+const TREASURY_CREATION_MAX_LOGIN_AGE_MINUTES: i64 = 5;
 
 async fn get_wallet_details(
     Extension(wallet_service): Extension<Arc<WalletService>>,
@@ -21,22 +57,1044 @@ async fn get_wallet_details(
     }
 }
 
-async fn update_wallet_details(
+async fn create_wallet(
     Extension(wallet_service): Extension<Arc<WalletService>>,
     user_guard: UserAuthenticatedGuard,
-    Json(wallet): Json<Wallet>,
+    Json(body): Json<CreateWallet>,
 ) -> Result<Json<Wallet>, Response> {
-    match wallet_service
-        .update_wallet(user_guard.user.id, wallet)
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_wallet: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .create_wallet(user_guard.user.id, body)
         .await
-    {
-        Ok(updated_wallet) => Ok(Json(updated_wallet)),
-        Err(e) => Err(e.into_response()),
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn rotate_wallet_key(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<RotateWalletKey>,
+) -> Result<Json<Wallet>, Response> {
+    user_guard.require_recent_login(Duration::minutes(KEY_ROTATION_MAX_LOGIN_AGE_MINUTES))?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in rotate_wallet_key: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .rotate_wallet_key(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+#[derive(Deserialize)]
+struct BalanceQuery {
+    address: Option<String>,
+    #[serde(default)]
+    refresh: bool,
+}
+
+async fn get_balance(
+    Extension(balance_service): Extension<Arc<BalanceService>>,
+    user_guard: UserAuthenticatedGuard,
+    Query(query): Query<BalanceQuery>,
+) -> Result<Json<WalletBalance>, Response> {
+    balance_service
+        .get_balance(user_guard.user.id, query.address, query.refresh)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_balance_history(
+    Extension(balance_service): Extension<Arc<BalanceService>>,
+    user_guard: UserAuthenticatedGuard,
+    Query(query): Query<BalanceHistoryQuery>,
+) -> Result<Json<BalanceHistory>, Response> {
+    balance_service
+        .get_history(user_guard.user.id, query)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+/// Streams the user's wallet events as Server-Sent Events. Each event's data is
+/// a JSON encoded `WalletEvent`.
+async fn wallet_events(
+    Extension(events): Extension<Arc<WalletEvents>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Sse<impl Stream<Item = Result<Event, Infallible>>> {
+    let user_id = user_guard.user.id;
+    let stream =
+        BroadcastStream::new(events.subscribe()).filter_map(move |received| match received {
+            Ok((recipient, event)) if recipient == user_id => Event::default()
+                .json_data(event)
+                .map_err(|e| log::error!("Error encoding wallet event: {:?}", e))
+                .ok()
+                .map(Ok),
+            Ok(_) => None,
+            Err(e) => {
+                log::warn!("Wallet event stream of user {} lagged: {:?}", user_id, e);
+                None
+            }
+        });
+
+    Sse::new(stream).keep_alive(KeepAlive::default())
+}
+
+#[derive(Deserialize)]
+struct NotificationsQuery {
+    #[serde(default)]
+    unread: bool,
+}
+
+async fn list_notifications(
+    Extension(notification_service): Extension<Arc<NotificationService>>,
+    user_guard: UserAuthenticatedGuard,
+    Query(query): Query<NotificationsQuery>,
+) -> Result<Json<Vec<Notification>>, Response> {
+    notification_service
+        .list_notifications(user_guard.user.id, query.unread)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn mark_notification_read(
+    Extension(notification_service): Extension<Arc<NotificationService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Notification>, Response> {
+    notification_service
+        .mark_read(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn mark_all_notifications_read(
+    Extension(notification_service): Extension<Arc<NotificationService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<StatusCode, Response> {
+    notification_service
+        .mark_all_read(user_guard.user.id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_accounts(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<Account>>, Response> {
+    wallet_service
+        .list_accounts(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_account(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Account>, Response> {
+    wallet_service
+        .get_account(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_account(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateAccount>,
+) -> Result<Json<Account>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_account: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .create_account(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn update_account(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateAccount>,
+) -> Result<Json<Account>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in update_account: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .update_account(user_guard.user.id, id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn rotate_account_key(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<RotateAccountKey>,
+) -> Result<Json<Account>, Response> {
+    user_guard.require_recent_login(Duration::minutes(KEY_ROTATION_MAX_LOGIN_AGE_MINUTES))?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in rotate_account_key: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .rotate_account_key(user_guard.user.id, id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+/// Needs no recent login: the app upgrades legacy keys as it unlocks them,
+/// and the service only accepts the same key, signed by it.
+async fn upgrade_account_key(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpgradeAccountKey>,
+) -> Result<Json<Account>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in upgrade_account_key: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    wallet_service
+        .upgrade_account_key(user_guard.user.id, id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn delete_account(
+    Extension(wallet_service): Extension<Arc<WalletService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, Response> {
+    wallet_service
+        .delete_account(user_guard.user.id, id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_contacts(
+    Extension(contact_service): Extension<Arc<ContactService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<Contact>>, Response> {
+    contact_service
+        .list_contacts(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_contact(
+    Extension(contact_service): Extension<Arc<ContactService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Contact>, Response> {
+    contact_service
+        .get_contact(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_contact(
+    Extension(contact_service): Extension<Arc<ContactService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateContact>,
+) -> Result<Json<Contact>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_contact: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    contact_service
+        .create_contact(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn update_contact(
+    Extension(contact_service): Extension<Arc<ContactService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateContact>,
+) -> Result<Json<Contact>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in update_contact: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    contact_service
+        .update_contact(user_guard.user.id, id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn delete_contact(
+    Extension(contact_service): Extension<Arc<ContactService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, Response> {
+    contact_service
+        .delete_contact(user_guard.user.id, id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_contracts(
+    Extension(contract_service): Extension<Arc<ContractService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<SavedContract>>, Response> {
+    contract_service
+        .list_contracts(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn save_contract(
+    Extension(contract_service): Extension<Arc<ContractService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateSavedContract>,
+) -> Result<Json<SavedContract>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in save_contract: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    contract_service
+        .save_contract(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn delete_contract(
+    Extension(contract_service): Extension<Arc<ContractService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, Response> {
+    contract_service
+        .delete_contract(user_guard.user.id, id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_payment_requests(
+    Extension(payment_request_service): Extension<Arc<PaymentRequestService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<PaymentRequest>>, Response> {
+    payment_request_service
+        .list_requests(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_payment_request(
+    Extension(payment_request_service): Extension<Arc<PaymentRequestService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PaymentRequest>, Response> {
+    payment_request_service
+        .get_request(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_payment_request(
+    Extension(payment_request_service): Extension<Arc<PaymentRequestService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreatePaymentRequest>,
+) -> Result<Json<PaymentRequest>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_payment_request: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    payment_request_service
+        .create_request(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn cancel_payment_request(
+    Extension(payment_request_service): Extension<Arc<PaymentRequestService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<PaymentRequest>, Response> {
+    payment_request_service
+        .cancel_request(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_transactions(
+    Extension(transaction_service): Extension<Arc<TransactionService>>,
+    user_guard: UserAuthenticatedGuard,
+    Query(query): Query<TransactionQuery>,
+) -> Result<Json<TransactionPage>, Response> {
+    if let Err(error) = query.validate() {
+        log::error!("Validation error in list_transactions: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid filters submitted.").into_response());
+    }
+
+    transaction_service
+        .query_transactions(user_guard.user.id, &query)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn record_transaction(
+    Extension(transaction_service): Extension<Arc<TransactionService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<RecordTransaction>,
+) -> Result<Json<Transaction>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in record_transaction: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    transaction_service
+        .record_send(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn broadcast_transaction(
+    Extension(relay_service): Extension<Arc<RelayService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<BroadcastTransaction>,
+) -> Result<Json<BroadcastResult>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in broadcast_transaction: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    relay_service
+        .broadcast(user_guard.user.id, &body.raw_transaction)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn export_transactions(
+    Extension(transaction_service): Extension<Arc<TransactionService>>,
+    user_guard: UserAuthenticatedGuard,
+    Query(query): Query<TransactionExportQuery>,
+) -> Result<Response, Response> {
+    let rows = transaction_service
+        .export(user_guard.user.id, &query)
+        .await
+        .map_err(|e| e.into_response())?;
+
+    let body = match query.format {
+        ExportFormat::Csv => to_csv(&rows),
+        ExportFormat::Json => serde_json::to_string_pretty(&rows).map_err(|e| {
+            log::error!("Error serializing transaction export: {:?}", e);
+            ErrorResponse::internal_error().into_response()
+        })?,
+    };
+    let filename = format!(
+        "transactions-{}.{}",
+        chrono::Utc::now().format("%Y-%m-%d"),
+        query.format.extension()
+    );
+
+    Ok((
+        [
+            (
+                header::CONTENT_TYPE,
+                query.format.content_type().to_string(),
+            ),
+            (
+                header::CONTENT_DISPOSITION,
+                format!("attachment; filename=\"{}\"", filename),
+            ),
+        ],
+        body,
+    )
+        .into_response())
+}
+
+async fn get_custody_status(
+    Extension(custody_service): Extension<Arc<CustodyService>>,
+    _user_guard: UserAuthenticatedGuard,
+) -> Json<CustodyStatus> {
+    Json(custody_service.status())
+}
+
+async fn create_custodial_account(
+    Extension(custody_service): Extension<Arc<CustodyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateCustodialAccount>,
+) -> Result<Json<Account>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_custodial_account: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    custody_service
+        .create_account(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn send_custodial(
+    Extension(custody_service): Extension<Arc<CustodyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CustodialSend>,
+) -> Result<Json<BroadcastResult>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in send_custodial: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    custody_service
+        .send(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_policy(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<PolicyOverview>, Response> {
+    policy_service
+        .overview(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn update_policy(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<SpendingPolicy>,
+) -> Result<Json<PolicyOverview>, Response> {
+    user_guard.require_recent_login(Duration::minutes(POLICY_CHANGE_MAX_LOGIN_AGE_MINUTES))?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in update_policy: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    policy_service
+        .request_change(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn cancel_policy_change(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<PolicyOverview>, Response> {
+    user_guard.require_recent_login(Duration::minutes(POLICY_CHANGE_MAX_LOGIN_AGE_MINUTES))?;
+    policy_service
+        .cancel_change(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn add_allowed_recipient(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateAllowedRecipient>,
+) -> Result<Json<AllowedRecipient>, Response> {
+    user_guard.require_recent_login(Duration::minutes(POLICY_CHANGE_MAX_LOGIN_AGE_MINUTES))?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in add_allowed_recipient: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    policy_service
+        .add_recipient(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn remove_allowed_recipient(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<StatusCode, Response> {
+    user_guard.require_recent_login(Duration::minutes(POLICY_CHANGE_MAX_LOGIN_AGE_MINUTES))?;
+    policy_service
+        .remove_recipient(user_guard.user.id, id)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+/// Called by the transaction worker before it signs a send.
+async fn check_spend(
+    Extension(policy_service): Extension<Arc<PolicyService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<SpendCheck>,
+) -> Result<StatusCode, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in check_spend: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
     }
+
+    policy_service
+        .check_spend(user_guard.user.id, &body)
+        .await
+        .map(|_| StatusCode::NO_CONTENT)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_treasuries(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<TreasuryOverview>>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .treasuries(user_guard.user.id, user_guard.user.role == "admin")
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_treasury(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateTreasury>,
+) -> Result<Json<TreasuryOverview>, Response> {
+    user_guard.require_admin()?;
+    user_guard.require_recent_login(Duration::minutes(TREASURY_CREATION_MAX_LOGIN_AGE_MINUTES))?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_treasury: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    treasury_service
+        .create_treasury(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_treasury_proposals(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TreasuryProposal>>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .proposals(id, user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_treasury_proposal(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<CreateTreasuryProposal>,
+) -> Result<Json<TreasuryProposal>, Response> {
+    user_guard.require_staff()?;
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_treasury_proposal: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    treasury_service
+        .propose(id, user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn get_treasury_audit_log(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<Vec<TreasuryAuditEntry>>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .audit_log(id, user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn approve_treasury_proposal(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TreasuryProposal>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .vote(id, user_guard.user.id, true)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn reject_treasury_proposal(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TreasuryProposal>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .vote(id, user_guard.user.id, false)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn execute_treasury_proposal(
+    Extension(treasury_service): Extension<Arc<TreasuryService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<TreasuryProposal>, Response> {
+    user_guard.require_staff()?;
+    treasury_service
+        .execute(id, user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_scheduled_payments(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<ScheduledPayment>>, Response> {
+    scheduled_payment_service
+        .list_payments(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn create_scheduled_payment(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+    Json(body): Json<CreateScheduledPayment>,
+) -> Result<Json<ScheduledPayment>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!("Validation error in create_scheduled_payment: {:#?}", error);
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    scheduled_payment_service
+        .create_payment(user_guard.user.id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn cancel_scheduled_payment(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ScheduledPayment>, Response> {
+    scheduled_payment_service
+        .cancel_payment(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn list_scheduled_payment_runs(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+) -> Result<Json<Vec<ScheduledPaymentRun>>, Response> {
+    scheduled_payment_service
+        .list_runs(user_guard.user.id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn submit_scheduled_payment_run(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+    Json(body): Json<SubmitScheduledPaymentRun>,
+) -> Result<Json<ScheduledPaymentRun>, Response> {
+    if let Err(error) = body.validate() {
+        log::error!(
+            "Validation error in submit_scheduled_payment_run: {:#?}",
+            error
+        );
+        return Err(ErrorResponse::bad_request("Invalid data submitted.").into_response());
+    }
+
+    scheduled_payment_service
+        .submit_run(user_guard.user.id, id, body)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+async fn skip_scheduled_payment_run(
+    Extension(scheduled_payment_service): Extension<Arc<ScheduledPaymentService>>,
+    user_guard: UserAuthenticatedGuard,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ScheduledPaymentRun>, Response> {
+    scheduled_payment_service
+        .skip_run(user_guard.user.id, id)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+#[derive(Deserialize)]
+struct ResolveQuery {
+    name: String,
+}
+
+async fn resolve_ens_name(
+    Extension(ens_service): Extension<Arc<EnsService>>,
+    _user_guard: UserAuthenticatedGuard,
+    Query(query): Query<ResolveQuery>,
+) -> Result<Json<EnsResolution>, Response> {
+    ens_service
+        .resolve(&query.name)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
+}
+
+/// `addresses` is a comma separated list.
+#[derive(Deserialize)]
+struct LookupQuery {
+    addresses: String,
+}
+
+async fn lookup_ens_names(
+    Extension(ens_service): Extension<Arc<EnsService>>,
+    _user_guard: UserAuthenticatedGuard,
+    Query(query): Query<LookupQuery>,
+) -> Result<Json<Vec<EnsResolution>>, Response> {
+    let addresses: Vec<String> = query
+        .addresses
+        .split(',')
+        .map(str::trim)
+        .filter(|address| !address.is_empty())
+        .map(str::to_string)
+        .collect();
+    if addresses.len() > MAX_LOOKUP_ADDRESSES {
+        return Err(ErrorResponse::bad_request("Too many addresses").into_response());
+    }
+
+    ens_service
+        .lookup(&addresses)
+        .await
+        .map(Json)
+        .map_err(|e| e.into_response())
 }
 
 pub fn app() -> Router {
     Router::new()
-        .route("/api/v1/wallet", axum::routing::get(get_wallet_details))
-        .route("/api/v1/wallet", axum::routing::put(update_wallet_details))
+        .route(
+            "/api/v1/wallet",
+            axum::routing::get(get_wallet_details).post(create_wallet),
+        )
+        .route(
+            "/api/v1/wallet/rotate-key",
+            axum::routing::post(rotate_wallet_key),
+        )
+        .route("/api/v1/wallet/balance", axum::routing::get(get_balance))
+        .route(
+            "/api/v1/wallet/balance/history",
+            axum::routing::get(get_balance_history),
+        )
+        .route("/api/v1/wallet/events", axum::routing::get(wallet_events))
+        .route(
+            "/api/v1/wallet/notifications",
+            axum::routing::get(list_notifications),
+        )
+        .route(
+            "/api/v1/wallet/notifications/read",
+            axum::routing::post(mark_all_notifications_read),
+        )
+        .route(
+            "/api/v1/wallet/notifications/:id/read",
+            axum::routing::post(mark_notification_read),
+        )
+        .route(
+            "/api/v1/wallet/accounts",
+            axum::routing::get(list_accounts).post(create_account),
+        )
+        .route(
+            "/api/v1/wallet/accounts/:id",
+            axum::routing::get(get_account)
+                .patch(update_account)
+                .delete(delete_account),
+        )
+        .route(
+            "/api/v1/wallet/accounts/:id/rotate-key",
+            axum::routing::post(rotate_account_key),
+        )
+        .route(
+            "/api/v1/wallet/accounts/:id/upgrade-key",
+            axum::routing::post(upgrade_account_key),
+        )
+        .route(
+            "/api/v1/wallet/contacts",
+            axum::routing::get(list_contacts).post(create_contact),
+        )
+        .route(
+            "/api/v1/wallet/contacts/:id",
+            axum::routing::get(get_contact)
+                .patch(update_contact)
+                .delete(delete_contact),
+        )
+        .route(
+            "/api/v1/wallet/contracts",
+            axum::routing::get(list_contracts).post(save_contract),
+        )
+        .route(
+            "/api/v1/wallet/contracts/:id",
+            axum::routing::delete(delete_contract),
+        )
+        .route(
+            "/api/v1/wallet/payment-requests",
+            axum::routing::get(list_payment_requests).post(create_payment_request),
+        )
+        .route(
+            "/api/v1/wallet/payment-requests/:id",
+            axum::routing::get(get_payment_request),
+        )
+        .route(
+            "/api/v1/wallet/payment-requests/:id/cancel",
+            axum::routing::post(cancel_payment_request),
+        )
+        .route(
+            "/api/v1/wallet/scheduled-payments",
+            axum::routing::get(list_scheduled_payments).post(create_scheduled_payment),
+        )
+        .route(
+            "/api/v1/wallet/scheduled-payments/:id/cancel",
+            axum::routing::post(cancel_scheduled_payment),
+        )
+        .route(
+            "/api/v1/wallet/scheduled-payment-runs",
+            axum::routing::get(list_scheduled_payment_runs),
+        )
+        .route(
+            "/api/v1/wallet/scheduled-payment-runs/:id/submit",
+            axum::routing::post(submit_scheduled_payment_run),
+        )
+        .route(
+            "/api/v1/wallet/scheduled-payment-runs/:id/skip",
+            axum::routing::post(skip_scheduled_payment_run),
+        )
+        .route(
+            "/api/v1/wallet/transactions",
+            axum::routing::get(list_transactions).post(record_transaction),
+        )
+        .route(
+            "/api/v1/wallet/transactions/broadcast",
+            axum::routing::post(broadcast_transaction),
+        )
+        .route(
+            "/api/v1/wallet/transactions/export",
+            axum::routing::get(export_transactions),
+        )
+        .route(
+            "/api/v1/wallet/custody",
+            axum::routing::get(get_custody_status),
+        )
+        .route(
+            "/api/v1/wallet/custody/accounts",
+            axum::routing::post(create_custodial_account),
+        )
+        .route(
+            "/api/v1/wallet/custody/send",
+            axum::routing::post(send_custodial),
+        )
+        .route(
+            "/api/v1/wallet/policy",
+            axum::routing::get(get_policy).put(update_policy),
+        )
+        .route(
+            "/api/v1/wallet/policy/pending",
+            axum::routing::delete(cancel_policy_change),
+        )
+        .route(
+            "/api/v1/wallet/policy/recipients",
+            axum::routing::post(add_allowed_recipient),
+        )
+        .route(
+            "/api/v1/wallet/policy/recipients/:id",
+            axum::routing::delete(remove_allowed_recipient),
+        )
+        .route(
+            "/api/v1/wallet/policy/check",
+            axum::routing::post(check_spend),
+        )
+        .route(
+            "/api/v1/wallet/treasuries",
+            axum::routing::get(list_treasuries).post(create_treasury),
+        )
+        .route(
+            "/api/v1/wallet/treasuries/:id/proposals",
+            axum::routing::get(list_treasury_proposals).post(create_treasury_proposal),
+        )
+        .route(
+            "/api/v1/wallet/treasuries/:id/audit",
+            axum::routing::get(get_treasury_audit_log),
+        )
+        .route(
+            "/api/v1/wallet/treasury-proposals/:id/approve",
+            axum::routing::post(approve_treasury_proposal),
+        )
+        .route(
+            "/api/v1/wallet/treasury-proposals/:id/reject",
+            axum::routing::post(reject_treasury_proposal),
+        )
+        .route(
+            "/api/v1/wallet/treasury-proposals/:id/execute",
+            axum::routing::post(execute_treasury_proposal),
+        )
+        .route(
+            "/api/v1/wallet/ens/resolve",
+            axum::routing::get(resolve_ens_name),
+        )
+        .route(
+            "/api/v1/wallet/ens/lookup",
+            axum::routing::get(lookup_ens_names),
+        )
 }
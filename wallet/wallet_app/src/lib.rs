@@ -1,5 +1,37 @@
+mod balance;
+mod chain_watcher;
+mod clock;
+mod contacts;
+mod contracts;
+mod custody;
+mod ens;
+mod events;
 mod handlers;
+mod notifications;
+mod payment_requests;
+mod policies;
+mod relay;
+mod scheduled_payments;
 mod services;
+mod transactions;
+mod treasury;
+mod vault;
 
+pub use balance::BalanceService;
+pub use chain_watcher::ChainWatcher;
+pub use clock::{Clock, ManualClock, SystemClock};
+pub use contacts::ContactService;
+pub use contracts::ContractService;
+pub use custody::CustodyService;
+pub use ens::EnsService;
+pub use events::WalletEvents;
 pub use handlers::app;
+pub use notifications::{IncomingTransfer, NotificationService};
+pub use payment_requests::PaymentRequestService;
+pub use policies::PolicyService;
+pub use relay::RelayService;
+pub use scheduled_payments::ScheduledPaymentService;
 pub use services::WalletService;
+pub use transactions::TransactionService;
+pub use treasury::TreasuryService;
+pub use vault::{FileKeyVault, KeyVault};
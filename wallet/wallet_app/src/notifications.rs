@@ -0,0 +1,123 @@
+use app_config::ErrorResponse;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::Notification;
+
+/// Notifications kept per user, newest first.
+const MAX_NOTIFICATIONS: i64 = 100;
+
+/// A transfer received by one of a user's accounts. The amount is in ether, or
+/// in units of `token` for token transfers.
+#[derive(Debug, Default)]
+pub struct IncomingTransfer<'a> {
+    pub address: &'a str,
+    pub counterparty: &'a str,
+    pub amount: Decimal,
+    pub token: Option<&'a str>,
+    pub token_symbol: Option<&'a str>,
+    pub tx_hash: &'a str,
+}
+
+#[derive(Debug)]
+pub struct NotificationService {
+    pool: PgPool,
+}
+
+impl NotificationService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_notifications(
+        &self,
+        user_id: Uuid,
+        unread_only: bool,
+    ) -> Result<Vec<Notification>, ErrorResponse> {
+        sqlx::query_as!(
+            Notification,
+            r#"
+            SELECT * FROM wallet_notifications
+            WHERE user_id = $1 AND (NOT $2 OR read_at IS NULL)
+            ORDER BY created_at DESC
+            LIMIT $3
+            "#,
+            user_id,
+            unread_only,
+            MAX_NOTIFICATIONS
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error listing notifications: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    pub async fn mark_read(&self, user_id: Uuid, id: Uuid) -> Result<Notification, ErrorResponse> {
+        sqlx::query_as!(
+            Notification,
+            r#"
+            UPDATE wallet_notifications
+            SET read_at = COALESCE(read_at, NOW())
+            WHERE user_id = $1 AND id = $2
+            RETURNING *
+            "#,
+            user_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error marking notification as read: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Notification not found"))
+    }
+
+    pub async fn mark_all_read(&self, user_id: Uuid) -> Result<(), ErrorResponse> {
+        sqlx::query!(
+            "UPDATE wallet_notifications SET read_at = NOW() WHERE user_id = $1 AND read_at IS NULL",
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map(|_| ())
+        .map_err(|e| {
+            log::error!("Error marking notifications as read: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    /// Records a transfer received by `transfer.address`. Returns `None` when
+    /// the user was already notified about the transaction.
+    pub async fn notify_incoming_transfer(
+        &self,
+        user_id: Uuid,
+        transfer: IncomingTransfer<'_>,
+    ) -> Result<Option<Notification>, anyhow::Error> {
+        let notification = sqlx::query_as!(
+            Notification,
+            r#"
+            INSERT INTO wallet_notifications (
+                user_id, kind, address, counterparty, amount, token, token_symbol, tx_hash
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, kind, tx_hash) DO NOTHING
+            RETURNING *
+            "#,
+            user_id,
+            Notification::INCOMING_TRANSFER,
+            transfer.address,
+            transfer.counterparty,
+            transfer.amount,
+            transfer.token,
+            transfer.token_symbol,
+            transfer.tx_hash
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+
+        Ok(notification)
+    }
+}
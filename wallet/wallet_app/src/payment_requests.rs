@@ -0,0 +1,174 @@
+use app_config::ErrorResponse;
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{amount::from_decimal, CreatePaymentRequest, PaymentRequest};
+
+use crate::{contacts::checksummed, transactions::is_own_account};
+
+#[derive(Debug)]
+pub struct PaymentRequestService {
+    pool: PgPool,
+}
+
+impl PaymentRequestService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list_requests(&self, user_id: Uuid) -> Result<Vec<PaymentRequest>, ErrorResponse> {
+        sqlx::query_as!(
+            PaymentRequest,
+            "SELECT * FROM wallet_payment_requests WHERE user_id = $1 ORDER BY created_at DESC",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error listing payment requests: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    pub async fn get_request(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<PaymentRequest, ErrorResponse> {
+        sqlx::query_as!(
+            PaymentRequest,
+            "SELECT * FROM wallet_payment_requests WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error fetching payment request: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Payment request not found"))
+    }
+
+    /// Creates a request to be paid to one of the user's accounts.
+    pub async fn create_request(
+        &self,
+        user_id: Uuid,
+        request: CreatePaymentRequest,
+    ) -> Result<PaymentRequest, ErrorResponse> {
+        let address = checksummed(&request.address)?;
+        let token = request.token.as_deref().map(checksummed).transpose()?;
+        if let Some(amount) = request.amount {
+            if amount <= Decimal::ZERO {
+                return Err(ErrorResponse::bad_request("Amount must be positive"));
+            }
+            from_decimal(amount, request.token_decimals)
+                .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        }
+        let memo = request.memo.filter(|memo| !memo.trim().is_empty());
+
+        if !is_own_account(&self.pool, user_id, &address).await? {
+            return Err(ErrorResponse::bad_request(
+                "Payments can only be requested to your own accounts",
+            ));
+        }
+
+        sqlx::query_as!(
+            PaymentRequest,
+            r#"
+            INSERT INTO wallet_payment_requests (user_id, address, token, token_decimals, chain_id, amount, memo)
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            RETURNING *
+            "#,
+            user_id,
+            address,
+            token,
+            request.token_decimals,
+            request.chain_id,
+            request.amount,
+            memo
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error creating payment request: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    pub async fn cancel_request(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<PaymentRequest, ErrorResponse> {
+        let request = self.get_request(user_id, id).await?;
+        if !request.is_open() {
+            return Err(ErrorResponse::conflict("Payment request is no longer open"));
+        }
+
+        sqlx::query_as!(
+            PaymentRequest,
+            r#"
+            UPDATE wallet_payment_requests
+            SET status = $3
+            WHERE user_id = $1 AND id = $2 AND status = $4
+            RETURNING *
+            "#,
+            user_id,
+            id,
+            PaymentRequest::CANCELLED,
+            PaymentRequest::OPEN
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error cancelling payment request: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::conflict("Payment request is no longer open"))
+    }
+
+    /// Marks the oldest open request that a transfer of `amount` (in the
+    /// token's smallest unit) to `address` settles as paid. Requests for the
+    /// exact amount are matched before requests without an amount. `token` is
+    /// `None` for ether, and requests for another chain are never matched.
+    pub async fn mark_paid(
+        &self,
+        address: &str,
+        token: Option<&str>,
+        chain_id: u64,
+        amount: U256,
+        tx_hash: &str,
+    ) -> Result<Option<PaymentRequest>, anyhow::Error> {
+        let request = sqlx::query_as!(
+            PaymentRequest,
+            r#"
+            UPDATE wallet_payment_requests
+            SET status = $6, paid_tx_hash = $5, paid_at = NOW()
+            WHERE id = (
+                SELECT id FROM wallet_payment_requests
+                WHERE status = $7
+                    AND LOWER(address) = LOWER($1)
+                    AND LOWER(token) IS NOT DISTINCT FROM LOWER($2)
+                    AND (chain_id IS NULL OR chain_id = $3)
+                    AND (amount IS NULL OR amount * POWER(10::NUMERIC, token_decimals) = $4::TEXT::NUMERIC)
+                ORDER BY amount IS NULL, created_at
+                LIMIT 1
+                FOR UPDATE SKIP LOCKED
+            )
+            RETURNING *
+            "#,
+            address,
+            token,
+            chain_id as i64,
+            amount.to_string(),
+            tx_hash,
+            PaymentRequest::PAID,
+            PaymentRequest::OPEN
+        )
+        .fetch_optional(&self.pool)
+        .await?;
+        Ok(request)
+    }
+}
@@ -0,0 +1,361 @@
+use app_config::ErrorResponse;
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgConnection, PgExecutor, PgPool};
+use uuid::Uuid;
+use wallet_api::{
+    AllowedRecipient, CreateAllowedRecipient, PendingPolicyChange, PolicyOverview, SpendCheck,
+    SpendingPolicy, Transaction,
+};
+
+use crate::{
+    contacts::checksummed,
+    transactions::{is_own_account, record_transaction, NewTransaction},
+};
+
+/// Stores users' spending policies and checks sends against them. Policy
+/// changes only take effect after a cooling-off period, so that someone who
+/// takes over a session cannot lift the limits and drain the wallet at once.
+#[derive(Debug)]
+pub struct PolicyService {
+    pool: PgPool,
+    cooling_off: Duration,
+}
+
+fn internal_error(action: &str, e: sqlx::Error) -> ErrorResponse {
+    log::error!("Error {}: {:?}", action, e);
+    ErrorResponse::internal_error()
+}
+
+impl PolicyService {
+    pub fn new(pool: PgPool, cooling_off: Duration) -> Self {
+        Self { pool, cooling_off }
+    }
+
+    /// The policy in force, after applying a change whose cooling-off period
+    /// has passed.
+    pub async fn active_policy(&self, user_id: Uuid) -> Result<SpendingPolicy, ErrorResponse> {
+        sqlx::query!(
+            r#"
+            WITH due AS (
+                DELETE FROM wallet_policy_changes
+                WHERE user_id = $1 AND effective_at <= NOW()
+                RETURNING *
+            )
+            INSERT INTO wallet_spending_policies (
+                user_id, daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours
+            )
+            SELECT user_id, daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours
+            FROM due
+            ON CONFLICT (user_id) DO UPDATE SET
+                daily_limit = EXCLUDED.daily_limit,
+                per_transaction_limit = EXCLUDED.per_transaction_limit,
+                allowlist_only = EXCLUDED.allowlist_only,
+                recipient_delay_hours = EXCLUDED.recipient_delay_hours,
+                updated_at = NOW()
+            "#,
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| internal_error("applying policy change", e))?;
+
+        let policy = sqlx::query_as!(
+            SpendingPolicy,
+            r#"
+            SELECT daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours
+            FROM wallet_spending_policies
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching spending policy", e))?;
+
+        Ok(policy.unwrap_or_default())
+    }
+
+    async fn pending_change(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Option<PendingPolicyChange>, ErrorResponse> {
+        sqlx::query_as!(
+            PendingPolicyChange,
+            r#"
+            SELECT daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours,
+                requested_at, effective_at
+            FROM wallet_policy_changes
+            WHERE user_id = $1
+            "#,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching policy change", e))
+    }
+
+    async fn recipients(&self, user_id: Uuid) -> Result<Vec<AllowedRecipient>, ErrorResponse> {
+        sqlx::query_as!(
+            AllowedRecipient,
+            "SELECT * FROM wallet_allowed_recipients WHERE user_id = $1 ORDER BY created_at",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| internal_error("listing allowed recipients", e))
+    }
+
+    /// Ether the user's accounts sent in the last 24 hours, counting sends
+    /// that are still pending.
+    async fn spent_today(
+        executor: impl PgExecutor<'_>,
+        user_id: Uuid,
+    ) -> Result<Decimal, ErrorResponse> {
+        sqlx::query_scalar!(
+            r#"
+            SELECT COALESCE(SUM(amount), 0) AS "spent!" FROM wallet_transactions
+            WHERE user_id = $1
+                AND direction = $2
+                AND token IS NULL
+                AND status IN ($3, $4)
+                AND COALESCE(block_time, created_at) > NOW() - INTERVAL '24 hours'
+            "#,
+            user_id,
+            Transaction::OUT,
+            Transaction::PENDING,
+            Transaction::CONFIRMED
+        )
+        .fetch_one(executor)
+        .await
+        .map_err(|e| internal_error("summing spent amount", e))
+    }
+
+    pub async fn overview(&self, user_id: Uuid) -> Result<PolicyOverview, ErrorResponse> {
+        Ok(PolicyOverview {
+            policy: self.active_policy(user_id).await?,
+            pending: self.pending_change(user_id).await?,
+            recipients: self.recipients(user_id).await?,
+            spent_today: Self::spent_today(&self.pool, user_id).await?,
+        })
+    }
+
+    /// Schedules `policy` to replace the current one after the cooling-off
+    /// period. A change that is already pending is replaced, and its period
+    /// starts over.
+    pub async fn request_change(
+        &self,
+        user_id: Uuid,
+        policy: SpendingPolicy,
+    ) -> Result<PolicyOverview, ErrorResponse> {
+        let effective_at = Utc::now() + self.cooling_off;
+        sqlx::query!(
+            r#"
+            INSERT INTO wallet_policy_changes (
+                user_id, daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours,
+                effective_at
+            )
+            VALUES ($1, $2, $3, $4, $5, $6)
+            ON CONFLICT (user_id) DO UPDATE SET
+                daily_limit = EXCLUDED.daily_limit,
+                per_transaction_limit = EXCLUDED.per_transaction_limit,
+                allowlist_only = EXCLUDED.allowlist_only,
+                recipient_delay_hours = EXCLUDED.recipient_delay_hours,
+                requested_at = NOW(),
+                effective_at = EXCLUDED.effective_at
+            "#,
+            user_id,
+            policy.daily_limit,
+            policy.per_transaction_limit,
+            policy.allowlist_only,
+            policy.recipient_delay_hours,
+            effective_at
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| internal_error("requesting policy change", e))?;
+
+        self.overview(user_id).await
+    }
+
+    /// Drops the pending change, keeping the current policy.
+    pub async fn cancel_change(&self, user_id: Uuid) -> Result<PolicyOverview, ErrorResponse> {
+        sqlx::query!(
+            "DELETE FROM wallet_policy_changes WHERE user_id = $1",
+            user_id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| internal_error("cancelling policy change", e))?;
+
+        self.overview(user_id).await
+    }
+
+    /// Allow-lists a recipient, who can be paid once the policy's time-lock
+    /// has passed.
+    pub async fn add_recipient(
+        &self,
+        user_id: Uuid,
+        recipient: CreateAllowedRecipient,
+    ) -> Result<AllowedRecipient, ErrorResponse> {
+        let address = checksummed(&recipient.address)?;
+        let label = recipient.label.filter(|label| !label.trim().is_empty());
+        let policy = self.active_policy(user_id).await?;
+        let active_at = Utc::now() + Duration::hours(policy.recipient_delay_hours as i64);
+
+        sqlx::query_as!(
+            AllowedRecipient,
+            r#"
+            INSERT INTO wallet_allowed_recipients (user_id, address, label, active_at)
+            VALUES ($1, $2, $3, $4)
+            RETURNING *
+            "#,
+            user_id,
+            address,
+            label,
+            active_at
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(e)
+                if e.constraint() == Some("wallet_allowed_recipients_address_idx") =>
+            {
+                ErrorResponse::conflict("This recipient is already allow-listed")
+            }
+            e => internal_error("adding allowed recipient", e),
+        })
+    }
+
+    pub async fn remove_recipient(&self, user_id: Uuid, id: Uuid) -> Result<(), ErrorResponse> {
+        let result = sqlx::query!(
+            "DELETE FROM wallet_allowed_recipients WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| internal_error("removing allowed recipient", e))?;
+
+        if result.rows_affected() == 0 {
+            return Err(ErrorResponse::not_found("Allowed recipient not found"));
+        }
+        Ok(())
+    }
+
+    /// Rejects a send that the user's policy does not allow, with the reason.
+    pub async fn check_spend(&self, user_id: Uuid, send: &SpendCheck) -> Result<(), ErrorResponse> {
+        let mut db = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        self.check_spend_locked(&mut db, user_id, send).await
+    }
+
+    /// Checks a send like `check_spend` and records it as `tx` when it is
+    /// allowed. The check and the record share a database transaction that
+    /// holds the lock on the user's policy, so that concurrent sends are
+    /// counted against the daily limit one after the other.
+    pub(crate) async fn check_and_record(
+        &self,
+        user_id: Uuid,
+        send: &SpendCheck,
+        tx: NewTransaction<'_>,
+    ) -> Result<Transaction, ErrorResponse> {
+        let mut db = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        self.check_spend_locked(&mut db, user_id, send).await?;
+        let recorded = record_transaction(&mut *db, tx)
+            .await
+            .map_err(|e| internal_error("recording transaction", e))?;
+        db.commit()
+            .await
+            .map_err(|e| internal_error("committing transaction", e))?;
+        Ok(recorded)
+    }
+
+    /// Checks a send against the policy, which stays locked in `db` until its
+    /// transaction ends. Users without a policy row have no limits to race.
+    async fn check_spend_locked(
+        &self,
+        db: &mut PgConnection,
+        user_id: Uuid,
+        send: &SpendCheck,
+    ) -> Result<(), ErrorResponse> {
+        if !is_own_account(&self.pool, user_id, &send.address).await? {
+            return Err(ErrorResponse::bad_request(
+                "Sends can only be made from your own accounts",
+            ));
+        }
+        // Applies a due change before the policy is locked
+        self.active_policy(user_id).await?;
+        let policy = sqlx::query_as!(
+            SpendingPolicy,
+            r#"
+            SELECT daily_limit, per_transaction_limit, allowlist_only, recipient_delay_hours
+            FROM wallet_spending_policies
+            WHERE user_id = $1
+            FOR UPDATE
+            "#,
+            user_id
+        )
+        .fetch_optional(&mut *db)
+        .await
+        .map_err(|e| internal_error("locking spending policy", e))?
+        .unwrap_or_default();
+
+        // Limits are in ether, so what a token transfer or another contract
+        // call moves cannot be counted against them
+        if send.contract_call
+            && (policy.per_transaction_limit.is_some() || policy.daily_limit.is_some())
+        {
+            return Err(ErrorResponse::forbidden(
+                "Token transfers and contract calls are blocked while a spending limit is set",
+            ));
+        }
+        if let Some(limit) = policy.per_transaction_limit {
+            if send.amount > limit {
+                return Err(ErrorResponse::forbidden(&format!(
+                    "The amount is above your per-transaction limit of {} ETH",
+                    limit.normalize()
+                )));
+            }
+        }
+        if let Some(limit) = policy.daily_limit {
+            let spent = Self::spent_today(&mut *db, user_id).await?;
+            if spent + send.amount > limit {
+                return Err(ErrorResponse::forbidden(&format!(
+                    "The amount is above what is left of your daily limit: {} of {} ETH",
+                    (limit - spent).max(Decimal::ZERO).normalize(),
+                    limit.normalize()
+                )));
+            }
+        }
+        if policy.allowlist_only && !is_own_account(&self.pool, user_id, &send.recipient).await? {
+            let recipient = self
+                .recipients(user_id)
+                .await?
+                .into_iter()
+                .find(|recipient| recipient.address.eq_ignore_ascii_case(&send.recipient));
+            match recipient {
+                Some(recipient) if recipient.is_active(Utc::now()) => {}
+                Some(recipient) => {
+                    return Err(ErrorResponse::forbidden(&format!(
+                        "This recipient can only be paid from {}",
+                        recipient.active_at.format("%Y-%m-%d %H:%M UTC")
+                    )))
+                }
+                None => {
+                    return Err(ErrorResponse::forbidden(
+                        "This recipient is not on your allow-list",
+                    ))
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,189 @@
+use std::sync::Arc;
+
+use app_config::ErrorResponse;
+use ethers::{
+    providers::{Http, Middleware, Provider, RpcError},
+    types::{Bytes, Transaction},
+    utils::rlp,
+};
+use uuid::Uuid;
+use wallet_api::{
+    amount::{to_decimal, ETHER_DECIMALS},
+    BroadcastResult, SpendCheck, Transaction as WalletTransaction, WalletEvent,
+};
+
+use crate::{
+    transactions::NewTransaction, ChainWatcher, PolicyService, TransactionService, WalletEvents,
+};
+
+/// Relays transactions the app signed to the node, so that the server learns
+/// about every send and can hold it to the user's spending policy first.
+#[derive(Debug)]
+pub struct RelayService {
+    provider: Provider<Http>,
+    watcher: Arc<ChainWatcher>,
+    policies: Arc<PolicyService>,
+    transactions: Arc<TransactionService>,
+    events: Arc<WalletEvents>,
+}
+
+impl RelayService {
+    pub fn new(
+        rpc_url: &str,
+        watcher: Arc<ChainWatcher>,
+        policies: Arc<PolicyService>,
+        transactions: Arc<TransactionService>,
+        events: Arc<WalletEvents>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            watcher,
+            policies,
+            transactions,
+            events,
+        })
+    }
+
+    /// Decodes a signed transaction and recovers its sender.
+    fn decode(raw: &Bytes) -> Result<Transaction, ErrorResponse> {
+        let mut tx: Transaction = rlp::decode(raw).map_err(|e| {
+            log::error!("Error decoding raw transaction: {:?}", e);
+            ErrorResponse::bad_request("Transaction could not be decoded")
+        })?;
+        tx.recover_from_mut().map_err(|e| {
+            log::error!("Error recovering transaction sender: {:?}", e);
+            ErrorResponse::bad_request("Transaction signature is invalid")
+        })?;
+        Ok(tx)
+    }
+
+    /// Records a send and tells its owner about it.
+    async fn record(&self, tx: NewTransaction<'_>) -> Result<(), ErrorResponse> {
+        let recorded = self.transactions.record(tx).await.map_err(|e| {
+            log::error!("Error recording transaction: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+        self.events
+            .publish(recorded.user_id, WalletEvent::TransactionRecorded(recorded));
+        Ok(())
+    }
+
+    /// Checks a signed transaction against the user's accounts and spending
+    /// policy, records it as pending and sends it to the node. A transaction
+    /// the node rejects is recorded as failed.
+    pub async fn broadcast(
+        &self,
+        user_id: Uuid,
+        raw_transaction: &str,
+    ) -> Result<BroadcastResult, ErrorResponse> {
+        let raw: Bytes = raw_transaction
+            .parse()
+            .map_err(|_| ErrorResponse::bad_request("Transaction must be hex encoded"))?;
+        let tx = Self::decode(&raw)?;
+        let Some(to) = tx.to else {
+            return Err(ErrorResponse::bad_request(
+                "Contract creations cannot be relayed",
+            ));
+        };
+
+        let chain_id = self.watcher.chain_id().await.map_err(|e| {
+            log::error!("Error reading chain id: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+        // Without a chain id the transaction could be replayed on other chains
+        if tx.chain_id != Some(chain_id.into()) {
+            return Err(ErrorResponse::bad_request(&format!(
+                "Transaction must be signed for chain {}",
+                chain_id
+            )));
+        }
+
+        let amount = to_decimal(tx.value, ETHER_DECIMALS)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        // Nonces and chain ids are stored as BIGINT
+        let nonce = u64::try_from(tx.nonce)
+            .ok()
+            .and_then(|nonce| i64::try_from(nonce).ok())
+            .ok_or_else(|| ErrorResponse::bad_request("Transaction nonce is too large"))?;
+        let chain_id = i64::try_from(chain_id)
+            .map_err(|_| ErrorResponse::bad_request("Chain id is too large to be recorded"))?;
+        // Token transfers are checked against, and recorded with, the
+        // recipient of the tokens rather than the token contract
+        let token_transfer = self.watcher.token_transfer(&tx).await;
+        let recipient = match &token_transfer {
+            Some(transfer) => transfer.recipient.clone(),
+            None => format!("{:?}", to),
+        };
+        let sender = format!("{:?}", tx.from);
+        let tx_hash = format!("{:?}", tx.hash);
+        let check = SpendCheck {
+            address: sender.clone(),
+            recipient: recipient.clone(),
+            amount,
+            contract_call: !tx.input.is_empty(),
+        };
+        let (amount, token) = match &token_transfer {
+            Some(transfer) => (transfer.amount, Some(transfer.token.as_str())),
+            None => (amount, None),
+        };
+        let send = NewTransaction {
+            user_id,
+            address: &sender,
+            tx_hash: Some(&tx_hash),
+            direction: WalletTransaction::OUT,
+            sender: &sender,
+            recipient: Some(&recipient),
+            amount,
+            token,
+            status: WalletTransaction::PENDING,
+            nonce: Some(nonce),
+            chain_id: Some(chain_id),
+            relayed: true,
+            ..Default::default()
+        };
+        // Recorded as pending under the policy's lock, so that it counts
+        // against the daily limit of any send checked after it
+        let recorded = self
+            .policies
+            .check_and_record(user_id, &check, send)
+            .await?;
+        self.events
+            .publish(user_id, WalletEvent::TransactionRecorded(recorded));
+
+        match self.provider.send_raw_transaction(raw).await {
+            Ok(pending) => Ok(BroadcastResult {
+                tx_hash: format!("{:?}", pending.tx_hash()),
+            }),
+            Err(e) => {
+                log::error!("Error relaying transaction {}: {:?}", tx_hash, e);
+                // A send the node did accept after all is marked confirmed
+                // by the chain watcher once it is mined
+                let rejection = e.as_error_response().map(|rejection| {
+                    format!("The node rejected the transaction: {}", rejection.message)
+                });
+                let message = rejection
+                    .clone()
+                    .unwrap_or_else(|| "The transaction could not be sent to the node".to_string());
+                self.record(NewTransaction {
+                    user_id,
+                    address: &sender,
+                    tx_hash: Some(&tx_hash),
+                    direction: WalletTransaction::OUT,
+                    sender: &sender,
+                    recipient: Some(&recipient),
+                    amount,
+                    token,
+                    status: WalletTransaction::FAILED,
+                    error: Some(&message),
+                    relayed: true,
+                    ..Default::default()
+                })
+                .await?;
+                Err(match rejection {
+                    Some(rejection) => ErrorResponse::bad_request(&rejection),
+                    None => ErrorResponse::internal_error(),
+                })
+            }
+        }
+    }
+}
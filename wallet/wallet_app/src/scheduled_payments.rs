@@ -0,0 +1,566 @@
+use std::{sync::Arc, time::Duration};
+
+use app_config::ErrorResponse;
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    types::H256,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{
+    address::parse_address,
+    amount::from_decimal,
+    scheduled_payment::{first_run, parse_schedule},
+    CreateScheduledPayment, ScheduledPayment, ScheduledPaymentRun, SubmitScheduledPaymentRun,
+    WalletEvent,
+};
+
+use crate::{contacts::checksummed, Clock, CustodyService, WalletEvents};
+
+/// Most runs listed, newest first.
+const MAX_LISTED_RUNS: i64 = 100;
+
+/// How far in the past a one-off payment may be set, to allow for the time
+/// it took to fill in the form.
+const ONE_OFF_GRACE_MINUTES: i64 = 5;
+
+/// A run still sending after this long was interrupted, e.g. by a restart.
+const INTERRUPTED_SEND_MINUTES: i64 = 10;
+
+/// Makes the payments users scheduled. When a payment comes due, a run is
+/// recorded for it. Runs from custodial accounts are signed and relayed right
+/// away, so they are held to the spending policy like any other send. Runs
+/// from accounts whose key only the app can unlock wait for the user to
+/// approve and sign them there.
+///
+/// Time is read from the [`Clock`], never from the database, so that a test
+/// can make payments come due by moving the clock. `updated_at` is written
+/// from it too, as interrupted sends are told by it; `created_at` is when the
+/// row was written, to match runs with the transactions the relay recorded.
+#[derive(Debug)]
+pub struct ScheduledPaymentService {
+    pool: PgPool,
+    provider: Provider<Http>,
+    custody: Arc<CustodyService>,
+    events: Arc<WalletEvents>,
+    clock: Arc<dyn Clock>,
+}
+
+fn internal_error(action: &str, e: impl std::fmt::Debug) -> ErrorResponse {
+    log::error!("Error {}: {:?}", action, e);
+    ErrorResponse::internal_error()
+}
+
+impl ScheduledPaymentService {
+    pub fn new(
+        pool: PgPool,
+        rpc_url: &str,
+        custody: Arc<CustodyService>,
+        events: Arc<WalletEvents>,
+        clock: Arc<dyn Clock>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            pool,
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            custody,
+            events,
+            clock,
+        })
+    }
+
+    pub async fn list_payments(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<ScheduledPayment>, ErrorResponse> {
+        sqlx::query_as!(
+            ScheduledPayment,
+            r#"
+            SELECT id, user_id, account_id, recipient, token, token_decimals, amount, memo,
+                schedule, next_run_at, status, created_at, updated_at
+            FROM wallet_scheduled_payments
+            WHERE user_id = $1
+            ORDER BY created_at DESC
+            "#,
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| internal_error("listing scheduled payments", e))
+    }
+
+    /// Schedules a payment from one of the user's accounts.
+    pub async fn create_payment(
+        &self,
+        user_id: Uuid,
+        payment: CreateScheduledPayment,
+    ) -> Result<ScheduledPayment, ErrorResponse> {
+        let recipient = checksummed(&payment.recipient)?;
+        let token = payment.token.as_deref().map(checksummed).transpose()?;
+        from_decimal(payment.amount, payment.token_decimals)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let memo = payment.memo.filter(|memo| !memo.trim().is_empty());
+        let schedule = payment
+            .schedule
+            .map(|schedule| schedule.trim().to_string())
+            .filter(|schedule| !schedule.is_empty());
+
+        let now = self.clock.now();
+        let next_run_at = match (&schedule, payment.run_at) {
+            (Some(schedule), run_at) => {
+                let schedule = parse_schedule(schedule)
+                    .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+                first_run(&schedule, run_at.unwrap_or(now).max(now))
+                    .ok_or_else(|| ErrorResponse::bad_request("Schedule never runs again"))?
+            }
+            (None, Some(run_at)) => {
+                if run_at < now - chrono::Duration::minutes(ONE_OFF_GRACE_MINUTES) {
+                    return Err(ErrorResponse::bad_request(
+                        "A one-off payment must be scheduled in the future",
+                    ));
+                }
+                run_at
+            }
+            (None, None) => {
+                return Err(ErrorResponse::bad_request(
+                    "Give a time or a schedule for the payment",
+                ))
+            }
+        };
+
+        sqlx::query!(
+            "SELECT id FROM wallet_accounts WHERE id = $1 AND user_id = $2",
+            payment.account_id,
+            user_id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching account", e))?
+        .ok_or_else(|| ErrorResponse::not_found("Account not found"))?;
+
+        sqlx::query_as!(
+            ScheduledPayment,
+            r#"
+            INSERT INTO wallet_scheduled_payments
+                (user_id, account_id, recipient, token, token_decimals, amount, memo, schedule, next_run_at, updated_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+            RETURNING id, user_id, account_id, recipient, token, token_decimals, amount, memo,
+                schedule, next_run_at, status, created_at, updated_at
+            "#,
+            user_id,
+            payment.account_id,
+            recipient,
+            token,
+            payment.token_decimals,
+            payment.amount,
+            memo,
+            schedule,
+            next_run_at,
+            now
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| internal_error("creating scheduled payment", e))
+    }
+
+    /// Stops a payment from running again. Runs waiting for approval stay,
+    /// for the user to approve or skip.
+    pub async fn cancel_payment(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<ScheduledPayment, ErrorResponse> {
+        sqlx::query_as!(
+            ScheduledPayment,
+            r#"
+            UPDATE wallet_scheduled_payments
+            SET status = $3, next_run_at = NULL, updated_at = $5
+            WHERE id = $1 AND user_id = $2 AND status = $4
+            RETURNING id, user_id, account_id, recipient, token, token_decimals, amount, memo,
+                schedule, next_run_at, status, created_at, updated_at
+            "#,
+            id,
+            user_id,
+            ScheduledPayment::CANCELLED,
+            ScheduledPayment::ACTIVE,
+            self.clock.now()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("cancelling scheduled payment", e))?
+        .ok_or_else(|| ErrorResponse::not_found("No active scheduled payment found"))
+    }
+
+    /// The user's runs, newest first, or only the run `id`. Each comes with
+    /// the status of its transaction as the chain watcher last saw it.
+    async fn runs(
+        &self,
+        user_id: Uuid,
+        id: Option<Uuid>,
+    ) -> Result<Vec<ScheduledPaymentRun>, sqlx::Error> {
+        sqlx::query_as!(
+            ScheduledPaymentRun,
+            r#"
+            SELECT r.id, r.scheduled_payment_id, r.account_id, r.recipient, r.token,
+                r.token_decimals, r.amount, r.due_at, r.status, r.tx_hash, r.error,
+                t.status AS "transaction_status?", r.created_at, r.updated_at
+            FROM wallet_scheduled_payment_runs r
+            LEFT JOIN LATERAL (
+                SELECT status
+                FROM wallet_transactions
+                WHERE user_id = r.user_id AND tx_hash = r.tx_hash AND direction = 'out'
+                ORDER BY created_at DESC
+                LIMIT 1
+            ) t ON TRUE
+            WHERE r.user_id = $1 AND ($2::UUID IS NULL OR r.id = $2)
+            ORDER BY r.due_at DESC, r.created_at DESC
+            LIMIT $3
+            "#,
+            user_id,
+            id,
+            MAX_LISTED_RUNS
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    pub async fn list_runs(
+        &self,
+        user_id: Uuid,
+    ) -> Result<Vec<ScheduledPaymentRun>, ErrorResponse> {
+        self.runs(user_id, None)
+            .await
+            .map_err(|e| internal_error("listing scheduled payment runs", e))
+    }
+
+    /// Reads a run back and tells its owner about it.
+    async fn publish_run(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<ScheduledPaymentRun, sqlx::Error> {
+        let run = self
+            .runs(user_id, Some(id))
+            .await?
+            .pop()
+            .ok_or(sqlx::Error::RowNotFound)?;
+        self.events
+            .publish(user_id, WalletEvent::ScheduledPaymentRun(run.clone()));
+        Ok(run)
+    }
+
+    /// Moves a run waiting for approval to `status`.
+    async fn settle_run(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        status: &str,
+        tx_hash: Option<&str>,
+    ) -> Result<ScheduledPaymentRun, ErrorResponse> {
+        sqlx::query!(
+            r#"
+            UPDATE wallet_scheduled_payment_runs
+            SET status = $3, tx_hash = LOWER($4), updated_at = $6
+            WHERE id = $1 AND user_id = $2 AND status = $5
+            RETURNING id
+            "#,
+            id,
+            user_id,
+            status,
+            tx_hash,
+            ScheduledPaymentRun::AWAITING_APPROVAL,
+            self.clock.now()
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| internal_error("updating scheduled payment run", e))?
+        .ok_or_else(|| ErrorResponse::not_found("No run waiting for approval found"))?;
+
+        self.publish_run(user_id, id)
+            .await
+            .map_err(|e| internal_error("fetching scheduled payment run", e))
+    }
+
+    /// Records the send the app signed for a run the user approved. The send
+    /// has to be known to the node and make the run's payment from the run's
+    /// account, and must not have been recorded for another run.
+    pub async fn submit_run(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        submit: SubmitScheduledPaymentRun,
+    ) -> Result<ScheduledPaymentRun, ErrorResponse> {
+        let tx_hash: H256 = submit
+            .tx_hash
+            .parse()
+            .map_err(|_| ErrorResponse::bad_request("Transaction hash is invalid"))?;
+        let run = self
+            .runs(user_id, Some(id))
+            .await
+            .map_err(|e| internal_error("fetching scheduled payment run", e))?
+            .pop()
+            .filter(ScheduledPaymentRun::is_awaiting_approval)
+            .ok_or_else(|| ErrorResponse::not_found("No run waiting for approval found"))?;
+        let account = sqlx::query_scalar!(
+            "SELECT address FROM wallet_accounts WHERE id = $1",
+            run.account_id
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching account", e))?;
+
+        let already_recorded = sqlx::query_scalar!(
+            r#"
+            SELECT EXISTS (
+                SELECT 1 FROM wallet_scheduled_payment_runs WHERE tx_hash = LOWER($1)
+            ) AS "exists!"
+            "#,
+            submit.tx_hash
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| internal_error("checking scheduled payment runs", e))?;
+        if already_recorded {
+            return Err(ErrorResponse::conflict(
+                "The transaction was already recorded for a run",
+            ));
+        }
+
+        let sent = self
+            .provider
+            .get_transaction(tx_hash)
+            .await
+            .map_err(|e| {
+                log::error!("Error fetching transaction {:?}: {:?}", tx_hash, e);
+                ErrorResponse::origin_is_unreachable()
+            })?
+            .ok_or_else(|| ErrorResponse::bad_request("The transaction was not found on chain"))?;
+        let (to, value, data) = run
+            .transaction()
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let from = parse_address(&account).map_err(|e| internal_error("parsing account", e))?;
+        if sent.from != from || sent.to != Some(to) || sent.value != value || sent.input != data {
+            return Err(ErrorResponse::bad_request(
+                "The transaction does not make this payment",
+            ));
+        }
+
+        self.settle_run(
+            user_id,
+            id,
+            ScheduledPaymentRun::SUBMITTED,
+            Some(&submit.tx_hash),
+        )
+        .await
+    }
+
+    /// Records that the user chose not to make a run's payment.
+    pub async fn skip_run(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+    ) -> Result<ScheduledPaymentRun, ErrorResponse> {
+        self.settle_run(user_id, id, ScheduledPaymentRun::SKIPPED, None)
+            .await
+    }
+
+    /// Records a run for every payment that came due and moves the payments
+    /// to their next run. A payment that missed several runs, e.g. while the
+    /// server was down, only runs once. Returns the number of runs recorded.
+    pub async fn run_due(&self) -> Result<usize, anyhow::Error> {
+        let now = self.clock.now();
+
+        let mut tx = self.pool.begin().await?;
+        let due = sqlx::query!(
+            r#"
+            SELECT p.id, p.user_id, p.account_id, p.recipient, p.token, p.token_decimals,
+                p.amount, p.schedule, p.next_run_at AS "next_run_at!", a.custodial
+            FROM wallet_scheduled_payments p
+            JOIN wallet_accounts a ON a.id = p.account_id
+            WHERE p.status = $1 AND p.next_run_at <= $2
+            ORDER BY p.next_run_at
+            FOR UPDATE OF p SKIP LOCKED
+            "#,
+            ScheduledPayment::ACTIVE,
+            now
+        )
+        .fetch_all(&mut *tx)
+        .await?;
+
+        let mut runs = Vec::new();
+        for payment in due {
+            // Schedules were checked when the payment was created
+            let next_run_at = payment
+                .schedule
+                .as_deref()
+                .and_then(|schedule| parse_schedule(schedule).ok())
+                .and_then(|schedule| schedule.after(&now).next());
+            let status = match next_run_at {
+                Some(_) => ScheduledPayment::ACTIVE,
+                None => ScheduledPayment::COMPLETED,
+            };
+            sqlx::query!(
+                r#"
+                UPDATE wallet_scheduled_payments
+                SET next_run_at = $2, status = $3, updated_at = $4
+                WHERE id = $1
+                "#,
+                payment.id,
+                next_run_at,
+                status,
+                now
+            )
+            .execute(&mut *tx)
+            .await?;
+
+            let run_status = if payment.custodial {
+                ScheduledPaymentRun::SENDING
+            } else {
+                ScheduledPaymentRun::AWAITING_APPROVAL
+            };
+            let run = sqlx::query!(
+                r#"
+                INSERT INTO wallet_scheduled_payment_runs
+                    (scheduled_payment_id, user_id, account_id, recipient, token, token_decimals, amount, due_at, status, updated_at)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+                ON CONFLICT (scheduled_payment_id, due_at) DO NOTHING
+                RETURNING id
+                "#,
+                payment.id,
+                payment.user_id,
+                payment.account_id,
+                payment.recipient,
+                payment.token,
+                payment.token_decimals,
+                payment.amount,
+                payment.next_run_at,
+                run_status,
+                now
+            )
+            .fetch_optional(&mut *tx)
+            .await?;
+            if let Some(run) = run {
+                runs.push((payment.user_id, run.id, payment.custodial));
+            }
+        }
+        tx.commit().await?;
+
+        for (user_id, id, custodial) in &runs {
+            let result = match self.publish_run(*user_id, *id).await {
+                Ok(run) if *custodial => self.send(*user_id, run).await,
+                Ok(_) => Ok(()),
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                log::error!("Error making scheduled payment run {}: {:?}", id, e);
+            }
+        }
+
+        Ok(runs.len())
+    }
+
+    /// Signs and relays the send of a run from a custodial account.
+    async fn send(&self, user_id: Uuid, run: ScheduledPaymentRun) -> Result<(), sqlx::Error> {
+        let result = match run.transaction() {
+            Ok((to, value, data)) => {
+                self.custody
+                    .send_from_account(user_id, run.account_id, to, value, data)
+                    .await
+            }
+            Err(e) => Err(ErrorResponse::bad_request(&e.to_string())),
+        };
+        let (status, tx_hash, error) = match result {
+            Ok(sent) => (ScheduledPaymentRun::SUBMITTED, Some(sent.tx_hash), None),
+            Err(e) => {
+                log::warn!("Scheduled payment run {} failed: {}", run.id, e.message);
+                (ScheduledPaymentRun::FAILED, None, Some(e.message))
+            }
+        };
+        sqlx::query!(
+            r#"
+            UPDATE wallet_scheduled_payment_runs
+            SET status = $2, tx_hash = $3, error = $4, updated_at = $5
+            WHERE id = $1
+            "#,
+            run.id,
+            status,
+            tx_hash,
+            error,
+            self.clock.now()
+        )
+        .execute(&self.pool)
+        .await?;
+
+        self.publish_run(user_id, run.id).await?;
+        Ok(())
+    }
+
+    /// Settles runs left sending by a server that stopped before it recorded
+    /// the outcome. The relay records a send before it broadcasts it, so a
+    /// run whose send was recorded is marked submitted with it. Any other run
+    /// is marked failed rather than sent again, as paying twice is worse than
+    /// asking the user to check. Returns the number of runs settled.
+    pub async fn reconcile_interrupted_runs(&self) -> Result<usize, anyhow::Error> {
+        let now = self.clock.now();
+        let cutoff = now - chrono::Duration::minutes(INTERRUPTED_SEND_MINUTES);
+        let settled = sqlx::query!(
+            r#"
+            UPDATE wallet_scheduled_payment_runs r
+            SET
+                status = CASE WHEN s.tx_hash IS NULL THEN $3 ELSE $4 END,
+                tx_hash = s.tx_hash,
+                error = CASE WHEN s.tx_hash IS NULL THEN $5 END,
+                updated_at = $6
+            FROM (
+                SELECT run.id, (
+                    SELECT t.tx_hash
+                    FROM wallet_transactions t
+                    JOIN wallet_accounts a ON a.id = run.account_id
+                    WHERE t.user_id = run.user_id
+                        AND LOWER(t.address) = LOWER(a.address)
+                        AND t.direction = 'out'
+                        AND t.relayed
+                        AND LOWER(t.recipient) = LOWER(run.recipient)
+                        AND t.amount = run.amount
+                        AND LOWER(t.token) IS NOT DISTINCT FROM LOWER(run.token)
+                        AND t.created_at >= run.created_at
+                    ORDER BY t.created_at
+                    LIMIT 1
+                ) AS tx_hash
+                FROM wallet_scheduled_payment_runs run
+                WHERE run.status = $1 AND run.updated_at < $2
+            ) s
+            WHERE r.id = s.id
+            RETURNING r.id, r.user_id
+            "#,
+            ScheduledPaymentRun::SENDING,
+            cutoff,
+            ScheduledPaymentRun::FAILED,
+            ScheduledPaymentRun::SUBMITTED,
+            "Interrupted before the payment was sent. Check the account's transactions before paying again.",
+            now
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        for run in &settled {
+            log::warn!("Settled interrupted scheduled payment run {}", run.id);
+            if let Err(e) = self.publish_run(run.user_id, run.id).await {
+                log::error!("Error publishing scheduled payment run {}: {:?}", run.id, e);
+            }
+        }
+        Ok(settled.len())
+    }
+
+    pub async fn continuously_run(self: Arc<Self>, period: Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reconcile_interrupted_runs().await {
+                log::error!("Error reconciling scheduled payment runs: {:?}", e);
+            }
+            if let Err(e) = self.run_due().await {
+                log::error!("Error running scheduled payments: {:?}", e);
+            }
+        }
+    }
+}
@@ -1,7 +1,19 @@
+use std::str::FromStr;
+
 use app_config::ErrorResponse;
-use sqlx::PgPool;
+use ethers::types::Signature;
+use sqlx::{PgConnection, PgPool};
 use uuid::Uuid;
-use wallet_api::Wallet;
+use wallet_api::{
+    address::parse_address, amount::ETHER_DECIMALS, Account, CreateAccount, CreateWallet,
+    RotateAccountKey, RotateWalletKey, UpdateAccount, UpgradeAccountKey, Wallet,
+};
+
+/// Whether an encrypted key is in the legacy base64 CBC format rather than a
+/// JSON keystore.
+fn is_legacy_key(encrypted_private_key: &str) -> bool {
+    !encrypted_private_key.trim_start().starts_with('{')
+}
 
 #[derive(Debug)]
 pub struct WalletService {
@@ -20,47 +32,479 @@ impl WalletService {
             .map_err(|_| ErrorResponse::not_found("Wallet not found"))
     }
 
-    pub async fn update_wallet(
+    pub async fn create_wallet(
         &self,
         user_id: Uuid,
-        wallet: Wallet,
+        wallet: CreateWallet,
     ) -> Result<Wallet, ErrorResponse> {
-        sqlx::query!(
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            log::error!("Error starting transaction: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        let created = sqlx::query_as!(
+            Wallet,
             r#"
-            INSERT INTO wallets (user_id, encrypted_private_key, address, balance, salt, token_decimals)
-            VALUES ($1, $2, $3, $4, $5, $6)
-            ON CONFLICT (user_id)
-            DO UPDATE SET
-                encrypted_private_key = EXCLUDED.encrypted_private_key,
-                address = EXCLUDED.address,
-                balance = EXCLUDED.balance,
-                salt = EXCLUDED.salt,
-                token_decimals = EXCLUDED.token_decimals
+            INSERT INTO wallets (user_id, encrypted_private_key, encrypted_mnemonic, derivation_path, address, salt, token_decimals)
+            VALUES ($1, $2, $3, $4, $5, '', $6)
+            ON CONFLICT (user_id) DO NOTHING
             RETURNING *
             "#,
             user_id,
             wallet.encrypted_private_key,
+            wallet.encrypted_mnemonic,
+            wallet.derivation_path,
             wallet.address,
-            wallet.balance,
-            wallet.salt,
-            wallet.token_decimals
+            ETHER_DECIMALS
         )
-        .fetch_one(&self.pool)
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            log::error!("Error creating wallet: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::conflict("Wallet already exists"))?;
+
+        upsert_primary_account(&mut tx, &created).await?;
+        audit(
+            &mut tx,
+            user_id,
+            "wallet_created",
+            None,
+            None,
+            &created.address,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            log::error!("Error committing wallet creation: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        Ok(created)
+    }
+
+    /// Replaces the wallet's key. When the address changes, the accounts that
+    /// were derived from the previous wallet are removed and the balance is
+    /// reset until it is next synced from the chain. Imported and custodial
+    /// accounts are kept, as their keys are not derived from the wallet and
+    /// their only copy is the one stored here.
+    pub async fn rotate_wallet_key(
+        &self,
+        user_id: Uuid,
+        wallet: RotateWalletKey,
+    ) -> Result<Wallet, ErrorResponse> {
+        let previous = self.get_wallet(user_id).await?;
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            log::error!("Error starting transaction: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        if previous.address != wallet.address {
+            sqlx::query!(
+                r#"
+                DELETE FROM wallet_accounts
+                WHERE user_id = $1 AND derivation_index IS NOT NULL AND NOT custodial
+                "#,
+                user_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                log::error!("Error removing stale accounts: {:?}", e);
+                ErrorResponse::internal_error()
+            })?;
+        }
+
+        let rotated = sqlx::query_as!(
+            Wallet,
+            r#"
+            UPDATE wallets
+            SET
+                encrypted_private_key = $2,
+                encrypted_mnemonic = $3,
+                derivation_path = $4,
+                balance = CASE WHEN address = $5 THEN balance ELSE 0 END,
+                address = $5,
+                salt = '',
+                updated_at = NOW()
+            WHERE user_id = $1
+            RETURNING *
+            "#,
+            user_id,
+            wallet.encrypted_private_key,
+            wallet.encrypted_mnemonic,
+            wallet.derivation_path,
+            wallet.address
+        )
+        .fetch_one(&mut *tx)
         .await
-        .map(|row| Wallet {
-            id: row.id,
-            user_id: row.user_id,
-            encrypted_private_key: row.encrypted_private_key,
-            address: row.address,
-            balance: row.balance,
-            salt: row.salt,
-            token_decimals: row.token_decimals,
-            created_at: row.created_at,
-            updated_at: row.updated_at,
-        })
         .map_err(|e| {
-            log::error!("Error updating wallet: {:?}", e);
+            log::error!("Error rotating wallet key: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        upsert_primary_account(&mut tx, &rotated).await?;
+        audit(
+            &mut tx,
+            user_id,
+            "wallet_key_rotated",
+            None,
+            Some(&previous.address),
+            &rotated.address,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            log::error!("Error committing wallet key rotation: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        Ok(rotated)
+    }
+
+    pub async fn list_accounts(&self, user_id: Uuid) -> Result<Vec<Account>, ErrorResponse> {
+        sqlx::query_as!(
+            Account,
+            "SELECT * FROM wallet_accounts WHERE user_id = $1 ORDER BY created_at, derivation_index",
+            user_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error listing accounts: {:?}", e);
             ErrorResponse::internal_error()
         })
     }
+
+    pub async fn get_account(&self, user_id: Uuid, id: Uuid) -> Result<Account, ErrorResponse> {
+        sqlx::query_as!(
+            Account,
+            "SELECT * FROM wallet_accounts WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error fetching account: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Account not found"))
+    }
+
+    /// Adds an account whose key the app holds. The account's key has to sign
+    /// the request, so that no one can add an address they do not hold and
+    /// follow its transfers and balance.
+    pub async fn create_account(
+        &self,
+        user_id: Uuid,
+        account: CreateAccount,
+    ) -> Result<Account, ErrorResponse> {
+        // Accounts belong to a wallet, so one has to be set up first
+        self.get_wallet(user_id).await?;
+
+        let address = parse_address(&account.address)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let message =
+            CreateAccount::message(user_id, &account.address, &account.encrypted_private_key);
+        let signer = Signature::from_str(account.signature.trim())
+            .and_then(|signature| signature.recover(message.as_str()))
+            .map_err(|_| ErrorResponse::bad_request("Signature is invalid"))?;
+        if signer != address {
+            return Err(ErrorResponse::forbidden(
+                "The account must be signed by its own key",
+            ));
+        }
+
+        sqlx::query_as!(
+            Account,
+            r#"
+            INSERT INTO wallet_accounts (user_id, label, derivation_index, encrypted_private_key, address)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, address) DO NOTHING
+            RETURNING *
+            "#,
+            user_id,
+            account.label,
+            account.derivation_index,
+            account.encrypted_private_key,
+            account.address
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error creating account: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::conflict("Account already exists"))
+    }
+
+    pub async fn update_account(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        account: UpdateAccount,
+    ) -> Result<Account, ErrorResponse> {
+        sqlx::query_as!(
+            Account,
+            r#"
+            UPDATE wallet_accounts
+            SET
+                label = COALESCE($3, label),
+                updated_at = NOW()
+            WHERE user_id = $1 AND id = $2
+            RETURNING *
+            "#,
+            user_id,
+            id,
+            account.label
+        )
+        .fetch_optional(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error updating account: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| ErrorResponse::not_found("Account not found"))
+    }
+
+    /// Replaces the encrypted key of an account. The address stays the same, so
+    /// this is only useful for re-encrypting the same key. The wallet's own copy
+    /// is kept in sync for its primary account.
+    pub async fn rotate_account_key(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        key: RotateAccountKey,
+    ) -> Result<Account, ErrorResponse> {
+        self.replace_account_key(user_id, id, key.encrypted_private_key, false)
+            .await
+    }
+
+    /// Replaces the encrypted key of an account, and the wallet's copy of it.
+    /// With `only_legacy`, a key already in the current format is left alone,
+    /// checked in the same statement that replaces it, so that two uploads
+    /// racing each other cannot both replace the key.
+    async fn replace_account_key(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        encrypted_private_key: String,
+        only_legacy: bool,
+    ) -> Result<Account, ErrorResponse> {
+        let mut tx = self.pool.begin().await.map_err(|e| {
+            log::error!("Error starting transaction: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        let account = sqlx::query_as!(
+            Account,
+            r#"
+            UPDATE wallet_accounts
+            SET
+                encrypted_private_key = $3,
+                updated_at = NOW()
+            WHERE user_id = $1 AND id = $2
+                AND (NOT $4 OR LTRIM(encrypted_private_key, E' \t\r\n') NOT LIKE '{%')
+            RETURNING *
+            "#,
+            user_id,
+            id,
+            encrypted_private_key,
+            only_legacy
+        )
+        .fetch_optional(&mut *tx)
+        .await
+        .map_err(|e| {
+            log::error!("Error rotating account key: {:?}", e);
+            ErrorResponse::internal_error()
+        })?
+        .ok_or_else(|| {
+            if only_legacy {
+                ErrorResponse::conflict("The key of this account is already in the current format")
+            } else {
+                ErrorResponse::not_found("Account not found")
+            }
+        })?;
+
+        sqlx::query!(
+            r#"
+            UPDATE wallets
+            SET
+                encrypted_private_key = $3,
+                salt = '',
+                updated_at = NOW()
+            WHERE user_id = $1 AND address = $2
+            "#,
+            user_id,
+            account.address,
+            account.encrypted_private_key
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| {
+            log::error!("Error syncing wallet key: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        audit(
+            &mut tx,
+            user_id,
+            "account_key_rotated",
+            Some(account.id),
+            None,
+            &account.address,
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            log::error!("Error committing account key rotation: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        Ok(account)
+    }
+
+    /// Replaces a key in the legacy format with the same key re-encrypted as a
+    /// keystore. The signature proves the uploader holds the account's key, and
+    /// keys already in the current format are left alone, so a session alone
+    /// cannot replace a key this way.
+    pub async fn upgrade_account_key(
+        &self,
+        user_id: Uuid,
+        id: Uuid,
+        key: UpgradeAccountKey,
+    ) -> Result<Account, ErrorResponse> {
+        let account = self.get_account(user_id, id).await?;
+        if !is_legacy_key(&account.encrypted_private_key) {
+            return Err(ErrorResponse::conflict(
+                "The key of this account is already in the current format",
+            ));
+        }
+        if is_legacy_key(&key.encrypted_private_key)
+            || serde_json::from_str::<serde_json::Value>(&key.encrypted_private_key).is_err()
+        {
+            return Err(ErrorResponse::bad_request(
+                "The upgraded key must be a keystore",
+            ));
+        }
+
+        let address = parse_address(&account.address)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let signer = Signature::from_str(key.signature.trim())
+            .and_then(|signature| signature.recover(key.encrypted_private_key.as_str()))
+            .map_err(|_| ErrorResponse::bad_request("Signature is invalid"))?;
+        if signer != address {
+            return Err(ErrorResponse::forbidden(
+                "The upgraded key must be signed by the account's key",
+            ));
+        }
+
+        self.replace_account_key(user_id, id, key.encrypted_private_key, true)
+            .await
+    }
+
+    /// Deletes an account. The wallet's own address cannot be removed, since it
+    /// is the account every wallet starts with, and neither can a custodial
+    /// account, whose key only the server holds.
+    pub async fn delete_account(&self, user_id: Uuid, id: Uuid) -> Result<(), ErrorResponse> {
+        let account = self.get_account(user_id, id).await?;
+        let wallet = self.get_wallet(user_id).await?;
+        if account.address == wallet.address {
+            return Err(ErrorResponse::bad_request(
+                "The wallet's primary account cannot be removed",
+            ));
+        }
+        if account.custodial {
+            return Err(ErrorResponse::bad_request(
+                "Custodial accounts cannot be removed, as their key would be lost",
+            ));
+        }
+
+        sqlx::query!(
+            "DELETE FROM wallet_accounts WHERE user_id = $1 AND id = $2",
+            user_id,
+            id
+        )
+        .execute(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error deleting account: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        Ok(())
+    }
+}
+
+/// Makes sure the wallet's own address is listed as an account, with the key
+/// the wallet holds for it.
+async fn upsert_primary_account(
+    conn: &mut PgConnection,
+    wallet: &Wallet,
+) -> Result<(), ErrorResponse> {
+    let derivation_index = wallet
+        .derivation_path
+        .as_deref()
+        .and_then(|path| path.rsplit('/').next())
+        .and_then(|index| index.parse::<i32>().ok());
+
+    sqlx::query!(
+        r#"
+        INSERT INTO wallet_accounts (user_id, label, derivation_index, encrypted_private_key, address)
+        VALUES ($1, 'Account 1', $2, $3, $4)
+        ON CONFLICT (user_id, address)
+        DO UPDATE SET
+            encrypted_private_key = EXCLUDED.encrypted_private_key,
+            updated_at = NOW()
+        "#,
+        wallet.user_id,
+        derivation_index,
+        wallet.encrypted_private_key,
+        wallet.address
+    )
+    .execute(conn)
+    .await
+    .map(|_| ())
+    .map_err(|e| {
+        log::error!("Error updating primary account: {:?}", e);
+        ErrorResponse::internal_error()
+    })
+}
+
+pub(crate) async fn audit(
+    conn: &mut PgConnection,
+    user_id: Uuid,
+    event: &str,
+    account_id: Option<Uuid>,
+    previous_address: Option<&str>,
+    address: &str,
+) -> Result<(), ErrorResponse> {
+    log::info!(
+        "Wallet audit: user {} {} {:?} -> {}",
+        user_id,
+        event,
+        previous_address,
+        address
+    );
+
+    sqlx::query!(
+        r#"
+        INSERT INTO wallet_audit_log (user_id, event, account_id, previous_address, address)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        user_id,
+        event,
+        account_id,
+        previous_address,
+        address
+    )
+    .execute(conn)
+    .await
+    .map(|_| ())
+    .map_err(|e| {
+        log::error!("Error writing wallet audit log: {:?}", e);
+        ErrorResponse::internal_error()
+    })
 }
@@ -0,0 +1,492 @@
+use app_config::ErrorResponse;
+use chrono::{DateTime, Days, NaiveDate, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{PgExecutor, PgPool};
+use uuid::Uuid;
+use wallet_api::{
+    address::parse_address, transaction::parse_cursor, ExportedTransaction, RecordTransaction,
+    Transaction, TransactionExportQuery, TransactionPage, TransactionQuery,
+};
+
+const CSV_HEADER: &str =
+    "hash,account,direction,status,counterparty,amount,token,fee,block_number,timestamp";
+
+/// A transaction to record for one of a user's accounts. Addresses and the
+/// hash are lowercase hex.
+#[derive(Debug, Default)]
+pub struct NewTransaction<'a> {
+    pub user_id: Uuid,
+    pub address: &'a str,
+    pub tx_hash: Option<&'a str>,
+    pub direction: &'static str,
+    pub sender: &'a str,
+    pub recipient: Option<&'a str>,
+    pub amount: Decimal,
+    pub token: Option<&'a str>,
+    pub status: &'static str,
+    pub gas_used: Option<i64>,
+    pub fee: Option<Decimal>,
+    pub nonce: Option<i64>,
+    pub block_number: Option<i64>,
+    pub block_time: Option<DateTime<Utc>>,
+    pub chain_id: Option<i64>,
+    pub error: Option<&'a str>,
+    /// Seen on chain by the chain watcher, so that its status overrides what
+    /// the app reported.
+    pub observed: bool,
+    /// Recorded by the relay, which checked it against the spending policy.
+    /// The app cannot change the status of such a send afterwards.
+    pub relayed: bool,
+}
+
+/// Lowercase hex form of an address, as stored with transactions.
+fn lowercase_address(address: &str) -> Result<String, ErrorResponse> {
+    parse_address(address)
+        .map(|address| format!("{:?}", address))
+        .map_err(|e| ErrorResponse::bad_request(&e.to_string()))
+}
+
+/// Whether `address` is one of the user's accounts.
+pub(crate) async fn is_own_account(
+    pool: &PgPool,
+    user_id: Uuid,
+    address: &str,
+) -> Result<bool, ErrorResponse> {
+    sqlx::query_scalar!(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM wallet_accounts WHERE user_id = $1 AND LOWER(address) = LOWER($2)
+        ) AS "exists!"
+        "#,
+        user_id,
+        address
+    )
+    .fetch_one(pool)
+    .await
+    .map_err(|e| {
+        log::error!("Error checking account ownership: {:?}", e);
+        ErrorResponse::internal_error()
+    })
+}
+
+/// Records a transaction, or updates it when it is known already. A
+/// status the app reported never overrides one seen on chain, nor one of a
+/// send that went through the relay, so that the app cannot mark a relayed
+/// send failed to free up the daily limit. The relay itself settles its
+/// sends until they are seen in a block.
+pub(crate) async fn record_transaction(
+    executor: impl PgExecutor<'_>,
+    tx: NewTransaction<'_>,
+) -> Result<Transaction, sqlx::Error> {
+    sqlx::query_as!(
+        Transaction,
+        r#"
+        INSERT INTO wallet_transactions (
+            user_id, address, tx_hash, direction, sender, recipient, amount, token, status,
+            gas_used, fee, nonce, block_number, block_time, chain_id, error, relayed
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $17, $8, $9, $10, $11, $12, $13, $14, $15, $18)
+        ON CONFLICT (user_id, address, tx_hash, direction) DO UPDATE SET
+            status = CASE
+                WHEN $16 THEN EXCLUDED.status
+                WHEN $18 AND wallet_transactions.block_number IS NULL THEN EXCLUDED.status
+                WHEN wallet_transactions.status = 'pending' AND NOT wallet_transactions.relayed
+                    THEN EXCLUDED.status
+                ELSE wallet_transactions.status
+            END,
+            error = CASE
+                WHEN $16 THEN EXCLUDED.error
+                WHEN $18 AND wallet_transactions.block_number IS NULL THEN EXCLUDED.error
+                WHEN wallet_transactions.status = 'pending' AND NOT wallet_transactions.relayed
+                    THEN EXCLUDED.error
+                ELSE wallet_transactions.error
+            END,
+            relayed = wallet_transactions.relayed OR EXCLUDED.relayed,
+            recipient = COALESCE(wallet_transactions.recipient, EXCLUDED.recipient),
+            gas_used = COALESCE(EXCLUDED.gas_used, wallet_transactions.gas_used),
+            fee = COALESCE(EXCLUDED.fee, wallet_transactions.fee),
+            nonce = COALESCE(EXCLUDED.nonce, wallet_transactions.nonce),
+            block_number = COALESCE(EXCLUDED.block_number, wallet_transactions.block_number),
+            block_time = COALESCE(EXCLUDED.block_time, wallet_transactions.block_time),
+            chain_id = COALESCE(EXCLUDED.chain_id, wallet_transactions.chain_id)
+        RETURNING *
+        "#,
+        tx.user_id,
+        tx.address,
+        tx.tx_hash,
+        tx.direction,
+        tx.sender,
+        tx.recipient,
+        tx.amount,
+        tx.status,
+        tx.gas_used,
+        tx.fee,
+        tx.nonce,
+        tx.block_number,
+        tx.block_time,
+        tx.chain_id,
+        tx.error,
+        tx.observed,
+        tx.token,
+        tx.relayed
+    )
+    .fetch_one(executor)
+    .await
+}
+
+/// Start of `date` in UTC.
+fn start_of(date: NaiveDate) -> DateTime<Utc> {
+    date.and_time(NaiveTime::MIN).and_utc()
+}
+
+/// Start and exclusive end of a range of days, each unbounded when `None`.
+type DateRange = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// The days from `from` to `to`.
+fn date_range(from: Option<NaiveDate>, to: Option<NaiveDate>) -> Result<DateRange, ErrorResponse> {
+    if let (Some(from), Some(to)) = (from, to) {
+        if from > to {
+            return Err(ErrorResponse::bad_request(
+                "The start date must not be after the end date",
+            ));
+        }
+    }
+    Ok((
+        from.map(start_of),
+        to.and_then(|to| to.checked_add_days(Days::new(1)))
+            .map(start_of),
+    ))
+}
+
+/// Escapes the wildcards of a `LIKE` pattern, so that `value` only matches
+/// itself. Patterns using it must say `ESCAPE '\'`.
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Quotes a CSV field when it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Formats a statement as CSV with a header row.
+pub fn to_csv(rows: &[ExportedTransaction]) -> String {
+    let mut csv = String::from(CSV_HEADER);
+    csv.push_str("\r\n");
+    for row in rows {
+        let fields = [
+            row.hash.clone(),
+            row.account.clone(),
+            row.direction.clone(),
+            row.status.clone(),
+            row.counterparty.clone().unwrap_or_default(),
+            row.amount.to_string(),
+            row.token.clone().unwrap_or_default(),
+            row.fee.map(|fee| fee.to_string()).unwrap_or_default(),
+            row.block_number.to_string(),
+            row.timestamp.to_rfc3339(),
+        ];
+        let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+        csv.push_str(&line.join(","));
+        csv.push_str("\r\n");
+    }
+    csv
+}
+
+#[derive(Debug)]
+pub struct TransactionService {
+    pool: PgPool,
+}
+
+impl TransactionService {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    /// Records a transaction, or updates it when it is known already.
+    pub async fn record(&self, tx: NewTransaction<'_>) -> Result<Transaction, sqlx::Error> {
+        record_transaction(&self.pool, tx).await
+    }
+
+    /// Marks the pending sends from `address` with `nonce` as replaced by the
+    /// mined transaction `tx_hash`.
+    pub async fn mark_replaced(
+        &self,
+        address: &str,
+        nonce: i64,
+        tx_hash: &str,
+    ) -> Result<Vec<Transaction>, sqlx::Error> {
+        sqlx::query_as!(
+            Transaction,
+            r#"
+            UPDATE wallet_transactions
+            SET status = $4
+            WHERE LOWER(address) = LOWER($1)
+                AND nonce = $2
+                AND direction = $5
+                AND status = $6
+                AND tx_hash IS DISTINCT FROM $3
+            RETURNING *
+            "#,
+            address,
+            nonce,
+            tx_hash,
+            Transaction::REPLACED,
+            Transaction::OUT,
+            Transaction::PENDING
+        )
+        .fetch_all(&self.pool)
+        .await
+    }
+
+    /// Records a send the app made from one of the user's accounts.
+    pub async fn record_send(
+        &self,
+        user_id: Uuid,
+        send: RecordTransaction,
+    ) -> Result<Transaction, ErrorResponse> {
+        let address = lowercase_address(&send.address)?;
+        let recipient = lowercase_address(&send.recipient)?;
+        if !is_own_account(&self.pool, user_id, &address).await? {
+            return Err(ErrorResponse::bad_request(
+                "Transactions can only be recorded for your own accounts",
+            ));
+        }
+        if send.amount.is_sign_negative() {
+            return Err(ErrorResponse::bad_request("Amount must not be negative"));
+        }
+        let status = match send.status.as_str() {
+            Transaction::CONFIRMED => Transaction::CONFIRMED,
+            Transaction::FAILED => Transaction::FAILED,
+            _ => Transaction::PENDING,
+        };
+        let tx_hash = send.tx_hash.map(|hash| hash.to_lowercase());
+
+        self.record(NewTransaction {
+            user_id,
+            address: &address,
+            tx_hash: tx_hash.as_deref(),
+            direction: Transaction::OUT,
+            sender: &address,
+            recipient: Some(&recipient),
+            amount: send.amount,
+            status,
+            nonce: send.nonce,
+            block_number: send.block_number,
+            chain_id: send.chain_id,
+            error: send.error.as_deref(),
+            ..Default::default()
+        })
+        .await
+        .map_err(|e| {
+            log::error!("Error recording transaction: {:?}", e);
+            ErrorResponse::internal_error()
+        })
+    }
+
+    /// A page of the user's transactions that match the query, newest first.
+    pub async fn query_transactions(
+        &self,
+        user_id: Uuid,
+        query: &TransactionQuery,
+    ) -> Result<TransactionPage, ErrorResponse> {
+        let (from, until) = date_range(query.from, query.to)?;
+        if let (Some(min), Some(max)) = (query.min_amount, query.max_amount) {
+            if min > max {
+                return Err(ErrorResponse::bad_request(
+                    "The minimum amount must not be above the maximum",
+                ));
+            }
+        }
+        let cursor = match (&query.after, &query.before) {
+            (Some(_), Some(_)) => {
+                return Err(ErrorResponse::bad_request(
+                    "A page can only start after or end before a transaction",
+                ))
+            }
+            (Some(cursor), None) | (None, Some(cursor)) => Some(
+                parse_cursor(cursor).ok_or_else(|| ErrorResponse::bad_request("Invalid cursor"))?,
+            ),
+            (None, None) => None,
+        };
+        let backward = query.before.is_some();
+        let counterparty = query
+            .counterparty
+            .as_deref()
+            .map(|counterparty| escape_like(&counterparty.to_lowercase()));
+        let ether_only = query.token.as_deref() == Some(TransactionQuery::ETHER);
+        let token = query
+            .token
+            .as_deref()
+            .filter(|_| !ether_only)
+            .map(|token| token.to_lowercase());
+        let limit = query.limit();
+
+        let total = sqlx::query_scalar!(
+            r#"
+            SELECT COUNT(*) AS "count!" FROM wallet_transactions
+            WHERE user_id = $1
+                AND ($2::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) >= $2)
+                AND ($3::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) < $3)
+                AND ($4::TEXT IS NULL OR direction = $4)
+                AND ($5::TEXT IS NULL OR status = $5)
+                AND ($6::TEXT IS NULL OR LOWER(CASE WHEN direction = 'out' THEN recipient ELSE sender END) LIKE '%' || $6 || '%' ESCAPE '\')
+                AND ($7::NUMERIC IS NULL OR amount >= $7)
+                AND ($8::NUMERIC IS NULL OR amount <= $8)
+                AND ($9::TEXT IS NULL OR LOWER(token) = $9)
+                AND (NOT $10 OR token IS NULL)
+            "#,
+            user_id,
+            from,
+            until,
+            query.direction,
+            query.status,
+            counterparty,
+            query.min_amount,
+            query.max_amount,
+            token,
+            ether_only
+        )
+        .fetch_one(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error counting transactions: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        // One more than a page tells whether there are more beyond it
+        let mut transactions = sqlx::query_as!(
+            Transaction,
+            r#"
+            SELECT * FROM wallet_transactions
+            WHERE user_id = $1
+                AND ($2::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) >= $2)
+                AND ($3::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) < $3)
+                AND ($4::TEXT IS NULL OR direction = $4)
+                AND ($5::TEXT IS NULL OR status = $5)
+                AND ($6::TEXT IS NULL OR LOWER(CASE WHEN direction = 'out' THEN recipient ELSE sender END) LIKE '%' || $6 || '%' ESCAPE '\')
+                AND ($7::NUMERIC IS NULL OR amount >= $7)
+                AND ($8::NUMERIC IS NULL OR amount <= $8)
+                AND ($9::TEXT IS NULL OR LOWER(token) = $9)
+                AND (NOT $10 OR token IS NULL)
+                AND ($11::TIMESTAMPTZ IS NULL OR CASE
+                    WHEN $13 THEN (COALESCE(block_time, created_at), id) > ($11::TIMESTAMPTZ, $12::UUID)
+                    ELSE (COALESCE(block_time, created_at), id) < ($11::TIMESTAMPTZ, $12::UUID)
+                END)
+            ORDER BY
+                CASE WHEN $13 THEN COALESCE(block_time, created_at) END ASC,
+                CASE WHEN $13 THEN id END ASC,
+                COALESCE(block_time, created_at) DESC,
+                id DESC
+            LIMIT $14
+            "#,
+            user_id,
+            from,
+            until,
+            query.direction,
+            query.status,
+            counterparty,
+            query.min_amount,
+            query.max_amount,
+            token,
+            ether_only,
+            cursor.map(|(time, _)| time),
+            cursor.map(|(_, id)| id),
+            backward,
+            limit + 1
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error querying transactions: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+
+        let has_more = transactions.len() as i64 > limit;
+        transactions.truncate(limit as usize);
+        let (next, previous) = if backward {
+            transactions.reverse();
+            (
+                transactions.last().map(Transaction::cursor),
+                transactions
+                    .first()
+                    .filter(|_| has_more)
+                    .map(Transaction::cursor),
+            )
+        } else {
+            (
+                transactions
+                    .last()
+                    .filter(|_| has_more)
+                    .map(Transaction::cursor),
+                transactions
+                    .first()
+                    .filter(|_| query.after.is_some())
+                    .map(Transaction::cursor),
+            )
+        };
+
+        Ok(TransactionPage {
+            transactions,
+            total,
+            next,
+            previous,
+        })
+    }
+
+    /// The user's transactions between the query's dates, oldest first. A
+    /// period with more than [`TransactionExportQuery::MAX_ROWS`] of them is
+    /// refused.
+    pub async fn export(
+        &self,
+        user_id: Uuid,
+        query: &TransactionExportQuery,
+    ) -> Result<Vec<ExportedTransaction>, ErrorResponse> {
+        let (from, until) = date_range(query.from, query.to)?;
+
+        // Only what happened on chain: no pending, replaced or unsent ones
+        let transactions = sqlx::query_as!(
+            Transaction,
+            r#"
+            SELECT * FROM wallet_transactions
+            WHERE user_id = $1
+                AND tx_hash IS NOT NULL
+                AND status IN ($5, $6)
+                AND ($2::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) >= $2)
+                AND ($3::TIMESTAMPTZ IS NULL OR COALESCE(block_time, created_at) < $3)
+            ORDER BY COALESCE(block_time, created_at), tx_hash, direction
+            LIMIT $4
+            "#,
+            user_id,
+            from,
+            until,
+            TransactionExportQuery::MAX_ROWS + 1,
+            Transaction::CONFIRMED,
+            Transaction::FAILED
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| {
+            log::error!("Error exporting transactions: {:?}", e);
+            ErrorResponse::internal_error()
+        })?;
+        if transactions.len() as i64 > TransactionExportQuery::MAX_ROWS {
+            return Err(ErrorResponse::bad_request(&format!(
+                "A statement covers at most {} transactions, choose a shorter period",
+                TransactionExportQuery::MAX_ROWS
+            )));
+        }
+
+        Ok(transactions
+            .into_iter()
+            .map(ExportedTransaction::from)
+            .collect())
+    }
+}
@@ -0,0 +1,732 @@
+use std::{collections::HashSet, sync::Arc};
+
+use app_config::ErrorResponse;
+use ethers::{
+    providers::{Http, Middleware, Provider, RpcError},
+    types::{Bytes, H256},
+    utils::keccak256,
+};
+use sqlx::{PgConnection, PgPool};
+use uuid::Uuid;
+use wallet_api::{
+    address::parse_address,
+    amount::{from_decimal, ETHER_DECIMALS},
+    CreateTreasury, CreateTreasuryProposal, Treasury, TreasuryAuditEntry, TreasuryOverview,
+    TreasuryProposal, TreasurySigner,
+};
+
+use crate::{contacts::checksummed, custody::VaultKey, CustodyService};
+
+/// Shared treasury wallets whose sends need the approval of several staff
+/// signers. The treasury key is held in the custody key vault, so treasuries
+/// are only available where custody is enabled.
+#[derive(Debug)]
+pub struct TreasuryService {
+    pool: PgPool,
+    provider: Provider<Http>,
+    custody: Arc<CustodyService>,
+}
+
+/// Proposals executing for longer than this are looked up on chain, as the
+/// server that broadcast their send stopped or could not reach the node.
+const UNSETTLED_EXECUTION_MINUTES: i64 = 10;
+
+/// What became of broadcasting the send of an executing proposal.
+enum Broadcast {
+    Sent(H256),
+    /// The node refused the send, so it can never be mined.
+    Rejected(String),
+    /// The node could not be reached, so it may or may not have the send.
+    Unknown,
+}
+
+fn internal_error(action: &str, e: impl std::fmt::Debug) -> ErrorResponse {
+    log::error!("Error {}: {:?}", action, e);
+    ErrorResponse::internal_error()
+}
+
+/// Appends an entry to a treasury's audit trail. Entries without a user were
+/// made by the server itself.
+async fn audit(
+    conn: &mut PgConnection,
+    treasury_id: Uuid,
+    proposal_id: Option<Uuid>,
+    user_id: Option<Uuid>,
+    event: &str,
+    detail: &str,
+) -> Result<(), ErrorResponse> {
+    log::info!(
+        "Treasury audit: {} {} {:?} by {:?}: {}",
+        treasury_id,
+        event,
+        proposal_id,
+        user_id,
+        detail
+    );
+
+    sqlx::query!(
+        r#"
+        INSERT INTO wallet_treasury_audit_log (treasury_id, proposal_id, user_id, event, detail)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+        treasury_id,
+        proposal_id,
+        user_id,
+        event,
+        detail
+    )
+    .execute(conn)
+    .await
+    .map(|_| ())
+    .map_err(|e| internal_error("writing treasury audit log", e))
+}
+
+impl TreasuryService {
+    pub fn new(
+        pool: PgPool,
+        rpc_url: &str,
+        custody: Arc<CustodyService>,
+    ) -> Result<Self, anyhow::Error> {
+        Ok(Self {
+            pool,
+            provider: Provider::<Http>::try_from(rpc_url)?,
+            custody,
+        })
+    }
+
+    async fn signers(&self, treasury_id: Uuid) -> Result<Vec<TreasurySigner>, ErrorResponse> {
+        sqlx::query_as!(
+            TreasurySigner,
+            r#"
+            SELECT u.id AS user_id, u.username, u.name
+            FROM wallet_treasury_signers s
+            JOIN auth_users u ON u.id = s.user_id
+            WHERE s.treasury_id = $1
+            ORDER BY u.username
+            "#,
+            treasury_id
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| internal_error("listing treasury signers", e))
+    }
+
+    /// Treasuries the user is a signer of, or all of them with `all`.
+    pub async fn treasuries(
+        &self,
+        user_id: Uuid,
+        all: bool,
+    ) -> Result<Vec<TreasuryOverview>, ErrorResponse> {
+        let treasuries = sqlx::query_as!(
+            Treasury,
+            r#"
+            SELECT t.id, t.name, t.address, t.threshold, t.created_by, t.created_at
+            FROM wallet_treasuries t
+            WHERE $2 OR EXISTS (
+                SELECT 1 FROM wallet_treasury_signers s
+                WHERE s.treasury_id = t.id AND s.user_id = $1
+            )
+            ORDER BY LOWER(t.name), t.created_at
+            "#,
+            user_id,
+            all
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| internal_error("listing treasuries", e))?;
+
+        let mut overviews = Vec::with_capacity(treasuries.len());
+        for treasury in treasuries {
+            let signers = self.signers(treasury.id).await?;
+            overviews.push(TreasuryOverview { treasury, signers });
+        }
+        Ok(overviews)
+    }
+
+    /// Creates a treasury with a new vault key. Every signer must be staff,
+    /// and the threshold must be reachable by them.
+    pub async fn create_treasury(
+        &self,
+        user_id: Uuid,
+        create: CreateTreasury,
+    ) -> Result<TreasuryOverview, ErrorResponse> {
+        let usernames: HashSet<&str> = create.signers.iter().map(|s| s.trim()).collect();
+        if create.threshold as usize > usernames.len() {
+            return Err(ErrorResponse::bad_request(
+                "The threshold cannot be more than the number of signers",
+            ));
+        }
+        let usernames: Vec<String> = usernames.into_iter().map(str::to_string).collect();
+        let signers = sqlx::query_as!(
+            TreasurySigner,
+            r#"
+            SELECT id AS user_id, username, name
+            FROM auth_users
+            WHERE username = ANY($1) AND role IN ('staff', 'admin')
+            ORDER BY username
+            "#,
+            &usernames
+        )
+        .fetch_all(&self.pool)
+        .await
+        .map_err(|e| internal_error("fetching treasury signers", e))?;
+        if signers.len() != usernames.len() {
+            return Err(ErrorResponse::bad_request(
+                "Every signer must be an existing staff member",
+            ));
+        }
+
+        let treasury_id = Uuid::new_v4();
+        let (handle, address) = self.custody.create_key(treasury_id).await?;
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        let treasury = sqlx::query_as!(
+            Treasury,
+            r#"
+            INSERT INTO wallet_treasuries (id, name, address, key_handle, threshold, created_by)
+            VALUES ($1, $2, $3, $4, $5, $6)
+            RETURNING id, name, address, threshold, created_by, created_at
+            "#,
+            treasury_id,
+            create.name.trim(),
+            address,
+            handle,
+            create.threshold,
+            user_id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal_error("creating treasury", e))?;
+        for signer in &signers {
+            sqlx::query!(
+                "INSERT INTO wallet_treasury_signers (treasury_id, user_id) VALUES ($1, $2)",
+                treasury.id,
+                signer.user_id
+            )
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| internal_error("adding treasury signer", e))?;
+        }
+        let detail = format!(
+            "{} of {} approvals required: {}",
+            treasury.threshold,
+            signers.len(),
+            signers
+                .iter()
+                .map(|signer| signer.username.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        audit(
+            &mut tx,
+            treasury.id,
+            None,
+            Some(user_id),
+            "treasury_created",
+            &detail,
+        )
+        .await?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing treasury", e))?;
+
+        Ok(TreasuryOverview { treasury, signers })
+    }
+
+    /// The treasury, if the user is one of its signers.
+    async fn signed_treasury(
+        &self,
+        conn: &mut PgConnection,
+        treasury_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Treasury, ErrorResponse> {
+        sqlx::query_as!(
+            Treasury,
+            r#"
+            SELECT t.id, t.name, t.address, t.threshold, t.created_by, t.created_at
+            FROM wallet_treasuries t
+            JOIN wallet_treasury_signers s ON s.treasury_id = t.id
+            WHERE t.id = $1 AND s.user_id = $2
+            "#,
+            treasury_id,
+            user_id
+        )
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| internal_error("fetching treasury", e))?
+        .ok_or_else(|| ErrorResponse::not_found("Treasury not found"))
+    }
+
+    async fn proposal(
+        conn: &mut PgConnection,
+        proposal_id: Uuid,
+    ) -> Result<TreasuryProposal, ErrorResponse> {
+        sqlx::query_as!(
+            TreasuryProposal,
+            r#"
+            SELECT
+                p.id, p.treasury_id, p.proposed_by, p.recipient, p.amount, p.memo, p.status,
+                COALESCE(ARRAY_AGG(v.user_id) FILTER (WHERE v.approved), '{}') AS "approvals!",
+                COALESCE(ARRAY_AGG(v.user_id) FILTER (WHERE NOT v.approved), '{}') AS "rejections!",
+                p.tx_hash, p.error, p.created_at, p.updated_at
+            FROM wallet_treasury_proposals p
+            LEFT JOIN wallet_treasury_votes v ON v.proposal_id = p.id
+            WHERE p.id = $1
+            GROUP BY p.id
+            "#,
+            proposal_id
+        )
+        .fetch_optional(conn)
+        .await
+        .map_err(|e| internal_error("fetching treasury proposal", e))?
+        .ok_or_else(|| ErrorResponse::not_found("Proposal not found"))
+    }
+
+    /// Locks a proposal for a change and checks that the user can sign for
+    /// its treasury.
+    async fn lock_proposal(
+        &self,
+        conn: &mut PgConnection,
+        proposal_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<(Treasury, TreasuryProposal), ErrorResponse> {
+        let treasury_id = sqlx::query_scalar!(
+            "SELECT treasury_id FROM wallet_treasury_proposals WHERE id = $1 FOR UPDATE",
+            proposal_id
+        )
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| internal_error("locking treasury proposal", e))?
+        .ok_or_else(|| ErrorResponse::not_found("Proposal not found"))?;
+        let treasury = self
+            .signed_treasury(&mut *conn, treasury_id, user_id)
+            .await
+            .map_err(|_| ErrorResponse::not_found("Proposal not found"))?;
+        let proposal = Self::proposal(conn, proposal_id).await?;
+        Ok((treasury, proposal))
+    }
+
+    /// A treasury's proposals, newest first.
+    pub async fn proposals(
+        &self,
+        treasury_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<TreasuryProposal>, ErrorResponse> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| internal_error("acquiring connection", e))?;
+        self.signed_treasury(&mut conn, treasury_id, user_id)
+            .await?;
+
+        sqlx::query_as!(
+            TreasuryProposal,
+            r#"
+            SELECT
+                p.id, p.treasury_id, p.proposed_by, p.recipient, p.amount, p.memo, p.status,
+                COALESCE(ARRAY_AGG(v.user_id) FILTER (WHERE v.approved), '{}') AS "approvals!",
+                COALESCE(ARRAY_AGG(v.user_id) FILTER (WHERE NOT v.approved), '{}') AS "rejections!",
+                p.tx_hash, p.error, p.created_at, p.updated_at
+            FROM wallet_treasury_proposals p
+            LEFT JOIN wallet_treasury_votes v ON v.proposal_id = p.id
+            WHERE p.treasury_id = $1
+            GROUP BY p.id
+            ORDER BY p.created_at DESC
+            "#,
+            treasury_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| internal_error("listing treasury proposals", e))
+    }
+
+    /// A treasury's audit trail, newest first.
+    pub async fn audit_log(
+        &self,
+        treasury_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<Vec<TreasuryAuditEntry>, ErrorResponse> {
+        let mut conn = self
+            .pool
+            .acquire()
+            .await
+            .map_err(|e| internal_error("acquiring connection", e))?;
+        self.signed_treasury(&mut conn, treasury_id, user_id)
+            .await?;
+
+        sqlx::query_as!(
+            TreasuryAuditEntry,
+            r#"
+            SELECT a.id, a.treasury_id, a.proposal_id, u.username AS "username?", a.event,
+                a.detail, a.created_at
+            FROM wallet_treasury_audit_log a
+            LEFT JOIN auth_users u ON u.id = a.user_id
+            WHERE a.treasury_id = $1
+            ORDER BY a.created_at DESC
+            "#,
+            treasury_id
+        )
+        .fetch_all(&mut *conn)
+        .await
+        .map_err(|e| internal_error("listing treasury audit log", e))
+    }
+
+    /// Proposes a send from a treasury. It waits for the signers' votes.
+    pub async fn propose(
+        &self,
+        treasury_id: Uuid,
+        user_id: Uuid,
+        proposal: CreateTreasuryProposal,
+    ) -> Result<TreasuryProposal, ErrorResponse> {
+        let recipient = checksummed(&proposal.recipient)?;
+        let memo = proposal.memo.filter(|memo| !memo.trim().is_empty());
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        self.signed_treasury(&mut tx, treasury_id, user_id).await?;
+        let id = sqlx::query_scalar!(
+            r#"
+            INSERT INTO wallet_treasury_proposals (treasury_id, proposed_by, recipient, amount, memo)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id
+            "#,
+            treasury_id,
+            user_id,
+            recipient,
+            proposal.amount,
+            memo
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal_error("creating treasury proposal", e))?;
+        audit(
+            &mut tx,
+            treasury_id,
+            Some(id),
+            Some(user_id),
+            "proposal_created",
+            &format!("{} ETH to {}", proposal.amount.normalize(), recipient),
+        )
+        .await?;
+        let created = Self::proposal(&mut tx, id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing treasury proposal", e))?;
+
+        Ok(created)
+    }
+
+    /// Records a signer's vote. The proposal is approved once the threshold
+    /// is reached, and rejected once it no longer can be.
+    pub async fn vote(
+        &self,
+        proposal_id: Uuid,
+        user_id: Uuid,
+        approve: bool,
+    ) -> Result<TreasuryProposal, ErrorResponse> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        let (treasury, proposal) = self.lock_proposal(&mut tx, proposal_id, user_id).await?;
+        if proposal.status != TreasuryProposal::PENDING {
+            return Err(ErrorResponse::conflict("Voting on this proposal has ended"));
+        }
+        if proposal.has_voted(user_id) {
+            return Err(ErrorResponse::conflict(
+                "You already voted on this proposal",
+            ));
+        }
+
+        sqlx::query!(
+            "INSERT INTO wallet_treasury_votes (proposal_id, user_id, approved) VALUES ($1, $2, $3)",
+            proposal_id,
+            user_id,
+            approve
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal_error("recording treasury vote", e))?;
+
+        let signers = sqlx::query_scalar!(
+            r#"SELECT COUNT(*) AS "count!" FROM wallet_treasury_signers WHERE treasury_id = $1"#,
+            treasury.id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal_error("counting treasury signers", e))?;
+        let threshold = treasury.threshold as i64;
+        let approvals = proposal.approvals.len() as i64 + approve as i64;
+        let rejections = proposal.rejections.len() as i64 + !approve as i64;
+        let status = if approvals >= threshold {
+            TreasuryProposal::APPROVED
+        } else if signers - rejections < threshold {
+            TreasuryProposal::REJECTED
+        } else {
+            TreasuryProposal::PENDING
+        };
+        sqlx::query!(
+            "UPDATE wallet_treasury_proposals SET status = $2, updated_at = NOW() WHERE id = $1",
+            proposal_id,
+            status
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal_error("updating treasury proposal", e))?;
+
+        let (event, detail) = if approve {
+            (
+                "proposal_approved",
+                format!("{} of {} approvals", approvals, threshold),
+            )
+        } else {
+            (
+                "proposal_rejected",
+                format!("{} of {} signers rejected", rejections, signers),
+            )
+        };
+        audit(
+            &mut tx,
+            treasury.id,
+            Some(proposal_id),
+            Some(user_id),
+            event,
+            &detail,
+        )
+        .await?;
+        let voted = Self::proposal(&mut tx, proposal_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing treasury vote", e))?;
+
+        Ok(voted)
+    }
+
+    /// Signs an approved proposal with the treasury key and sends it to the
+    /// node. A send the node refuses marks the proposal failed; errors before
+    /// that, such as a lack of funds, leave it approved to be tried again. When
+    /// the node cannot be reached it may still have the send, so the proposal
+    /// stays executing for `reconcile_executing_proposals` to settle.
+    ///
+    /// The proposal is marked executing, with the hash of the signed send, in
+    /// its own committed transaction before the send is broadcast, so that no
+    /// lock is held while the node is called and a proposal left executing by
+    /// a server that stopped can be traced by its hash.
+    pub async fn execute(
+        &self,
+        proposal_id: Uuid,
+        user_id: Uuid,
+    ) -> Result<TreasuryProposal, ErrorResponse> {
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        let (treasury, proposal) = self.lock_proposal(&mut tx, proposal_id, user_id).await?;
+        if proposal.status != TreasuryProposal::APPROVED {
+            return Err(ErrorResponse::conflict(
+                "Only approved proposals can be executed",
+            ));
+        }
+        let key_handle = sqlx::query_scalar!(
+            "SELECT key_handle FROM wallet_treasuries WHERE id = $1",
+            treasury.id
+        )
+        .fetch_one(&mut *tx)
+        .await
+        .map_err(|e| internal_error("fetching treasury key", e))?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing transaction", e))?;
+
+        let from = parse_address(&treasury.address)
+            .map_err(|e| internal_error("parsing treasury address", e))?;
+        let to = parse_address(&proposal.recipient)
+            .map_err(|e| internal_error("parsing proposal recipient", e))?;
+        let value = from_decimal(proposal.amount, ETHER_DECIMALS)
+            .map_err(|e| ErrorResponse::bad_request(&e.to_string()))?;
+        let sent = self
+            .custody
+            .sign_and_submit(
+                VaultKey {
+                    owner: treasury.id,
+                    handle: &key_handle,
+                    address: from,
+                },
+                to,
+                value,
+                Bytes::new(),
+                |raw| async move {
+                    let tx_hash = H256::from(keccak256(&raw));
+                    // Only one execution gets past this, as it leaves the
+                    // proposal no longer approved
+                    sqlx::query!(
+                        r#"
+                        UPDATE wallet_treasury_proposals
+                        SET status = $2, tx_hash = $3, updated_at = NOW()
+                        WHERE id = $1 AND status = $4
+                        RETURNING id
+                        "#,
+                        proposal_id,
+                        TreasuryProposal::EXECUTING,
+                        format!("{:?}", tx_hash),
+                        TreasuryProposal::APPROVED
+                    )
+                    .fetch_optional(&self.pool)
+                    .await
+                    .map_err(|e| internal_error("marking treasury proposal executing", e))?
+                    .ok_or_else(|| {
+                        ErrorResponse::conflict("Only approved proposals can be executed")
+                    })?;
+
+                    Ok(match self.provider.send_raw_transaction(raw).await {
+                        Ok(_) => Broadcast::Sent(tx_hash),
+                        Err(e) => {
+                            log::error!("Error sending treasury proposal {}: {:?}", proposal_id, e);
+                            match e.as_error_response() {
+                                Some(rejection) => Broadcast::Rejected(format!(
+                                    "The node rejected the transaction: {}",
+                                    rejection.message
+                                )),
+                                None => Broadcast::Unknown,
+                            }
+                        }
+                    })
+                },
+            )
+            .await?;
+
+        let sent = match sent {
+            Broadcast::Sent(tx_hash) => Ok(tx_hash),
+            Broadcast::Rejected(error) => Err(error),
+            // The node may have the send, so the proposal stays executing
+            // until the send is found on chain or known to be lost
+            Broadcast::Unknown => {
+                let mut conn = self
+                    .pool
+                    .acquire()
+                    .await
+                    .map_err(|e| internal_error("acquiring connection", e))?;
+                return Self::proposal(&mut conn, proposal_id).await;
+            }
+        };
+        self.settle_execution(treasury.id, proposal_id, Some(user_id), sent)
+            .await
+    }
+
+    /// Settles proposals left executing for longer than
+    /// `UNSETTLED_EXECUTION_MINUTES`, by a server that stopped or a node that
+    /// could not be reached, by looking their send up on chain. A send the
+    /// node knows marks the proposal executed and one it does not know failed;
+    /// proposals are left as they are while the node cannot be reached.
+    pub async fn reconcile_executing_proposals(&self) -> Result<usize, anyhow::Error> {
+        let executing = sqlx::query!(
+            r#"
+            SELECT id, treasury_id, tx_hash
+            FROM wallet_treasury_proposals
+            WHERE status = $1 AND updated_at < NOW() - make_interval(mins => $2)
+            "#,
+            TreasuryProposal::EXECUTING,
+            UNSETTLED_EXECUTION_MINUTES as i32
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let mut settled = 0;
+        for proposal in executing {
+            let tx_hash = proposal
+                .tx_hash
+                .as_deref()
+                .and_then(|tx_hash| tx_hash.parse::<H256>().ok());
+            let sent = match tx_hash {
+                Some(tx_hash) => match self.provider.get_transaction(tx_hash).await? {
+                    Some(_) => Ok(tx_hash),
+                    None => Err("The transaction was not found on chain".to_string()),
+                },
+                None => Err("The transaction was not recorded".to_string()),
+            };
+            self.settle_execution(proposal.treasury_id, proposal.id, None, sent)
+                .await
+                .map_err(|e| anyhow::anyhow!(e.message))?;
+            settled += 1;
+        }
+        Ok(settled)
+    }
+
+    pub async fn continuously_reconcile(self: Arc<Self>, period: std::time::Duration) {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            if let Err(e) = self.reconcile_executing_proposals().await {
+                log::error!("Error reconciling treasury proposals: {:?}", e);
+            }
+        }
+    }
+
+    /// Records the outcome of broadcasting the send of an executing proposal.
+    async fn settle_execution(
+        &self,
+        treasury_id: Uuid,
+        proposal_id: Uuid,
+        user_id: Option<Uuid>,
+        sent: Result<H256, String>,
+    ) -> Result<TreasuryProposal, ErrorResponse> {
+        let (status, error, event, detail) = match &sent {
+            Ok(tx_hash) => (
+                TreasuryProposal::EXECUTED,
+                None,
+                "proposal_executed",
+                format!("{:?}", tx_hash),
+            ),
+            Err(error) => (
+                TreasuryProposal::FAILED,
+                Some(error.as_str()),
+                "proposal_failed",
+                error.clone(),
+            ),
+        };
+
+        let mut tx = self
+            .pool
+            .begin()
+            .await
+            .map_err(|e| internal_error("starting transaction", e))?;
+        sqlx::query!(
+            r#"
+            UPDATE wallet_treasury_proposals
+            SET status = $2, error = $3, updated_at = NOW()
+            WHERE id = $1 AND status = $4
+            "#,
+            proposal_id,
+            status,
+            error,
+            TreasuryProposal::EXECUTING
+        )
+        .execute(&mut *tx)
+        .await
+        .map_err(|e| internal_error("updating treasury proposal", e))?;
+        audit(
+            &mut tx,
+            treasury_id,
+            Some(proposal_id),
+            user_id,
+            event,
+            &detail,
+        )
+        .await?;
+        let executed = Self::proposal(&mut tx, proposal_id).await?;
+        tx.commit()
+            .await
+            .map_err(|e| internal_error("committing treasury execution", e))?;
+
+        Ok(executed)
+    }
+}
@@ -0,0 +1,117 @@
+use std::{fmt, path::Path};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, OsRng, Payload},
+    Aes256Gcm, KeyInit, Nonce,
+};
+use async_trait::async_trait;
+use ethers::{
+    core::rand::thread_rng,
+    signers::{LocalWallet, Signer},
+    types::{transaction::eip2718::TypedTransaction, Address, Signature},
+    utils::hex,
+};
+use uuid::Uuid;
+
+/// Length of the AES-GCM nonce that starts every sealed key.
+const NONCE_LENGTH: usize = 12;
+
+/// Holds the private keys of custodial accounts and signs with them. Keys are
+/// only ever handed out as opaque handles, which the caller stores and passes
+/// back to sign. A vault backed by an HSM or a cloud KMS can use the handle
+/// to name a key it keeps itself.
+///
+/// Every key belongs to an owner, the custodial account or treasury it was
+/// created for, and its handle only works for that owner. A handle copied to
+/// another row cannot sign for it.
+#[async_trait]
+pub trait KeyVault: fmt::Debug + Send + Sync {
+    /// Creates a key for `owner`, returning its handle and address.
+    async fn create_key(&self, owner: Uuid) -> Result<(Vec<u8>, Address), anyhow::Error>;
+
+    /// Signs `tx`, which must carry its chain id, with the key of `owner`
+    /// that `handle` names.
+    async fn sign_transaction(
+        &self,
+        owner: Uuid,
+        handle: &[u8],
+        tx: &TypedTransaction,
+    ) -> Result<Signature, anyhow::Error>;
+}
+
+/// Stand-in for a KMS: keys are encrypted with AES-256-GCM under a master key
+/// read from a file, and the handle is the encrypted key itself. The owner's
+/// id is authenticated along with it.
+pub struct FileKeyVault {
+    cipher: Aes256Gcm,
+}
+
+impl fmt::Debug for FileKeyVault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FileKeyVault").finish_non_exhaustive()
+    }
+}
+
+impl FileKeyVault {
+    /// Reads the 32 byte master key, hex encoded, from `path`.
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, anyhow::Error> {
+        let contents = std::fs::read_to_string(path)?;
+        let master_key = hex::decode(contents.trim().trim_start_matches("0x"))?;
+        let cipher = Aes256Gcm::new_from_slice(&master_key)
+            .map_err(|_| anyhow::anyhow!("Master key must be 32 bytes"))?;
+        Ok(Self { cipher })
+    }
+
+    fn seal(&self, owner: Uuid, key: &[u8]) -> Result<Vec<u8>, anyhow::Error> {
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let payload = Payload {
+            msg: key,
+            aad: owner.as_bytes(),
+        };
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, payload)
+            .map_err(|_| anyhow::anyhow!("Failed to encrypt key"))?;
+        Ok([nonce.as_slice(), &ciphertext].concat())
+    }
+
+    fn open(&self, owner: Uuid, handle: &[u8]) -> Result<LocalWallet, anyhow::Error> {
+        if handle.len() <= NONCE_LENGTH {
+            return Err(anyhow::anyhow!("Key handle is malformed"));
+        }
+        let (nonce, ciphertext) = handle.split_at(NONCE_LENGTH);
+        let payload = Payload {
+            msg: ciphertext,
+            aad: owner.as_bytes(),
+        };
+        let key = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), payload)
+            .map_err(|_| {
+                anyhow::anyhow!("Key could not be decrypted with the master key for its owner")
+            })?;
+        Ok(LocalWallet::from_bytes(&key)?)
+    }
+}
+
+#[async_trait]
+impl KeyVault for FileKeyVault {
+    async fn create_key(&self, owner: Uuid) -> Result<(Vec<u8>, Address), anyhow::Error> {
+        let wallet = LocalWallet::new(&mut thread_rng());
+        let handle = self.seal(owner, &wallet.signer().to_bytes())?;
+        Ok((handle, wallet.address()))
+    }
+
+    async fn sign_transaction(
+        &self,
+        owner: Uuid,
+        handle: &[u8],
+        tx: &TypedTransaction,
+    ) -> Result<Signature, anyhow::Error> {
+        let chain_id = tx
+            .chain_id()
+            .ok_or_else(|| anyhow::anyhow!("Transaction has no chain id"))?;
+        let wallet = self.open(owner, handle)?.with_chain_id(chain_id.as_u64());
+        Ok(wallet.sign_transaction_sync(tx)?)
+    }
+}
@@ -0,0 +1,221 @@
+//! Adds accounts to wallets, and rotates and upgrades their keys, in the
+//! database in `DATABASE_URL`.
+
+use app_config::ErrorResponse;
+use axum::http::StatusCode;
+use ethers::{
+    core::rand::thread_rng,
+    signers::{LocalWallet, Signer},
+    utils::hash_message,
+};
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{Account, CreateAccount, CreateWallet, RotateWalletKey, UpgradeAccountKey};
+use wallet_app::WalletService;
+
+/// Derivation path of the wallets' own keys.
+const WALLET_PATH: &str = "m/44'/60'/0'/0/0";
+
+/// A user with a wallet, whose own key is `key`.
+async fn wallet(pool: &PgPool, service: &WalletService, key: &LocalWallet) -> Uuid {
+    let user_id = sqlx::query_scalar!(
+        "INSERT INTO auth_users (username) VALUES ($1) RETURNING id",
+        format!("accounts-{}", Uuid::new_v4())
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    service
+        .create_wallet(
+            user_id,
+            CreateWallet {
+                encrypted_private_key: "{}".to_string(),
+                encrypted_mnemonic: None,
+                derivation_path: Some(WALLET_PATH.to_string()),
+                address: format!("{:?}", key.address()),
+            },
+        )
+        .await
+        .unwrap();
+    user_id
+}
+
+/// A request to add the imported account of `key`, signed by `signer` for
+/// `user_id`.
+fn new_account(key: &LocalWallet, signer: &LocalWallet, user_id: Uuid) -> CreateAccount {
+    let address = format!("{:?}", key.address());
+    let encrypted_private_key = "{\"version\":3}".to_string();
+    let message = CreateAccount::message(user_id, &address, &encrypted_private_key);
+    let signature = signer.sign_hash(hash_message(message)).unwrap();
+    CreateAccount {
+        label: "Imported".to_string(),
+        derivation_index: None,
+        encrypted_private_key,
+        address,
+        signature: format!("0x{}", signature),
+    }
+}
+
+/// Status of a refused request. Accounts are not `Debug`, as they hold keys.
+fn refusal(result: Result<Account, ErrorResponse>) -> StatusCode {
+    match result {
+        Ok(_) => panic!("The account was added"),
+        Err(e) => e.status_code,
+    }
+}
+
+async fn delete_user(pool: &PgPool, user_id: Uuid) {
+    sqlx::query!("DELETE FROM auth_users WHERE id = $1", user_id)
+        .execute(pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn adds_only_accounts_signed_by_their_key() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let service = WalletService::new(pool.clone());
+    let user_id = wallet(&pool, &service, &LocalWallet::new(&mut thread_rng())).await;
+    let other_user_id = wallet(&pool, &service, &LocalWallet::new(&mut thread_rng())).await;
+    let key = LocalWallet::new(&mut thread_rng());
+
+    // Signed by another key
+    let refused = service
+        .create_account(
+            user_id,
+            new_account(&key, &LocalWallet::new(&mut thread_rng()), user_id),
+        )
+        .await;
+    assert_eq!(refusal(refused), StatusCode::FORBIDDEN);
+
+    // Signed for another user
+    let refused = service
+        .create_account(user_id, new_account(&key, &key, other_user_id))
+        .await;
+    assert_eq!(refusal(refused), StatusCode::FORBIDDEN);
+
+    let account = service
+        .create_account(user_id, new_account(&key, &key, user_id))
+        .await
+        .unwrap();
+    assert_eq!(account.address, format!("{:?}", key.address()));
+
+    let refused = service
+        .create_account(user_id, new_account(&key, &key, user_id))
+        .await;
+    assert_eq!(refusal(refused), StatusCode::CONFLICT);
+
+    delete_user(&pool, user_id).await;
+    delete_user(&pool, other_user_id).await;
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn keeps_imported_accounts_when_the_wallet_key_changes() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let service = WalletService::new(pool.clone());
+    let user_id = wallet(&pool, &service, &LocalWallet::new(&mut thread_rng())).await;
+    let imported = LocalWallet::new(&mut thread_rng());
+    service
+        .create_account(user_id, new_account(&imported, &imported, user_id))
+        .await
+        .unwrap();
+    let derived = LocalWallet::new(&mut thread_rng());
+    service
+        .create_account(
+            user_id,
+            CreateAccount {
+                derivation_index: Some(1),
+                ..new_account(&derived, &derived, user_id)
+            },
+        )
+        .await
+        .unwrap();
+
+    let key = LocalWallet::new(&mut thread_rng());
+    service
+        .rotate_wallet_key(
+            user_id,
+            RotateWalletKey {
+                encrypted_private_key: "{}".to_string(),
+                encrypted_mnemonic: None,
+                derivation_path: Some(WALLET_PATH.to_string()),
+                address: format!("{:?}", key.address()),
+            },
+        )
+        .await
+        .unwrap();
+
+    let mut addresses = service
+        .list_accounts(user_id)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|account| account.address)
+        .collect::<Vec<_>>();
+    addresses.sort();
+    let mut expected = vec![
+        format!("{:?}", key.address()),
+        format!("{:?}", imported.address()),
+    ];
+    expected.sort();
+    assert_eq!(addresses, expected);
+
+    delete_user(&pool, user_id).await;
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn upgrades_a_legacy_key_only_once() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let service = WalletService::new(pool.clone());
+    let user_id = wallet(&pool, &service, &LocalWallet::new(&mut thread_rng())).await;
+    let key = LocalWallet::new(&mut thread_rng());
+    let address = format!("{:?}", key.address());
+    let encrypted_private_key = "bGVnYWN5".to_string();
+    let message = CreateAccount::message(user_id, &address, &encrypted_private_key);
+    let signature = key.sign_hash(hash_message(message)).unwrap();
+    let account = service
+        .create_account(
+            user_id,
+            CreateAccount {
+                label: "Legacy".to_string(),
+                derivation_index: None,
+                encrypted_private_key,
+                address,
+                signature: format!("0x{}", signature),
+            },
+        )
+        .await
+        .unwrap();
+
+    let upgrade = |version: u32| {
+        let encrypted_private_key = format!("{{\"version\":{}}}", version);
+        let signature = key.sign_hash(hash_message(&encrypted_private_key)).unwrap();
+        service.upgrade_account_key(
+            user_id,
+            account.id,
+            UpgradeAccountKey {
+                encrypted_private_key,
+                signature: format!("0x{}", signature),
+            },
+        )
+    };
+
+    // Racing uploads replace the key once between them
+    let (first, second) = tokio::join!(upgrade(3), upgrade(4));
+    let refused = match (first, second) {
+        (Ok(_), refused) | (refused, Ok(_)) => refused,
+        _ => panic!("Neither upload upgraded the key"),
+    };
+    assert_eq!(refusal(refused), StatusCode::CONFLICT);
+
+    delete_user(&pool, user_id).await;
+}
@@ -0,0 +1,189 @@
+//! Resolves names against an ENS registry and resolver deployed on a local
+//! Anvil node.
+
+use axum::http::StatusCode;
+use ethers::{
+    abi::{self, Token},
+    middleware::SignerMiddleware,
+    providers::{ens, Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, Bytes, TransactionRequest},
+    utils::{hex, id, Anvil, AnvilInstance},
+};
+use wallet_api::{address::checksum_address, EnsResolution};
+use wallet_app::EnsService;
+
+type Client = SignerMiddleware<Provider<Http>, LocalWallet>;
+
+/// Runtime code of a registry that lets anyone set the resolver of a node:
+///
+/// ```text
+///     PUSH1 0x00 CALLDATALOAD PUSH1 0xe0 SHR
+///     DUP1 PUSH4 0x0178b8bf EQ PUSH1 resolver JUMPI      ; resolver(bytes32)
+///     DUP1 PUSH4 0x1896f70a EQ PUSH1 set_resolver JUMPI  ; setResolver(bytes32,address)
+///     PUSH1 0x00 DUP1 REVERT
+/// resolver:
+///     JUMPDEST PUSH1 0x04 CALLDATALOAD SLOAD
+///     PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+/// set_resolver:
+///     JUMPDEST PUSH1 0x24 CALLDATALOAD PUSH1 0x04 CALLDATALOAD SSTORE STOP
+/// ```
+const REGISTRY_RUNTIME: &str =
+    "60003560e01c80630178b8bf14601e5780631896f70a14602b57600080fd5b60043554\
+                                60005260206000f35b6024356004355500";
+
+/// Runtime code of a resolver that lets anyone set the address and the name
+/// of a node, names being at most 32 bytes long. It claims to support every
+/// interface.
+///
+/// ```text
+///     PUSH1 0x00 CALLDATALOAD PUSH1 0xe0 SHR
+///     DUP1 PUSH4 0x01ffc9a7 EQ PUSH1 supports JUMPI  ; supportsInterface(bytes4)
+///     DUP1 PUSH4 0x3b3b57de EQ PUSH1 addr JUMPI      ; addr(bytes32)
+///     DUP1 PUSH4 0xd5fa2b00 EQ PUSH1 set_addr JUMPI  ; setAddr(bytes32,address)
+///     DUP1 PUSH4 0x691f3431 EQ PUSH1 name JUMPI      ; name(bytes32)
+///     DUP1 PUSH4 0x77372213 EQ PUSH1 set_name JUMPI  ; setName(bytes32,string)
+///     PUSH1 0x00 DUP1 REVERT
+/// supports:
+///     JUMPDEST PUSH1 0x01 PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+/// addr:
+///     JUMPDEST PUSH1 0x04 CALLDATALOAD SLOAD
+///     PUSH1 0x00 MSTORE PUSH1 0x20 PUSH1 0x00 RETURN
+/// set_addr:
+///     JUMPDEST PUSH1 0x24 CALLDATALOAD PUSH1 0x04 CALLDATALOAD SSTORE STOP
+/// name:                                  ; length at node + 1, bytes at node + 2
+///     JUMPDEST PUSH1 0x20 PUSH1 0x00 MSTORE
+///     PUSH1 0x04 CALLDATALOAD PUSH1 0x01 ADD SLOAD PUSH1 0x20 MSTORE
+///     PUSH1 0x04 CALLDATALOAD PUSH1 0x02 ADD SLOAD PUSH1 0x40 MSTORE
+///     PUSH1 0x60 PUSH1 0x00 RETURN
+/// set_name:
+///     JUMPDEST PUSH1 0x44 CALLDATALOAD PUSH1 0x04 CALLDATALOAD PUSH1 0x01 ADD SSTORE
+///     PUSH1 0x64 CALLDATALOAD PUSH1 0x04 CALLDATALOAD PUSH1 0x02 ADD SSTORE STOP
+/// ```
+const RESOLVER_RUNTIME: &str = "60003560e01c806301ffc9a714603c5780633b3b57de146047578063d5fa2b0014605457\
+                                8063691f343114605d5780637737221314607c57600080fd5b600160005260206000f3\
+                                5b6004355460005260206000f35b60243560043555005b602060005260043560010154\
+                                6020526004356002015460405260606000f35b604435600435600101556064356004\
+                                356002015500";
+
+/// Creation code that deploys `runtime` as is.
+fn creation_code(runtime: &[u8]) -> Vec<u8> {
+    // PUSH1 len DUP1 PUSH1 0x0b PUSH1 0x00 CODECOPY PUSH1 0x00 RETURN
+    let mut code = vec![0x60, runtime.len() as u8];
+    code.extend_from_slice(&[0x80, 0x60, 0x0b, 0x60, 0x00, 0x39, 0x60, 0x00, 0xf3]);
+    code.extend_from_slice(runtime);
+    code
+}
+
+async fn deploy(client: &Client, runtime: &str) -> Address {
+    let runtime = hex::decode(runtime).unwrap();
+    let tx = TransactionRequest::new().data(creation_code(&runtime));
+    let receipt = client
+        .send_transaction(tx, None)
+        .await
+        .unwrap()
+        .await
+        .unwrap()
+        .unwrap();
+    receipt.contract_address.unwrap()
+}
+
+async fn call(client: &Client, contract: Address, signature: &str, args: &[Token]) {
+    let data = [&id(signature)[..], &abi::encode(args)].concat();
+    let tx = TransactionRequest::new()
+        .to(contract)
+        .data(Bytes::from(data));
+    let receipt = client
+        .send_transaction(tx, None)
+        .await
+        .unwrap()
+        .await
+        .unwrap()
+        .unwrap();
+    assert_eq!(receipt.status, Some(1.into()), "{} failed", signature);
+}
+
+fn node(name: &str) -> Token {
+    Token::FixedBytes(ens::namehash(name).0.to_vec())
+}
+
+/// Points `name` at `resolver` in the registry.
+async fn set_resolver(client: &Client, registry: Address, name: &str, resolver: Address) {
+    call(
+        client,
+        registry,
+        "setResolver(bytes32,address)",
+        &[node(name), Token::Address(resolver)],
+    )
+    .await;
+}
+
+/// Deploys the registry and resolver, and gives `named` the name
+/// `alice.eth` both ways. `impostor` claims the same name in its reverse
+/// record, but the name does not resolve to it.
+async fn deploy_ens(anvil: &AnvilInstance, named: Address, impostor: Address) -> Address {
+    let provider = Provider::<Http>::try_from(anvil.endpoint()).unwrap();
+    let wallet = LocalWallet::from(anvil.keys()[0].clone()).with_chain_id(anvil.chain_id());
+    let client = SignerMiddleware::new(provider, wallet);
+
+    let registry = deploy(&client, REGISTRY_RUNTIME).await;
+    let resolver = deploy(&client, RESOLVER_RUNTIME).await;
+
+    set_resolver(&client, registry, "alice.eth", resolver).await;
+    call(
+        &client,
+        resolver,
+        "setAddr(bytes32,address)",
+        &[node("alice.eth"), Token::Address(named)],
+    )
+    .await;
+    for address in [named, impostor] {
+        let reverse_name = ens::reverse_address(address);
+        set_resolver(&client, registry, &reverse_name, resolver).await;
+        call(
+            &client,
+            resolver,
+            "setName(bytes32,string)",
+            &[node(&reverse_name), Token::String("alice.eth".to_string())],
+        )
+        .await;
+    }
+
+    registry
+}
+
+#[tokio::test]
+#[ignore = "needs anvil on the PATH"]
+async fn resolves_names_both_ways() {
+    let anvil = Anvil::new().spawn();
+    let (named, impostor, nameless) = (
+        anvil.addresses()[1],
+        anvil.addresses()[2],
+        anvil.addresses()[3],
+    );
+    let registry = deploy_ens(&anvil, named, impostor).await;
+    let service = EnsService::new(&anvil.endpoint(), Some(registry)).unwrap();
+
+    let resolution = service.resolve("Alice.eth").await.unwrap();
+    assert_eq!(
+        resolution,
+        EnsResolution {
+            name: "alice.eth".to_string(),
+            address: checksum_address(&named),
+        }
+    );
+
+    let error = service.resolve("bob.eth").await.unwrap_err();
+    assert_eq!(error.status_code, StatusCode::NOT_FOUND);
+
+    // Only the name that resolves back to its address counts
+    let addresses = [named, impostor, nameless].map(|address| format!("{:?}", address));
+    let resolutions = service.lookup(&addresses).await.unwrap();
+    assert_eq!(
+        resolutions,
+        vec![EnsResolution {
+            name: "alice.eth".to_string(),
+            address: checksum_address(&named),
+        }]
+    );
+}
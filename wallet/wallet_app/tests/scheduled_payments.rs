@@ -0,0 +1,307 @@
+//! Runs the scheduler against the database in `DATABASE_URL`, with a
+//! [`ManualClock`] standing in for the time of day, and against a local Anvil
+//! node for the runs that are sent.
+
+use std::sync::Arc;
+
+use chrono::{Datelike, Duration, TimeZone, Utc, Weekday};
+use ethers::{
+    middleware::SignerMiddleware,
+    providers::{Http, Middleware, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, TransactionRequest, U256},
+    utils::{parse_ether, Anvil},
+};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{
+    CreateScheduledPayment, ScheduledPaymentRun, SubmitScheduledPaymentRun, Transaction,
+};
+use wallet_app::{
+    BalanceService, ChainWatcher, Clock, CustodyService, ManualClock, NotificationService,
+    PaymentRequestService, PolicyService, RelayService, ScheduledPaymentService,
+    TransactionService, WalletEvents,
+};
+
+/// The node of the tests that send nothing, which never call it.
+const RPC_URL: &str = "http://localhost:8545";
+
+const RECIPIENT: &str = "0x2222222222222222222222222222222222222222";
+
+async fn scheduler(pool: &PgPool, clock: Arc<ManualClock>) -> ScheduledPaymentService {
+    scheduler_on(pool, clock, RPC_URL).0
+}
+
+/// The scheduler, and the chain watcher that settles its runs, using the node
+/// at `rpc_url`.
+fn scheduler_on(
+    pool: &PgPool,
+    clock: Arc<ManualClock>,
+    rpc_url: &str,
+) -> (ScheduledPaymentService, Arc<ChainWatcher>) {
+    let events = Arc::new(WalletEvents::new());
+    let transactions = Arc::new(TransactionService::new(pool.clone()));
+    let watcher = Arc::new(
+        ChainWatcher::new(
+            pool.clone(),
+            rpc_url,
+            Arc::new(BalanceService::new(pool.clone(), rpc_url, events.clone()).unwrap()),
+            Arc::new(NotificationService::new(pool.clone())),
+            Arc::new(PaymentRequestService::new(pool.clone())),
+            transactions.clone(),
+            events.clone(),
+        )
+        .unwrap(),
+    );
+    let relay = Arc::new(
+        RelayService::new(
+            rpc_url,
+            watcher.clone(),
+            Arc::new(PolicyService::new(pool.clone(), Duration::hours(24))),
+            transactions,
+            events.clone(),
+        )
+        .unwrap(),
+    );
+    let custody =
+        Arc::new(CustodyService::new(pool.clone(), None, rpc_url, watcher.clone(), relay).unwrap());
+    let scheduler =
+        ScheduledPaymentService::new(pool.clone(), rpc_url, custody, events, clock).unwrap();
+    (scheduler, watcher)
+}
+
+/// A user with an account whose key only the app holds, so that its runs
+/// wait for approval instead of being sent.
+async fn account(pool: &PgPool) -> (Uuid, Uuid) {
+    account_at(pool, "0x1111111111111111111111111111111111111111").await
+}
+
+async fn account_at(pool: &PgPool, address: &str) -> (Uuid, Uuid) {
+    let user_id = sqlx::query_scalar!(
+        "INSERT INTO auth_users (username) VALUES ($1) RETURNING id",
+        format!("scheduler-{}", Uuid::new_v4())
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    let account_id = sqlx::query_scalar!(
+        r#"
+        INSERT INTO wallet_accounts (user_id, label, encrypted_private_key, address)
+        VALUES ($1, 'Main', '{}', $2)
+        RETURNING id
+        "#,
+        user_id,
+        address
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    (user_id, account_id)
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn runs_a_weekly_payment_when_the_clock_passes_its_time() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    // Monday 2024-07-01, an hour before the payment is due
+    let clock = Arc::new(ManualClock::new(
+        Utc.with_ymd_and_hms(2024, 7, 1, 8, 0, 0).unwrap(),
+    ));
+    let scheduler = scheduler(&pool, clock.clone()).await;
+    let (user_id, account_id) = account(&pool).await;
+
+    let payment = scheduler
+        .create_payment(
+            user_id,
+            CreateScheduledPayment {
+                account_id,
+                recipient: RECIPIENT.to_string(),
+                token: None,
+                token_decimals: 18,
+                amount: Decimal::new(5, 1),
+                memo: None,
+                schedule: Some("0 9 * * 1".to_string()),
+                run_at: None,
+            },
+        )
+        .await
+        .unwrap();
+    let first_due = Utc.with_ymd_and_hms(2024, 7, 1, 9, 0, 0).unwrap();
+    assert_eq!(payment.next_run_at, Some(first_due));
+
+    scheduler.run_due().await.unwrap();
+    assert!(scheduler.list_runs(user_id).await.unwrap().is_empty());
+
+    clock.advance(Duration::hours(2));
+    scheduler.run_due().await.unwrap();
+    let runs = scheduler.list_runs(user_id).await.unwrap();
+    assert_eq!(runs.len(), 1);
+    assert_eq!(runs[0].due_at, first_due);
+    assert_eq!(runs[0].status, ScheduledPaymentRun::AWAITING_APPROVAL);
+
+    // The payment moved on to the next Monday, and does not run again before
+    let payment = scheduler.list_payments(user_id).await.unwrap().remove(0);
+    let next_run_at = payment.next_run_at.unwrap();
+    assert_eq!(next_run_at, first_due + Duration::weeks(1));
+    assert_eq!(next_run_at.weekday(), Weekday::Mon);
+    scheduler.run_due().await.unwrap();
+    assert_eq!(scheduler.list_runs(user_id).await.unwrap().len(), 1);
+
+    clock.set(next_run_at);
+    scheduler.run_due().await.unwrap();
+    assert_eq!(scheduler.list_runs(user_id).await.unwrap().len(), 2);
+
+    sqlx::query!("DELETE FROM auth_users WHERE id = $1", user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn fails_a_run_left_sending_once_the_clock_moves_on() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let clock = Arc::new(ManualClock::new(
+        Utc.with_ymd_and_hms(2024, 7, 1, 8, 0, 0).unwrap(),
+    ));
+    let scheduler = scheduler(&pool, clock.clone()).await;
+    let (user_id, account_id) = account(&pool).await;
+
+    scheduler
+        .create_payment(
+            user_id,
+            CreateScheduledPayment {
+                account_id,
+                recipient: RECIPIENT.to_string(),
+                token: None,
+                token_decimals: 18,
+                amount: Decimal::new(5, 1),
+                memo: None,
+                schedule: None,
+                run_at: Some(clock.now()),
+            },
+        )
+        .await
+        .unwrap();
+    scheduler.run_due().await.unwrap();
+    // As a server that stopped while sending the run would have left it
+    let run_id = scheduler.list_runs(user_id).await.unwrap()[0].id;
+    sqlx::query!(
+        "UPDATE wallet_scheduled_payment_runs SET status = $2 WHERE id = $1",
+        run_id,
+        ScheduledPaymentRun::SENDING
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    clock.advance(Duration::minutes(5));
+    scheduler.reconcile_interrupted_runs().await.unwrap();
+    let run = scheduler.list_runs(user_id).await.unwrap().remove(0);
+    assert_eq!(run.status, ScheduledPaymentRun::SENDING);
+
+    clock.advance(Duration::minutes(10));
+    scheduler.reconcile_interrupted_runs().await.unwrap();
+    let run = scheduler.list_runs(user_id).await.unwrap().remove(0);
+    assert_eq!(run.status, ScheduledPaymentRun::FAILED);
+    assert!(run.tx_hash.is_none());
+
+    sqlx::query!("DELETE FROM auth_users WHERE id = $1", user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL and anvil on the PATH"]
+async fn settles_a_run_the_app_sent_through_the_node() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let anvil = Anvil::new().spawn();
+    // Every Anvil node starts from block 0 on the same chain
+    sqlx::query!(
+        "DELETE FROM wallet_chain_watcher_progress WHERE chain_id = $1",
+        anvil.chain_id() as i64
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let clock = Arc::new(ManualClock::new(Utc::now()));
+    let (scheduler, watcher) = scheduler_on(&pool, clock.clone(), &anvil.endpoint());
+    watcher.poll().await.unwrap();
+
+    // The app holds the key of the account, and sends from it on its own
+    let wallet = LocalWallet::from(anvil.keys()[1].clone()).with_chain_id(anvil.chain_id());
+    let app = SignerMiddleware::new(
+        Provider::<Http>::try_from(anvil.endpoint()).unwrap(),
+        wallet.clone(),
+    );
+    let (user_id, account_id) = account_at(&pool, &format!("{:?}", wallet.address())).await;
+    scheduler
+        .create_payment(
+            user_id,
+            CreateScheduledPayment {
+                account_id,
+                recipient: RECIPIENT.to_string(),
+                token: None,
+                token_decimals: 18,
+                amount: Decimal::new(5, 1),
+                memo: None,
+                schedule: None,
+                run_at: Some(clock.now()),
+            },
+        )
+        .await
+        .unwrap();
+    scheduler.run_due().await.unwrap();
+    let run = scheduler.list_runs(user_id).await.unwrap().remove(0);
+    assert_eq!(run.status, ScheduledPaymentRun::AWAITING_APPROVAL);
+
+    let send = |value: U256| {
+        let app = &app;
+        async move {
+            let tx = TransactionRequest::new()
+                .to(RECIPIENT.parse::<Address>().unwrap())
+                .value(value);
+            let receipt = app
+                .send_transaction(tx, None)
+                .await
+                .unwrap()
+                .await
+                .unwrap()
+                .unwrap();
+            SubmitScheduledPaymentRun {
+                tx_hash: format!("{:?}", receipt.transaction_hash),
+            }
+        }
+    };
+
+    // A send of another amount does not make the payment
+    let wrong = send(parse_ether("0.4").unwrap()).await;
+    assert!(scheduler.submit_run(user_id, run.id, wrong).await.is_err());
+
+    let right = send(parse_ether("0.5").unwrap()).await;
+    let tx_hash = right.tx_hash.clone();
+    let run = scheduler.submit_run(user_id, run.id, right).await.unwrap();
+    assert_eq!(run.status, ScheduledPaymentRun::SUBMITTED);
+    assert_eq!(run.tx_hash.as_deref(), Some(tx_hash.as_str()));
+
+    // The chain watcher settles the run's transaction once it is mined
+    watcher.poll().await.unwrap();
+    let run = scheduler.list_runs(user_id).await.unwrap().remove(0);
+    assert_eq!(
+        run.transaction_status.as_deref(),
+        Some(Transaction::CONFIRMED)
+    );
+
+    sqlx::query!("DELETE FROM auth_users WHERE id = $1", user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
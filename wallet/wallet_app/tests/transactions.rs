@@ -0,0 +1,71 @@
+//! Exports statements from the database in `DATABASE_URL`.
+
+use axum::http::StatusCode;
+use sqlx::PgPool;
+use uuid::Uuid;
+use wallet_api::{Transaction, TransactionExportQuery};
+use wallet_app::TransactionService;
+
+const ADDRESS: &str = "0x00000000000000000000000000000000000000aa";
+
+/// A user with `count` confirmed incoming transactions, one a second.
+async fn user_with_transactions(pool: &PgPool, count: i64) -> Uuid {
+    let user_id = sqlx::query_scalar!(
+        "INSERT INTO auth_users (username) VALUES ($1) RETURNING id",
+        format!("transactions-{}", Uuid::new_v4())
+    )
+    .fetch_one(pool)
+    .await
+    .unwrap();
+    sqlx::query!(
+        r#"
+        INSERT INTO wallet_transactions
+            (user_id, address, tx_hash, direction, sender, recipient, amount, status, block_time)
+        SELECT $1, $2, '0x' || LPAD(TO_HEX(n), 64, '0'), 'in', $2, $2, 1, $3,
+            TIMESTAMPTZ '2024-01-01' + make_interval(secs => n)
+        FROM generate_series(1, $4::BIGINT) AS n
+        "#,
+        user_id,
+        ADDRESS,
+        Transaction::CONFIRMED,
+        count
+    )
+    .execute(pool)
+    .await
+    .unwrap();
+    user_id
+}
+
+#[tokio::test]
+#[ignore = "needs the database in DATABASE_URL"]
+async fn refuses_statements_with_too_many_transactions() {
+    let pool = PgPool::connect(&std::env::var("DATABASE_URL").unwrap())
+        .await
+        .unwrap();
+    let service = TransactionService::new(pool.clone());
+    let user_id = user_with_transactions(&pool, TransactionExportQuery::MAX_ROWS + 1).await;
+    let query = TransactionExportQuery::default();
+
+    let error = service.export(user_id, &query).await.unwrap_err();
+    assert_eq!(error.status_code, StatusCode::BAD_REQUEST);
+
+    // One fewer fits
+    sqlx::query!(
+        r#"
+        DELETE FROM wallet_transactions
+        WHERE user_id = $1
+            AND block_time = (SELECT MAX(block_time) FROM wallet_transactions WHERE user_id = $1)
+        "#,
+        user_id
+    )
+    .execute(&pool)
+    .await
+    .unwrap();
+    let rows = service.export(user_id, &query).await.unwrap();
+    assert_eq!(rows.len() as i64, TransactionExportQuery::MAX_ROWS);
+
+    sqlx::query!("DELETE FROM auth_users WHERE id = $1", user_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+}
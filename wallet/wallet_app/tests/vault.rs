@@ -0,0 +1,31 @@
+//! Creates keys in a vault whose master key is in a temporary file, and signs
+//! with them.
+
+use ethers::types::{transaction::eip2718::TypedTransaction, TransactionRequest};
+use uuid::Uuid;
+use wallet_app::{FileKeyVault, KeyVault};
+
+fn vault() -> FileKeyVault {
+    let path = std::env::temp_dir().join(format!("vault-{}.key", Uuid::new_v4()));
+    std::fs::write(&path, format!("0x{}", "11".repeat(32))).unwrap();
+    let vault = FileKeyVault::from_file(&path).unwrap();
+    std::fs::remove_file(path).unwrap();
+    vault
+}
+
+#[tokio::test]
+async fn signs_only_for_the_owner_of_a_key() {
+    let vault = vault();
+    let owner = Uuid::new_v4();
+    let (handle, address) = vault.create_key(owner).await.unwrap();
+    let tx: TypedTransaction = TransactionRequest::new().chain_id(1).into();
+
+    let signature = vault.sign_transaction(owner, &handle, &tx).await.unwrap();
+    assert_eq!(signature.recover(tx.sighash()).unwrap(), address);
+
+    // The handle of another account's key does not sign for this one
+    assert!(vault
+        .sign_transaction(Uuid::new_v4(), &handle, &tx)
+        .await
+        .is_err());
+}
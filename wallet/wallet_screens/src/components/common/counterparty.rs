@@ -0,0 +1,29 @@
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use super::short_address;
+use crate::store::WalletStore;
+
+#[derive(Properties, PartialEq)]
+pub struct CounterpartyProps {
+    pub address: AttrValue,
+}
+
+/// An address in a list, shown by its contact or account name when it has one.
+/// The full address is available on hover.
+#[function_component(Counterparty)]
+pub fn counterparty(props: &CounterpartyProps) -> Html {
+    let (state, _) = use_store::<WalletStore>();
+
+    match state.name_for(&props.address) {
+        Some(name) => html! {
+            <span title={props.address.clone()}>
+                <span class="font-medium">{ name }</span>
+                <span class="ml-2 text-indigo-300">{ short_address(&props.address) }</span>
+            </span>
+        },
+        None => html! {
+            <span title={props.address.clone()}>{ short_address(&props.address) }</span>
+        },
+    }
+}
@@ -0,0 +1,31 @@
+use ethers::types::U256;
+use rust_decimal::Decimal;
+use wallet_api::amount::{format_amount, format_amount_exact, format_decimal};
+
+use crate::services::browser::number_locale;
+
+/// Shortens an address to its first and last characters, e.g. `0x1234…abcd`.
+pub fn short_address(address: &str) -> String {
+    if address.len() > 12 {
+        format!("{}…{}", &address[..6], &address[address.len() - 4..])
+    } else {
+        address.to_string()
+    }
+}
+
+/// Formats an amount in the token's smallest unit with `fraction_digits`
+/// decimal places in the user's locale.
+pub fn display_amount(amount: U256, decimals: i16, fraction_digits: u32) -> String {
+    format_amount(amount, decimals, fraction_digits, number_locale())
+        .unwrap_or_else(|e| e.to_string())
+}
+
+/// Like [`display_amount`], but with every significant decimal place.
+pub fn display_amount_exact(amount: U256, decimals: i16) -> String {
+    format_amount_exact(amount, decimals, number_locale()).unwrap_or_else(|e| e.to_string())
+}
+
+/// Formats a decimal amount, such as a stored balance, in the user's locale.
+pub fn display_decimal(amount: Decimal, fraction_digits: u32) -> String {
+    format_decimal(amount, fraction_digits, number_locale())
+}
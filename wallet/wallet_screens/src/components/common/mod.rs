@@ -1,5 +1,9 @@
+mod counterparty;
+mod format;
 mod qr_code;
 mod transaction_table;
 
+pub use counterparty::Counterparty;
+pub use format::{display_amount, display_amount_exact, display_decimal, short_address};
 pub use qr_code::{QrCode, QrCodeCard};
-pub use transaction_table::TransactionTable;
+pub use transaction_table::{counterparties, TransactionRow, TransactionTable};
@@ -1,4 +1,5 @@
 use qrcode_generator::QrCodeEcc;
+use wallet_api::PaymentUri;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
@@ -31,14 +32,19 @@ pub fn qr_code(props: &QrCodeProps) -> Html {
 pub fn qr_code_card() -> Html {
     let (state, _) = use_store::<WalletStore>();
 
-    let address = match &state.wallet {
-        Some(wallet) => wallet.address.clone(),
+    // Wallet apps open an ethereum: link as a payment to the address
+    let data = match state.selected_account() {
+        Some(account) => PaymentUri {
+            address: account.address.clone(),
+            ..Default::default()
+        }
+        .to_uri(),
         None => String::from("No wallet address available"),
     };
 
     html! {
         <div class="bg-gradient-to-br from-pink-500 to-orange-400 rounded-xl p-6 flex items-center justify-center">
-            <QrCode data={address} size={Some(128)} />
+            <QrCode data={data} size={Some(128)} />
         </div>
     }
 }
@@ -1,7 +1,68 @@
+use wallet_api::Transaction;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
-use crate::store::WalletStore;
+use super::{display_decimal, short_address, Counterparty};
+use crate::store::{WalletAction, WalletStore};
+
+/// Counterparties of `transactions`, for looking up their ENS names.
+pub fn counterparties<'a>(transactions: impl Iterator<Item = &'a Transaction>) -> Vec<String> {
+    transactions
+        .filter_map(|tx| tx.counterparty().map(str::to_string))
+        .collect()
+}
+
+#[derive(Properties, PartialEq)]
+pub struct TransactionRowProps {
+    pub transaction: Transaction,
+    #[prop_or(AttrValue::Static("%Y-%m-%d %H:%M"))]
+    pub date_format: AttrValue,
+}
+
+/// One transaction in a table: outgoing amounts are negative, and the fee is
+/// shown for sends that reached the chain. Token amounts are labelled with
+/// the token contract.
+#[function_component(TransactionRow)]
+pub fn transaction_row(props: &TransactionRowProps) -> Html {
+    let tx = &props.transaction;
+    let (sign, counterparty_label) = if tx.is_outgoing() {
+        ("-", "To")
+    } else {
+        ("+", "From")
+    };
+    let unit = match &tx.token {
+        Some(token) => short_address(token),
+        None => "ETH".to_string(),
+    };
+    let status_class = match tx.status.as_str() {
+        Transaction::CONFIRMED => "text-green-400",
+        Transaction::FAILED => "text-red-400",
+        Transaction::REPLACED => "text-indigo-400",
+        _ => "text-yellow-300",
+    };
+
+    html! {
+        <tr class="border-t border-indigo-700">
+            <td class="py-2">{ tx.date().format(&props.date_format).to_string() }</td>
+            <td class="py-2">{ format!("{}{} {}", sign, display_decimal(tx.amount, 4), unit) }</td>
+            <td class="py-2">
+                <span class="text-indigo-300 mr-1">{ counterparty_label }</span>
+                {
+                    match tx.counterparty() {
+                        Some(address) => html! { <Counterparty address={address.to_string()} /> },
+                        None => html! { { "Contract creation" } },
+                    }
+                }
+            </td>
+            <td class="py-2">
+                <span class={status_class} title={tx.error.clone()}>{ &tx.status }</span>
+            </td>
+            <td class="py-2">
+                { tx.fee.map(|fee| display_decimal(fee, 6)).unwrap_or_default() }
+            </td>
+        </tr>
+    }
+}
 
 #[derive(Properties, PartialEq)]
 pub struct TransactionTableProps {
@@ -10,43 +71,37 @@ pub struct TransactionTableProps {
 
 #[function_component(TransactionTable)]
 pub fn transaction_table(props: &TransactionTableProps) -> Html {
-    let (state, _) = use_store::<WalletStore>();
+    let (state, dispatch) = use_store::<WalletStore>();
+    let limit = props.limit.unwrap_or(usize::MAX);
+
+    let counterparties = counterparties(state.transactions.iter().take(limit));
+    use_effect_with(counterparties, move |counterparties| {
+        dispatch.apply(WalletAction::LookupEnsNames(counterparties.clone()));
+        || ()
+    });
 
     html! {
         <table class="w-full text-sm">
             <thead>
                 <tr class="text-left text-indigo-300">
                     <th class="pb-2">{ "Date" }</th>
-                    <th class="pb-2">{ "Amount (ETH)" }</th>
-                    <th class="pb-2">{ "Recipient" }</th>
+                    <th class="pb-2">{ "Amount" }</th>
+                    <th class="pb-2">{ "Counterparty" }</th>
+                    <th class="pb-2">{ "Status" }</th>
+                    <th class="pb-2">{ "Fee (ETH)" }</th>
                 </tr>
             </thead>
             <tbody>
-                {
-                    if let Some(wallet) = &state.wallet {
-                        let transactions = match props.limit {
-                            Some(limit) => state.transactions.iter().take(limit),
-                            None => state.transactions.iter().take(state.transactions.len())
-                        };
-
-                        transactions.map(|tx| {
-                            html! {
-                                <tr class="border-t border-indigo-700">
-                                    <td class="py-2">{ tx.date.format("%Y-%m-%d %H:%M").to_string() }</td>
-                                    <td class="py-2">{ format!("{:.4}", (tx.amount.as_u128() as f64) / (10f64.powi(wallet.token_decimals as i32))) }</td>
-                                    <td class="py-2">{ &tx.recipient[0..10] }{ "..." }</td>
-                                </tr>
-                            }
-                        }).collect::<Html>()
-                    } else {
-                        html! {
-                            <tr>
-                                <td colspan="3" class="py-2 text-center text-indigo-300">
-                                    { "No transactions to display. Please create or import a wallet." }
-                                </td>
-                            </tr>
-                        }
-                    }
+                if state.wallet.is_some() {
+                    { for state.transactions.iter().take(limit).map(|tx| html! {
+                        <TransactionRow transaction={tx.clone()} />
+                    }) }
+                } else {
+                    <tr>
+                        <td colspan="5" class="py-2 text-center text-indigo-300">
+                            { "No transactions to display. Please create or import a wallet." }
+                        </td>
+                    </tr>
                 }
             </tbody>
         </table>
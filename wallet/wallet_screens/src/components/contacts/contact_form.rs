@@ -0,0 +1,158 @@
+use uuid::Uuid;
+use validator::Validate;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    contact::DEFAULT_NETWORK,
+    Contact, CreateContact, UpdateContact,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::{WalletAction, WalletStore};
+
+#[derive(Properties, PartialEq)]
+pub struct ContactFormProps {
+    /// Contact to edit; a new one is added when `None`.
+    pub contact: Option<Contact>,
+    pub on_done: Callback<()>,
+}
+
+#[function_component(ContactForm)]
+pub fn contact_form(props: &ContactFormProps) -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let initial = props.contact.clone().unwrap_or_default();
+    let name = use_state(|| initial.name.clone());
+    let address = use_state(|| initial.address.clone());
+    let network = use_state(|| {
+        Some(initial.network.clone())
+            .filter(|network| !network.is_empty())
+            .unwrap_or_else(|| DEFAULT_NETWORK.to_string())
+    });
+    let note = use_state(|| initial.note.clone().unwrap_or_default());
+    let error = use_state(|| None::<String>);
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_name_input = input(&name);
+    let on_address_input = input(&address);
+    let on_network_input = input(&network);
+    let on_note_input = {
+        let note = note.clone();
+        Callback::from(move |e: InputEvent| {
+            note.set(
+                e.target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                    .value(),
+            )
+        })
+    };
+
+    let on_submit = {
+        let id: Option<Uuid> = props.contact.as_ref().map(|contact| contact.id);
+        let name = name.clone();
+        let address = address.clone();
+        let network = network.clone();
+        let note = note.clone();
+        let error = error.clone();
+        let on_done = props.on_done.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let checksummed = match parse_address(&address) {
+                Ok(parsed) => checksum_address(&parsed),
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    return;
+                }
+            };
+            let contact = CreateContact {
+                name: name.trim().to_string(),
+                address: checksummed,
+                network: network.trim().to_string(),
+                note: Some(note.trim().to_string()),
+            };
+            if let Err(errors) = contact.validate() {
+                let message = errors
+                    .field_errors()
+                    .into_values()
+                    .flatten()
+                    .filter_map(|error| error.message.as_ref().map(|message| message.to_string()))
+                    .next()
+                    .unwrap_or_else(|| "Contact is invalid".to_string());
+                error.set(Some(message));
+                return;
+            }
+
+            match id {
+                Some(id) => dispatch.apply(WalletAction::EditContact {
+                    id,
+                    update: UpdateContact {
+                        name: Some(contact.name),
+                        address: Some(contact.address),
+                        network: Some(contact.network),
+                        note: contact.note,
+                    },
+                }),
+                None => dispatch.apply(WalletAction::AddContact(CreateContact {
+                    note: contact.note.filter(|note| !note.is_empty()),
+                    ..contact
+                })),
+            }
+            error.set(None);
+            on_done.emit(());
+        })
+    };
+
+    let on_cancel = {
+        let on_done = props.on_done.clone();
+        Callback::from(move |_| on_done.emit(()))
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="space-y-2">
+            <input
+                type="text"
+                placeholder="Name"
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                value={(*name).clone()}
+                oninput={on_name_input}
+            />
+            <input
+                type="text"
+                placeholder="0x..."
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                value={(*address).clone()}
+                oninput={on_address_input}
+            />
+            <input
+                type="text"
+                placeholder="Network"
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                value={(*network).clone()}
+                oninput={on_network_input}
+            />
+            <textarea
+                placeholder="Note (optional)"
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                value={(*note).clone()}
+                oninput={on_note_input}
+            />
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400">{ error }</p>
+            }
+            <div class="flex gap-2">
+                <button type="submit" class="flex-1 bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                    { if props.contact.is_some() { "Save" } else { "Add Contact" } }
+                </button>
+                <button type="button" class="px-4 text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel}>
+                    { "Cancel" }
+                </button>
+            </div>
+        </form>
+    }
+}
@@ -0,0 +1,111 @@
+mod contact_form;
+
+pub use contact_form::ContactForm;
+use uuid::Uuid;
+use wallet_api::contact::DEFAULT_NETWORK;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::{WalletAction, WalletStore};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum ContactsView {
+    List,
+    Add,
+    Edit(Uuid),
+}
+
+/// The user's address book.
+#[function_component(Contacts)]
+pub fn contacts() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let view = use_state(|| ContactsView::List);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadContacts);
+            || ()
+        });
+    }
+
+    let show = |target: ContactsView| {
+        let view = view.clone();
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| {
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            view.set(target.clone());
+        })
+    };
+    let on_done = {
+        let view = view.clone();
+        Callback::from(move |_| view.set(ContactsView::List))
+    };
+
+    html! {
+        <div class="flex-1">
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <div class="flex justify-between items-center mb-4">
+                    <h2 class="text-xl font-bold">{ "Contacts" }</h2>
+                    if *view == ContactsView::List {
+                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={show(ContactsView::Add)}>
+                            { "Add Contact" }
+                        </button>
+                    }
+                </div>
+                if *view == ContactsView::Add {
+                    <div class="mb-4">
+                        <ContactForm contact={None::<wallet_api::Contact>} on_done={on_done.clone()} />
+                    </div>
+                }
+                if let Some(error) = &state.last_error {
+                    <p class="text-sm text-red-400 mb-4">{ &error.message }</p>
+                }
+                if state.contacts.is_empty() {
+                    <p class="text-indigo-300">{ "No contacts yet. Save the addresses you send to often." }</p>
+                } else {
+                    <ul class="divide-y divide-indigo-700">
+                        { for state.contacts.iter().map(|contact| {
+                            if *view == ContactsView::Edit(contact.id) {
+                                return html! {
+                                    <li class="py-3">
+                                        <ContactForm contact={Some(contact.clone())} on_done={on_done.clone()} />
+                                    </li>
+                                };
+                            }
+                            let on_remove = {
+                                let dispatch = dispatch.clone();
+                                let id = contact.id;
+                                Callback::from(move |_| dispatch.apply(WalletAction::RemoveContact(id)))
+                            };
+                            html! {
+                                <li class="py-3 flex items-start justify-between gap-4">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">
+                                            { &contact.name }
+                                            if contact.network != DEFAULT_NETWORK {
+                                                <span class="ml-2 text-xs text-indigo-300">{ &contact.network }</span>
+                                            }
+                                        </p>
+                                        <p class="text-sm text-indigo-300 font-mono break-all">{ &contact.address }</p>
+                                        if let Some(note) = &contact.note {
+                                            <p class="text-sm mt-1">{ note }</p>
+                                        }
+                                    </div>
+                                    <div class="flex gap-4 shrink-0">
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={show(ContactsView::Edit(contact.id))}>
+                                            { "Edit" }
+                                        </button>
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_remove}>
+                                            { "Remove" }
+                                        </button>
+                                    </div>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            </div>
+        </div>
+    }
+}
@@ -0,0 +1,176 @@
+use ethers::{
+    abi::Function,
+    types::{Address, U256},
+};
+use wallet_api::{
+    address::parse_address,
+    amount::{parse_amount, ETHER_DECIMALS},
+};
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    services::{
+        browser::number_locale,
+        contracts::{call, encode_call, format_token, is_payable, is_read_only, param_label},
+    },
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct FunctionFormProps {
+    pub address: Address,
+    pub function: Function,
+}
+
+/// A form for one function of a contract, with an input per argument. Read
+/// functions are called right away and show what they return; write
+/// functions are signed with the selected account and sent.
+#[function_component(FunctionForm)]
+pub fn function_form(props: &FunctionFormProps) -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let args = use_state(|| vec![String::new(); props.function.inputs.len()]);
+    let value = use_state(String::new);
+    let password = use_state(String::new);
+    // Formatted return values of a read, or why the call failed
+    let result = use_state(|| None::<Result<Vec<String>, String>>);
+    let loading = use_state(|| false);
+
+    let read_only = is_read_only(&props.function);
+    let payable = is_payable(&props.function);
+    let is_custodial = state
+        .selected_account()
+        .is_some_and(|account| account.custodial);
+
+    let on_arg_input = |index: usize| {
+        let args = args.clone();
+        Callback::from(move |e: InputEvent| {
+            let mut updated = (*args).clone();
+            updated[index] = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            args.set(updated);
+        })
+    };
+
+    let on_submit = {
+        let function = props.function.clone();
+        let address = props.address;
+        let from = state
+            .selected_account()
+            .and_then(|account| parse_address(&account.address).ok());
+        let args = args.clone();
+        let value = value.clone();
+        let password = password.clone();
+        let result = result.clone();
+        let loading = loading.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let data = match encode_call(&function, &args) {
+                Ok(data) => data,
+                Err(e) => {
+                    result.set(Some(Err(e.to_string())));
+                    return;
+                }
+            };
+
+            if read_only {
+                let function = function.clone();
+                let result = result.clone();
+                let loading = loading.clone();
+                loading.set(true);
+                spawn_local(async move {
+                    let outcome = call(&function, from, address, data)
+                        .await
+                        .map(|tokens| tokens.iter().map(format_token).collect())
+                        .map_err(|e| e.to_string());
+                    result.set(Some(outcome));
+                    loading.set(false);
+                });
+                return;
+            }
+
+            let amount = if payable && !value.trim().is_empty() {
+                match parse_amount(&value, ETHER_DECIMALS, number_locale()) {
+                    Ok(amount) => amount,
+                    Err(e) => {
+                        result.set(Some(Err(e.to_string())));
+                        return;
+                    }
+                }
+            } else {
+                U256::zero()
+            };
+            dispatch.apply(WalletAction::SendTransaction {
+                to: address,
+                amount,
+                data,
+                password: (*password).clone(),
+            });
+            value.set(String::new());
+            password.set(String::new());
+            result.set(None);
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="border border-indigo-700 rounded-lg p-4 space-y-2">
+            <p class="font-medium font-mono break-all">{ props.function.signature() }</p>
+            { for props.function.inputs.iter().enumerate().map(|(index, param)| html! {
+                <input
+                    type="text"
+                    value={args[index].clone()}
+                    oninput={on_arg_input(index)}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    placeholder={param_label(param, index)}
+                />
+            }) }
+            if payable {
+                <div class="w-full bg-indigo-700 rounded-lg py-2 px-4 flex items-center space-x-2">
+                    <span class="text-indigo-300">{ "ETH" }</span>
+                    <input
+                        type="text"
+                        value={(*value).clone()}
+                        placeholder="Amount to send with the call"
+                        oninput={Callback::from(move |e: InputEvent| value.set(e.target_unchecked_into::<web_sys::HtmlInputElement>().value()))}
+                        class="bg-transparent flex-1 outline-none focus:outline-none"
+                    />
+                </div>
+            }
+            if !read_only && !is_custodial {
+                <input
+                    type="password"
+                    value={(*password).clone()}
+                    oninput={Callback::from(move |e: InputEvent| password.set(e.target_unchecked_into::<web_sys::HtmlInputElement>().value()))}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    placeholder="Enter your password"
+                />
+            }
+            <button type="submit" disabled={*loading} class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                { if *loading { "Calling…" } else if read_only { "Call" } else { "Send" } }
+            </button>
+            {
+                match &*result {
+                    Some(Ok(values)) if values.is_empty() => html! {
+                        <p class="text-sm text-indigo-300">{ "The call returned nothing." }</p>
+                    },
+                    Some(Ok(values)) => html! {
+                        <dl class="text-sm">
+                            { for props.function.outputs.iter().zip(values).enumerate().map(|(index, (output, value))| html! {
+                                <div class="flex gap-2">
+                                    <dt class="text-indigo-300 shrink-0">{ param_label(output, index) }</dt>
+                                    <dd class="font-mono break-all">{ value }</dd>
+                                </div>
+                            }) }
+                        </dl>
+                    },
+                    Some(Err(error)) => html! {
+                        <p class="text-sm text-red-400">{ error }</p>
+                    },
+                    None => html! {},
+                }
+            }
+        </form>
+    }
+}
@@ -0,0 +1,197 @@
+mod function_form;
+
+pub use function_form::FunctionForm;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    contact::DEFAULT_NETWORK,
+    contract::parse_abi,
+    CreateSavedContract, SavedContract,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::transfer::PendingTransactions,
+    services::contracts::is_read_only,
+    store::{WalletAction, WalletStore},
+};
+
+/// Calls contracts through forms generated from their ABI. Contracts can be
+/// saved to come back to them later.
+#[function_component(Contracts)]
+pub fn contracts() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let name = use_state(String::new);
+    let address = use_state(String::new);
+    let abi = use_state(String::new);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadContracts);
+            || ()
+        });
+    }
+
+    let parsed_address = parse_address(&address);
+    let parsed_abi = parse_abi(&abi);
+    let address_error = parsed_address
+        .as_ref()
+        .err()
+        .filter(|_| !address.trim().is_empty())
+        .map(|e| e.to_string());
+    let abi_error = parsed_abi
+        .as_ref()
+        .err()
+        .filter(|_| !abi.trim().is_empty())
+        .map(|e| format!("ABI is not valid: {}", e));
+    let can_save = !name.trim().is_empty() && parsed_address.is_ok() && parsed_abi.is_ok();
+
+    let on_input = |field: UseStateHandle<String>| {
+        Callback::from(move |e: InputEvent| {
+            field.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_abi_input = {
+        let abi = abi.clone();
+        Callback::from(move |e: InputEvent| {
+            abi.set(
+                e.target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_open = |contract: &SavedContract| {
+        let name = name.clone();
+        let address = address.clone();
+        let abi = abi.clone();
+        let dispatch = dispatch.clone();
+        let contract = contract.clone();
+        Callback::from(move |_| {
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            name.set(contract.name.clone());
+            address.set(contract.address.clone());
+            abi.set(contract.abi.clone());
+        })
+    };
+    let on_save = {
+        let name = name.clone();
+        let abi = abi.clone();
+        let dispatch = dispatch.clone();
+        let address = parsed_address.as_ref().ok().map(checksum_address);
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some(address) = address.clone() {
+                dispatch.apply(WalletAction::SaveContract(CreateSavedContract {
+                    name: name.trim().to_string(),
+                    address,
+                    network: DEFAULT_NETWORK.to_string(),
+                    abi: (*abi).clone(),
+                }));
+            }
+        })
+    };
+
+    let (read_functions, write_functions): (Vec<_>, Vec<_>) = parsed_abi
+        .as_ref()
+        .map(|abi| abi.functions().collect::<Vec<_>>())
+        .unwrap_or_default()
+        .into_iter()
+        .partition(|function| is_read_only(function));
+
+    html! {
+        <div class="flex-1 space-y-6">
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <h2 class="text-xl font-bold mb-4">{ "Contracts" }</h2>
+                if state.contracts.is_empty() {
+                    <p class="text-indigo-300 mb-4">{ "No contracts saved yet. Paste an address and its ABI to call it." }</p>
+                } else {
+                    <ul class="divide-y divide-indigo-700 mb-4">
+                        { for state.contracts.iter().map(|contract| {
+                            let on_remove = {
+                                let dispatch = dispatch.clone();
+                                let id = contract.id;
+                                Callback::from(move |_| dispatch.apply(WalletAction::RemoveContract(id)))
+                            };
+                            html! {
+                                <li class="py-3 flex items-start justify-between gap-4">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">{ &contract.name }</p>
+                                        <p class="text-sm text-indigo-300 font-mono break-all">{ &contract.address }</p>
+                                    </div>
+                                    <div class="flex gap-4 shrink-0">
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_open(contract)}>
+                                            { "Open" }
+                                        </button>
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_remove}>
+                                            { "Remove" }
+                                        </button>
+                                    </div>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+                <form onsubmit={on_save} class="space-y-2">
+                    <input
+                        type="text"
+                        value={(*address).clone()}
+                        oninput={on_input(address.clone())}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+                        placeholder="Contract address"
+                    />
+                    if let Some(error) = address_error {
+                        <p class="text-xs text-red-400">{ error }</p>
+                    }
+                    <textarea
+                        value={(*abi).clone()}
+                        oninput={on_abi_input}
+                        rows="6"
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono text-sm"
+                        placeholder="ABI JSON"
+                    />
+                    if let Some(error) = abi_error {
+                        <p class="text-xs text-red-400">{ error }</p>
+                    }
+                    <input
+                        type="text"
+                        value={(*name).clone()}
+                        oninput={on_input(name.clone())}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        placeholder="Name to save the contract as"
+                    />
+                    <button type="submit" disabled={!can_save} class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                        { "Save contract" }
+                    </button>
+                </form>
+                if let Some(error) = &state.last_error {
+                    <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+                }
+            </div>
+            if let Ok(contract) = parsed_address {
+                if !read_functions.is_empty() {
+                    <div class="bg-indigo-800 rounded-xl p-6 space-y-4">
+                        <h2 class="text-xl font-bold">{ "Read" }</h2>
+                        { for read_functions.into_iter().map(|function| html! {
+                            <FunctionForm key={function.signature()} address={contract} function={function.clone()} />
+                        }) }
+                    </div>
+                }
+                if !write_functions.is_empty() {
+                    <div class="bg-indigo-800 rounded-xl p-6 space-y-4">
+                        <h2 class="text-xl font-bold">{ "Write" }</h2>
+                        { for write_functions.into_iter().map(|function| html! {
+                            <FunctionForm key={function.signature()} address={contract} function={function.clone()} />
+                        }) }
+                        if !state.pending_transactions.is_empty() {
+                            <PendingTransactions />
+                        }
+                    </div>
+                }
+            }
+        </div>
+    }
+}
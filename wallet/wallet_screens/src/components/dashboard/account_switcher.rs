@@ -0,0 +1,208 @@
+use uuid::Uuid;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::{display_decimal, short_address},
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AccountForm {
+    None,
+    Add,
+    AddCustodial,
+    Rename,
+}
+
+#[function_component(AccountSwitcher)]
+pub fn account_switcher() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let form = use_state(|| AccountForm::None);
+    let label = use_state(String::new);
+    let password = use_state(String::new);
+    let passphrase = use_state(String::new);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadCustodyStatus);
+            || ()
+        });
+    }
+
+    let selected = state.selected_account().cloned();
+    let is_primary = match (&selected, &state.wallet) {
+        (Some(account), Some(wallet)) => account.address == wallet.address,
+        _ => true,
+    };
+    // The server holds the only copy of a custodial account's key
+    let can_remove = !is_primary && selected.as_ref().is_some_and(|account| !account.custodial);
+
+    let on_select = {
+        let dispatch = dispatch.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            if let Ok(id) = value.parse::<Uuid>() {
+                dispatch.apply(WalletAction::SelectAccount(id));
+            }
+        })
+    };
+
+    let open_form = |target: AccountForm, initial_label: String| {
+        let form = form.clone();
+        let label = label.clone();
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| {
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            label.set(initial_label.clone());
+            form.set(if *form == target {
+                AccountForm::None
+            } else {
+                target.clone()
+            });
+        })
+    };
+    let on_add_click = open_form(
+        AccountForm::Add,
+        format!("Account {}", state.accounts.len() + 1),
+    );
+    let on_add_custodial_click = open_form(
+        AccountForm::AddCustodial,
+        format!("Account {}", state.accounts.len() + 1),
+    );
+    let on_rename_click = open_form(
+        AccountForm::Rename,
+        selected
+            .as_ref()
+            .map(|account| account.label.clone())
+            .unwrap_or_default(),
+    );
+
+    let on_remove_click = {
+        let dispatch = dispatch.clone();
+        let id = selected.as_ref().map(|account| account.id);
+        Callback::from(move |_| {
+            if let Some(id) = id {
+                dispatch.apply(WalletAction::RemoveAccount(id));
+            }
+        })
+    };
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            state.set(value);
+        })
+    };
+    let on_label_change = input(&label);
+    let on_password_change = input(&password);
+    let on_passphrase_change = input(&passphrase);
+
+    let on_submit = {
+        let form = form.clone();
+        let label = label.clone();
+        let password = password.clone();
+        let passphrase = passphrase.clone();
+        let id = selected.as_ref().map(|account| account.id);
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            match *form {
+                AccountForm::Add => dispatch.apply(WalletAction::AddAccount {
+                    label: (*label).clone(),
+                    password: (*password).clone(),
+                    passphrase: Some((*passphrase).clone())
+                        .filter(|passphrase| !passphrase.is_empty()),
+                }),
+                AccountForm::AddCustodial => dispatch.apply(WalletAction::AddCustodialAccount {
+                    label: (*label).clone(),
+                }),
+                AccountForm::Rename => {
+                    if let Some(id) = id {
+                        dispatch.apply(WalletAction::RenameAccount {
+                            id,
+                            label: (*label).clone(),
+                        });
+                    }
+                }
+                AccountForm::None => {}
+            }
+            password.set(String::new());
+            passphrase.set(String::new());
+            form.set(AccountForm::None);
+        })
+    };
+
+    html! {
+        <div class="bg-indigo-800 rounded-xl p-6 mb-6">
+            <div class="flex items-center gap-4">
+                <select class="flex-1 bg-indigo-700 rounded-lg py-2 px-4" onchange={on_select}>
+                    { for state.accounts.iter().map(|account| {
+                        let is_selected = selected.as_ref().map(|selected| selected.id) == Some(account.id);
+                        html! {
+                            <option value={account.id.to_string()} selected={is_selected}>
+                                { format!("{} · {} · {} ETH", account.label, short_address(&account.address), display_decimal(account.balance, 4)) }
+                                { if account.custodial { " · custodial" } else { "" } }
+                            </option>
+                        }
+                    }) }
+                </select>
+                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_add_click}>{ "Add Account" }</button>
+                if state.custody.enabled {
+                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_add_custodial_click}>{ "Add Custodial Account" }</button>
+                }
+                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_rename_click}>{ "Rename" }</button>
+                if can_remove {
+                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_remove_click}>{ "Remove" }</button>
+                }
+            </div>
+            if *form != AccountForm::None {
+                <form onsubmit={on_submit} class="mt-4 space-y-2">
+                    <input
+                        type="text"
+                        placeholder="Account name"
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        value={(*label).clone()}
+                        onchange={on_label_change}
+                    />
+                    if *form == AccountForm::AddCustodial {
+                        <p class="text-sm text-yellow-300">
+                            { "The server will hold this account's key and sign for it, within your spending policy." }
+                        </p>
+                    }
+                    if *form == AccountForm::Add {
+                        <input
+                            type="password"
+                            placeholder="Wallet password"
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            onchange={on_password_change}
+                        />
+                        <input
+                            type="password"
+                            placeholder="BIP-39 passphrase (if the wallet uses one)"
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            onchange={on_passphrase_change}
+                        />
+                    }
+                    <button type="submit" class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                        {
+                            match *form {
+                                AccountForm::Add => "Derive Account",
+                                AccountForm::AddCustodial => "Create Custodial Account",
+                                _ => "Save",
+                            }
+                        }
+                    </button>
+                </form>
+            }
+            if let Some(error) = &state.last_error {
+                <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+            }
+        </div>
+    }
+}
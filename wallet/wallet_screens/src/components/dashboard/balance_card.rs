@@ -1,7 +1,9 @@
+use chrono::Local;
+use rust_decimal::Decimal;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
-use crate::store::WalletStore;
+use crate::{components::common::display_decimal, store::WalletStore};
 
 #[function_component(BalanceCard)]
 pub fn balance_card() -> Html {
@@ -17,15 +19,31 @@ pub fn balance_card() -> Html {
                     <path d="M12 2C6.48 2 2 6.48 2 12s4.48 10 10 10 10-4.48 10-10S17.52 2 12 2zm0 18c-4.41 0-8-3.59-8-8s3.59-8 8-8 8 3.59 8 8-3.59 8-8 8z"/>
                 </svg>
             </div>
-            <div class="text-2xl mb-4">{ "My Ethereum Wallet" }</div>
             {
-                if let Some(wallet) = &state.wallet {
+                if let Some(account) = state.selected_account() {
                     html! {
-                        <div class="text-3xl font-bold">{ format!("{:.2} ETH", wallet.balance) }</div>
+                        <>
+                            <div class="text-2xl mb-4">{ &account.label }</div>
+                            <div class="text-3xl font-bold">{ format!("{} ETH", display_decimal(account.balance, 2)) }</div>
+                            <div class="text-sm opacity-75 mt-1">
+                                {
+                                    match account.balance_synced_at {
+                                        Some(synced_at) => format!(
+                                            "Updated {}",
+                                            synced_at.with_timezone(&Local).format("%b %e, %H:%M")
+                                        ),
+                                        None => "Not synced yet".to_string(),
+                                    }
+                                }
+                            </div>
+                        </>
                     }
                 } else {
                     html! {
-                        <div class="text-3xl font-bold">{ "0.00 ETH" }</div>
+                        <>
+                            <div class="text-2xl mb-4">{ "My Ethereum Wallet" }</div>
+                            <div class="text-3xl font-bold">{ format!("{} ETH", display_decimal(Decimal::ZERO, 2)) }</div>
+                        </>
                     }
                 }
             }
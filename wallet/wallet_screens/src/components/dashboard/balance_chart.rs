@@ -0,0 +1,247 @@
+use chrono::{DateTime, Local, Utc};
+use rust_decimal::prelude::ToPrimitive;
+use wallet_api::{BalancePeriod, BalancePoint};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::display_decimal,
+    store::{WalletAction, WalletStore},
+};
+
+const WIDTH: f64 = 600.0;
+const HEIGHT: f64 = 160.0;
+
+fn period_label(period: BalancePeriod) -> &'static str {
+    match period {
+        BalancePeriod::Day => "24h",
+        BalancePeriod::Week => "7d",
+        BalancePeriod::Month => "30d",
+        BalancePeriod::Year => "1y",
+    }
+}
+
+/// Where a point is drawn, as a fraction of the chart's width and height.
+fn position(
+    point: &BalancePoint,
+    from: DateTime<Utc>,
+    to: DateTime<Utc>,
+    min: f64,
+    max: f64,
+) -> (f64, f64) {
+    let span = (to - from).num_seconds().max(1) as f64;
+    let x = ((point.at - from).num_seconds() as f64 / span).clamp(0.0, 1.0);
+    let balance = point.balance.to_f64().unwrap_or_default();
+    // A flat history is drawn through the middle
+    let y = if max > min {
+        (balance - min) / (max - min)
+    } else {
+        0.5
+    };
+    (x, y)
+}
+
+/// Balance of the selected account over the chosen period, drawn as steps
+/// since a balance holds until the next change.
+#[function_component(BalanceChart)]
+pub fn balance_chart() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let period = use_state(BalancePeriod::default);
+    let hovered = use_state(|| None::<usize>);
+
+    let account = state.selected_account().cloned();
+
+    {
+        let dispatch = dispatch.clone();
+        // Reloaded when the balance changes, as its history then has a new point
+        let deps = (
+            *period,
+            account
+                .as_ref()
+                .map(|account| (account.address.clone(), account.balance)),
+        );
+        use_effect_with(deps, move |(period, account)| {
+            if account.is_some() {
+                dispatch.apply(WalletAction::LoadBalanceHistory(*period));
+            }
+            || ()
+        });
+    }
+
+    let history = state.balance_history.as_ref().filter(|history| {
+        history.period == *period
+            && account
+                .as_ref()
+                .is_some_and(|account| account.address == history.address)
+    });
+    let points = history
+        .map(|history| history.points.as_slice())
+        .unwrap_or_default();
+
+    let to = Utc::now();
+    let from = to - period.duration();
+    let low = points
+        .iter()
+        .map(|point| point.balance)
+        .min()
+        .unwrap_or_default();
+    let high = points
+        .iter()
+        .map(|point| point.balance)
+        .max()
+        .unwrap_or_default();
+    let (min, max) = (
+        low.to_f64().unwrap_or_default(),
+        high.to_f64().unwrap_or_default(),
+    );
+    let positions: Vec<(f64, f64)> = points
+        .iter()
+        .map(|point| position(point, from, to, min, max))
+        .collect();
+
+    // Each balance holds until the next point, and the last one until now
+    let mut line = Vec::new();
+    for (i, &(x, y)) in positions.iter().enumerate() {
+        if i > 0 {
+            line.push((x, positions[i - 1].1));
+        }
+        line.push((x, y));
+    }
+    if let Some(&(_, y)) = positions.last() {
+        line.push((1.0, y));
+    }
+    let to_svg =
+        |(x, y): (f64, f64)| format!("{:.1},{:.1}", x * WIDTH, (1.0 - y) * (HEIGHT - 8.0) + 4.0);
+    let polyline = line
+        .iter()
+        .copied()
+        .map(to_svg)
+        .collect::<Vec<_>>()
+        .join(" ");
+    let area = match (line.first(), line.last()) {
+        (Some(&(first_x, _)), Some(&(last_x, _))) => format!(
+            "{:.1},{HEIGHT} {} {:.1},{HEIGHT}",
+            first_x * WIDTH,
+            polyline,
+            last_x * WIDTH
+        ),
+        _ => String::new(),
+    };
+
+    let on_mouse_move = {
+        let hovered = hovered.clone();
+        let positions = positions.clone();
+        Callback::from(move |e: MouseEvent| {
+            let width = e.target_unchecked_into::<web_sys::Element>().client_width();
+            if width <= 0 || positions.is_empty() {
+                return;
+            }
+            let x = e.offset_x() as f64 / width as f64;
+            let nearest = positions
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| (a.0 - x).abs().total_cmp(&(b.0 - x).abs()))
+                .map(|(i, _)| i);
+            hovered.set(nearest);
+        })
+    };
+    let on_mouse_leave = {
+        let hovered = hovered.clone();
+        Callback::from(move |_| hovered.set(None))
+    };
+
+    let hovered_point = hovered.and_then(|i| points.get(i).zip(positions.get(i)));
+    let change = match (points.first(), points.last()) {
+        (Some(first), Some(last)) => Some(last.balance - first.balance),
+        _ => None,
+    };
+
+    html! {
+        <div class="mt-6 bg-indigo-800 rounded-xl p-6">
+            <div class="flex items-center justify-between mb-4">
+                <h2 class="text-xl font-bold">{ "Balance History" }</h2>
+                <div class="flex gap-4 text-sm">
+                    { for BalancePeriod::ALL.into_iter().map(|option| {
+                        let onclick = {
+                            let period = period.clone();
+                            let hovered = hovered.clone();
+                            Callback::from(move |_| {
+                                hovered.set(None);
+                                period.set(option);
+                            })
+                        };
+                        let class = if option == *period {
+                            "font-bold"
+                        } else {
+                            "text-indigo-300 hover:text-indigo-200 transition-colors"
+                        };
+                        html! {
+                            <button {class} {onclick}>{ period_label(option) }</button>
+                        }
+                    }) }
+                </div>
+            </div>
+            if points.is_empty() {
+                <p class="text-indigo-300">
+                    { if history.is_some() { "No balance recorded in this period yet." } else { "Loading…" } }
+                </p>
+            } else {
+                <div class="flex items-baseline justify-between mb-2 text-sm">
+                    {
+                        match hovered_point {
+                            Some((point, _)) => html! {
+                                <span>
+                                    { format!(
+                                        "{} ETH on {}",
+                                        display_decimal(point.balance, 4),
+                                        point.at.with_timezone(&Local).format("%b %e, %H:%M")
+                                    ) }
+                                </span>
+                            },
+                            None => html! {
+                                <span class="text-indigo-300">
+                                    { format!("Low {} · High {} ETH", display_decimal(low, 4), display_decimal(high, 4)) }
+                                </span>
+                            },
+                        }
+                    }
+                    if let Some(change) = change {
+                        <span class={if change.is_sign_negative() { "text-red-400" } else { "text-green-400" }}>
+                            { format!("{}{} ETH", if change.is_sign_negative() { "" } else { "+" }, display_decimal(change, 4)) }
+                        </span>
+                    }
+                </div>
+                <div class="relative h-40">
+                    <svg class="w-full h-full" viewBox={format!("0 0 {WIDTH} {HEIGHT}")} preserveAspectRatio="none">
+                        <polygon points={area} class="text-pink-500" fill="currentColor" fill-opacity="0.15" />
+                        <polyline
+                            points={polyline}
+                            class="text-pink-400"
+                            fill="none"
+                            stroke="currentColor"
+                            stroke-width="2"
+                            vector-effect="non-scaling-stroke"
+                        />
+                        if let Some((_, (x, _))) = hovered_point {
+                            <line
+                                x1={format!("{:.1}", x * WIDTH)}
+                                x2={format!("{:.1}", x * WIDTH)}
+                                y1="0"
+                                y2={HEIGHT.to_string()}
+                                class="text-indigo-300"
+                                stroke="currentColor"
+                                stroke-width="1"
+                                vector-effect="non-scaling-stroke"
+                            />
+                        }
+                    </svg>
+                    <div class="absolute inset-0" onmousemove={on_mouse_move} onmouseleave={on_mouse_leave}></div>
+                </div>
+                <div class="flex justify-between mt-2 text-xs text-indigo-300">
+                    <span>{ from.with_timezone(&Local).format("%b %e, %H:%M").to_string() }</span>
+                    <span>{ "Now" }</span>
+                </div>
+            }
+        </div>
+    }
+}
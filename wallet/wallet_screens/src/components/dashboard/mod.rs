@@ -1,10 +1,19 @@
+mod account_switcher;
 mod balance_card;
+mod balance_chart;
 mod recent_transactions;
+mod sign_in_addresses;
+mod spending_policy;
 mod wallet_details;
 mod wallet_security;
 
+pub use account_switcher::AccountSwitcher;
 pub use balance_card::BalanceCard;
+pub use balance_chart::BalanceChart;
 pub use recent_transactions::RecentTransactions;
+pub use sign_in_addresses::SignInAddresses;
+pub use spending_policy::SpendingPolicyPanel;
+use wallet_api::TransactionQuery;
 pub use wallet_details::WalletDetails;
 pub use wallet_security::WalletSecurity;
 use yew::prelude::*;
@@ -23,23 +32,29 @@ pub fn dashboard() -> Html {
         let dispatch = dispatch.clone();
         use_effect_with((), move |_| {
             dispatch.apply(WalletAction::RefreshBalance);
-            dispatch.apply(WalletAction::RefreshTransactions);
+            dispatch.apply(WalletAction::QueryTransactions(TransactionQuery::default()));
             || ()
         });
     }
 
     html! {
         <div class="flex-1">
+            <AccountSwitcher />
+
             <div class="grid grid-cols-3 gap-4">
                 <BalanceCard />
                 <QrCodeCard />
             </div>
 
+            <BalanceChart />
+
             <RecentTransactions />
 
             <div class="grid grid-cols-2 gap-4 mt-6">
                 <WalletDetails />
                 <WalletSecurity />
+                <SpendingPolicyPanel />
+                <SignInAddresses />
             </div>
         </div>
     }
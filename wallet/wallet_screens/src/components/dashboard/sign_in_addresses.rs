@@ -0,0 +1,100 @@
+use wallet_api::address::same_address;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::Counterparty,
+    store::{WalletAction, WalletStore},
+};
+
+/// Addresses the user can sign in with instead of their password. Linking
+/// signs a Sign-In With Ethereum message with the selected account's key.
+#[function_component(SignInAddresses)]
+pub fn sign_in_addresses() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let password = use_state(String::new);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with(state.wallet.is_some(), move |has_wallet| {
+            if *has_wallet {
+                dispatch.apply(WalletAction::LoadSignInAddresses);
+            }
+            || ()
+        });
+    }
+
+    let on_password_input = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            password.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_link = {
+        let dispatch = dispatch.clone();
+        let password = password.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            dispatch.apply(WalletAction::LinkSignInAddress {
+                password: (*password).clone(),
+            });
+            password.set(String::new());
+        })
+    };
+
+    let selected = state.selected_account();
+    let is_linked = selected.is_some_and(|account| {
+        state
+            .sign_in_addresses
+            .iter()
+            .any(|linked| same_address(&linked.address, &account.address))
+    });
+
+    html! {
+        <div class="bg-indigo-800 rounded-xl p-6">
+            <h2 class="text-xl font-bold mb-4">{ "Sign-In With Ethereum" }</h2>
+            if state.sign_in_addresses.is_empty() {
+                <p class="text-sm text-indigo-300 mb-2">{ "No addresses linked. Link one to sign in with a browser wallet holding its key." }</p>
+            }
+            { for state.sign_in_addresses.iter().map(|linked| {
+                let on_unlink = {
+                    let dispatch = dispatch.clone();
+                    let address = linked.address.clone();
+                    Callback::from(move |_| dispatch.apply(WalletAction::UnlinkSignInAddress(address.clone())))
+                };
+                html! {
+                    <div class="flex justify-between items-center mb-2 text-sm">
+                        <Counterparty address={linked.address.clone()} />
+                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_unlink}>
+                            { "Unlink" }
+                        </button>
+                    </div>
+                }
+            }) }
+            if let Some(account) = selected.filter(|_| !is_linked) {
+                if account.custodial {
+                    <p class="text-sm text-indigo-300 mt-2">{ "Custodial accounts cannot be linked, as their keys are held by the server." }</p>
+                } else {
+                    <form onsubmit={on_link} class="space-y-2 mt-4">
+                        <input
+                            type="password"
+                            value={(*password).clone()}
+                            oninput={on_password_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            placeholder="Enter your password"
+                        />
+                        <button type="submit" class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                            { format!("Link {}", account.label) }
+                        </button>
+                    </form>
+                }
+            }
+            if let Some(error) = &state.last_error {
+                <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,363 @@
+use chrono::{DateTime, Local, Utc};
+use rust_decimal::Decimal;
+use wallet_api::{
+    amount::{format_decimal, parse_amount, to_decimal, ETHER_DECIMALS},
+    CreateAllowedRecipient, SpendingPolicy,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::{display_decimal, Counterparty},
+    services::browser::number_locale,
+    store::{WalletAction, WalletStore},
+};
+
+fn limit_text(limit: Option<Decimal>) -> String {
+    limit
+        .map(|limit| format_decimal(limit, limit.normalize().scale(), number_locale()))
+        .unwrap_or_default()
+}
+
+fn limit_label(limit: Option<Decimal>) -> String {
+    match limit {
+        Some(limit) => format!("{} ETH", display_decimal(limit, 4)),
+        None => "No limit".to_string(),
+    }
+}
+
+fn local_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local)
+        .format("%Y-%m-%d %H:%M")
+        .to_string()
+}
+
+fn policy_summary(policy: &SpendingPolicy) -> Html {
+    html! {
+        <>
+            <div class="flex justify-between items-center mb-2">
+                <span>{ "Daily Limit" }</span>
+                <span>{ limit_label(policy.daily_limit) }</span>
+            </div>
+            <div class="flex justify-between items-center mb-2">
+                <span>{ "Per Transaction" }</span>
+                <span>{ limit_label(policy.per_transaction_limit) }</span>
+            </div>
+            <div class="flex justify-between items-center mb-2">
+                <span>{ "Recipients" }</span>
+                <span>
+                    {
+                        if policy.allowlist_only {
+                            format!("Allow-list only, after {} h", policy.recipient_delay_hours)
+                        } else {
+                            "Anyone".to_string()
+                        }
+                    }
+                </span>
+            </div>
+        </>
+    }
+}
+
+/// The user's spending limits and allow-list. The server checks every send
+/// against them before it is signed. Changes need a recent login and only
+/// apply after a cooling-off period, during which they can be cancelled.
+#[function_component(SpendingPolicyPanel)]
+pub fn spending_policy_panel() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let editing = use_state(|| false);
+    let daily_limit = use_state(String::new);
+    let per_transaction_limit = use_state(String::new);
+    let allowlist_only = use_state(|| false);
+    let delay_hours = use_state(String::new);
+    let recipient_address = use_state(String::new);
+    let recipient_label = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with(state.wallet.is_some(), move |has_wallet| {
+            if *has_wallet {
+                dispatch.apply(WalletAction::LoadSpendingPolicy);
+            }
+            || ()
+        });
+    }
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_daily_limit_input = input(&daily_limit);
+    let on_per_transaction_limit_input = input(&per_transaction_limit);
+    let on_delay_hours_input = input(&delay_hours);
+    let on_recipient_address_input = input(&recipient_address);
+    let on_recipient_label_input = input(&recipient_label);
+    let on_allowlist_only_change = {
+        let allowlist_only = allowlist_only.clone();
+        Callback::from(move |e: Event| {
+            allowlist_only.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .checked(),
+            )
+        })
+    };
+
+    let on_edit = {
+        let policy = state
+            .spending_policy
+            .as_ref()
+            .map(|overview| overview.policy.clone())
+            .unwrap_or_default();
+        let editing = editing.clone();
+        let daily_limit = daily_limit.clone();
+        let per_transaction_limit = per_transaction_limit.clone();
+        let allowlist_only = allowlist_only.clone();
+        let delay_hours = delay_hours.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            daily_limit.set(limit_text(policy.daily_limit));
+            per_transaction_limit.set(limit_text(policy.per_transaction_limit));
+            allowlist_only.set(policy.allowlist_only);
+            delay_hours.set(policy.recipient_delay_hours.to_string());
+            error.set(None);
+            editing.set(true);
+        })
+    };
+    let on_cancel_edit = {
+        let editing = editing.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            error.set(None);
+            editing.set(false);
+        })
+    };
+
+    let on_save = {
+        let dispatch = dispatch.clone();
+        let editing = editing.clone();
+        let daily_limit = daily_limit.clone();
+        let per_transaction_limit = per_transaction_limit.clone();
+        let allowlist_only = allowlist_only.clone();
+        let delay_hours = delay_hours.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let limit = |limit: &str| -> Result<Option<Decimal>, String> {
+                match limit.trim() {
+                    "" => Ok(None),
+                    limit => parse_amount(limit, ETHER_DECIMALS, number_locale())
+                        .and_then(|limit| to_decimal(limit, ETHER_DECIMALS))
+                        .map(Some)
+                        .map_err(|e| e.to_string()),
+                }
+            };
+            let daily_limit = match limit(&daily_limit) {
+                Ok(limit) => limit,
+                Err(e) => {
+                    error.set(Some(format!("Daily limit: {}", e)));
+                    return;
+                }
+            };
+            let per_transaction_limit = match limit(&per_transaction_limit) {
+                Ok(limit) => limit,
+                Err(e) => {
+                    error.set(Some(format!("Per-transaction limit: {}", e)));
+                    return;
+                }
+            };
+            let recipient_delay_hours = match delay_hours.trim() {
+                "" => 0,
+                hours => match hours.parse::<i32>() {
+                    Ok(hours) if (0..=744).contains(&hours) => hours,
+                    _ => {
+                        error.set(Some(
+                            "Time-lock must be a whole number of hours, at most 744".to_string(),
+                        ));
+                        return;
+                    }
+                },
+            };
+
+            error.set(None);
+            editing.set(false);
+            dispatch.apply(WalletAction::UpdateSpendingPolicy(SpendingPolicy {
+                daily_limit,
+                per_transaction_limit,
+                allowlist_only: *allowlist_only,
+                recipient_delay_hours,
+            }));
+        })
+    };
+
+    let on_cancel_change = {
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| dispatch.apply(WalletAction::CancelPolicyChange))
+    };
+
+    let on_add_recipient = {
+        let dispatch = dispatch.clone();
+        let recipient_address = recipient_address.clone();
+        let recipient_label = recipient_label.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let address = recipient_address.trim().to_string();
+            if address.is_empty() {
+                return;
+            }
+            let label = Some(recipient_label.trim().to_string()).filter(|label| !label.is_empty());
+            dispatch.apply(WalletAction::AddAllowedRecipient(CreateAllowedRecipient {
+                address,
+                label,
+            }));
+            recipient_address.set(String::new());
+            recipient_label.set(String::new());
+        })
+    };
+
+    let Some(overview) = state.spending_policy.clone() else {
+        return html! {};
+    };
+    let now = Utc::now();
+
+    html! {
+        <div class="bg-indigo-800 rounded-xl p-6 col-span-2">
+            <div class="flex justify-between items-center mb-4">
+                <h2 class="text-xl font-bold">{ "Spending Policy" }</h2>
+                if !*editing {
+                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_edit}>{ "Change" }</button>
+                }
+            </div>
+            <div class="grid grid-cols-2 gap-6">
+                <div>
+                    if *editing {
+                        <form onsubmit={on_save} class="space-y-2">
+                            <label class="block text-sm text-indigo-300">{ "Daily limit (ETH, empty for none)" }</label>
+                            <input
+                                type="text"
+                                value={(*daily_limit).clone()}
+                                oninput={on_daily_limit_input}
+                                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            />
+                            <label class="block text-sm text-indigo-300">{ "Per-transaction limit (ETH, empty for none)" }</label>
+                            <input
+                                type="text"
+                                value={(*per_transaction_limit).clone()}
+                                oninput={on_per_transaction_limit_input}
+                                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            />
+                            <label class="flex items-center gap-2">
+                                <input
+                                    type="checkbox"
+                                    checked={*allowlist_only}
+                                    onchange={on_allowlist_only_change}
+                                />
+                                { "Only pay allow-listed recipients" }
+                            </label>
+                            <label class="block text-sm text-indigo-300">{ "Hours before a new recipient can be paid" }</label>
+                            <input
+                                type="text"
+                                value={(*delay_hours).clone()}
+                                oninput={on_delay_hours_input}
+                                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            />
+                            <p class="text-sm text-yellow-300">
+                                { "Changes apply after a cooling-off period, and need a recent login." }
+                            </p>
+                            <p class="text-sm text-indigo-300">
+                                { "While a limit is set, token transfers and contract calls are blocked." }
+                            </p>
+                            <button type="submit" class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                                { "Request Change" }
+                            </button>
+                            <button type="button" class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel_edit}>
+                                { "Cancel" }
+                            </button>
+                        </form>
+                    } else {
+                        { policy_summary(&overview.policy) }
+                        <div class="flex justify-between items-center mb-2">
+                            <span>{ "Sent in the last 24 hours" }</span>
+                            <span>{ format!("{} ETH", display_decimal(overview.spent_today, 4)) }</span>
+                        </div>
+                    }
+                    if let Some(pending) = &overview.pending {
+                        <div class="mt-4 pt-4 border-t border-indigo-700">
+                            <div class="flex justify-between items-center mb-2">
+                                <span class="text-indigo-300">
+                                    { format!("Pending change, applies {}", local_time(pending.effective_at)) }
+                                </span>
+                                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel_change}>
+                                    { "Cancel Change" }
+                                </button>
+                            </div>
+                            { policy_summary(&pending.policy()) }
+                        </div>
+                    }
+                </div>
+                <div>
+                    <h3 class="font-bold mb-2">{ "Allow-listed Recipients" }</h3>
+                    if overview.recipients.is_empty() {
+                        <p class="text-sm text-indigo-300 mb-2">{ "No recipients allow-listed." }</p>
+                    }
+                    { for overview.recipients.iter().map(|recipient| {
+                        let on_remove = {
+                            let dispatch = dispatch.clone();
+                            let id = recipient.id;
+                            Callback::from(move |_| dispatch.apply(WalletAction::RemoveAllowedRecipient(id)))
+                        };
+                        html! {
+                            <div class="flex justify-between items-center mb-2 text-sm">
+                                <div>
+                                    if let Some(label) = &recipient.label {
+                                        <span class="mr-2">{ label }</span>
+                                    }
+                                    <Counterparty address={recipient.address.clone()} />
+                                    if !recipient.is_active(now) {
+                                        <span class="ml-2 text-yellow-300">
+                                            { format!("from {}", local_time(recipient.active_at)) }
+                                        </span>
+                                    }
+                                </div>
+                                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_remove}>
+                                    { "Remove" }
+                                </button>
+                            </div>
+                        }
+                    }) }
+                    <form onsubmit={on_add_recipient} class="space-y-2 mt-2">
+                        <input
+                            type="text"
+                            placeholder="Recipient address"
+                            value={(*recipient_address).clone()}
+                            oninput={on_recipient_address_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        />
+                        <input
+                            type="text"
+                            placeholder="Label (optional)"
+                            value={(*recipient_label).clone()}
+                            oninput={on_recipient_label_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        />
+                        <button type="submit" class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                            { "Add Recipient" }
+                        </button>
+                    </form>
+                </div>
+            </div>
+            if let Some(error) = (*error).clone() {
+                <p class="text-sm text-red-400 mt-2">{ error }</p>
+            }
+            if let Some(error) = &state.last_error {
+                <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+            }
+        </div>
+    }
+}
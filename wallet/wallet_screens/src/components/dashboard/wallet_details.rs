@@ -16,12 +16,12 @@ pub fn wallet_details() -> Html {
                 </svg>
             </div>
             {
-                if let Some(wallet) = &state.wallet {
-                    let address = wallet.address.clone();
+                if let Some(account) = state.selected_account() {
+                    let address = account.address.clone();
                     html! {
                         <>
                             <div class="mb-2">
-                                <span class="text-indigo-300">{ "Wallet Name :" }</span>{ " My Ethereum Wallet" }
+                                <span class="text-indigo-300">{ "Account :" }</span>{ format!(" {}", account.label) }
                             </div>
                             <div class="mb-2">
                                 <span class="text-indigo-300">{ "Network :" }</span>{ " Ethereum Mainnet" }
@@ -1,7 +1,153 @@
+use chrono::Utc;
+use ethers::utils::hex;
+use gloo_console as console;
 use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    services::{
+        browser::download_text,
+        crypto::{decrypt, CryptoError, Web3Keystore},
+    },
+    store::WalletStore,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Secret {
+    Mnemonic,
+    PrivateKey,
+}
+
+/// Decrypts the requested secret. Secrets only ever live in component state,
+/// never in the `WalletStore`, which is persisted to local storage.
+fn reveal(state: &WalletStore, secret: Secret, password: &str) -> Result<String, CryptoError> {
+    let wallet = state
+        .wallet
+        .as_ref()
+        .ok_or_else(|| CryptoError::Malformed("no wallet loaded".to_string()))?;
+    match secret {
+        Secret::Mnemonic => match &wallet.encrypted_mnemonic {
+            Some(encrypted_mnemonic) => {
+                decrypt(encrypted_mnemonic, password, &wallet.salt).map(|key| key.private_key)
+            }
+            None => Err(CryptoError::Malformed(
+                "this wallet was imported without a recovery phrase".to_string(),
+            )),
+        },
+        Secret::PrivateKey => {
+            let account = state
+                .selected_account()
+                .ok_or_else(|| CryptoError::Malformed("no account selected".to_string()))?;
+            decrypt(&account.encrypted_private_key, password, &wallet.salt)
+                .map(|key| format!("0x{}", key.private_key.to_lowercase()))
+        }
+    }
+}
 
 #[function_component(WalletSecurity)]
 pub fn wallet_security() -> Html {
+    let (state, _dispatch) = use_store::<WalletStore>();
+    let selected = use_state(|| None::<Secret>);
+    let password = use_state(String::new);
+    let revealed = use_state(|| None::<String>);
+    let error = use_state(|| None::<String>);
+
+    let select = |secret: Secret| {
+        let selected = selected.clone();
+        let revealed = revealed.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            selected.set(Some(secret));
+            revealed.set(None);
+            error.set(None);
+        })
+    };
+
+    let on_close = {
+        let selected = selected.clone();
+        let password = password.clone();
+        let revealed = revealed.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            selected.set(None);
+            password.set(String::new());
+            revealed.set(None);
+            error.set(None);
+        })
+    };
+
+    let on_password_change = {
+        let password = password.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            password.set(value);
+        })
+    };
+
+    {
+        // Never keep a revealed key around once another account is selected
+        let revealed = revealed.clone();
+        use_effect_with(state.selected_account, move |_| {
+            revealed.set(None);
+            || ()
+        });
+    }
+
+    let on_reveal = {
+        let state = state.clone();
+        let selected = selected.clone();
+        let password = password.clone();
+        let revealed = revealed.clone();
+        let error = error.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            let Some(secret) = *selected else {
+                return;
+            };
+            match reveal(&state, secret, &password) {
+                Ok(secret) => {
+                    revealed.set(Some(secret));
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    };
+
+    let on_download_keystore = {
+        let address = state
+            .selected_account()
+            .map(|account| account.address.clone());
+        let password = password.clone();
+        let revealed = revealed.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            let (Some(address), Some(private_key)) = (address.as_ref(), revealed.as_ref()) else {
+                return;
+            };
+            let keystore = hex::decode(private_key.trim_start_matches("0x"))
+                .map_err(|e| CryptoError::Malformed(e.to_string()))
+                .and_then(|private_key| Web3Keystore::encrypt(&private_key, &password))
+                .and_then(|keystore| keystore.to_json());
+            match keystore {
+                Ok(keystore) => {
+                    // Same naming scheme as geth, so other wallets recognise the file
+                    let filename = format!(
+                        "UTC--{}--{}.json",
+                        Utc::now().format("%Y-%m-%dT%H-%M-%S%.3fZ"),
+                        address.trim_start_matches("0x").to_lowercase()
+                    );
+                    if let Err(e) = download_text(&filename, &keystore, "application/json") {
+                        console::error!("Failed to download keystore", e);
+                    }
+                }
+                Err(e) => error.set(Some(e.to_string())),
+            }
+        })
+    };
+
     html! {
         <div class="bg-indigo-800 rounded-xl p-6">
             <div class="flex justify-between items-center mb-4">
@@ -12,11 +158,11 @@ pub fn wallet_security() -> Html {
             </div>
             <div class="flex justify-between items-center mb-2">
                 <span>{ "Backup Phrase" }</span>
-                <button class="text-indigo-300 hover:text-indigo-200 transition-colors">{ "View" }</button>
+                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={select(Secret::Mnemonic)}>{ "View" }</button>
             </div>
             <div class="flex justify-between items-center mb-2">
                 <span>{ "Private Key" }</span>
-                <button class="text-indigo-300 hover:text-indigo-200 transition-colors">{ "Export" }</button>
+                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={select(Secret::PrivateKey)}>{ "Export" }</button>
             </div>
             <div class="flex justify-between items-center">
                 <span>{ "Password Protection" }</span>
@@ -24,6 +170,43 @@ pub fn wallet_security() -> Html {
                     <div class="w-4 h-4 bg-white rounded-full absolute right-1 top-1 transition-transform"></div>
                 </div>
             </div>
+            if let Some(secret) = *selected {
+                <div class="mt-4 pt-4 border-t border-indigo-700">
+                    <div class="flex justify-between items-center mb-2">
+                        <span class="text-indigo-300">
+                            { if secret == Secret::Mnemonic { "Backup Phrase" } else { "Private Key" } }
+                        </span>
+                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_close}>{ "Hide" }</button>
+                    </div>
+                    if let Some(value) = (*revealed).clone() {
+                        <div class="bg-indigo-700 rounded-lg py-2 px-4 font-mono text-sm break-all mb-2">{ value }</div>
+                        if let (Secret::Mnemonic, Some(path)) = (secret, state.wallet.as_ref().and_then(|wallet| wallet.derivation_path.clone())) {
+                            <p class="text-sm text-indigo-300 mb-2">{ format!("Derivation path: {}", path) }</p>
+                        }
+                        <p class="text-sm text-yellow-300 mb-2">{ "Anyone with this secret can spend your funds. Never share it." }</p>
+                        if secret == Secret::PrivateKey {
+                            <button class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold" onclick={on_download_keystore}>
+                                { "Download Keystore File" }
+                            </button>
+                        }
+                    } else {
+                        <form onsubmit={on_reveal} class="space-y-2">
+                            <input
+                                type="password"
+                                placeholder="Wallet password"
+                                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                                onchange={on_password_change}
+                            />
+                            <button type="submit" class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                                { "Reveal" }
+                            </button>
+                        </form>
+                    }
+                    if let Some(error) = (*error).clone() {
+                        <p class="text-sm text-red-400 mt-2">{ error }</p>
+                    }
+                </div>
+            }
         </div>
     }
 }
@@ -0,0 +1,48 @@
+mod notification_center;
+mod payment_toasts;
+
+pub use notification_center::NotificationCenter;
+pub use payment_toasts::PaymentToasts;
+use wallet_api::Notification;
+
+use crate::{
+    components::common::{display_decimal, short_address},
+    store::WalletStore,
+};
+
+/// Title and message shown for a notification. Addresses are named after the
+/// user's accounts and contacts where possible.
+fn describe(notification: &Notification, state: &WalletStore) -> (String, String) {
+    let name = |address: &str| {
+        state
+            .name_for(address)
+            .map(str::to_string)
+            .unwrap_or_else(|| short_address(address))
+    };
+    let account = name(&notification.address);
+    let unit = match (&notification.token_symbol, &notification.token) {
+        (Some(symbol), _) => symbol.clone(),
+        (None, Some(token)) => short_address(token),
+        (None, None) => "ETH".to_string(),
+    };
+    let amount = notification
+        .amount
+        .map(|amount| {
+            format!(
+                "{} {}",
+                display_decimal(amount, amount.normalize().scale()),
+                unit
+            )
+        })
+        .unwrap_or_else(|| "A payment".to_string());
+    let sender = notification
+        .counterparty
+        .as_deref()
+        .map(name)
+        .unwrap_or_else(|| "an unknown sender".to_string());
+
+    (
+        "Payment received".to_string(),
+        format!("{} from {} to {}", amount, sender, account),
+    )
+}
@@ -0,0 +1,84 @@
+use chrono::Local;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use super::describe;
+use crate::store::{WalletAction, WalletStore};
+
+/// Bell button with the number of unread notifications, opening a list of the
+/// most recent ones.
+#[function_component(NotificationCenter)]
+pub fn notification_center() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let open = use_state(|| false);
+    let unread = state.unread_notifications();
+
+    let on_toggle = {
+        let open = open.clone();
+        Callback::from(move |_| open.set(!*open))
+    };
+
+    let on_mark_all_read = {
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| dispatch.apply(WalletAction::MarkAllNotificationsRead))
+    };
+
+    html! {
+        <div class="relative">
+            <button onclick={on_toggle} class="relative p-2 text-zinc-500 hover:text-zinc-700 dark:text-zinc-300 dark:hover:text-white transition-colors" title="Notifications">
+                <svg class="h-6 w-6" fill="none" stroke="currentColor" stroke-width="1.5" viewBox="0 0 24 24">
+                    <path stroke-linecap="round" stroke-linejoin="round" d="M14.857 17.082a23.848 23.848 0 0 0 5.454-1.31A8.967 8.967 0 0 1 18 9.75V9A6 6 0 0 0 6 9v.75a8.967 8.967 0 0 1-2.312 6.022c1.733.64 3.56 1.085 5.455 1.31m5.714 0a24.255 24.255 0 0 1-5.714 0m5.714 0a3 3 0 1 1-5.714 0" />
+                </svg>
+                if unread > 0 {
+                    <span class="absolute top-0 right-0 min-w-[1.25rem] h-5 px-1 rounded-full bg-pink-500 text-white text-xs font-bold flex items-center justify-center">
+                        { if unread > 9 { "9+".to_string() } else { unread.to_string() } }
+                    </span>
+                }
+            </button>
+            if *open {
+                <div class="absolute right-0 mt-2 w-80 max-h-96 overflow-y-auto bg-white dark:bg-zinc-800 border border-zinc-200 dark:border-zinc-700 rounded-lg shadow-lg z-50">
+                    <div class="flex justify-between items-center px-4 py-3 border-b border-zinc-200 dark:border-zinc-700">
+                        <span class="font-semibold dark:text-white">{ "Notifications" }</span>
+                        if unread > 0 {
+                            <button onclick={on_mark_all_read} class="text-sm text-blue-500 hover:text-blue-400">
+                                { "Mark all as read" }
+                            </button>
+                        }
+                    </div>
+                    if state.notifications.is_empty() {
+                        <div class="px-4 py-6 text-center text-sm text-zinc-500">{ "No notifications yet" }</div>
+                    }
+                    { for state.notifications.iter().map(|notification| {
+                        let (title, message) = describe(notification, &state);
+                        let on_click = {
+                            let dispatch = dispatch.clone();
+                            let id = notification.id;
+                            Callback::from(move |_| dispatch.apply(WalletAction::MarkNotificationRead(id)))
+                        };
+                        let class = if notification.is_read() {
+                            "px-4 py-3 border-b border-zinc-100 dark:border-zinc-700 cursor-pointer"
+                        } else {
+                            "px-4 py-3 border-b border-zinc-100 dark:border-zinc-700 cursor-pointer bg-blue-50 dark:bg-zinc-700"
+                        };
+                        html! {
+                            <div key={notification.id.to_string()} {class} onclick={on_click}>
+                                <div class="flex justify-between text-sm font-medium dark:text-white">
+                                    <span>{ title }</span>
+                                    if !notification.is_read() {
+                                        <span class="w-2 h-2 mt-1.5 rounded-full bg-blue-500"></span>
+                                    }
+                                </div>
+                                <div class="text-sm text-zinc-600 dark:text-zinc-300">{ message }</div>
+                                if let Some(created_at) = notification.created_at {
+                                    <div class="text-xs text-zinc-400 mt-1">
+                                        { created_at.with_timezone(&Local).format("%b %e, %H:%M").to_string() }
+                                    </div>
+                                }
+                            </div>
+                        }
+                    }) }
+                </div>
+            }
+        </div>
+    }
+}
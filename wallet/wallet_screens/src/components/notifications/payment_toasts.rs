@@ -0,0 +1,27 @@
+use wonopui::use_notify;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use super::describe;
+use crate::store::{WalletAction, WalletStore};
+
+/// Shows a toast for every notification received while the app is open. Has to
+/// be mounted inside wonopui's `NotificationProvider`.
+#[function_component(PaymentToasts)]
+pub fn payment_toasts() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let notify = use_notify();
+
+    use_effect_with(state.pending_toasts.clone(), move |pending_toasts| {
+        if !pending_toasts.is_empty() {
+            for notification in pending_toasts {
+                let (title, message) = describe(notification, &state);
+                notify.emit((title, message, None));
+            }
+            dispatch.apply(WalletAction::ToastsShown);
+        }
+        || ()
+    });
+
+    html! {}
+}
@@ -0,0 +1,232 @@
+mod payment_form;
+
+use chrono::Local;
+pub use payment_form::ScheduledPaymentForm;
+use wallet_api::{ScheduledPayment, ScheduledPaymentRun, Transaction};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::{
+        common::{display_decimal, short_address, Counterparty},
+        transfer::PendingTransactions,
+    },
+    store::{WalletAction, WalletStore},
+};
+
+/// Unit the amount of a payment is in.
+fn unit(token: &Option<String>) -> String {
+    match token {
+        Some(token) => short_address(token),
+        None => "ETH".to_string(),
+    }
+}
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        ScheduledPaymentRun::SUBMITTED | Transaction::CONFIRMED => "text-green-400",
+        ScheduledPaymentRun::FAILED => "text-red-400",
+        ScheduledPaymentRun::SKIPPED | ScheduledPayment::CANCELLED => "text-indigo-300",
+        _ => "text-yellow-400",
+    }
+}
+
+fn run_status_label(run: &ScheduledPaymentRun) -> String {
+    match (run.status.as_str(), &run.transaction_status) {
+        (ScheduledPaymentRun::SUBMITTED, Some(status)) => status.clone(),
+        (ScheduledPaymentRun::AWAITING_APPROVAL, _) => "awaiting approval".to_string(),
+        (status, _) => status.to_string(),
+    }
+}
+
+/// Payments made once at a set time or on a recurring schedule, and every
+/// time one came due. Payments from accounts the server holds no key for
+/// are signed here once the user approves them.
+#[function_component(ScheduledPayments)]
+pub fn scheduled_payments() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let password = use_state(String::new);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadScheduledPayments);
+            || ()
+        });
+    }
+
+    let account_label = |account_id| {
+        state
+            .accounts
+            .iter()
+            .find(|account| account.id == account_id)
+            .map(|account| account.label.clone())
+            .unwrap_or_else(|| "A removed account".to_string())
+    };
+    let needs_password = state.scheduled_runs.iter().any(|run| {
+        run.is_awaiting_approval()
+            && state
+                .accounts
+                .iter()
+                .any(|account| account.id == run.account_id && !account.custodial)
+    });
+    let on_password_input = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            password.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+
+    html! {
+        <div class="flex-1 space-y-6">
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <h2 class="text-xl font-bold mb-4">{ "Schedule a payment" }</h2>
+                <ScheduledPaymentForm />
+                if let Some(error) = &state.last_error {
+                    <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+                }
+            </div>
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <h2 class="text-xl font-bold mb-4">{ "Scheduled payments" }</h2>
+                if state.scheduled_payments.is_empty() {
+                    <p class="text-indigo-300">{ "No payments scheduled yet." }</p>
+                } else {
+                    <ul class="divide-y divide-indigo-700">
+                        { for state.scheduled_payments.iter().map(|payment| {
+                            let id = payment.id;
+                            let on_cancel = {
+                                let dispatch = dispatch.clone();
+                                Callback::from(move |_| dispatch.apply(WalletAction::CancelScheduledPayment(id)))
+                            };
+                            html! {
+                                <li class="py-3 flex items-start justify-between gap-4">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">
+                                            { format!("{} {} to ", display_decimal(payment.amount, 6), unit(&payment.token)) }
+                                            <Counterparty address={payment.recipient.clone()} />
+                                        </p>
+                                        if let Some(memo) = &payment.memo {
+                                            <p class="text-sm">{ memo }</p>
+                                        }
+                                        <p class="text-sm text-indigo-300">
+                                            { format!("From {}", account_label(payment.account_id)) }
+                                            if let Some(schedule) = &payment.schedule {
+                                                { " · Repeats " }
+                                                <span class="font-mono">{ schedule }</span>
+                                                { " (UTC)" }
+                                            }
+                                        </p>
+                                        if let Some(next_run_at) = payment.next_run_at {
+                                            <p class="text-sm text-indigo-300">
+                                                { format!("Next payment {}", next_run_at.with_timezone(&Local).format("%b %e, %H:%M")) }
+                                            </p>
+                                        }
+                                    </div>
+                                    <div class="flex flex-col items-end gap-1 shrink-0">
+                                        <span class={classes!("text-sm", "capitalize", status_class(&payment.status))}>
+                                            { &payment.status }
+                                        </span>
+                                        if payment.status == ScheduledPayment::ACTIVE {
+                                            <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel}>
+                                                { "Cancel" }
+                                            </button>
+                                        }
+                                    </div>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+            </div>
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <h2 class="text-xl font-bold mb-4">{ "Runs" }</h2>
+                if needs_password {
+                    <div class="mb-4">
+                        <label class="block mb-2">{ "Password to approve payments" }</label>
+                        <input
+                            type="password"
+                            value={(*password).clone()}
+                            oninput={on_password_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                            placeholder="Enter your password"
+                        />
+                    </div>
+                }
+                if state.scheduled_runs.is_empty() {
+                    <p class="text-indigo-300">{ "No payment came due yet." }</p>
+                } else {
+                    <ul class="divide-y divide-indigo-700">
+                        { for state.scheduled_runs.iter().map(|run| {
+                            let id = run.id;
+                            let is_sending = state
+                                .pending_transactions
+                                .iter()
+                                .any(|pending| pending.scheduled_run == Some(id));
+                            let on_approve = {
+                                let dispatch = dispatch.clone();
+                                let password = password.clone();
+                                Callback::from(move |_| {
+                                    dispatch.apply(WalletAction::ApproveScheduledRun {
+                                        id,
+                                        password: (*password).clone(),
+                                    })
+                                })
+                            };
+                            let on_skip = {
+                                let dispatch = dispatch.clone();
+                                Callback::from(move |_| dispatch.apply(WalletAction::SkipScheduledRun(id)))
+                            };
+                            let status = run_status_label(run);
+                            html! {
+                                <li class="py-3 flex items-start justify-between gap-4">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">
+                                            { format!("{} {} to ", display_decimal(run.amount, 6), unit(&run.token)) }
+                                            <Counterparty address={run.recipient.clone()} />
+                                        </p>
+                                        <p class="text-sm text-indigo-300">
+                                            { format!(
+                                                "Due {} from {}",
+                                                run.due_at.with_timezone(&Local).format("%b %e, %H:%M"),
+                                                account_label(run.account_id)
+                                            ) }
+                                        </p>
+                                        if let Some(tx_hash) = &run.tx_hash {
+                                            <p class="text-sm text-indigo-300 font-mono" title={tx_hash.clone()}>
+                                                { format!("Transaction {}", short_address(tx_hash)) }
+                                            </p>
+                                        }
+                                        if let Some(error) = &run.error {
+                                            <p class="text-sm text-red-400">{ error }</p>
+                                        }
+                                    </div>
+                                    <div class="flex flex-col items-end gap-1 shrink-0">
+                                        <span class={classes!("text-sm", "capitalize", status_class(&status))}>
+                                            { status.clone() }
+                                        </span>
+                                        if run.is_awaiting_approval() && !is_sending {
+                                            <div class="flex gap-4">
+                                                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_approve}>
+                                                    { "Approve" }
+                                                </button>
+                                                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_skip}>
+                                                    { "Skip" }
+                                                </button>
+                                            </div>
+                                        }
+                                    </div>
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+                if !state.pending_transactions.is_empty() {
+                    <PendingTransactions />
+                }
+            </div>
+        </div>
+    }
+}
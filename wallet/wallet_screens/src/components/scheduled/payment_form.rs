@@ -0,0 +1,357 @@
+use chrono::{DateTime, Local, NaiveDateTime, TimeZone, Utc};
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    amount::{parse_amount, to_decimal, ETHER_DECIMALS},
+    scheduled_payment::{first_run, parse_schedule},
+    CreateScheduledPayment,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::transfer::RecipientInput,
+    services::browser::number_locale,
+    store::{WalletAction, WalletStore},
+};
+
+/// Schedules offered next to the free-form expression.
+const PRESETS: [(&str, &str); 4] = [
+    ("Daily", "0 9 * * *"),
+    ("Weekly", "0 9 * * 1"),
+    ("Monthly", "0 9 1 * *"),
+    ("Yearly", "0 9 1 1 *"),
+];
+
+/// How many upcoming runs of a schedule are previewed.
+const PREVIEWED_RUNS: usize = 3;
+
+/// Value of a `datetime-local` input, in the browser's time zone.
+fn parse_local_time(value: &str) -> Option<DateTime<Utc>> {
+    let time = NaiveDateTime::parse_from_str(value, "%Y-%m-%dT%H:%M").ok()?;
+    Local
+        .from_local_datetime(&time)
+        .earliest()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+/// Sets up a payment from one of the user's accounts, either once at a given
+/// time or on a recurring schedule.
+#[function_component(ScheduledPaymentForm)]
+pub fn scheduled_payment_form() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+
+    let account_id = use_state(|| None::<String>);
+    let recipient = use_state(String::new);
+    let amount = use_state(String::new);
+    let token = use_state(String::new);
+    let token_decimals = use_state(|| ETHER_DECIMALS.to_string());
+    let memo = use_state(String::new);
+    let recurring = use_state(|| false);
+    let run_at = use_state(String::new);
+    let schedule = use_state(|| PRESETS[2].1.to_string());
+    let error = use_state(|| None::<String>);
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_amount_input = input(&amount);
+    let on_token_input = input(&token);
+    let on_token_decimals_input = input(&token_decimals);
+    let on_memo_input = input(&memo);
+    let on_run_at_input = input(&run_at);
+    let on_schedule_input = input(&schedule);
+    let on_recipient_input = {
+        let recipient = recipient.clone();
+        Callback::from(move |value: String| recipient.set(value))
+    };
+    let on_account_change = {
+        let account_id = account_id.clone();
+        Callback::from(move |e: Event| {
+            account_id.set(Some(
+                e.target_unchecked_into::<web_sys::HtmlSelectElement>()
+                    .value(),
+            ))
+        })
+    };
+    let on_recurring = |value: bool| {
+        let recurring = recurring.clone();
+        Callback::from(move |_| recurring.set(value))
+    };
+
+    // Defaults to the selected account until another one is picked
+    let account = account_id
+        .as_deref()
+        .and_then(|id| {
+            state
+                .accounts
+                .iter()
+                .find(|account| account.id.to_string() == id)
+        })
+        .or_else(|| state.selected_account())
+        .cloned();
+
+    let preview = if *recurring {
+        Some(parse_schedule(&schedule).map(|parsed| {
+            let now = Utc::now();
+            let from = parse_local_time(&run_at).unwrap_or(now).max(now);
+            first_run(&parsed, from)
+                .map(|first| {
+                    std::iter::once(first)
+                        .chain(parsed.after(&first))
+                        .take(PREVIEWED_RUNS)
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        }))
+    } else {
+        None
+    };
+
+    let on_submit = {
+        let recipient = recipient.clone();
+        let amount = amount.clone();
+        let token = token.clone();
+        let token_decimals = token_decimals.clone();
+        let memo = memo.clone();
+        let recurring = recurring.clone();
+        let run_at = run_at.clone();
+        let schedule = schedule.clone();
+        let error = error.clone();
+        let account = account.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let Some(account) = &account else {
+                error.set(Some("Pick an account to pay from".to_string()));
+                return;
+            };
+            let recipient_address = match parse_address(recipient.trim()) {
+                Ok(address) => checksum_address(&address),
+                Err(e) => {
+                    error.set(Some(format!("Recipient: {}", e)));
+                    return;
+                }
+            };
+            let token = match token.trim() {
+                "" => None,
+                token => match parse_address(token) {
+                    Ok(token) => Some(checksum_address(&token)),
+                    Err(e) => {
+                        error.set(Some(format!("Token: {}", e)));
+                        return;
+                    }
+                },
+            };
+            let decimals = if token.is_some() {
+                match token_decimals.trim().parse::<i16>() {
+                    Ok(decimals) if (0..=28).contains(&decimals) => decimals,
+                    _ => {
+                        error.set(Some("Token decimals must be 0 to 28".to_string()));
+                        return;
+                    }
+                }
+            } else {
+                ETHER_DECIMALS
+            };
+            let value = match parse_amount(amount.trim(), decimals, number_locale())
+                .and_then(|value| to_decimal(value, decimals))
+            {
+                Ok(value) if !value.is_zero() => value,
+                Ok(_) => {
+                    error.set(Some("Amount must be positive".to_string()));
+                    return;
+                }
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    return;
+                }
+            };
+            let run_at = match run_at.trim() {
+                "" if *recurring => None,
+                "" => {
+                    error.set(Some("Pick when to make the payment".to_string()));
+                    return;
+                }
+                value => match parse_local_time(value) {
+                    Some(run_at) => Some(run_at),
+                    None => {
+                        error.set(Some("Time is invalid".to_string()));
+                        return;
+                    }
+                },
+            };
+            let schedule = if *recurring {
+                match parse_schedule(&schedule) {
+                    Ok(_) => Some(schedule.trim().to_string()),
+                    Err(e) => {
+                        error.set(Some(e.to_string()));
+                        return;
+                    }
+                }
+            } else {
+                None
+            };
+            let note = Some(memo.trim().to_string()).filter(|note| !note.is_empty());
+
+            error.set(None);
+            dispatch.apply(WalletAction::CreateScheduledPayment(
+                CreateScheduledPayment {
+                    account_id: account.id,
+                    recipient: recipient_address,
+                    token,
+                    token_decimals: decimals,
+                    amount: value,
+                    memo: note,
+                    schedule,
+                    run_at,
+                },
+            ));
+            recipient.set(String::new());
+            amount.set(String::new());
+            memo.set(String::new());
+        })
+    };
+
+    let is_token = !token.trim().is_empty();
+    let toggle_class = |active: bool| {
+        if active {
+            "flex-1 rounded-lg py-2 bg-indigo-600 font-medium"
+        } else {
+            "flex-1 rounded-lg py-2 text-indigo-300 hover:text-indigo-200 transition-colors"
+        }
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="space-y-4">
+            <div>
+                <label class="block mb-2">{ "Pay from" }</label>
+                <select onchange={on_account_change} class="w-full bg-indigo-700 rounded-lg py-2 px-4">
+                    { for state.accounts.iter().map(|option| html! {
+                        <option
+                            value={option.id.to_string()}
+                            selected={account.as_ref().is_some_and(|account| account.id == option.id)}
+                        >
+                            { format!("{} ({})", option.label, option.address) }
+                        </option>
+                    }) }
+                </select>
+                if account.as_ref().is_some_and(|account| !account.custodial) {
+                    <p class="text-xs mt-1 text-indigo-300">
+                        { "The server holds no key for this account, so each payment waits for you to approve it here." }
+                    </p>
+                }
+            </div>
+            <div>
+                <label class="block mb-2">{ "Pay to" }</label>
+                <RecipientInput value={(*recipient).clone()} oninput={on_recipient_input} />
+            </div>
+            <div>
+                <label class="block mb-2">{ "Amount" }</label>
+                <input
+                    type="text"
+                    value={(*amount).clone()}
+                    placeholder={if is_token { "Amount in tokens" } else { "Amount in ETH" }}
+                    oninput={on_amount_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            <div class="flex gap-2">
+                <div class="flex-1">
+                    <label class="block mb-2">{ "Token contract (optional)" }</label>
+                    <input
+                        type="text"
+                        value={(*token).clone()}
+                        placeholder="Empty for ETH"
+                        oninput={on_token_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                if is_token {
+                    <div class="w-28">
+                        <label class="block mb-2">{ "Decimals" }</label>
+                        <input
+                            type="number"
+                            value={(*token_decimals).clone()}
+                            oninput={on_token_decimals_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        />
+                    </div>
+                }
+            </div>
+            <div>
+                <label class="block mb-2">{ "Memo (optional)" }</label>
+                <input
+                    type="text"
+                    value={(*memo).clone()}
+                    maxlength="200"
+                    oninput={on_memo_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            <div class="flex gap-2 bg-indigo-700 rounded-lg p-1">
+                <button type="button" class={toggle_class(!*recurring)} onclick={on_recurring(false)}>
+                    { "Once" }
+                </button>
+                <button type="button" class={toggle_class(*recurring)} onclick={on_recurring(true)}>
+                    { "Recurring" }
+                </button>
+            </div>
+            <div>
+                <label class="block mb-2">{ if *recurring { "Starting (optional)" } else { "Pay at" } }</label>
+                <input
+                    type="datetime-local"
+                    value={(*run_at).clone()}
+                    oninput={on_run_at_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            if *recurring {
+                <div>
+                    <label class="block mb-2">{ "Schedule (cron, UTC)" }</label>
+                    <div class="flex flex-wrap gap-4 mb-2 text-sm">
+                        { for PRESETS.iter().map(|(label, expression)| {
+                            let schedule = schedule.clone();
+                            let onclick = Callback::from(move |_| schedule.set(expression.to_string()));
+                            html! {
+                                <button type="button" class="text-indigo-300 hover:text-indigo-200 transition-colors" {onclick}>
+                                    { label }
+                                </button>
+                            }
+                        }) }
+                    </div>
+                    <input
+                        type="text"
+                        value={(*schedule).clone()}
+                        placeholder="minute hour day month weekday"
+                        oninput={on_schedule_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+                    />
+                    {
+                        match &preview {
+                            Some(Ok(runs)) => html! {
+                                <p class="text-xs mt-1 text-indigo-300">
+                                    { "Next payments: " }
+                                    { runs.iter().map(|run| run.with_timezone(&Local).format("%b %e, %H:%M").to_string()).collect::<Vec<_>>().join(", ") }
+                                </p>
+                            },
+                            Some(Err(e)) => html! { <p class="text-xs mt-1 text-red-400">{ e.to_string() }</p> },
+                            None => html! {},
+                        }
+                    }
+                </div>
+            }
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400">{ error }</p>
+            }
+            <button type="submit" class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                { "Schedule Payment" }
+            </button>
+        </form>
+    }
+}
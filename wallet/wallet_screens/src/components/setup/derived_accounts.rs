@@ -0,0 +1,150 @@
+use ethers::{
+    providers::{Http, Middleware, Provider},
+    signers::Signer,
+    types::{Address, U256},
+};
+use gloo_console as console;
+use wallet_api::amount::ETHER_DECIMALS;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+
+use crate::{
+    components::common::display_amount,
+    services::hd_wallet::{derivation_path, derive_signer},
+};
+
+const PAGE_SIZE: u32 = 5;
+
+#[derive(Clone, PartialEq)]
+struct DerivedAccount {
+    index: u32,
+    address: Address,
+    balance: Option<U256>,
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DerivedAccountsProps {
+    pub mnemonic: String,
+    pub passphrase: String,
+    pub path_prefix: String,
+    pub selected: u32,
+    pub on_select: Callback<u32>,
+}
+
+/// Lists the first accounts derived from a recovery phrase together with their
+/// balances, so the user can pick the one they have been using.
+#[function_component(DerivedAccounts)]
+pub fn derived_accounts(props: &DerivedAccountsProps) -> Html {
+    let count = use_state(|| PAGE_SIZE);
+    let accounts = use_state(Vec::<DerivedAccount>::new);
+    let error = use_state(|| None::<String>);
+
+    {
+        let accounts = accounts.clone();
+        let error = error.clone();
+        use_effect_with(
+            (
+                props.mnemonic.clone(),
+                props.passphrase.clone(),
+                props.path_prefix.clone(),
+                *count,
+            ),
+            move |(mnemonic, passphrase, path_prefix, count)| {
+                let derived = (0..*count)
+                    .map(|index| {
+                        derive_signer(
+                            mnemonic,
+                            Some(passphrase.as_str()),
+                            &derivation_path(path_prefix, index),
+                        )
+                        .map(|signer| DerivedAccount {
+                            index,
+                            address: signer.address(),
+                            balance: None,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>();
+
+                match derived {
+                    Ok(derived) => {
+                        error.set(None);
+                        accounts.set(derived.clone());
+                        spawn_local(async move {
+                            let provider = match Provider::<Http>::try_from("http://localhost:8545")
+                            {
+                                Ok(provider) => provider,
+                                Err(e) => {
+                                    console::error!(format!("Could not reach the node: {}", e));
+                                    return;
+                                }
+                            };
+                            let mut with_balances = Vec::with_capacity(derived.len());
+                            for account in derived {
+                                let balance =
+                                    provider.get_balance(account.address, None).await.ok();
+                                with_balances.push(DerivedAccount { balance, ..account });
+                            }
+                            accounts.set(with_balances);
+                        });
+                    }
+                    Err(e) => {
+                        accounts.set(Vec::new());
+                        error.set(Some(e.to_string()));
+                    }
+                }
+                || ()
+            },
+        );
+    }
+
+    let on_show_more = {
+        let count = count.clone();
+        Callback::from(move |_| count.set(*count + PAGE_SIZE))
+    };
+
+    if let Some(error) = (*error).clone() {
+        return html! {
+            <p class="text-sm text-red-600 dark:text-red-400">{ error }</p>
+        };
+    }
+
+    html! {
+        <div class="space-y-2">
+            { for accounts.iter().map(|account| {
+                let index = account.index;
+                let on_select = props.on_select.clone();
+                let selected = index == props.selected;
+                html! {
+                    <label class={format!(
+                        "flex items-center gap-3 px-4 py-2 rounded-lg border-2 cursor-pointer text-sm {}",
+                        if selected { "border-blue-500" } else { "border-zinc-200 dark:border-zinc-700" }
+                    )}>
+                        <input
+                            type="radio"
+                            name="account"
+                            checked={selected}
+                            onchange={Callback::from(move |_| on_select.emit(index))}
+                        />
+                        <span class="text-zinc-500 dark:text-zinc-400">{ format!("#{}", index) }</span>
+                        <span class="flex-1 truncate font-mono text-zinc-700 dark:text-zinc-300">{ format!("{:?}", account.address) }</span>
+                        <span class="text-zinc-700 dark:text-zinc-300">
+                            {
+                                match account.balance {
+                                    Some(balance) => format!("{} ETH", display_amount(balance, ETHER_DECIMALS, 4)),
+                                    None => "…".to_string(),
+                                }
+                            }
+                        </span>
+                    </label>
+                }
+            }) }
+            <button
+                type="button"
+                onclick={on_show_more}
+                class="text-sm text-blue-600 dark:text-blue-400 hover:underline"
+            >
+                {"Show more accounts"}
+            </button>
+        </div>
+    }
+}
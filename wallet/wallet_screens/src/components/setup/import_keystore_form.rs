@@ -0,0 +1,140 @@
+use gloo_console as console;
+use wasm_bindgen_futures::spawn_local;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    services::browser::read_file_text,
+    store::{WalletAction, WalletStore},
+};
+
+#[function_component(ImportKeystoreForm)]
+pub fn import_keystore_form() -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let keystore = use_state(String::new);
+    let keystore_password = use_state(String::new);
+    let password = use_state(String::new);
+
+    let on_file_change = {
+        let keystore = keystore.clone();
+        Callback::from(move |e: Event| {
+            let input = e.target_unchecked_into::<web_sys::HtmlInputElement>();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                let keystore = keystore.clone();
+                spawn_local(async move {
+                    match read_file_text(file).await {
+                        Ok(contents) => keystore.set(contents),
+                        Err(e) => console::error!("Failed to read keystore file", e),
+                    }
+                });
+            }
+        })
+    };
+
+    let on_keystore_change = {
+        let keystore = keystore.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                .value();
+            keystore.set(value);
+        })
+    };
+
+    let on_keystore_password_change = {
+        let keystore_password = keystore_password.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            keystore_password.set(value);
+        })
+    };
+
+    let on_password_change = {
+        let password = password.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            password.set(value);
+        })
+    };
+
+    let on_submit = {
+        let keystore = keystore.clone();
+        let keystore_password = keystore_password.clone();
+        let password = password.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            dispatch.apply(WalletAction::ImportKeystore {
+                keystore: (*keystore).clone(),
+                keystore_password: (*keystore_password).clone(),
+                password: (*password).clone(),
+            });
+        })
+    };
+
+    html! {
+        <div class="bg-white/95 dark:bg-zinc-900/95 p-8 space-y-6 rounded-2xl shadow-lg">
+            <form onsubmit={on_submit} class="space-y-6">
+                <div class="space-y-5">
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="keystore" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Keystore File (JSON)"}
+                        </label>
+                        <input
+                            type="file"
+                            accept=".json,application/json"
+                            class="w-full mb-2 text-sm text-zinc-700 dark:text-zinc-300"
+                            onchange={on_file_change}
+                        />
+                        <textarea
+                            id="keystore"
+                            placeholder="Or paste the contents of the keystore file"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 font-mono text-xs focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            rows="5"
+                            value={(*keystore).clone()}
+                            onchange={on_keystore_change}
+                        />
+                    </div>
+
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="keystore-password" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Keystore Password"}
+                        </label>
+                        <input
+                            type="password"
+                            id="keystore-password"
+                            placeholder="The password the keystore file was exported with"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            onchange={on_keystore_password_change}
+                        />
+                    </div>
+
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="password" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Encryption Password"}
+                        </label>
+                        <input
+                            type="password"
+                            id="password"
+                            placeholder="Enter a strong password to encrypt your wallet"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            onchange={on_password_change}
+                        />
+                    </div>
+                </div>
+
+                <button
+                    type="submit"
+                    class="w-full py-3.5 font-medium text-white bg-gradient-to-r from-blue-500 to-indigo-600 hover:from-blue-600 hover:to-indigo-700 rounded-lg focus:ring-4 focus:ring-blue-400/50 dark:focus:ring-blue-500/50 transform transition-all duration-200 active:scale-[0.98]"
+                >
+                    {"Import Wallet"}
+                </button>
+            </form>
+        </div>
+    }
+}
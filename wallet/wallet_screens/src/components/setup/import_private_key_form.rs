@@ -0,0 +1,87 @@
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::{WalletAction, WalletStore};
+
+#[function_component(ImportPrivateKeyForm)]
+pub fn import_private_key_form() -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let private_key = use_state(String::new);
+    let password = use_state(String::new);
+
+    let on_private_key_change = {
+        let private_key = private_key.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            private_key.set(value);
+        })
+    };
+
+    let on_password_change = {
+        let password = password.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            password.set(value);
+        })
+    };
+
+    let on_submit = {
+        let private_key = private_key.clone();
+        let password = password.clone();
+
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            dispatch.apply(WalletAction::ImportPrivateKey {
+                private_key: (*private_key).clone(),
+                password: (*password).clone(),
+            });
+        })
+    };
+
+    html! {
+        <div class="bg-white/95 dark:bg-zinc-900/95 p-8 space-y-6 rounded-2xl shadow-lg">
+            <form onsubmit={on_submit} class="space-y-6">
+                <div class="space-y-5">
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="private-key" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Private Key"}
+                        </label>
+                        <input
+                            type="password"
+                            id="private-key"
+                            placeholder="0x..."
+                            autocomplete="off"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            onchange={on_private_key_change}
+                        />
+                    </div>
+
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="password" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Encryption Password"}
+                        </label>
+                        <input
+                            type="password"
+                            id="password"
+                            placeholder="Enter a strong password to encrypt your wallet"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            onchange={on_password_change}
+                        />
+                    </div>
+                </div>
+
+                <button
+                    type="submit"
+                    class="w-full py-3.5 font-medium text-white bg-gradient-to-r from-blue-500 to-indigo-600 hover:from-blue-600 hover:to-indigo-700 rounded-lg focus:ring-4 focus:ring-blue-400/50 dark:focus:ring-blue-500/50 transform transition-all duration-200 active:scale-[0.98]"
+                >
+                    {"Import Wallet"}
+                </button>
+            </form>
+        </div>
+    }
+}
@@ -1,9 +1,46 @@
+mod derived_accounts;
+mod import_keystore_form;
+mod import_private_key_form;
 mod setup_form;
+pub use derived_accounts::DerivedAccounts;
+pub use import_keystore_form::ImportKeystoreForm;
+pub use import_private_key_form::ImportPrivateKeyForm;
 pub use setup_form::SetupForm;
 use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::WalletStore;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SetupView {
+    RecoveryPhrase,
+    PrivateKey,
+    KeystoreFile,
+}
 
 #[function_component(Setup)]
 pub fn setup() -> Html {
+    let (state, _dispatch) = use_store::<WalletStore>();
+    let active_tab = use_state(|| SetupView::RecoveryPhrase);
+
+    let tab = |view: SetupView, label: &str| {
+        let active_tab = active_tab.clone();
+        let class = if *active_tab == view {
+            "border-b-2 border-blue-500 text-zinc-900 dark:text-zinc-100"
+        } else {
+            "text-zinc-500 dark:text-zinc-400"
+        };
+        html! {
+            <button
+                type="button"
+                class={format!("flex-1 text-center py-2 text-sm font-medium {}", class)}
+                onclick={Callback::from(move |_| active_tab.set(view.clone()))}
+            >
+                { label }
+            </button>
+        }
+    };
+
     html! {
         <div class="min-h-screen bg-gradient-to-br from-zinc-50 via-zinc-100 to-zinc-50 dark:from-zinc-900 dark:via-zinc-800 dark:to-zinc-900 flex items-center justify-center antialiased py-16 px-4 sm:px-6 lg:px-8">
             <div class="container relative mx-auto flex flex-col items-center justify-center max-w-md">
@@ -16,7 +53,21 @@ pub fn setup() -> Html {
                             {"Configure your Ethereum wallet settings"}
                         </p>
                     </div>
-                    <SetupForm />
+                    <div class="flex px-8">
+                        { tab(SetupView::RecoveryPhrase, "Recovery Phrase") }
+                        { tab(SetupView::PrivateKey, "Private Key") }
+                        { tab(SetupView::KeystoreFile, "Keystore File") }
+                    </div>
+                    if let Some(error) = &state.last_error {
+                        <p class="mx-8 mt-4 text-sm text-red-600 dark:text-red-400">{ &error.message }</p>
+                    }
+                    {
+                        match *active_tab {
+                            SetupView::RecoveryPhrase => html! { <SetupForm /> },
+                            SetupView::PrivateKey => html! { <ImportPrivateKeyForm /> },
+                            SetupView::KeystoreFile => html! { <ImportKeystoreForm /> },
+                        }
+                    }
                 </div>
             </div>
         </div>
@@ -3,26 +3,81 @@ use gloo_console as console;
 use yew::prelude::*;
 use yewdux::prelude::*;
 
-use crate::store::{WalletAction, WalletStore};
+use super::DerivedAccounts;
+use crate::{
+    services::hd_wallet::{derivation_path, validate_phrase, DEFAULT_DERIVATION_PATH_PREFIX},
+    store::{WalletAction, WalletStore},
+};
 
 #[function_component(SetupForm)]
 pub fn setup_form() -> Html {
     let (_, dispatch) = use_store::<WalletStore>();
     let mnemonic = use_state(String::new);
+    let passphrase = use_state(String::new);
+    let path_prefix = use_state(|| DEFAULT_DERIVATION_PATH_PREFIX.to_string());
+    let account_index = use_state(|| 0u32);
     let password = use_state(String::new);
 
+    let phrase = if mnemonic.trim().is_empty() {
+        None
+    } else {
+        Some(validate_phrase(&mnemonic))
+    };
+    let valid_phrase = phrase.clone().and_then(Result::ok);
+
     let generate_mnemonic = {
         let mnemonic = mnemonic.clone();
+        let account_index = account_index.clone();
         Callback::from(move |_| {
+            account_index.set(0);
             let mut rng = rand::thread_rng();
             let new_mnemonic = Mnemonic::<English>::new(&mut rng);
             mnemonic.set(new_mnemonic.to_phrase());
         })
     };
 
+    let on_mnemonic_change = {
+        let mnemonic = mnemonic.clone();
+        let account_index = account_index.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                .value();
+            mnemonic.set(value);
+            account_index.set(0);
+        })
+    };
+
+    let on_passphrase_change = {
+        let passphrase = passphrase.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            passphrase.set(value);
+        })
+    };
+
+    let on_path_prefix_change = {
+        let path_prefix = path_prefix.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlInputElement>()
+                .value();
+            path_prefix.set(value);
+        })
+    };
+
+    let on_account_select = {
+        let account_index = account_index.clone();
+        Callback::from(move |index: u32| account_index.set(index))
+    };
+
     let set_anvil_mnemonic = {
         let mnemonic = mnemonic.clone();
+        let account_index = account_index.clone();
         Callback::from(move |_| {
+            account_index.set(0);
             mnemonic.set("test test test test test test test test test test test junk".to_string());
         })
     };
@@ -38,15 +93,24 @@ pub fn setup_form() -> Html {
     };
 
     let on_submit = {
-        let mnemonic = mnemonic.clone();
+        let valid_phrase = valid_phrase.clone();
+        let passphrase = passphrase.clone();
+        let path_prefix = path_prefix.clone();
+        let account_index = account_index.clone();
         let password = password.clone();
         let dispatch = dispatch.clone();
 
         Callback::from(move |e: SubmitEvent| {
             e.prevent_default();
+            let Some(mnemonic) = valid_phrase.clone() else {
+                return;
+            };
             console::log!("Setting up wallet");
+            dispatch.apply(WalletAction::ClearErrorMessage);
             dispatch.apply(WalletAction::GenerateWallet {
-                mnemonic: (*mnemonic).clone(),
+                mnemonic,
+                passphrase: Some((*passphrase).clone()).filter(|passphrase| !passphrase.is_empty()),
+                derivation_path: derivation_path(&path_prefix, *account_index),
                 password: (*password).clone(),
             });
         })
@@ -64,9 +128,15 @@ pub fn setup_form() -> Html {
                             id="mnemonic"
                             class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
                             rows="3"
+                            placeholder="Enter your recovery phrase or generate a new one"
+                            autocomplete="off"
+                            spellcheck="false"
                             value={(*mnemonic).clone()}
-                            readonly=true
+                            onchange={on_mnemonic_change}
                         />
+                        if let Some(Err(error)) = &phrase {
+                            <p class="mt-1 text-sm text-red-600 dark:text-red-400">{ error.to_string() }</p>
+                        }
                         <div class="flex gap-2 mt-2">
                             <button
                                 type="button"
@@ -85,6 +155,53 @@ pub fn setup_form() -> Html {
                         </div>
                     </div>
 
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="passphrase" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"BIP-39 Passphrase (optional)"}
+                        </label>
+                        <input
+                            type="password"
+                            id="passphrase"
+                            placeholder="Leave empty unless your wallet uses one"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            onchange={on_passphrase_change}
+                        />
+                        <p class="mt-1 text-xs text-zinc-500 dark:text-zinc-400">
+                            {"The passphrase is not stored. You will need it together with the recovery phrase to restore this wallet."}
+                        </p>
+                    </div>
+
+                    <div class="relative group min-h-[5.5rem]">
+                        <label for="derivation-path" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                            {"Derivation Path"}
+                        </label>
+                        <input
+                            type="text"
+                            id="derivation-path"
+                            class="w-full px-4 py-3 rounded-lg border-2 border-zinc-200 dark:border-zinc-700 bg-white dark:bg-zinc-800 text-zinc-700 dark:text-zinc-300 font-mono focus:ring-2 focus:ring-blue-400 focus:border-transparent transition-all duration-200"
+                            value={(*path_prefix).clone()}
+                            onchange={on_path_prefix_change}
+                        />
+                        <p class="mt-1 text-xs text-zinc-500 dark:text-zinc-400">
+                            { format!("Account #{} uses {}", *account_index, derivation_path(&path_prefix, *account_index)) }
+                        </p>
+                    </div>
+
+                    if let Some(mnemonic) = valid_phrase.clone() {
+                        <div class="relative group">
+                            <span class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
+                                {"Account"}
+                            </span>
+                            <DerivedAccounts
+                                mnemonic={mnemonic}
+                                passphrase={(*passphrase).clone()}
+                                path_prefix={(*path_prefix).clone()}
+                                selected={*account_index}
+                                on_select={on_account_select}
+                            />
+                        </div>
+                    }
+
                     <div class="relative group min-h-[5.5rem]">
                         <label for="password" class="text-sm font-medium text-zinc-700 dark:text-zinc-300 mb-1.5 block">
                             {"Encryption Password"}
@@ -101,7 +218,8 @@ pub fn setup_form() -> Html {
 
                 <button
                     type="submit"
-                    class="w-full py-3.5 font-medium text-white bg-gradient-to-r from-blue-500 to-indigo-600 hover:from-blue-600 hover:to-indigo-700 rounded-lg focus:ring-4 focus:ring-blue-400/50 dark:focus:ring-blue-500/50 transform transition-all duration-200 active:scale-[0.98]"
+                    disabled={valid_phrase.is_none()}
+                    class="w-full disabled:opacity-50 py-3.5 font-medium text-white bg-gradient-to-r from-blue-500 to-indigo-600 hover:from-blue-600 hover:to-indigo-700 rounded-lg focus:ring-4 focus:ring-blue-400/50 dark:focus:ring-blue-500/50 transform transition-all duration-200 active:scale-[0.98]"
                 >
                     {"Setup Wallet"}
                 </button>
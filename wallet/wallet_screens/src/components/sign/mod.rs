@@ -0,0 +1,182 @@
+mod sign_form;
+mod verify_form;
+
+use ethers::utils::hex;
+pub use sign_form::SignForm;
+pub use verify_form::VerifyForm;
+use yew::prelude::*;
+
+use crate::services::signing::{is_hex_message, message_bytes, SignPayload};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PayloadKind {
+    Message,
+    TypedData,
+}
+
+impl PayloadKind {
+    fn from_value(value: &str) -> Self {
+        match value {
+            "typed_data" => Self::TypedData,
+            _ => Self::Message,
+        }
+    }
+
+    pub fn parse(self, input: &str) -> Result<SignPayload, String> {
+        match self {
+            Self::Message => Ok(SignPayload::Message(input.to_string())),
+            Self::TypedData => SignPayload::typed_data(input).map_err(|e| e.to_string()),
+        }
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PayloadKindSelectProps {
+    pub kind: PayloadKind,
+    pub onchange: Callback<PayloadKind>,
+}
+
+#[function_component(PayloadKindSelect)]
+pub fn payload_kind_select(props: &PayloadKindSelectProps) -> Html {
+    let onchange = {
+        let onchange = props.onchange.clone();
+        Callback::from(move |e: Event| {
+            onchange.emit(PayloadKind::from_value(
+                &e.target_unchecked_into::<web_sys::HtmlSelectElement>()
+                    .value(),
+            ))
+        })
+    };
+
+    html! {
+        <select {onchange} class="w-full bg-indigo-700 rounded-lg py-2 px-4">
+            <option value="message" selected={props.kind == PayloadKind::Message}>{ "Message (personal_sign)" }</option>
+            <option value="typed_data" selected={props.kind == PayloadKind::TypedData}>{ "Typed data (EIP-712)" }</option>
+        </select>
+    }
+}
+
+fn row(label: &str, value: String) -> Html {
+    html! {
+        <div class="flex justify-between items-start gap-4 mb-2">
+            <span class="text-indigo-300">{ label }</span>
+            <span class="font-mono break-all text-right">{ value }</span>
+        </div>
+    }
+}
+
+#[derive(Properties, PartialEq)]
+pub struct PayloadPreviewProps {
+    pub payload: SignPayload,
+}
+
+/// Exactly what a signature covers: the message bytes, or the domain, types
+/// and values of typed data, with the hash that is signed.
+#[function_component(PayloadPreview)]
+pub fn payload_preview(props: &PayloadPreviewProps) -> Html {
+    let hash = props
+        .payload
+        .hash()
+        .map(|hash| format!("{:?}", hash))
+        .unwrap_or_else(|e| e.to_string());
+
+    let details = match &props.payload {
+        SignPayload::Message(message) => {
+            let bytes = message_bytes(message);
+            html! {
+                <>
+                    if is_hex_message(message) {
+                        <p class="text-sm text-yellow-300 mb-2">
+                            { format!("Signed as {} bytes of hex, not as text.", bytes.len()) }
+                        </p>
+                    } else {
+                        <pre class="whitespace-pre-wrap break-all bg-indigo-900 rounded-lg p-3 mb-2">{ message }</pre>
+                    }
+                    { row("Bytes", format!("0x{}", hex::encode(&bytes))) }
+                </>
+            }
+        }
+        SignPayload::TypedData(typed_data) => {
+            let domain = &typed_data.domain;
+            let message = serde_json::to_string_pretty(&typed_data.message).unwrap_or_default();
+            html! {
+                <>
+                    { row("Application", domain.name.clone().unwrap_or_else(|| "Not given".to_string())) }
+                    if let Some(version) = &domain.version {
+                        { row("Version", version.clone()) }
+                    }
+                    { row("Chain", domain.chain_id.map(|chain_id| chain_id.to_string()).unwrap_or_else(|| "Any".to_string())) }
+                    if let Some(contract) = domain.verifying_contract {
+                        { row("Verifying contract", format!("{:?}", contract)) }
+                    }
+                    if let Some(salt) = domain.salt {
+                        { row("Salt", format!("0x{}", hex::encode(salt))) }
+                    }
+                    { row("Type", typed_data.primary_type.clone()) }
+                    { for typed_data.types.iter().filter(|(name, _)| name.as_str() != "EIP712Domain").map(|(name, fields)| {
+                        let fields = fields
+                            .iter()
+                            .map(|field| format!("{} {}", field.r#type, field.name))
+                            .collect::<Vec<_>>()
+                            .join(", ");
+                        html! {
+                            <p class="font-mono text-sm break-all mb-1">{ format!("{}({})", name, fields) }</p>
+                        }
+                    }) }
+                    <pre class="whitespace-pre-wrap break-all bg-indigo-900 rounded-lg p-3 my-2">{ message }</pre>
+                </>
+            }
+        }
+    };
+
+    html! {
+        <div class="bg-indigo-700 rounded-lg p-4 mb-4 text-sm">
+            { details }
+            { row("Hash", hash) }
+        </div>
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SignView {
+    Sign,
+    Verify,
+}
+
+/// Signs messages and typed data with the selected account, for logging in
+/// to applications or approving off-chain orders, and checks signatures.
+#[function_component(Sign)]
+pub fn sign() -> Html {
+    let view = use_state(|| SignView::Sign);
+
+    let tab = |target: SignView, label: &str| {
+        let onclick = {
+            let view = view.clone();
+            let target = target.clone();
+            Callback::from(move |_| view.set(target.clone()))
+        };
+        html! {
+            <button
+                class={format!("flex-1 text-center py-2 {}", if *view == target { "border-b-2 border-white" } else { "text-indigo-300" })}
+                {onclick}
+            >
+                { label }
+            </button>
+        }
+    };
+
+    html! {
+        <div class="mt-6 bg-indigo-800 rounded-xl p-6">
+            <h2 class="text-xl font-bold mb-4">{ "Sign" }</h2>
+            <div class="flex mb-4">
+                { tab(SignView::Sign, "Sign") }
+                { tab(SignView::Verify, "Verify") }
+            </div>
+            if *view == SignView::Sign {
+                <SignForm />
+            } else {
+                <VerifyForm />
+            }
+        </div>
+    }
+}
@@ -0,0 +1,128 @@
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use super::{PayloadKind, PayloadKindSelect, PayloadPreview};
+use crate::{
+    components::common::Counterparty,
+    store::{WalletAction, WalletStore},
+};
+
+/// Signs a message or typed data with the selected account. What will be
+/// signed is shown in full before the password is asked for.
+#[function_component(SignForm)]
+pub fn sign_form() -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let kind = use_state(|| PayloadKind::Message);
+    let input = use_state(String::new);
+    let password = use_state(String::new);
+    let is_custodial = state
+        .selected_account()
+        .is_some_and(|account| account.custodial);
+    let payload = (!input.is_empty()).then(|| kind.parse(&input));
+
+    {
+        // A signature shown for other input would be misleading
+        let dispatch = dispatch.clone();
+        use_effect_with((*kind, (*input).clone()), move |_| {
+            dispatch.apply(WalletAction::ClearSignature);
+            || ()
+        });
+    }
+
+    let on_kind_change = {
+        let kind = kind.clone();
+        Callback::from(move |value: PayloadKind| kind.set(value))
+    };
+    let on_input = {
+        let input = input.clone();
+        Callback::from(move |e: InputEvent| {
+            input.set(
+                e.target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_password_input = {
+        let password = password.clone();
+        Callback::from(move |e: InputEvent| {
+            password.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+
+    let on_submit = {
+        let dispatch = dispatch.clone();
+        let payload = payload.clone();
+        let password = password.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some(Ok(payload)) = payload.clone() {
+                dispatch.apply(WalletAction::SignMessage {
+                    payload,
+                    password: (*password).clone(),
+                });
+                password.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="space-y-4">
+            <PayloadKindSelect kind={*kind} onchange={on_kind_change} />
+            <textarea
+                rows="6"
+                placeholder={if *kind == PayloadKind::Message { "Message" } else { "Typed data JSON, as passed to eth_signTypedData_v4" }}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+                value={(*input).clone()}
+                oninput={on_input}
+            />
+            if let Some(account) = state.selected_account() {
+                <div class="flex justify-between items-center">
+                    <span class="text-indigo-300">{ "Signing as" }</span>
+                    <Counterparty address={account.address.clone()} />
+                </div>
+            }
+            {
+                match &payload {
+                    Some(Ok(payload)) => html! { <PayloadPreview payload={payload.clone()} /> },
+                    Some(Err(error)) => html! { <p class="text-sm text-red-400">{ error }</p> },
+                    None => html! {},
+                }
+            }
+            if is_custodial {
+                <p class="text-sm text-yellow-300">
+                    { "Custodial accounts cannot sign messages, as their keys are held by the server." }
+                </p>
+            } else {
+                <input
+                    type="password"
+                    value={(*password).clone()}
+                    oninput={on_password_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    placeholder="Enter your password"
+                />
+                <button
+                    type="submit"
+                    disabled={!matches!(payload, Some(Ok(_))) || password.is_empty()}
+                    class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold disabled:opacity-50"
+                >
+                    { "Sign" }
+                </button>
+            }
+            {
+                match &state.signature {
+                    Some(Ok(signature)) => html! {
+                        <div>
+                            <label class="block mb-2">{ "Signature" }</label>
+                            <p class="font-mono text-sm break-all bg-indigo-700 rounded-lg p-3">{ signature }</p>
+                        </div>
+                    },
+                    Some(Err(error)) => html! { <p class="text-sm text-red-400">{ error }</p> },
+                    None => html! {},
+                }
+            }
+        </form>
+    }
+}
@@ -0,0 +1,108 @@
+use wallet_api::address::parse_address;
+use yew::prelude::*;
+
+use super::{PayloadKind, PayloadKindSelect, PayloadPreview};
+use crate::{components::common::Counterparty, services::signing::recover};
+
+/// Checks which address signed a message or typed data, and whether it is the
+/// one expected.
+#[function_component(VerifyForm)]
+pub fn verify_form() -> Html {
+    let kind = use_state(|| PayloadKind::Message);
+    let input = use_state(String::new);
+    let signature = use_state(String::new);
+    let expected = use_state(String::new);
+    let payload = (!input.is_empty()).then(|| kind.parse(&input));
+
+    let on_kind_change = {
+        let kind = kind.clone();
+        Callback::from(move |value: PayloadKind| kind.set(value))
+    };
+    let on_input = {
+        let input = input.clone();
+        Callback::from(move |e: InputEvent| {
+            input.set(
+                e.target_unchecked_into::<web_sys::HtmlTextAreaElement>()
+                    .value(),
+            )
+        })
+    };
+    let text_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_signature_input = text_input(&signature);
+    let on_expected_input = text_input(&expected);
+
+    let signer = match &payload {
+        Some(Ok(payload)) if !signature.trim().is_empty() => {
+            Some(recover(payload, &signature).map_err(|e| e.to_string()))
+        }
+        _ => None,
+    };
+    let expected_address = Some(expected.trim())
+        .filter(|expected| !expected.is_empty())
+        .map(|expected| parse_address(expected).map_err(|e| e.to_string()));
+
+    html! {
+        <div class="space-y-4">
+            <PayloadKindSelect kind={*kind} onchange={on_kind_change} />
+            <textarea
+                rows="6"
+                placeholder={if *kind == PayloadKind::Message { "Message" } else { "Typed data JSON" }}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+                value={(*input).clone()}
+                oninput={on_input}
+            />
+            {
+                match &payload {
+                    Some(Ok(payload)) => html! { <PayloadPreview payload={payload.clone()} /> },
+                    Some(Err(error)) => html! { <p class="text-sm text-red-400">{ error }</p> },
+                    None => html! {},
+                }
+            }
+            <input
+                type="text"
+                placeholder="Signature (0x...)"
+                value={(*signature).clone()}
+                oninput={on_signature_input}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+            />
+            <input
+                type="text"
+                placeholder="Expected signer (optional)"
+                value={(*expected).clone()}
+                oninput={on_expected_input}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+            />
+            {
+                match (&signer, &expected_address) {
+                    (Some(Err(error)), _) | (_, Some(Err(error))) => html! {
+                        <p class="text-sm text-red-400">{ error }</p>
+                    },
+                    (Some(Ok(signer)), expected) => html! {
+                        <div class="bg-indigo-700 rounded-lg p-4">
+                            <div class="flex justify-between items-center mb-2">
+                                <span class="text-indigo-300">{ "Signed by" }</span>
+                                <Counterparty address={format!("{:?}", signer)} />
+                            </div>
+                            if let Some(Ok(expected)) = expected {
+                                if signer == expected {
+                                    <p class="text-green-400 font-bold">{ "The signature is valid for the expected signer." }</p>
+                                } else {
+                                    <p class="text-red-400 font-bold">{ "The signature was not made by the expected signer." }</p>
+                                }
+                            }
+                        </div>
+                    },
+                    _ => html! {},
+                }
+            }
+        </div>
+    }
+}
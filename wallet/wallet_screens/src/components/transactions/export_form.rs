@@ -0,0 +1,110 @@
+use chrono::NaiveDate;
+use wallet_api::{ExportFormat, TransactionExportQuery};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::{WalletAction, WalletStore};
+
+/// Downloads the transactions between two optional dates as CSV or JSON,
+/// for bookkeeping.
+#[function_component(TransactionExport)]
+pub fn transaction_export() -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let format = use_state(ExportFormat::default);
+    let from = use_state(String::new);
+    let to = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    let date_input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_from_change = date_input(&from);
+    let on_to_change = date_input(&to);
+    let on_format_change = {
+        let format = format.clone();
+        Callback::from(move |e: Event| {
+            let value = e
+                .target_unchecked_into::<web_sys::HtmlSelectElement>()
+                .value();
+            format.set(if value == "json" {
+                ExportFormat::Json
+            } else {
+                ExportFormat::Csv
+            });
+        })
+    };
+
+    let on_download = {
+        let format = format.clone();
+        let from = from.clone();
+        let to = to.clone();
+        let error = error.clone();
+        Callback::from(move |_| {
+            // Date inputs always give ISO dates, or nothing when left empty
+            let parse = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+            let (from, to) = (parse(&from), parse(&to));
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    error.set(Some(
+                        "The start date must not be after the end date".to_string(),
+                    ));
+                    return;
+                }
+            }
+
+            error.set(None);
+            dispatch.apply(WalletAction::ExportTransactions(TransactionExportQuery {
+                format: *format,
+                from,
+                to,
+            }));
+        })
+    };
+
+    html! {
+        <div class="mb-4">
+            <div class="flex flex-wrap items-end gap-2">
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "From" }</label>
+                    <input
+                        type="date"
+                        value={(*from).clone()}
+                        onchange={on_from_change}
+                        class="bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "To" }</label>
+                    <input
+                        type="date"
+                        value={(*to).clone()}
+                        onchange={on_to_change}
+                        class="bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Format" }</label>
+                    <select onchange={on_format_change} class="bg-indigo-700 rounded-lg py-2 px-4">
+                        <option value="csv" selected={*format == ExportFormat::Csv}>{ "CSV" }</option>
+                        <option value="json" selected={*format == ExportFormat::Json}>{ "JSON" }</option>
+                    </select>
+                </div>
+                <button
+                    class="bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-2 px-4 font-bold"
+                    onclick={on_download}
+                >
+                    { "Download" }
+                </button>
+            </div>
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400 mt-2">{ error }</p>
+            }
+        </div>
+    }
+}
@@ -0,0 +1,308 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    amount::{format_decimal, parse_amount, to_decimal, ETHER_DECIMALS},
+    Transaction, TransactionQuery,
+};
+use yew::prelude::*;
+
+use crate::services::browser::number_locale;
+
+#[derive(Properties, PartialEq)]
+pub struct TransactionFiltersProps {
+    /// Filters currently applied.
+    pub query: TransactionQuery,
+    /// Called with the new filters, on their first page.
+    pub on_apply: Callback<TransactionQuery>,
+}
+
+/// Text of an optional filter, empty when it is not set.
+fn text(value: &Option<String>) -> String {
+    value.clone().unwrap_or_default()
+}
+
+fn amount_text(amount: Option<Decimal>) -> String {
+    amount
+        .map(|amount| format_decimal(amount, amount.normalize().scale(), number_locale()))
+        .unwrap_or_default()
+}
+
+/// Filters the transaction history by date, direction, status, counterparty,
+/// amount and token.
+#[function_component(TransactionFilters)]
+pub fn transaction_filters(props: &TransactionFiltersProps) -> Html {
+    let query = &props.query;
+    let from = use_state(|| query.from.map(|from| from.to_string()).unwrap_or_default());
+    let to = use_state(|| query.to.map(|to| to.to_string()).unwrap_or_default());
+    let direction = use_state(|| text(&query.direction));
+    let status = use_state(|| text(&query.status));
+    let counterparty = use_state(|| text(&query.counterparty));
+    let min_amount = use_state(|| amount_text(query.min_amount));
+    let max_amount = use_state(|| amount_text(query.max_amount));
+    let token = use_state(|| text(&query.token));
+    let error = use_state(|| None::<String>);
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let change = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let select = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: Event| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlSelectElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_from_change = change(&from);
+    let on_to_change = change(&to);
+    let on_direction_change = select(&direction);
+    let on_status_change = select(&status);
+    let on_counterparty_input = input(&counterparty);
+    let on_min_amount_input = input(&min_amount);
+    let on_max_amount_input = input(&max_amount);
+    let on_token_input = input(&token);
+
+    let on_submit = {
+        let from = from.clone();
+        let to = to.clone();
+        let direction = direction.clone();
+        let status = status.clone();
+        let counterparty = counterparty.clone();
+        let min_amount = min_amount.clone();
+        let max_amount = max_amount.clone();
+        let token = token.clone();
+        let error = error.clone();
+        let limit = query.limit;
+        let on_apply = props.on_apply.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let optional = |value: &str| Some(value.trim().to_string()).filter(|v| !v.is_empty());
+            // Date inputs always give ISO dates, or nothing when left empty
+            let date = |date: &str| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok();
+            let amount = |amount: &str| -> Result<Option<Decimal>, String> {
+                match amount.trim() {
+                    "" => Ok(None),
+                    amount => parse_amount(amount, ETHER_DECIMALS, number_locale())
+                        .and_then(|amount| to_decimal(amount, ETHER_DECIMALS))
+                        .map(Some)
+                        .map_err(|e| e.to_string()),
+                }
+            };
+
+            let (from, to) = (date(&from), date(&to));
+            if let (Some(from), Some(to)) = (from, to) {
+                if from > to {
+                    error.set(Some(
+                        "The start date must not be after the end date".to_string(),
+                    ));
+                    return;
+                }
+            }
+            let (min_amount, max_amount) = match (amount(&min_amount), amount(&max_amount)) {
+                (Ok(min), Ok(max)) => (min, max),
+                (Err(e), _) => {
+                    error.set(Some(format!("Minimum amount: {}", e)));
+                    return;
+                }
+                (_, Err(e)) => {
+                    error.set(Some(format!("Maximum amount: {}", e)));
+                    return;
+                }
+            };
+            if let (Some(min), Some(max)) = (min_amount, max_amount) {
+                if min > max {
+                    error.set(Some(
+                        "The minimum amount must not be above the maximum".to_string(),
+                    ));
+                    return;
+                }
+            }
+            let counterparty =
+                optional(&counterparty).map(|counterparty| counterparty.to_lowercase());
+            if let Some(counterparty) = &counterparty {
+                let digits = counterparty.strip_prefix("0x").unwrap_or(counterparty);
+                if digits.len() > 40 || !digits.chars().all(|c| c.is_ascii_hexdigit()) {
+                    error.set(Some(
+                        "Counterparty must be an address or part of one".to_string(),
+                    ));
+                    return;
+                }
+            }
+            let token = match optional(&token) {
+                Some(token) if token.eq_ignore_ascii_case(TransactionQuery::ETHER) => {
+                    Some(TransactionQuery::ETHER.to_string())
+                }
+                Some(token) => match parse_address(&token) {
+                    Ok(token) => Some(checksum_address(&token)),
+                    Err(e) => {
+                        error.set(Some(format!("Token: {}", e)));
+                        return;
+                    }
+                },
+                None => None,
+            };
+
+            error.set(None);
+            on_apply.emit(TransactionQuery {
+                from,
+                to,
+                direction: optional(&direction),
+                status: optional(&status),
+                counterparty,
+                min_amount,
+                max_amount,
+                token,
+                limit,
+                ..Default::default()
+            });
+        })
+    };
+    let on_clear = {
+        let from = from.clone();
+        let to = to.clone();
+        let direction = direction.clone();
+        let status = status.clone();
+        let counterparty = counterparty.clone();
+        let min_amount = min_amount.clone();
+        let max_amount = max_amount.clone();
+        let token = token.clone();
+        let error = error.clone();
+        let limit = query.limit;
+        let on_apply = props.on_apply.clone();
+        Callback::from(move |_| {
+            for state in [
+                &from,
+                &to,
+                &direction,
+                &status,
+                &counterparty,
+                &min_amount,
+                &max_amount,
+                &token,
+            ] {
+                state.set(String::new());
+            }
+            error.set(None);
+            on_apply.emit(TransactionQuery {
+                limit,
+                ..Default::default()
+            });
+        })
+    };
+
+    html! {
+        <form onsubmit={on_submit} class="mb-4">
+            <div class="flex flex-wrap items-end gap-2">
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "From" }</label>
+                    <input
+                        type="date"
+                        value={(*from).clone()}
+                        onchange={on_from_change}
+                        class="bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "To" }</label>
+                    <input
+                        type="date"
+                        value={(*to).clone()}
+                        onchange={on_to_change}
+                        class="bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Direction" }</label>
+                    <select onchange={on_direction_change} class="bg-indigo-700 rounded-lg py-2 px-4">
+                        <option value="" selected={direction.is_empty()}>{ "Any" }</option>
+                        <option value={Transaction::IN} selected={*direction == Transaction::IN}>{ "Incoming" }</option>
+                        <option value={Transaction::OUT} selected={*direction == Transaction::OUT}>{ "Outgoing" }</option>
+                    </select>
+                </div>
+                <div>
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Status" }</label>
+                    <select onchange={on_status_change} class="bg-indigo-700 rounded-lg py-2 px-4">
+                        <option value="" selected={status.is_empty()}>{ "Any" }</option>
+                        { for [
+                            Transaction::PENDING,
+                            Transaction::CONFIRMED,
+                            Transaction::FAILED,
+                            Transaction::REPLACED,
+                        ].into_iter().map(|value| html! {
+                            <option value={value} selected={*status == value}>{ value }</option>
+                        }) }
+                    </select>
+                </div>
+            </div>
+            <div class="flex flex-wrap items-end gap-2 mt-2">
+                <div class="flex-1 min-w-48">
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Counterparty" }</label>
+                    <input
+                        type="text"
+                        value={(*counterparty).clone()}
+                        placeholder="Address or part of one"
+                        oninput={on_counterparty_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div class="w-28">
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Min amount" }</label>
+                    <input
+                        type="text"
+                        value={(*min_amount).clone()}
+                        oninput={on_min_amount_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div class="w-28">
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Max amount" }</label>
+                    <input
+                        type="text"
+                        value={(*max_amount).clone()}
+                        oninput={on_max_amount_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                <div class="flex-1 min-w-48">
+                    <label class="block mb-1 text-sm text-indigo-300">{ "Token" }</label>
+                    <input
+                        type="text"
+                        value={(*token).clone()}
+                        placeholder="Contract address, or eth for ether"
+                        oninput={on_token_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+            </div>
+            <div class="flex gap-4 items-center mt-2">
+                <button type="submit" class="bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-2 px-4 font-bold">
+                    { "Apply Filters" }
+                </button>
+                <button type="button" class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_clear}>
+                    { "Clear" }
+                </button>
+            </div>
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400 mt-2">{ error }</p>
+            }
+        </form>
+    }
+}
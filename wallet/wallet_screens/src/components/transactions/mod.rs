@@ -1,37 +1,78 @@
+mod export_form;
+mod filter_form;
+
+use app_config::AppRoute;
+pub use export_form::TransactionExport;
+pub use filter_form::TransactionFilters;
+use gloo_console as console;
+use wallet_api::TransactionQuery;
 use yew::prelude::*;
+use yew_router::prelude::*;
 use yewdux::prelude::*;
 
-// TODO: Refactor
-use crate::store::{WalletAction, WalletStore};
+use crate::{
+    components::common::{counterparties, TransactionRow},
+    store::{WalletAction, WalletStore},
+};
 
+/// The transaction history. Its filters and page are kept in the query
+/// string, e.g. `/transactions?direction=in&after=...`, so that they survive
+/// reloads and can be linked to.
 #[function_component(Transactions)]
 pub fn transactions() -> Html {
     let (state, dispatch) = use_store::<WalletStore>();
-    let current_page = use_state(|| 1);
-    let transactions_per_page = 10;
+    let navigator = use_navigator();
+    let query = use_location()
+        .and_then(|location| location.query::<TransactionQuery>().ok())
+        .unwrap_or_default();
 
     {
         let dispatch = dispatch.clone();
-        use_effect_with((), move |_| {
-            dispatch.apply(WalletAction::RefreshTransactions);
+        use_effect_with(query.clone(), move |query| {
+            dispatch.apply(WalletAction::QueryTransactions(query.clone()));
+            || ()
+        });
+    }
+    {
+        let counterparties = counterparties(state.transactions.iter());
+        let dispatch = dispatch.clone();
+        use_effect_with(counterparties, move |counterparties| {
+            dispatch.apply(WalletAction::LookupEnsNames(counterparties.clone()));
             || ()
         });
     }
 
-    let total_pages = 0; /* // TODO: state.wallet.as_ref().map_or(0, |wallet| {
-                             (wallet.total_transactions as f64 / transactions_per_page as f64).ceil() as usize
-                         });*/
-
-    let paginated_transactions = state.wallet.as_ref().map(|_wallet| {
-        let start = ((*current_page - 1) * transactions_per_page) as usize;
-        let end = start + transactions_per_page;
-        state
-            .transactions
-            .iter()
-            .skip(start)
-            .take(end - start)
-            .collect::<Vec<_>>()
+    let navigate = Callback::from(move |query: TransactionQuery| {
+        if let Some(navigator) = &navigator {
+            if let Err(e) = navigator.push_with_query(&AppRoute::Transactions, &query) {
+                console::error!(format!("Failed to navigate: {:?}", e));
+            }
+        }
     });
+    let on_previous = {
+        let navigate = navigate.clone();
+        let query = query.clone();
+        let previous = state.transactions_previous.clone();
+        Callback::from(move |_| {
+            navigate.emit(TransactionQuery {
+                after: None,
+                before: previous.clone(),
+                ..query.clone()
+            })
+        })
+    };
+    let on_next = {
+        let navigate = navigate.clone();
+        let query = query.clone();
+        let next = state.transactions_next.clone();
+        Callback::from(move |_| {
+            navigate.emit(TransactionQuery {
+                after: next.clone(),
+                before: None,
+                ..query.clone()
+            })
+        })
+    };
 
     let format = "%Y-%m-%d %H:%M:%S";
 
@@ -39,62 +80,70 @@ pub fn transactions() -> Html {
         <div class="flex-1">
             <div class="bg-indigo-800 rounded-xl p-6">
                 <h2 class="text-xl font-bold mb-4">{ "Transaction History" }</h2>
-                {
-                    match &state.wallet {
-                        Some(wallet) => html! {
-                            <>
-                                <div class="mb-4">
-                                    <p class="text-lg">{ format!("Total Transactions: {}", state.transactions.len()) }</p>
-                                </div>
-                                <table class="w-full text-sm">
-                                    <thead>
-                                        <tr class="text-left text-indigo-300">
-                                            <th class="pb-2">{ "Date" }</th>
-                                            <th class="pb-2">{ "Amount (ETH)" }</th>
-                                            <th class="pb-2">{ "Recipient" }</th>
-                                        </tr>
-                                    </thead>
-                                    <tbody>
-                                        {
-                                            paginated_transactions.as_ref().map(|transactions| {
-                                                transactions.iter().map(|tx| {
-                                                    html! {
-                                                        <tr class="border-t border-indigo-700">
-                                                            <td class="py-2">{ tx.date.format(format).to_string() }</td>
-                                                            <td class="py-2">{ format!("{:.4}", (tx.amount.as_u128() as f64) / (10f64.powi(wallet.token_decimals as i32))) }</td>
-                                                            <td class="py-2">{ &tx.recipient[0..10] }{ "..." }</td>
-                                                        </tr>
-                                                    }
-                                                }).collect::<Html>()
-                                            }).unwrap_or_default()
-                                        }
-                                    </tbody>
-                                </table>
-                                <div class="mt-4 flex justify-between items-center">
-                                    <button
-                                        class="bg-indigo-600 text-white px-4 py-2 rounded-full disabled:opacity-50"
-                                        disabled={*current_page == 1}
-                                        onclick={let current_page = current_page.clone(); Callback::from(move |_| current_page.set(*current_page - 1))}
-                                    >
-                                        { "Previous" }
-                                    </button>
-                                    <span>{ format!("Page {} of {}", *current_page, total_pages) }</span>
-                                    <button
-                                        class="bg-indigo-600 text-white px-4 py-2 rounded-full disabled:opacity-50"
-                                        disabled={*current_page == total_pages}
-                                        onclick={let current_page = current_page.clone(); Callback::from(move |_| current_page.set(*current_page + 1))}
-                                    >
-                                        { "Next" }
-                                    </button>
-                                </div>
-                            </>
-                        },
-                        None => html! {
-                            <div class="flex flex-col items-center justify-center h-full">
-                                <p class="text-lg text-indigo-300">{ "No wallet initialized. Please create or import a wallet to view transactions." }</p>
-                            </div>
-                        }
+                if state.wallet.is_some() {
+                    <div class="mb-4">
+                        <p class="text-lg">
+                            {
+                                if query.is_filtered() {
+                                    format!("Matching Transactions: {}", state.transactions_total)
+                                } else {
+                                    format!("Total Transactions: {}", state.transactions_total)
+                                }
+                            }
+                        </p>
+                    </div>
+                    <TransactionFilters
+                        key={format!("{:?}", query.first_page())}
+                        query={query.clone()}
+                        on_apply={navigate}
+                    />
+                    <TransactionExport />
+                    if let Some(error) = &state.last_error {
+                        <p class="text-sm text-red-400 mb-4">{ &error.message }</p>
                     }
+                    <table class="w-full text-sm">
+                        <thead>
+                            <tr class="text-left text-indigo-300">
+                                <th class="pb-2">{ "Date" }</th>
+                                <th class="pb-2">{ "Amount" }</th>
+                                <th class="pb-2">{ "Counterparty" }</th>
+                                <th class="pb-2">{ "Status" }</th>
+                                <th class="pb-2">{ "Fee (ETH)" }</th>
+                            </tr>
+                        </thead>
+                        <tbody>
+                            { for state.transactions.iter().map(|tx| html! {
+                                <TransactionRow transaction={tx.clone()} date_format={format} />
+                            }) }
+                            if state.transactions.is_empty() {
+                                <tr>
+                                    <td colspan="5" class="py-2 text-center text-indigo-300">
+                                        { "No transactions found." }
+                                    </td>
+                                </tr>
+                            }
+                        </tbody>
+                    </table>
+                    <div class="mt-4 flex justify-between items-center">
+                        <button
+                            class="bg-indigo-600 text-white px-4 py-2 rounded-full disabled:opacity-50"
+                            disabled={state.transactions_previous.is_none()}
+                            onclick={on_previous}
+                        >
+                            { "Previous" }
+                        </button>
+                        <button
+                            class="bg-indigo-600 text-white px-4 py-2 rounded-full disabled:opacity-50"
+                            disabled={state.transactions_next.is_none()}
+                            onclick={on_next}
+                        >
+                            { "Next" }
+                        </button>
+                    </div>
+                } else {
+                    <div class="flex flex-col items-center justify-center h-full">
+                        <p class="text-lg text-indigo-300">{ "No wallet initialized. Please create or import a wallet to view transactions." }</p>
+                    </div>
                 }
             </div>
         </div>
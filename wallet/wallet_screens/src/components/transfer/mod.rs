@@ -1,8 +1,16 @@
+mod payment_request_form;
+mod pending_transactions;
 mod receive_form;
+mod recipient_input;
 mod send_form;
+pub use payment_request_form::PaymentRequestForm;
+pub use pending_transactions::PendingTransactions;
 pub use receive_form::ReceiveForm;
+pub use recipient_input::RecipientInput;
 pub use send_form::SendForm;
+use serde::Deserialize;
 use yew::prelude::*;
+use yew_router::prelude::*;
 use yewdux::prelude::*;
 
 use crate::store::WalletStore;
@@ -13,9 +21,19 @@ enum TransferView {
     Receive,
 }
 
+/// Query of links to the transfer screen, e.g. `/transfer?uri=ethereum:...`.
+#[derive(Deserialize)]
+struct TransferQuery {
+    uri: Option<String>,
+}
+
 #[function_component(Transfer)]
 pub fn transfer() -> Html {
     let (_state, _dispatch) = use_store::<WalletStore>();
+    let uri = use_location()
+        .and_then(|location| location.query::<TransferQuery>().ok())
+        .and_then(|query| query.uri)
+        .map(AttrValue::from);
 
     let active_tab = use_state(|| TransferView::Send);
 
@@ -52,7 +70,7 @@ pub fn transfer() -> Html {
             </div>
             {
                 match *active_tab {
-                    TransferView::Send => html! { <SendForm /> },
+                    TransferView::Send => html! { <SendForm uri={uri.clone()} /> },
                     TransferView::Receive => html! { <ReceiveForm /> },
                 }
             }
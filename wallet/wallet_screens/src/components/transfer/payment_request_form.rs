@@ -0,0 +1,197 @@
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    amount::{parse_amount, to_decimal, ETHER_DECIMALS},
+    CreatePaymentRequest,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    services::browser::number_locale,
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct PaymentRequestFormProps {
+    /// Account the payment is requested to.
+    pub address: AttrValue,
+    pub on_done: Callback<()>,
+}
+
+/// Asks for a payment of an optional amount of ether or a token. The amount
+/// can be left empty to let the payer choose it.
+#[function_component(PaymentRequestForm)]
+pub fn payment_request_form(props: &PaymentRequestFormProps) -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+
+    let amount = use_state(String::new);
+    let token = use_state(String::new);
+    let token_decimals = use_state(|| ETHER_DECIMALS.to_string());
+    let chain_id = use_state(String::new);
+    let memo = use_state(String::new);
+    let error = use_state(|| None::<String>);
+
+    let input = |state: &UseStateHandle<String>| {
+        let state = state.clone();
+        Callback::from(move |e: InputEvent| {
+            state.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_amount_input = input(&amount);
+    let on_token_input = input(&token);
+    let on_token_decimals_input = input(&token_decimals);
+    let on_chain_id_input = input(&chain_id);
+    let on_memo_input = input(&memo);
+
+    let on_submit = {
+        let amount = amount.clone();
+        let token = token.clone();
+        let token_decimals = token_decimals.clone();
+        let chain_id = chain_id.clone();
+        let memo = memo.clone();
+        let error = error.clone();
+        let address = props.address.to_string();
+        let on_done = props.on_done.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+
+            let token = match token.trim() {
+                "" => None,
+                token => match parse_address(token) {
+                    Ok(token) => Some(checksum_address(&token)),
+                    Err(e) => {
+                        error.set(Some(format!("Token: {}", e)));
+                        return;
+                    }
+                },
+            };
+            let decimals = if token.is_some() {
+                match token_decimals.trim().parse::<i16>() {
+                    Ok(decimals) if (0..=28).contains(&decimals) => decimals,
+                    _ => {
+                        error.set(Some("Token decimals must be 0 to 28".to_string()));
+                        return;
+                    }
+                }
+            } else {
+                ETHER_DECIMALS
+            };
+            let amount = match amount.trim() {
+                "" => None,
+                amount => match parse_amount(amount, decimals, number_locale())
+                    .and_then(|amount| to_decimal(amount, decimals))
+                {
+                    Ok(amount) if !amount.is_zero() => Some(amount),
+                    Ok(_) => {
+                        error.set(Some("Amount must be positive".to_string()));
+                        return;
+                    }
+                    Err(e) => {
+                        error.set(Some(e.to_string()));
+                        return;
+                    }
+                },
+            };
+            let chain_id = match chain_id.trim() {
+                "" => None,
+                chain_id => match chain_id.parse::<i64>() {
+                    Ok(chain_id) if chain_id > 0 => Some(chain_id),
+                    _ => {
+                        error.set(Some("Chain id must be a positive number".to_string()));
+                        return;
+                    }
+                },
+            };
+            let memo = Some(memo.trim().to_string()).filter(|memo| !memo.is_empty());
+
+            error.set(None);
+            dispatch.apply(WalletAction::CreatePaymentRequest(CreatePaymentRequest {
+                address: address.clone(),
+                token,
+                token_decimals: decimals,
+                chain_id,
+                amount,
+                memo,
+            }));
+            on_done.emit(());
+        })
+    };
+    let on_cancel = {
+        let on_done = props.on_done.clone();
+        Callback::from(move |_| on_done.emit(()))
+    };
+
+    let is_token = !token.trim().is_empty();
+
+    html! {
+        <form onsubmit={on_submit} class="space-y-4">
+            <div>
+                <label class="block mb-2">{ "Amount" }</label>
+                <input
+                    type="text"
+                    value={(*amount).clone()}
+                    placeholder={if is_token { "Leave empty to let the payer choose" } else { "ETH, leave empty to let the payer choose" }}
+                    oninput={on_amount_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            <div class="flex gap-2">
+                <div class="flex-1">
+                    <label class="block mb-2">{ "Token contract (optional)" }</label>
+                    <input
+                        type="text"
+                        value={(*token).clone()}
+                        placeholder="Empty for ETH"
+                        oninput={on_token_input}
+                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                    />
+                </div>
+                if is_token {
+                    <div class="w-28">
+                        <label class="block mb-2">{ "Decimals" }</label>
+                        <input
+                            type="number"
+                            value={(*token_decimals).clone()}
+                            oninput={on_token_decimals_input}
+                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                        />
+                    </div>
+                }
+            </div>
+            <div>
+                <label class="block mb-2">{ "Chain id (optional)" }</label>
+                <input
+                    type="text"
+                    value={(*chain_id).clone()}
+                    placeholder="e.g. 1 for Ethereum Mainnet"
+                    oninput={on_chain_id_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            <div>
+                <label class="block mb-2">{ "Memo (optional)" }</label>
+                <input
+                    type="text"
+                    value={(*memo).clone()}
+                    maxlength="200"
+                    oninput={on_memo_input}
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+            </div>
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400">{ error }</p>
+            }
+            <div class="flex gap-2">
+                <button type="submit" class="flex-1 bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold">
+                    { "Create Request" }
+                </button>
+                <button type="button" class="px-4 text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel}>
+                    { "Cancel" }
+                </button>
+            </div>
+        </form>
+    }
+}
@@ -0,0 +1,90 @@
+use wallet_api::amount::ETHER_DECIMALS;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::display_amount_exact,
+    services::transactions::{PendingTransaction, TransactionStatus},
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Properties, PartialEq)]
+struct PendingTransactionRowProps {
+    pub transaction: PendingTransaction,
+}
+
+#[function_component(PendingTransactionRow)]
+fn pending_transaction_row(props: &PendingTransactionRowProps) -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let tx = &props.transaction;
+
+    let on_dismiss = {
+        let id = tx.id;
+        Callback::from(move |_| {
+            dispatch.apply(WalletAction::DismissTransaction(id));
+        })
+    };
+
+    let status = match &tx.status {
+        TransactionStatus::Confirmed {
+            tx_hash,
+            block_number,
+        } => html! {
+            <>
+                <div class="text-green-300">{ format!("Confirmed in block {}", block_number) }</div>
+                <div class="text-xs text-indigo-300 truncate">{ tx_hash.to_string() }</div>
+            </>
+        },
+        TransactionStatus::Failed { error } => html! {
+            <div class="text-red-300">{ format!("Failed: {}", error) }</div>
+        },
+        status => html! {
+            <div class="flex items-center space-x-2">
+                <div class="animate-spin rounded-full h-4 w-4 border-t-2 border-b-2 border-indigo-300"></div>
+                <span>{ status.to_string() }</span>
+            </div>
+        },
+    };
+
+    html! {
+        <div class="border-t border-indigo-700 py-3 flex justify-between items-center">
+            <div class="flex-1 min-w-0">
+                <div class="mb-1">
+                    if tx.data.is_empty() {
+                        { format!("{} ETH to {}", display_amount_exact(tx.amount, ETHER_DECIMALS), tx.to) }
+                    } else {
+                        { format!("Call to {} with {} ETH", tx.to, display_amount_exact(tx.amount, ETHER_DECIMALS)) }
+                    }
+                    if let Some(nonce) = tx.nonce {
+                        <span class="text-xs text-indigo-300 ml-2">{ format!("nonce {}", nonce) }</span>
+                    }
+                </div>
+                { status }
+            </div>
+            if tx.status.is_complete() {
+                <button
+                    onclick={on_dismiss}
+                    class="text-indigo-300 hover:text-indigo-200 transition-colors ml-4"
+                >
+                    { "Dismiss" }
+                </button>
+            }
+        </div>
+    }
+}
+
+#[function_component(PendingTransactions)]
+pub fn pending_transactions() -> Html {
+    let (state, _) = use_store::<WalletStore>();
+
+    html! {
+        <div class="mt-6">
+            <h3 class="text-lg font-bold mb-2">{ "Outgoing Transactions" }</h3>
+            {
+                state.pending_transactions.iter().rev().map(|tx| {
+                    html! { <PendingTransactionRow key={tx.id.to_string()} transaction={tx.clone()} /> }
+                }).collect::<Html>()
+            }
+        </div>
+    }
+}
@@ -1,54 +1,236 @@
-use qrcode_generator::QrCodeEcc;
+use uuid::Uuid;
+use wallet_api::{amount::format_amount_exact, PaymentRequest, PaymentUri};
 use yew::prelude::*;
 use yewdux::prelude::*;
 
-use crate::store::WalletStore;
+use super::PaymentRequestForm;
+use crate::{
+    components::common::{short_address, QrCode},
+    services::browser::{copy_to_clipboard, number_locale, payment_link},
+    store::{WalletAction, WalletStore},
+};
+
+/// The requested amount with its unit, e.g. `1.5 ETH`.
+fn describe_amount(request: &PaymentRequest) -> String {
+    let unit = match &request.token {
+        Some(token) => short_address(token),
+        None => "ETH".to_string(),
+    };
+    match request.to_uri().amount {
+        Some(amount) => format!(
+            "{} {}",
+            format_amount_exact(amount, request.token_decimals, number_locale())
+                .unwrap_or_default(),
+            unit
+        ),
+        None => format!("Any amount of {}", unit),
+    }
+}
 
 #[function_component(ReceiveForm)]
 pub fn receive_form() -> Html {
-    let (state, _) = use_store::<WalletStore>();
-
-    match &state.wallet {
-        Some(wallet) => {
-            let address = wallet.address.clone();
-
-            let qr =
-                qrcode_generator::to_svg_to_string(&address, QrCodeEcc::Low, 256, None::<&str>)
-                    .unwrap_or_else(|_| String::from("Failed to generate QR code"));
-
-            let qr_dom = Html::from_html_unchecked(AttrValue::from(qr));
-
-            html! {
-                <div>
-                    <div class="mb-4 flex justify-center">
-                        <div class="bg-white p-4 rounded-lg shadow-md mb-4">
-                            { qr_dom }
-                        </div>
-                    </div>
-                    <div class="mb-4">
-                        <label class="block mb-2">{ "Your wallet address" }</label>
-                        <input
-                            type="text"
-                            value={address.clone()}
-                            readonly=true
-                            class="w-full bg-indigo-700 rounded-lg py-2 px-4"
-                        />
-                        <p class="text-xs mt-1 text-indigo-300">
-                            { "Share this address to receive funds" }
-                        </p>
-                    </div>
-                </div>
+    let (state, dispatch) = use_store::<WalletStore>();
+    let show_form = use_state(|| false);
+    let selected = use_state(|| None::<Uuid>);
+    // Set while a new request is being saved, so that it is shown once it arrives
+    let awaiting_request = use_state(|| false);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadPaymentRequests);
+            || ()
+        });
+    }
+    {
+        let selected = selected.clone();
+        let awaiting_request = awaiting_request.clone();
+        let newest = state.payment_requests.first().map(|request| request.id);
+        use_effect_with(newest, move |newest| {
+            if *awaiting_request && newest.is_some() {
+                selected.set(*newest);
+                awaiting_request.set(false);
             }
+            || ()
+        });
+    }
+
+    let Some(account) = state.selected_account() else {
+        return html! {
+            <div class="flex flex-col items-center space-y-4">
+                <h2 class="text-2xl font-bold">{ "Receive" }</h2>
+                <p class="text-lg">
+                    { "No wallet initialized. Please create or import a wallet." }
+                </p>
+            </div>
+        };
+    };
+    let address = account.address.clone();
+
+    let shown_request = (*selected).and_then(|id| {
+        state
+            .payment_requests
+            .iter()
+            .find(|request| request.id == id)
+    });
+    let uri = match shown_request {
+        Some(request) => request.to_uri().to_uri(),
+        None => PaymentUri {
+            address: address.clone(),
+            ..Default::default()
         }
-        None => {
-            html! {
-                <div class="flex flex-col items-center space-y-4">
-                    <h2 class="text-2xl font-bold">{ "Receive" }</h2>
-                    <p class="text-lg">
-                        { "No wallet initialized. Please create or import a wallet." }
-                    </p>
+        .to_uri(),
+    };
+    let requests: Vec<_> = state
+        .payment_requests
+        .iter()
+        .filter(|request| request.address.eq_ignore_ascii_case(&address))
+        .collect();
+
+    let on_request_click = {
+        let show_form = show_form.clone();
+        let dispatch = dispatch.clone();
+        Callback::from(move |_| {
+            dispatch.apply(WalletAction::ClearErrorMessage);
+            show_form.set(true);
+        })
+    };
+    let on_form_done = {
+        let show_form = show_form.clone();
+        let awaiting_request = awaiting_request.clone();
+        Callback::from(move |_| {
+            show_form.set(false);
+            awaiting_request.set(true);
+        })
+    };
+    let on_show_address = {
+        let selected = selected.clone();
+        Callback::from(move |_| selected.set(None))
+    };
+    let on_copy_uri = {
+        let uri = uri.clone();
+        Callback::from(move |_| copy_to_clipboard(&uri))
+    };
+    let on_copy_link = {
+        let uri = uri.clone();
+        Callback::from(move |_| {
+            if let Some(link) = payment_link(&uri) {
+                copy_to_clipboard(&link);
+            }
+        })
+    };
+
+    html! {
+        <div>
+            <div class="mb-4 flex justify-center">
+                <QrCode data={uri.clone()} size={Some(256)} />
+            </div>
+            if let Some(request) = shown_request {
+                <div class="mb-4 text-center">
+                    <p class="font-bold">{ describe_amount(request) }</p>
+                    if let Some(memo) = &request.memo {
+                        <p class="text-sm text-indigo-300">{ memo }</p>
+                    }
+                    if let Some(chain_id) = request.chain_id {
+                        <p class="text-xs text-indigo-300">{ format!("On chain {}", chain_id) }</p>
+                    }
+                    <button class="text-sm text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_show_address}>
+                        { "Show plain address" }
+                    </button>
                 </div>
             }
-        }
+            <div class="mb-4">
+                <label class="block mb-2">{ "Your wallet address" }</label>
+                <input
+                    type="text"
+                    value={address.clone()}
+                    readonly=true
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                />
+                <p class="text-xs mt-1 text-indigo-300">
+                    { "Share this address to receive funds" }
+                </p>
+            </div>
+            <div class="mb-4">
+                <label class="block mb-2">{ "Payment link" }</label>
+                <input
+                    type="text"
+                    value={uri}
+                    readonly=true
+                    class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono text-sm"
+                />
+                <div class="flex gap-4 mt-1 text-sm">
+                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_copy_uri}>
+                        { "Copy ethereum: link" }
+                    </button>
+                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_copy_link}>
+                        { "Copy app link" }
+                    </button>
+                </div>
+            </div>
+            if *show_form {
+                <div class="mb-4">
+                    <PaymentRequestForm address={address.clone()} on_done={on_form_done} />
+                </div>
+            } else {
+                <button
+                    class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold mb-4"
+                    onclick={on_request_click}
+                >
+                    { "Request a Payment" }
+                </button>
+            }
+            if let Some(error) = &state.last_error {
+                <p class="text-sm text-red-400 mb-4">{ &error.message }</p>
+            }
+            if !requests.is_empty() {
+                <h3 class="font-bold mb-2">{ "Payment requests" }</h3>
+                <ul class="divide-y divide-indigo-700">
+                    { for requests.into_iter().map(|request| {
+                        let on_show = {
+                            let selected = selected.clone();
+                            let id = request.id;
+                            Callback::from(move |_| selected.set(Some(id)))
+                        };
+                        let on_cancel = {
+                            let dispatch = dispatch.clone();
+                            let id = request.id;
+                            Callback::from(move |_| dispatch.apply(WalletAction::CancelPaymentRequest(id)))
+                        };
+                        let status_class = match request.status.as_str() {
+                            PaymentRequest::PAID => "text-green-400",
+                            PaymentRequest::CANCELLED => "text-indigo-400",
+                            _ => "text-yellow-300",
+                        };
+                        html! {
+                            <li class="py-3 flex items-start justify-between gap-4">
+                                <div class="min-w-0">
+                                    <p class="font-medium">
+                                        { describe_amount(request) }
+                                        <span class={classes!("ml-2", "text-xs", status_class)}>{ &request.status }</span>
+                                    </p>
+                                    if let Some(memo) = &request.memo {
+                                        <p class="text-sm text-indigo-300 truncate">{ memo }</p>
+                                    }
+                                    if let Some(tx_hash) = &request.paid_tx_hash {
+                                        <p class="text-xs text-indigo-300 font-mono">{ format!("Paid in {}", short_address(tx_hash)) }</p>
+                                    }
+                                </div>
+                                <div class="flex gap-4 shrink-0">
+                                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_show}>
+                                        { "Show" }
+                                    </button>
+                                    if request.is_open() {
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_cancel}>
+                                            { "Cancel" }
+                                        </button>
+                                    }
+                                </div>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            }
+        </div>
     }
 }
@@ -0,0 +1,94 @@
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{components::common::short_address, store::WalletStore};
+
+/// Contacts suggested at most while typing a recipient.
+const MAX_SUGGESTIONS: usize = 5;
+
+#[derive(Properties, PartialEq)]
+pub struct RecipientInputProps {
+    pub value: AttrValue,
+    pub oninput: Callback<String>,
+}
+
+/// Address input that suggests contacts whose name or address matches what
+/// has been typed. Picking a suggestion fills in its address.
+#[function_component(RecipientInput)]
+pub fn recipient_input(props: &RecipientInputProps) -> Html {
+    let (state, _) = use_store::<WalletStore>();
+    let focused = use_state(|| false);
+
+    let query = props.value.trim().to_lowercase();
+    let suggestions: Vec<_> = if *focused && !query.is_empty() {
+        state
+            .contacts
+            .iter()
+            .filter(|contact| {
+                contact.name.to_lowercase().contains(&query)
+                    || contact.address.to_lowercase().starts_with(&query)
+            })
+            .filter(|contact| contact.address.to_lowercase() != query)
+            .take(MAX_SUGGESTIONS)
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let on_input = {
+        let oninput = props.oninput.clone();
+        Callback::from(move |e: InputEvent| {
+            oninput.emit(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_focus = {
+        let focused = focused.clone();
+        Callback::from(move |_: FocusEvent| focused.set(true))
+    };
+    let on_blur = {
+        let focused = focused.clone();
+        Callback::from(move |_: FocusEvent| focused.set(false))
+    };
+
+    html! {
+        <div class="relative">
+            <input
+                type="text"
+                placeholder="0x..., name.eth, contact name or ethereum: link"
+                autocomplete="off"
+                value={props.value.clone()}
+                oninput={on_input}
+                onfocus={on_focus}
+                onblur={on_blur}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+            />
+            if !suggestions.is_empty() {
+                <ul class="absolute left-0 right-0 mt-1 bg-indigo-700 rounded-lg shadow-lg overflow-hidden z-10">
+                    { for suggestions.into_iter().map(|contact| {
+                        // Mouse down fires before the input loses focus
+                        let on_pick = {
+                            let oninput = props.oninput.clone();
+                            let address = contact.address.clone();
+                            Callback::from(move |e: MouseEvent| {
+                                e.prevent_default();
+                                oninput.emit(address.clone());
+                            })
+                        };
+                        html! {
+                            <li
+                                class="px-4 py-2 cursor-pointer hover:bg-indigo-600 flex justify-between"
+                                onmousedown={on_pick}
+                            >
+                                <span class="font-medium">{ &contact.name }</span>
+                                <span class="text-indigo-300">{ short_address(&contact.address) }</span>
+                            </li>
+                        }
+                    }) }
+                </ul>
+            }
+        </div>
+    }
+}
@@ -1,26 +1,135 @@
-use ethers::{types::H160, utils::parse_ether};
+use gloo_timers::callback::Timeout;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    amount::{format_amount_exact, parse_amount, ETHER_DECIMALS},
+    ens::{is_ens_name, normalize_name},
+    PaymentUri,
+};
 use yew::prelude::*;
 use yewdux::prelude::*;
 
+use super::{PendingTransactions, RecipientInput};
 use crate::{
-    services::transactions::status::TransactionStatus,
+    services::browser::number_locale,
     store::{WalletAction, WalletStore},
 };
 
+/// A name is only resolved once the user stopped typing for this long.
+const ENS_RESOLVE_DELAY_MS: u32 = 400;
+
+#[derive(Properties, PartialEq)]
+pub struct SendFormProps {
+    /// EIP-681 payment link to fill the form from, e.g. from a shared link.
+    #[prop_or_default]
+    pub uri: Option<AttrValue>,
+}
+
 #[function_component(SendForm)]
-pub fn send_form() -> Html {
+pub fn send_form(props: &SendFormProps) -> Html {
     let (state, dispatch) = use_store::<WalletStore>();
 
     let recipient = use_state(String::new);
     let amount = use_state(String::new);
     let password = use_state(String::new);
+    let error = use_state(|| None::<String>);
+    // ENS names and addresses never sent to before need a second click
+    let confirmed_recipient = use_state(|| false);
+    // Payment request the form was filled from
+    let payment_request = use_state(|| None::<PaymentUri>);
+    // The server signs for custodial accounts, so no password is needed
+    let is_custodial = state
+        .selected_account()
+        .is_some_and(|account| account.custodial);
 
-    let on_new_transaction = {
+    let apply_uri = {
+        let recipient = recipient.clone();
+        let amount = amount.clone();
+        let error = error.clone();
+        let confirmed_recipient = confirmed_recipient.clone();
+        let payment_request = payment_request.clone();
+        Callback::from(move |uri: String| {
+            let request = match uri.parse::<PaymentUri>() {
+                Ok(request) => request,
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    return;
+                }
+            };
+            confirmed_recipient.set(false);
+            if request.token.is_some() {
+                error.set(Some("Token payments are not supported yet".to_string()));
+                payment_request.set(None);
+                return;
+            }
+
+            recipient.set(request.address.clone());
+            if let Some(requested) = request.amount {
+                amount.set(
+                    format_amount_exact(requested, ETHER_DECIMALS, number_locale())
+                        .unwrap_or_default(),
+                );
+            }
+            error.set(None);
+            payment_request.set(Some(request));
+        })
+    };
+    {
+        let apply_uri = apply_uri.clone();
+        use_effect_with(props.uri.clone(), move |uri| {
+            if let Some(uri) = uri {
+                apply_uri.emit(uri.to_string());
+            }
+            || ()
+        });
+    }
+
+    let ens_name = is_ens_name(&recipient).then(|| normalize_name(&recipient));
+    {
         let dispatch = dispatch.clone();
-        Callback::from(move |_| {
-            dispatch.apply(WalletAction::UpdateTransactionStatus(
-                TransactionStatus::None,
-            ));
+        use_effect_with(ens_name.clone(), move |name| {
+            let timeout = name.clone().map(|name| {
+                Timeout::new(ENS_RESOLVE_DELAY_MS, move || {
+                    dispatch.apply(WalletAction::ResolveEnsName(name))
+                })
+            });
+            move || drop(timeout)
+        });
+    }
+
+    let (recipient_address, recipient_error) = match &ens_name {
+        Some(name) => match state.ens_addresses.get(name) {
+            Some(Ok(address)) => (parse_address(address).ok(), None),
+            Some(Err(e)) => (None, Some(e.clone())),
+            None => (None, None),
+        },
+        None => match parse_address(&recipient) {
+            Ok(address) => (Some(address), None),
+            Err(e) if !recipient.trim().is_empty() => (None, Some(e.to_string())),
+            Err(_) => (None, None),
+        },
+    };
+    let is_resolving =
+        ens_name.is_some() && recipient_address.is_none() && recipient_error.is_none();
+    let recipient_name = recipient_address
+        .and_then(|address| state.name_for(&checksum_address(&address)))
+        .map(str::to_string);
+    let is_new_recipient = recipient_address
+        .is_some_and(|address| !state.is_known_recipient(&checksum_address(&address)));
+    let needs_confirmation = ens_name.is_some() || is_new_recipient;
+
+    let on_recipient_input = {
+        let recipient = recipient.clone();
+        let confirmed_recipient = confirmed_recipient.clone();
+        let payment_request = payment_request.clone();
+        Callback::from(move |value: String| {
+            // A pasted payment link fills in the whole form
+            if value.trim_start().starts_with("ethereum:") {
+                apply_uri.emit(value);
+                return;
+            }
+            recipient.set(value);
+            confirmed_recipient.set(false);
+            payment_request.set(None);
         })
     };
 
@@ -28,55 +137,117 @@ pub fn send_form() -> Html {
         let recipient = recipient.clone();
         let amount = amount.clone();
         let password = password.clone();
+        let error = error.clone();
+        let confirmed_recipient = confirmed_recipient.clone();
+        let payment_request = payment_request.clone();
+        let recipient_error = recipient_error.clone();
         let dispatch = dispatch.clone();
         Callback::from(move |e: MouseEvent| {
             e.prevent_default();
-            if let Ok(amount_value) = amount.parse::<f64>() {
-                let wei_amount = parse_ether(amount_value.to_string()).unwrap();
-
-                if let Ok(recipient_address) = recipient.parse::<H160>() {
-                    dispatch.apply(WalletAction::SendTransaction {
-                        to: recipient_address,
-                        amount: wei_amount,
-                        password: (*password).clone(),
-                    });
-                    recipient.set(String::new());
-                    amount.set(String::new());
-                    password.set(String::new());
+            let Some(recipient_address) = recipient_address else {
+                error.set(Some(recipient_error.clone().unwrap_or_else(|| {
+                    if is_resolving {
+                        "Wait for the name to resolve".to_string()
+                    } else {
+                        "Enter an address".to_string()
+                    }
+                })));
+                return;
+            };
+            let wei_amount = match parse_amount(&amount, ETHER_DECIMALS, number_locale()) {
+                Ok(wei_amount) => wei_amount,
+                Err(e) => {
+                    error.set(Some(e.to_string()));
+                    return;
                 }
+            };
+            if needs_confirmation && !*confirmed_recipient {
+                error.set(None);
+                confirmed_recipient.set(true);
+                return;
             }
+
+            dispatch.apply(WalletAction::SendTransaction {
+                to: recipient_address,
+                amount: wei_amount,
+                data: Default::default(),
+                password: (*password).clone(),
+            });
+            recipient.set(String::new());
+            amount.set(String::new());
+            password.set(String::new());
+            error.set(None);
+            confirmed_recipient.set(false);
+            payment_request.set(None);
         })
     };
 
-    match &state.transaction_status {
-        TransactionStatus::None => html! {
-            <div>
-                <div class="mb-4">
-                    <label class="block mb-2">{ "Pay to" }</label>
-                    <input
-                        type="text"
-                        placeholder="0x..."
-                        value={(*recipient).clone()}
-                        oninput={Callback::from(move |e: InputEvent| recipient.set(e.target_unchecked_into::<web_sys::HtmlInputElement>().value()))}
-                        class="w-full bg-indigo-700 rounded-lg py-2 px-4"
-                    />
-                    <p class="text-xs mt-1 text-indigo-300">{ "Please enter the Wallet ID" }</p>
+    let submit_label = match (needs_confirmation && *confirmed_recipient, is_new_recipient) {
+        (true, true) => "Confirm sending to a new address",
+        (true, false) => "Confirm recipient",
+        (false, _) => "Send",
+    };
+
+    html! {
+        <div>
+            if let Some(request) = &*payment_request {
+                <div class="mb-4 bg-indigo-700 rounded-lg py-2 px-4 text-sm">
+                    <p class="font-medium">{ "Payment request" }</p>
+                    if let Some(memo) = &request.memo {
+                        <p class="text-indigo-300">{ memo }</p>
+                    }
+                    if let Some(chain_id) = request.chain_id {
+                        <p class="text-indigo-300">{ format!("Requested on chain {}", chain_id) }</p>
+                    }
                 </div>
-                <div class="flex mb-4">
-                    <div class="flex-1 mr-2">
-                        <label class="block mb-2">{ "Amount" }</label>
-                        <div class="w-full bg-indigo-700 rounded-lg py-2 px-4 flex items-center space-x-2">
-                            <span class="text-indigo-300">{ "$" }</span>
-                            <input
-                                type="text"
-                                value={(*amount).clone()}
-                                placeholder="Amount to send"
-                                oninput={Callback::from(move |e: InputEvent| amount.set(e.target_unchecked_into::<web_sys::HtmlInputElement>().value()))}
-                                class="bg-transparent flex-1 outline-none focus:outline-none"
-                            />
-                        </div>
+            }
+            <div class="mb-4">
+                <label class="block mb-2">{ "Pay to" }</label>
+                <RecipientInput value={(*recipient).clone()} oninput={on_recipient_input} />
+                {
+                    if let Some(recipient_error) = recipient_error {
+                        html! { <p class="text-xs mt-1 text-red-400">{ recipient_error }</p> }
+                    } else if is_resolving {
+                        html! { <p class="text-xs mt-1 text-indigo-300">{ "Resolving name…" }</p> }
+                    } else {
+                        html! {
+                            <>
+                                if let (Some(name), Some(address)) = (&ens_name, recipient_address) {
+                                    <p class="text-xs mt-1 text-indigo-300">
+                                        { format!("{} resolves to ", name) }
+                                        <span class="font-mono break-all text-white">{ checksum_address(&address) }</span>
+                                    </p>
+                                }
+                                if let Some(name) = &recipient_name {
+                                    <p class="text-xs mt-1 text-indigo-300">{ format!("Sending to {}", name) }</p>
+                                } else if is_new_recipient {
+                                    <p class="text-xs mt-1 text-yellow-300">
+                                        { "You have never sent to this address before. Check it carefully, or save it as a contact first." }
+                                    </p>
+                                } else if recipient_address.is_none() {
+                                    <p class="text-xs mt-1 text-indigo-300">{ "Enter an address, an ENS name or pick a contact" }</p>
+                                }
+                            </>
+                        }
+                    }
+                }
+            </div>
+            <div class="flex mb-4">
+                <div class="flex-1 mr-2">
+                    <label class="block mb-2">{ "Amount" }</label>
+                    <div class="w-full bg-indigo-700 rounded-lg py-2 px-4 flex items-center space-x-2">
+                        <span class="text-indigo-300">{ "ETH" }</span>
+                        <input
+                            type="text"
+                            value={(*amount).clone()}
+                            placeholder="Amount to send"
+                            oninput={Callback::from(move |e: InputEvent| amount.set(e.target_unchecked_into::<web_sys::HtmlInputElement>().value()))}
+                            class="bg-transparent flex-1 outline-none focus:outline-none"
+                        />
                     </div>
                 </div>
+            </div>
+            if !is_custodial {
                 <div class="mb-4">
                     <label class="block mb-2">{ "Password" }</label>
                     <input
@@ -87,55 +258,19 @@ pub fn send_form() -> Html {
                         placeholder="Enter your password"
                     />
                 </div>
-                <button
-                    onclick={on_submit}
-                    class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold"
-                >
-                    { "Send" }
-                </button>
-            </div>
-        },
-        TransactionStatus::Building
-        | TransactionStatus::Preparing
-        | TransactionStatus::Signing { .. }
-        | TransactionStatus::Submitting { .. } => html! {
-            <div class="flex items-center justify-center h-full">
-                <div class="animate-spin rounded-full h-32 w-32 border-t-2 border-b-2 border-indigo-300"></div>
-            </div>
-        },
-        TransactionStatus::Confirmed {
-            tx_hash,
-            block_number,
-        } => html! {
-            <div class="bg-indigo-800 rounded-xl p-6">
-                <h3 class="text-xl font-bold mb-4">{ "Transaction Receipt" }</h3>
-                <div class="mb-2">
-                    <span class="text-indigo-300">{ "Transaction Hash: " }</span>{ tx_hash.to_string() }
-                </div>
-                <div class="mb-2">
-                    <span class="text-indigo-300">{ "Block Number: " }</span>{ block_number.to_string() }
-                </div>
-                <button
-                    onclick={on_new_transaction}
-                    class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold"
-                >
-                    { "Send New Transaction" }
-                </button>
-            </div>
-        },
-        TransactionStatus::Failed { error } => html! {
-            <div class="bg-indigo-800 rounded-xl p-6">
-                <h3 class="text-xl font-bold mb-4">{ "Transaction Failed" }</h3>
-                <div class="mb-4">
-                    <span class="text-indigo-300">{ "Error: " }</span>{ error }
-                </div>
-                <button
-                    onclick={on_new_transaction}
-                    class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold"
-                >
-                    { "Try Again" }
-                </button>
-            </div>
-        },
+            }
+            if let Some(error) = &*error {
+                <p class="text-sm text-red-400 mb-4">{ error }</p>
+            }
+            <button
+                onclick={on_submit}
+                class="w-full bg-gradient-to-r from-pink-500 to-orange-400 rounded-lg py-3 font-bold"
+            >
+                { submit_label }
+            </button>
+            if !state.pending_transactions.is_empty() {
+                <PendingTransactions />
+            }
+        </div>
     }
 }
@@ -0,0 +1,90 @@
+use wallet_api::CreateTreasury;
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::store::{WalletAction, WalletStore};
+
+/// Lets an admin set up a treasury. Its key is generated by the server.
+#[function_component(CreateTreasuryForm)]
+pub fn create_treasury_form() -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let name = use_state(String::new);
+    let signers = use_state(String::new);
+    let threshold = use_state(|| "2".to_string());
+
+    let usernames: Vec<String> = signers
+        .split(',')
+        .map(|username| username.trim().to_string())
+        .filter(|username| !username.is_empty())
+        .collect();
+    let parsed_threshold = threshold.trim().parse::<i32>().ok().filter(|t| *t >= 1);
+    let threshold_error = match parsed_threshold {
+        None => Some("At least one approval must be required".to_string()),
+        Some(t) if !usernames.is_empty() && t as usize > usernames.len() => {
+            Some(format!("Only {} signers could approve", usernames.len()))
+        }
+        Some(_) => None,
+    };
+    let can_create = !name.trim().is_empty() && !usernames.is_empty() && threshold_error.is_none();
+
+    let on_input = |field: UseStateHandle<String>| {
+        Callback::from(move |e: InputEvent| {
+            field.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_create = {
+        let name = name.clone();
+        let signers = signers.clone();
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some(threshold) = parsed_threshold {
+                dispatch.apply(WalletAction::CreateTreasury(CreateTreasury {
+                    name: name.trim().to_string(),
+                    threshold,
+                    signers: usernames.clone(),
+                }));
+                name.set(String::new());
+                signers.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <form onsubmit={on_create} class="space-y-2">
+            <h3 class="font-semibold">{ "New treasury" }</h3>
+            <input
+                type="text"
+                value={(*name).clone()}
+                oninput={on_input(name.clone())}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                placeholder="Name"
+            />
+            <input
+                type="text"
+                value={(*signers).clone()}
+                oninput={on_input(signers.clone())}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                placeholder="Signers, as comma-separated staff usernames"
+            />
+            <label class="flex items-center gap-2 text-sm text-indigo-300">
+                { "Approvals required" }
+                <input
+                    type="number"
+                    min="1"
+                    value={(*threshold).clone()}
+                    oninput={on_input(threshold.clone())}
+                    class="w-20 bg-indigo-700 rounded-lg py-1 px-2 text-white"
+                />
+            </label>
+            if let Some(error) = threshold_error {
+                <p class="text-xs text-red-400">{ error }</p>
+            }
+            <button type="submit" disabled={!can_create} class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                { "Create treasury" }
+            </button>
+        </form>
+    }
+}
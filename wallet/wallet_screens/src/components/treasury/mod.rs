@@ -0,0 +1,250 @@
+mod create_form;
+mod proposal_form;
+
+use chrono::Local;
+pub use create_form::CreateTreasuryForm;
+pub use proposal_form::ProposalForm;
+use uuid::Uuid;
+use wallet_api::{TreasuryOverview, TreasuryProposal};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    components::common::{display_decimal, short_address, Counterparty},
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct TreasuryProps {
+    /// Admins can create treasuries.
+    pub is_admin: bool,
+}
+
+/// Name of a signer, or a placeholder once the user was deleted.
+fn signer_name(overview: &TreasuryOverview, user_id: Option<Uuid>) -> String {
+    user_id
+        .and_then(|id| overview.signers.iter().find(|signer| signer.user_id == id))
+        .map(|signer| {
+            signer
+                .name
+                .clone()
+                .unwrap_or_else(|| signer.username.clone())
+        })
+        .unwrap_or_else(|| "A former signer".to_string())
+}
+
+fn status_class(status: &str) -> &'static str {
+    match status {
+        TreasuryProposal::APPROVED | TreasuryProposal::EXECUTED => "text-green-400",
+        TreasuryProposal::REJECTED | TreasuryProposal::FAILED => "text-red-400",
+        _ => "text-yellow-400",
+    }
+}
+
+fn event_label(event: &str) -> &str {
+    match event {
+        "treasury_created" => "Treasury created",
+        "proposal_created" => "Send proposed",
+        "proposal_approved" => "Approved",
+        "proposal_rejected" => "Rejected",
+        "proposal_executed" => "Send executed",
+        "proposal_failed" => "Send failed",
+        event => event,
+    }
+}
+
+/// Shared wallets run by staff. Sends are proposed by a signer and only go
+/// out once enough signers approved them.
+#[function_component(Treasury)]
+pub fn treasury(props: &TreasuryProps) -> Html {
+    let (state, dispatch) = use_store::<WalletStore>();
+    let selected = use_state(|| None::<Uuid>);
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with((), move |_| {
+            dispatch.apply(WalletAction::LoadTreasuries);
+            || ()
+        });
+    }
+
+    let open = selected
+        .and_then(|id| state.treasuries.iter().find(|o| o.treasury.id == id))
+        .or_else(|| state.treasuries.first())
+        .cloned();
+
+    {
+        let dispatch = dispatch.clone();
+        use_effect_with(open.as_ref().map(|o| o.treasury.id), move |id| {
+            if let Some(id) = *id {
+                dispatch.apply(WalletAction::OpenTreasury(id));
+            }
+            || ()
+        });
+    }
+
+    let user_id = state.wallet.as_ref().map(|wallet| wallet.user_id);
+
+    html! {
+        <div class="flex-1 space-y-6">
+            <div class="bg-indigo-800 rounded-xl p-6">
+                <h2 class="text-xl font-bold mb-4">{ "Treasury" }</h2>
+                if state.treasuries.is_empty() {
+                    <p class="text-indigo-300 mb-4">{ "You are not a signer of any treasury." }</p>
+                } else {
+                    <ul class="divide-y divide-indigo-700 mb-4">
+                        { for state.treasuries.iter().map(|overview| {
+                            let id = overview.treasury.id;
+                            let is_open = open.as_ref().is_some_and(|o| o.treasury.id == id);
+                            let on_open = {
+                                let selected = selected.clone();
+                                let dispatch = dispatch.clone();
+                                Callback::from(move |_| {
+                                    dispatch.apply(WalletAction::ClearErrorMessage);
+                                    selected.set(Some(id));
+                                })
+                            };
+                            html! {
+                                <li class="py-3 flex items-start justify-between gap-4">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">{ &overview.treasury.name }</p>
+                                        <p class="text-sm text-indigo-300 font-mono break-all">{ &overview.treasury.address }</p>
+                                        <p class="text-sm text-indigo-300">
+                                            { format!(
+                                                "{} of {} signers must approve",
+                                                overview.treasury.threshold,
+                                                overview.signers.len()
+                                            ) }
+                                        </p>
+                                    </div>
+                                    if !is_open {
+                                        <button class="text-indigo-300 hover:text-indigo-200 transition-colors shrink-0" onclick={on_open}>
+                                            { "Open" }
+                                        </button>
+                                    }
+                                </li>
+                            }
+                        }) }
+                    </ul>
+                }
+                if props.is_admin {
+                    <CreateTreasuryForm />
+                }
+                if let Some(error) = &state.last_error {
+                    <p class="text-sm text-red-400 mt-2">{ &error.message }</p>
+                }
+            </div>
+            if let Some(overview) = open {
+                <div class="bg-indigo-800 rounded-xl p-6">
+                    <h2 class="text-xl font-bold mb-1">{ &overview.treasury.name }</h2>
+                    <p class="text-sm text-indigo-300 mb-4">
+                        { "Signers: " }
+                        { overview.signers.iter().map(|signer| signer.username.clone()).collect::<Vec<_>>().join(", ") }
+                    </p>
+                    <ProposalForm treasury_id={overview.treasury.id} />
+                </div>
+                <div class="bg-indigo-800 rounded-xl p-6">
+                    <h2 class="text-xl font-bold mb-4">{ "Proposals" }</h2>
+                    if state.treasury_proposals.is_empty() {
+                        <p class="text-indigo-300">{ "No sends proposed yet." }</p>
+                    } else {
+                        <ul class="divide-y divide-indigo-700">
+                            { for state.treasury_proposals.iter().map(|proposal| {
+                                let id = proposal.id;
+                                let can_vote = proposal.status == TreasuryProposal::PENDING
+                                    && user_id.is_some_and(|user_id| {
+                                        !proposal.has_voted(user_id)
+                                            && overview.signers.iter().any(|signer| signer.user_id == user_id)
+                                    });
+                                let on_vote = |approve: bool| {
+                                    let dispatch = dispatch.clone();
+                                    Callback::from(move |_| {
+                                        dispatch.apply(WalletAction::VoteTreasuryProposal { id, approve })
+                                    })
+                                };
+                                let on_execute = {
+                                    let dispatch = dispatch.clone();
+                                    Callback::from(move |_| dispatch.apply(WalletAction::ExecuteTreasuryProposal(id)))
+                                };
+                                html! {
+                                    <li class="py-3 flex items-start justify-between gap-4">
+                                        <div class="min-w-0">
+                                            <p class="font-medium">
+                                                { format!("{} ETH to ", display_decimal(proposal.amount, 6)) }
+                                                <Counterparty address={proposal.recipient.clone()} />
+                                            </p>
+                                            if let Some(memo) = &proposal.memo {
+                                                <p class="text-sm">{ memo }</p>
+                                            }
+                                            <p class="text-sm text-indigo-300">
+                                                { format!(
+                                                    "Proposed by {} on {} · {} of {} approvals, {} rejections",
+                                                    signer_name(&overview, proposal.proposed_by),
+                                                    proposal.created_at.with_timezone(&Local).format("%b %e, %H:%M"),
+                                                    proposal.approvals.len(),
+                                                    overview.treasury.threshold,
+                                                    proposal.rejections.len()
+                                                ) }
+                                            </p>
+                                            if let Some(tx_hash) = &proposal.tx_hash {
+                                                <p class="text-sm text-indigo-300 font-mono" title={tx_hash.clone()}>
+                                                    { format!("Transaction {}", short_address(tx_hash)) }
+                                                </p>
+                                            }
+                                            if let Some(error) = &proposal.error {
+                                                <p class="text-sm text-red-400">{ error }</p>
+                                            }
+                                        </div>
+                                        <div class="flex flex-col items-end gap-1 shrink-0">
+                                            <span class={classes!("text-sm", "capitalize", status_class(&proposal.status))}>
+                                                { &proposal.status }
+                                            </span>
+                                            if can_vote {
+                                                <div class="flex gap-4">
+                                                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_vote(true)}>
+                                                        { "Approve" }
+                                                    </button>
+                                                    <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_vote(false)}>
+                                                        { "Reject" }
+                                                    </button>
+                                                </div>
+                                            }
+                                            if proposal.status == TreasuryProposal::APPROVED {
+                                                <button class="text-indigo-300 hover:text-indigo-200 transition-colors" onclick={on_execute}>
+                                                    { "Execute" }
+                                                </button>
+                                            }
+                                        </div>
+                                    </li>
+                                }
+                            }) }
+                        </ul>
+                    }
+                </div>
+                <div class="bg-indigo-800 rounded-xl p-6">
+                    <h2 class="text-xl font-bold mb-4">{ "Audit trail" }</h2>
+                    if state.treasury_audit.is_empty() {
+                        <p class="text-indigo-300">{ "Nothing recorded yet." }</p>
+                    } else {
+                        <ul class="divide-y divide-indigo-700">
+                            { for state.treasury_audit.iter().map(|entry| html! {
+                                <li class="py-3 flex items-start justify-between gap-4 text-sm">
+                                    <div class="min-w-0">
+                                        <p class="font-medium">{ event_label(&entry.event) }</p>
+                                        if let Some(detail) = &entry.detail {
+                                            <p class="text-indigo-300 break-all">{ detail }</p>
+                                        }
+                                    </div>
+                                    <div class="text-right text-indigo-300 shrink-0">
+                                        <p>{ entry.username.clone().unwrap_or_else(|| "Deleted user".to_string()) }</p>
+                                        <p>{ entry.created_at.with_timezone(&Local).format("%b %e, %H:%M").to_string() }</p>
+                                    </div>
+                                </li>
+                            }) }
+                        </ul>
+                    }
+                </div>
+            }
+        </div>
+    }
+}
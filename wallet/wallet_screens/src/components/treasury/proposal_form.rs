@@ -0,0 +1,116 @@
+use uuid::Uuid;
+use wallet_api::{
+    address::{checksum_address, parse_address},
+    amount::{parse_amount, to_decimal, ETHER_DECIMALS},
+    CreateTreasuryProposal,
+};
+use yew::prelude::*;
+use yewdux::prelude::*;
+
+use crate::{
+    services::browser::number_locale,
+    store::{WalletAction, WalletStore},
+};
+
+#[derive(Properties, PartialEq)]
+pub struct ProposalFormProps {
+    pub treasury_id: Uuid,
+}
+
+/// Proposes a send from a treasury, to be voted on by its signers.
+#[function_component(ProposalForm)]
+pub fn proposal_form(props: &ProposalFormProps) -> Html {
+    let (_, dispatch) = use_store::<WalletStore>();
+    let recipient = use_state(String::new);
+    let amount = use_state(String::new);
+    let memo = use_state(String::new);
+
+    let parsed_recipient = parse_address(&recipient);
+    let parsed_amount = parse_amount(amount.trim(), ETHER_DECIMALS, number_locale())
+        .and_then(|amount| to_decimal(amount, ETHER_DECIMALS));
+    let recipient_error = parsed_recipient
+        .as_ref()
+        .err()
+        .filter(|_| !recipient.trim().is_empty())
+        .map(|e| e.to_string());
+    let amount_error = parsed_amount
+        .as_ref()
+        .err()
+        .filter(|_| !amount.trim().is_empty())
+        .map(|e| e.to_string());
+    let can_propose = parsed_recipient.is_ok()
+        && parsed_amount
+            .as_ref()
+            .is_ok_and(|amount| amount.is_sign_positive() && !amount.is_zero());
+
+    let on_input = |field: UseStateHandle<String>| {
+        Callback::from(move |e: InputEvent| {
+            field.set(
+                e.target_unchecked_into::<web_sys::HtmlInputElement>()
+                    .value(),
+            )
+        })
+    };
+    let on_propose = {
+        let recipient = recipient.clone();
+        let amount = amount.clone();
+        let memo = memo.clone();
+        let treasury_id = props.treasury_id;
+        let proposal = parsed_recipient
+            .as_ref()
+            .ok()
+            .zip(parsed_amount.as_ref().ok())
+            .map(|(address, amount)| (checksum_address(address), *amount));
+        Callback::from(move |e: SubmitEvent| {
+            e.prevent_default();
+            if let Some((address, value)) = proposal.clone() {
+                dispatch.apply(WalletAction::ProposeTreasurySend {
+                    treasury_id,
+                    proposal: CreateTreasuryProposal {
+                        recipient: address,
+                        amount: value,
+                        memo: Some(memo.trim().to_string()).filter(|memo| !memo.is_empty()),
+                    },
+                });
+                recipient.set(String::new());
+                amount.set(String::new());
+                memo.set(String::new());
+            }
+        })
+    };
+
+    html! {
+        <form onsubmit={on_propose} class="space-y-2">
+            <input
+                type="text"
+                value={(*recipient).clone()}
+                oninput={on_input(recipient.clone())}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4 font-mono"
+                placeholder="Recipient address"
+            />
+            if let Some(error) = recipient_error {
+                <p class="text-xs text-red-400">{ error }</p>
+            }
+            <input
+                type="text"
+                value={(*amount).clone()}
+                oninput={on_input(amount.clone())}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                placeholder="Amount in ETH"
+            />
+            if let Some(error) = amount_error {
+                <p class="text-xs text-red-400">{ error }</p>
+            }
+            <input
+                type="text"
+                value={(*memo).clone()}
+                oninput={on_input(memo.clone())}
+                class="w-full bg-indigo-700 rounded-lg py-2 px-4"
+                placeholder="Memo (optional)"
+            />
+            <button type="submit" disabled={!can_propose} class="text-indigo-300 hover:text-indigo-200 transition-colors">
+                { "Propose send" }
+            </button>
+        </form>
+    }
+}
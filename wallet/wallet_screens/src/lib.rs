@@ -1,13 +1,23 @@
 pub mod components {
     pub mod common;
+    pub mod contacts;
+    pub mod contracts;
     pub mod dashboard;
+    pub mod notifications;
+    pub mod scheduled;
     pub mod setup;
+    pub mod sign;
     pub mod transactions;
     pub mod transfer;
+    pub mod treasury;
 }
 
 pub mod store;
 pub mod services {
+    pub mod browser;
+    pub mod contracts;
     pub mod crypto;
+    pub mod hd_wallet;
+    pub mod signing;
     pub mod transactions;
 }
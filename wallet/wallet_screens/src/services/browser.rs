@@ -0,0 +1,65 @@
+//! Thin wrappers around the browser APIs used to move files in and out of the
+//! wallet and to pick up the user's preferences.
+
+use app_config::AppRoute;
+use wallet_api::amount::NumberLocale;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::{Blob, BlobPropertyBag, File, HtmlAnchorElement, Url};
+use yew_router::Routable;
+
+/// Offers `contents` to the user as a file download named `filename`.
+pub fn download_text(filename: &str, contents: &str, mime_type: &str) -> Result<(), JsValue> {
+    let options = BlobPropertyBag::new();
+    options.set_type(mime_type);
+    let parts = js_sys::Array::of1(&JsValue::from_str(contents));
+    let blob = Blob::new_with_str_sequence_and_options(&parts, &options)?;
+    let url = Url::create_object_url_with_blob(&blob)?;
+
+    let document = web_sys::window()
+        .and_then(|window| window.document())
+        .ok_or_else(|| JsValue::from_str("no document"))?;
+    let anchor = document
+        .create_element("a")?
+        .dyn_into::<HtmlAnchorElement>()?;
+    anchor.set_href(&url);
+    anchor.set_download(filename);
+    anchor.click();
+
+    Url::revoke_object_url(&url)
+}
+
+/// Reads a file picked through an `<input type="file">` as text.
+pub async fn read_file_text(file: File) -> Result<String, JsValue> {
+    JsFuture::from(file.text())
+        .await?
+        .as_string()
+        .ok_or_else(|| JsValue::from_str("file is not valid text"))
+}
+
+/// Number formatting conventions of the browser's preferred language.
+pub fn number_locale() -> NumberLocale {
+    web_sys::window()
+        .and_then(|window| window.navigator().language())
+        .map(|language| NumberLocale::from_language_tag(&language))
+        .unwrap_or_default()
+}
+
+/// Link to the app's transfer screen that opens with the payment `uri`
+/// filled in, for sharing with people who use this wallet too.
+pub fn payment_link(uri: &str) -> Option<String> {
+    let origin = web_sys::window()?.location().origin().ok()?;
+    Some(format!(
+        "{}{}?uri={}",
+        origin,
+        AppRoute::Transfer.to_path(),
+        String::from(js_sys::encode_uri_component(uri))
+    ))
+}
+
+/// Copies `text` to the clipboard, ignoring browsers that refuse it.
+pub fn copy_to_clipboard(text: &str) {
+    if let Some(window) = web_sys::window() {
+        let _ = window.navigator().clipboard().write_text(text);
+    }
+}
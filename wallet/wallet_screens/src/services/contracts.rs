@@ -0,0 +1,124 @@
+//! Calls to contracts described by their ABI: encoding the arguments typed
+//! into the generated forms, `eth_call` for functions that only read state,
+//! and formatting the values they return.
+
+use ethers::{
+    abi::{
+        token::{LenientTokenizer, Tokenizer},
+        Function, Param, StateMutability, Token,
+    },
+    providers::{Http, Middleware, Provider},
+    types::{transaction::eip2718::TypedTransaction, Address, Bytes, TransactionRequest, I256},
+    utils::hex,
+};
+use wallet_api::address::checksum_address;
+
+/// Node read-only calls are sent to.
+const RPC_URL: &str = "http://localhost:8545";
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum ContractError {
+    #[error("Invalid {name}: {message}")]
+    InvalidArgument { name: String, message: String },
+    #[error("Could not encode the call: {0}")]
+    Encoding(String),
+    #[error("Call failed: {0}")]
+    Call(String),
+    #[error("Could not decode the result: {0}")]
+    Decoding(String),
+}
+
+/// Whether `function` only reads state, so that it is run with `eth_call`
+/// rather than sent as a transaction.
+pub fn is_read_only(function: &Function) -> bool {
+    matches!(
+        function.state_mutability,
+        StateMutability::Pure | StateMutability::View
+    )
+}
+
+pub fn is_payable(function: &Function) -> bool {
+    function.state_mutability == StateMutability::Payable
+}
+
+/// Label of an input or output, e.g. `amount (uint256)`. Unnamed ones are
+/// numbered.
+pub fn param_label(param: &Param, index: usize) -> String {
+    let name = match param.name.as_str() {
+        "" => format!("#{}", index + 1),
+        name => name.to_string(),
+    };
+    format!("{} ({})", name, param.kind)
+}
+
+/// Encodes a call to `function` with one typed argument per input. Numbers
+/// may be decimal or hex, arrays are written as `[1,2]` and tuples as
+/// `(0x…,3)`.
+pub fn encode_call(function: &Function, args: &[String]) -> Result<Bytes, ContractError> {
+    let tokens = function
+        .inputs
+        .iter()
+        .zip(args)
+        .enumerate()
+        .map(|(index, (param, arg))| {
+            LenientTokenizer::tokenize(&param.kind, arg.trim()).map_err(|e| {
+                ContractError::InvalidArgument {
+                    name: param_label(param, index),
+                    message: e.to_string(),
+                }
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    function
+        .encode_input(&tokens)
+        .map(Bytes::from)
+        .map_err(|e| ContractError::Encoding(e.to_string()))
+}
+
+/// Runs a read-only call with `eth_call` and decodes what it returned. `from`
+/// matters to functions that look at `msg.sender`.
+pub async fn call(
+    function: &Function,
+    from: Option<Address>,
+    to: Address,
+    data: Bytes,
+) -> Result<Vec<Token>, ContractError> {
+    let provider =
+        Provider::<Http>::try_from(RPC_URL).map_err(|e| ContractError::Call(e.to_string()))?;
+    let mut tx = TransactionRequest::new().to(to).data(data);
+    if let Some(from) = from {
+        tx = tx.from(from);
+    }
+
+    let output = provider
+        .call(&TypedTransaction::Legacy(tx), None)
+        .await
+        .map_err(|e| ContractError::Call(e.to_string()))?;
+    function
+        .decode_output(&output)
+        .map_err(|e| ContractError::Decoding(e.to_string()))
+}
+
+/// Formats a returned value: numbers in decimal, addresses with their
+/// checksum and bytes as hex.
+pub fn format_token(token: &Token) -> String {
+    let join = |tokens: &[Token]| {
+        tokens
+            .iter()
+            .map(format_token)
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+
+    match token {
+        Token::Address(address) => checksum_address(address),
+        Token::Uint(value) => value.to_string(),
+        Token::Int(value) => I256::from_raw(*value).to_string(),
+        Token::Bool(value) => value.to_string(),
+        Token::String(value) => value.clone(),
+        Token::Bytes(bytes) | Token::FixedBytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Token::Array(tokens) | Token::FixedArray(tokens) => format!("[{}]", join(tokens)),
+        Token::Tuple(tokens) => format!("({})", join(tokens)),
+    }
+}
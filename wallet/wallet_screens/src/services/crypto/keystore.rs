@@ -0,0 +1,139 @@
+use aes_gcm::{aead::Aead, Aes256Gcm, KeyInit, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::{engine::general_purpose, Engine as _};
+use rand::{thread_rng, RngCore};
+use serde::{Deserialize, Serialize};
+
+use super::CryptoError;
+
+pub const KEYSTORE_VERSION: u8 = 2;
+
+const KEY_LENGTH: usize = 32;
+const SALT_LENGTH: usize = 16;
+const NONCE_LENGTH: usize = 12;
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Cipher {
+    Aes256Gcm,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "name", rename_all = "lowercase")]
+pub enum Kdf {
+    Argon2id {
+        memory_kib: u32,
+        iterations: u32,
+        parallelism: u32,
+    },
+}
+
+impl Default for Kdf {
+    fn default() -> Self {
+        Kdf::Argon2id {
+            memory_kib: Params::DEFAULT_M_COST,
+            iterations: Params::DEFAULT_T_COST,
+            parallelism: Params::DEFAULT_P_COST,
+        }
+    }
+}
+
+impl Kdf {
+    fn derive_key(&self, password: &str, salt: &[u8]) -> Result<[u8; KEY_LENGTH], CryptoError> {
+        match self {
+            Kdf::Argon2id {
+                memory_kib,
+                iterations,
+                parallelism,
+            } => {
+                let params = Params::new(*memory_kib, *iterations, *parallelism, Some(KEY_LENGTH))
+                    .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+                let mut key = [0u8; KEY_LENGTH];
+                Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+                    .hash_password_into(password.as_bytes(), salt, &mut key)
+                    .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+/// Self-describing envelope for an encrypted private key.
+///
+/// The envelope records the cipher, the KDF and its parameters alongside the
+/// salt and nonce, so the parameters can be raised later without breaking keys
+/// that were encrypted with the old ones.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Keystore {
+    pub version: u8,
+    pub cipher: Cipher,
+    pub kdf: Kdf,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+}
+
+impl Keystore {
+    pub fn encrypt(plaintext: &str, password: &str) -> Result<Self, CryptoError> {
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut nonce = [0u8; NONCE_LENGTH];
+        thread_rng().fill_bytes(&mut salt);
+        thread_rng().fill_bytes(&mut nonce);
+
+        let kdf = Kdf::default();
+        let key = kdf.derive_key(password, &salt)?;
+        let cipher =
+            Aes256Gcm::new_from_slice(&key).map_err(|e| CryptoError::Malformed(e.to_string()))?;
+        let ciphertext = cipher
+            .encrypt(Nonce::from_slice(&nonce), plaintext.as_bytes())
+            .map_err(|_| CryptoError::Malformed("encryption failed".to_string()))?;
+
+        Ok(Self {
+            version: KEYSTORE_VERSION,
+            cipher: Cipher::Aes256Gcm,
+            kdf,
+            salt: general_purpose::STANDARD.encode(salt),
+            nonce: general_purpose::STANDARD.encode(nonce),
+            ciphertext: general_purpose::STANDARD.encode(ciphertext),
+        })
+    }
+
+    pub fn decrypt(&self, password: &str) -> Result<String, CryptoError> {
+        if self.version != KEYSTORE_VERSION {
+            return Err(CryptoError::UnsupportedVersion(self.version));
+        }
+
+        let salt = decode_field("salt", &self.salt)?;
+        let nonce = decode_field("nonce", &self.nonce)?;
+        let ciphertext = decode_field("ciphertext", &self.ciphertext)?;
+        if nonce.len() != NONCE_LENGTH {
+            return Err(CryptoError::Malformed("invalid nonce length".to_string()));
+        }
+
+        let key = self.kdf.derive_key(password, &salt)?;
+        let plaintext = match self.cipher {
+            Cipher::Aes256Gcm => Aes256Gcm::new_from_slice(&key)
+                .map_err(|e| CryptoError::Malformed(e.to_string()))?
+                .decrypt(Nonce::from_slice(&nonce), ciphertext.as_slice())
+                // The authentication tag only fails to verify when the key is wrong
+                // or the envelope was tampered with.
+                .map_err(|_| CryptoError::InvalidPassword)?,
+        };
+
+        String::from_utf8(plaintext).map_err(|e| CryptoError::Malformed(e.to_string()))
+    }
+
+    pub fn to_json(&self) -> Result<String, CryptoError> {
+        serde_json::to_string(self).map_err(|e| CryptoError::Malformed(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, CryptoError> {
+        serde_json::from_str(json).map_err(|e| CryptoError::Malformed(e.to_string()))
+    }
+}
+
+fn decode_field(name: &str, value: &str) -> Result<Vec<u8>, CryptoError> {
+    general_purpose::STANDARD
+        .decode(value)
+        .map_err(|e| CryptoError::Malformed(format!("invalid {}: {}", name, e)))
+}
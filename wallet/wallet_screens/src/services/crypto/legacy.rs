@@ -0,0 +1,37 @@
+//! The original key format: AES-256-CBC with PKCS7 padding, a key derived with
+//! 10,000 rounds of PBKDF2 and the PBKDF2 salt doubling as IV. It is only kept
+//! so that existing blobs can be decrypted once and re-encrypted as a
+//! [`super::keystore::Keystore`].
+
+use std::num::NonZeroU32;
+
+use aes::Aes256;
+use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
+use ring::pbkdf2;
+
+use super::CryptoError;
+
+type Aes256Cbc = Cbc<Aes256, Pkcs7>;
+
+const KEY_LENGTH: usize = 32;
+const ITERATIONS: u32 = 10_000;
+
+pub fn decrypt(ciphertext: &[u8], password: &str, salt: &[u8]) -> Result<String, CryptoError> {
+    let mut key = [0u8; KEY_LENGTH];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        NonZeroU32::new(ITERATIONS).unwrap(),
+        salt,
+        password.as_bytes(),
+        &mut key,
+    );
+
+    let cipher = Aes256Cbc::new_from_slices(&key, salt)
+        .map_err(|_| CryptoError::Malformed("invalid legacy salt".to_string()))?;
+    cipher
+        .decrypt_vec(ciphertext)
+        .map_err(|_| CryptoError::InvalidPassword)
+        .and_then(|decrypted| {
+            String::from_utf8(decrypted).map_err(|_| CryptoError::InvalidPassword)
+        })
+}
@@ -1,41 +1,58 @@
-use std::num::NonZeroU32;
+mod keystore;
+mod legacy;
+mod web3;
 
-use aes::Aes256;
-use block_modes::{block_padding::Pkcs7, BlockMode, Cbc};
-use ring::pbkdf2;
+use base64::{engine::general_purpose, Engine as _};
+pub use keystore::{Cipher, Kdf, Keystore, KEYSTORE_VERSION};
+pub use web3::Web3Keystore;
 
-type Aes256Cbc = Cbc<Aes256, Pkcs7>;
-
-const KEY_LENGTH: usize = 32;
-const ITERATIONS: u32 = 10_000;
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum CryptoError {
+    #[error("Incorrect password")]
+    InvalidPassword,
+    #[error("Unsupported keystore version {0}")]
+    UnsupportedVersion(u8),
+    #[error("Malformed encrypted key: {0}")]
+    Malformed(String),
+}
 
-pub fn encrypt(plaintext: String, password: &str, salt: &[u8]) -> Vec<u8> {
-    let mut key = [0u8; KEY_LENGTH];
-    pbkdf2::derive(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        NonZeroU32::new(ITERATIONS).unwrap(),
-        salt,
-        password.as_bytes(),
-        &mut key,
-    );
+pub struct UnlockedKey {
+    pub private_key: String,
+    /// Set when the stored key was in an older format. Holds the re-encrypted
+    /// keystore that should replace `Wallet::encrypted_private_key`.
+    pub upgraded: Option<String>,
+}
 
-    let cipher = Aes256Cbc::new_from_slices(&key, salt).unwrap();
-    cipher.encrypt_vec(plaintext.as_bytes())
+/// Encrypts `plaintext` under `password` and returns the serialised keystore
+/// that is stored in `Wallet::encrypted_private_key`.
+pub fn encrypt(plaintext: &str, password: &str) -> Result<String, CryptoError> {
+    Keystore::encrypt(plaintext, password)?.to_json()
 }
 
-pub fn decrypt(ciphertext: &[u8], password: &str, salt: &[u8]) -> Result<String, String> {
-    let mut key = [0u8; KEY_LENGTH];
-    pbkdf2::derive(
-        pbkdf2::PBKDF2_HMAC_SHA256,
-        NonZeroU32::new(ITERATIONS).unwrap(),
-        salt,
-        password.as_bytes(),
-        &mut key,
-    );
+/// Decrypts a stored key. Keys in the legacy base64 CBC format are decrypted
+/// with `legacy_salt` and re-encrypted, so that the caller can persist the
+/// upgraded keystore.
+pub fn decrypt(
+    encrypted: &str,
+    password: &str,
+    legacy_salt: &[u8],
+) -> Result<UnlockedKey, CryptoError> {
+    if encrypted.trim_start().starts_with('{') {
+        let private_key = Keystore::from_json(encrypted)?.decrypt(password)?;
+        return Ok(UnlockedKey {
+            private_key,
+            upgraded: None,
+        });
+    }
+
+    let ciphertext = general_purpose::STANDARD
+        .decode(encrypted)
+        .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+    let private_key = legacy::decrypt(&ciphertext, password, legacy_salt)?;
+    let upgraded = encrypt(&private_key, password)?;
 
-    let cipher = Aes256Cbc::new_from_slices(&key, salt).unwrap();
-    cipher
-        .decrypt_vec(ciphertext)
-        .map_err(|_| String::from("Decryption failed"))
-        .and_then(|decrypted| String::from_utf8(decrypted).map_err(|e| e.to_string()))
+    Ok(UnlockedKey {
+        private_key,
+        upgraded: Some(upgraded),
+    })
 }
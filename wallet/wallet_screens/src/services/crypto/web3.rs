@@ -0,0 +1,213 @@
+//! Web3 Secret Storage (V3 keystore) files, as written by geth, MetaMask and
+//! Foundry. Keys are only ever converted to and from this format on import and
+//! export; the wallet itself stores them as a [`super::Keystore`].
+
+use std::num::NonZeroU32;
+
+use aes_gcm::aes::Aes128;
+use ctr::cipher::{KeyIvInit, StreamCipher};
+use ethers::{
+    signers::{LocalWallet, Signer},
+    utils::{hex, keccak256},
+};
+use rand::{thread_rng, RngCore};
+use ring::pbkdf2;
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::CryptoError;
+
+type Aes128Ctr = ctr::Ctr128BE<Aes128>;
+
+const CIPHER: &str = "aes-128-ctr";
+const DKLEN: u8 = 32;
+const IV_LENGTH: usize = 16;
+const SALT_LENGTH: usize = 32;
+// Matches the defaults of `cast wallet` and eth-keystore. geth uses 2^18, which
+// takes far too long to derive in the browser.
+const SCRYPT_LOG_N: u8 = 13;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Web3Keystore {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(alias = "Crypto")]
+    pub crypto: Web3Crypto,
+    pub id: Uuid,
+    pub version: u8,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Web3Crypto {
+    pub cipher: String,
+    pub cipherparams: Web3CipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: Web3KdfParams,
+    pub mac: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Web3CipherParams {
+    pub iv: String,
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Web3KdfParams {
+    Pbkdf2 {
+        c: u32,
+        dklen: u8,
+        prf: String,
+        salt: String,
+    },
+    Scrypt {
+        dklen: u8,
+        n: u32,
+        p: u32,
+        r: u32,
+        salt: String,
+    },
+}
+
+impl Web3Keystore {
+    /// Encrypts a raw secp256k1 private key into a V3 keystore.
+    pub fn encrypt(private_key: &[u8], password: &str) -> Result<Self, CryptoError> {
+        let signer = LocalWallet::from_bytes(private_key)
+            .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+
+        let mut salt = [0u8; SALT_LENGTH];
+        let mut iv = [0u8; IV_LENGTH];
+        thread_rng().fill_bytes(&mut salt);
+        thread_rng().fill_bytes(&mut iv);
+
+        let kdfparams = Web3KdfParams::Scrypt {
+            dklen: DKLEN,
+            n: 1 << SCRYPT_LOG_N,
+            p: SCRYPT_P,
+            r: SCRYPT_R,
+            salt: hex::encode(salt),
+        };
+        let key = kdfparams.derive_key(password)?;
+
+        let mut ciphertext = private_key.to_vec();
+        Aes128Ctr::new_from_slices(&key[..16], &iv)
+            .map_err(|e| CryptoError::Malformed(e.to_string()))?
+            .apply_keystream(&mut ciphertext);
+        let mac = mac(&key, &ciphertext);
+
+        Ok(Self {
+            address: Some(hex::encode(signer.address())),
+            crypto: Web3Crypto {
+                cipher: CIPHER.to_string(),
+                cipherparams: Web3CipherParams {
+                    iv: hex::encode(iv),
+                },
+                ciphertext: hex::encode(ciphertext),
+                kdf: "scrypt".to_string(),
+                kdfparams,
+                mac: hex::encode(mac),
+            },
+            id: Uuid::new_v4(),
+            version: 3,
+        })
+    }
+
+    /// Decrypts the keystore and returns the raw private key.
+    pub fn decrypt(&self, password: &str) -> Result<Vec<u8>, CryptoError> {
+        if self.version != 3 {
+            return Err(CryptoError::UnsupportedVersion(self.version));
+        }
+        if self.crypto.cipher != CIPHER {
+            return Err(CryptoError::Malformed(format!(
+                "unsupported cipher {}",
+                self.crypto.cipher
+            )));
+        }
+
+        let key = self.crypto.kdfparams.derive_key(password)?;
+        let mut ciphertext = decode_hex("ciphertext", &self.crypto.ciphertext)?;
+        let expected_mac = decode_hex("mac", &self.crypto.mac)?;
+        if mac(&key, &ciphertext).as_slice() != expected_mac.as_slice() {
+            return Err(CryptoError::InvalidPassword);
+        }
+
+        let iv = decode_hex("iv", &self.crypto.cipherparams.iv)?;
+        Aes128Ctr::new_from_slices(&key[..16], &iv)
+            .map_err(|e| CryptoError::Malformed(e.to_string()))?
+            .apply_keystream(&mut ciphertext);
+
+        Ok(ciphertext)
+    }
+
+    pub fn to_json(&self) -> Result<String, CryptoError> {
+        serde_json::to_string_pretty(self).map_err(|e| CryptoError::Malformed(e.to_string()))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, CryptoError> {
+        serde_json::from_str(json).map_err(|e| CryptoError::Malformed(e.to_string()))
+    }
+}
+
+impl Web3KdfParams {
+    fn derive_key(&self, password: &str) -> Result<Vec<u8>, CryptoError> {
+        match self {
+            Web3KdfParams::Pbkdf2 {
+                c,
+                dklen,
+                prf,
+                salt,
+            } => {
+                if prf != "hmac-sha256" {
+                    return Err(CryptoError::Malformed(format!("unsupported prf {}", prf)));
+                }
+                let iterations = NonZeroU32::new(*c)
+                    .ok_or_else(|| CryptoError::Malformed("invalid iteration count".to_string()))?;
+                let mut key = vec![0u8; usize::from(*dklen).max(32)];
+                pbkdf2::derive(
+                    pbkdf2::PBKDF2_HMAC_SHA256,
+                    iterations,
+                    &decode_hex("salt", salt)?,
+                    password.as_bytes(),
+                    &mut key,
+                );
+                Ok(key)
+            }
+            Web3KdfParams::Scrypt {
+                dklen,
+                n,
+                p,
+                r,
+                salt,
+            } => {
+                if !n.is_power_of_two() {
+                    return Err(CryptoError::Malformed(
+                        "scrypt n must be a power of two".to_string(),
+                    ));
+                }
+                let params = scrypt::Params::new(n.trailing_zeros() as u8, *r, *p)
+                    .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+                let mut key = vec![0u8; usize::from(*dklen).max(32)];
+                scrypt::scrypt(
+                    password.as_bytes(),
+                    &decode_hex("salt", salt)?,
+                    &params,
+                    &mut key,
+                )
+                .map_err(|e| CryptoError::Malformed(e.to_string()))?;
+                Ok(key)
+            }
+        }
+    }
+}
+
+fn mac(key: &[u8], ciphertext: &[u8]) -> [u8; 32] {
+    keccak256([&key[16..32], ciphertext].concat())
+}
+
+fn decode_hex(name: &str, value: &str) -> Result<Vec<u8>, CryptoError> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| CryptoError::Malformed(format!("invalid {}: {}", name, e)))
+}
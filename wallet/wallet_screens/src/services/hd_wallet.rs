@@ -0,0 +1,83 @@
+//! BIP-39 recovery phrases and BIP-44 account derivation.
+
+use ethers::signers::{
+    coins_bip39::{English, Mnemonic, Wordlist},
+    LocalWallet, MnemonicBuilder,
+};
+
+/// BIP-44 path of Ethereum accounts, without the trailing address index.
+pub const DEFAULT_DERIVATION_PATH_PREFIX: &str = "m/44'/60'/0'/0";
+
+const VALID_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum HdWalletError {
+    #[error("A recovery phrase has 12, 15, 18, 21 or 24 words, found {0}")]
+    WordCount(usize),
+    #[error("Word {position} (\"{word}\") is not in the BIP-39 English word list")]
+    UnknownWord { position: usize, word: String },
+    #[error("Invalid checksum: a word is misspelled or the words are in the wrong order")]
+    Checksum,
+    #[error("Invalid derivation path: {0}")]
+    DerivationPath(String),
+}
+
+/// Lowercases the phrase and collapses the whitespace between words.
+pub fn normalize_phrase(phrase: &str) -> String {
+    phrase
+        .split_whitespace()
+        .map(str::to_lowercase)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks the word count, every word and the checksum of a recovery phrase and
+/// returns the normalized phrase.
+pub fn validate_phrase(phrase: &str) -> Result<String, HdWalletError> {
+    let phrase = normalize_phrase(phrase);
+    let words = phrase
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>();
+    if !VALID_WORD_COUNTS.contains(&words.len()) {
+        return Err(HdWalletError::WordCount(words.len()));
+    }
+
+    if let Some((position, word)) = words
+        .iter()
+        .enumerate()
+        .find(|(_, word)| English::get_index(word).is_err())
+    {
+        return Err(HdWalletError::UnknownWord {
+            position: position + 1,
+            word: word.to_string(),
+        });
+    }
+
+    Mnemonic::<English>::new_from_phrase(&phrase).map_err(|_| HdWalletError::Checksum)?;
+    Ok(phrase)
+}
+
+pub fn derivation_path(prefix: &str, index: u32) -> String {
+    format!("{}/{}", prefix.trim_end_matches('/'), index)
+}
+
+/// Derives the account at `path` from a validated recovery phrase and an
+/// optional BIP-39 passphrase.
+pub fn derive_signer(
+    phrase: &str,
+    passphrase: Option<&str>,
+    path: &str,
+) -> Result<LocalWallet, HdWalletError> {
+    let phrase = validate_phrase(phrase)?;
+    let mut builder = MnemonicBuilder::<English>::default()
+        .phrase(phrase.as_str())
+        .derivation_path(path)
+        .map_err(|e| HdWalletError::DerivationPath(e.to_string()))?;
+    if let Some(passphrase) = passphrase.filter(|passphrase| !passphrase.is_empty()) {
+        builder = builder.password(passphrase);
+    }
+    builder
+        .build()
+        .map_err(|e| HdWalletError::DerivationPath(e.to_string()))
+}
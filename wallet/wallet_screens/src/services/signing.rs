@@ -0,0 +1,91 @@
+//! Signing of off-chain messages: EIP-191 `personal_sign` and EIP-712 typed
+//! data, and recovery of the signer from such signatures.
+
+use std::str::FromStr;
+
+use ethers::{
+    signers::LocalWallet,
+    types::{
+        transaction::eip712::{Eip712, TypedData},
+        Address, Signature, H256,
+    },
+    utils::{hash_message, hex},
+};
+
+#[derive(Debug, Clone, PartialEq, thiserror::Error)]
+pub enum SigningError {
+    #[error("Invalid typed data: {0}")]
+    InvalidTypedData(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Invalid private key: {0}")]
+    InvalidKey(String),
+}
+
+/// What the user is asked to sign.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SignPayload {
+    /// Signed with `personal_sign`. Text starting with `0x` that is valid hex
+    /// is signed as the bytes it encodes, as other wallets do.
+    Message(String),
+    TypedData(Box<TypedData>),
+}
+
+impl SignPayload {
+    pub fn typed_data(json: &str) -> Result<Self, SigningError> {
+        let typed_data: TypedData = serde_json::from_str(json)
+            .map_err(|e| SigningError::InvalidTypedData(e.to_string()))?;
+        // Fails on types that do not match the message
+        typed_data
+            .encode_eip712()
+            .map_err(|e| SigningError::InvalidTypedData(e.to_string()))?;
+        Ok(Self::TypedData(Box::new(typed_data)))
+    }
+
+    /// The hash the signature is made over.
+    pub fn hash(&self) -> Result<H256, SigningError> {
+        match self {
+            Self::Message(message) => Ok(hash_message(message_bytes(message))),
+            Self::TypedData(typed_data) => typed_data
+                .encode_eip712()
+                .map(H256::from)
+                .map_err(|e| SigningError::InvalidTypedData(e.to_string())),
+        }
+    }
+}
+
+/// The bytes `personal_sign` signs for `message`.
+pub fn message_bytes(message: &str) -> Vec<u8> {
+    message
+        .strip_prefix("0x")
+        .and_then(|encoded| hex::decode(encoded).ok())
+        .unwrap_or_else(|| message.as_bytes().to_vec())
+}
+
+/// Whether `message` is signed as the bytes its hex encodes rather than as
+/// text.
+pub fn is_hex_message(message: &str) -> bool {
+    message
+        .strip_prefix("0x")
+        .is_some_and(|encoded| hex::decode(encoded).is_ok())
+}
+
+/// Signs `payload` with a decrypted private key and returns the 65-byte
+/// signature as `0x`-prefixed hex.
+pub fn sign(private_key: &str, payload: &SignPayload) -> Result<String, SigningError> {
+    let wallet =
+        LocalWallet::from_str(private_key).map_err(|e| SigningError::InvalidKey(e.to_string()))?;
+    let signature = wallet
+        .sign_hash(payload.hash()?)
+        .map_err(|e| SigningError::InvalidKey(e.to_string()))?;
+    Ok(format!("0x{}", signature))
+}
+
+/// The address that made `signature` over `payload`.
+pub fn recover(payload: &SignPayload, signature: &str) -> Result<Address, SigningError> {
+    let signature = Signature::from_str(signature.trim())
+        .map_err(|e| SigningError::InvalidSignature(e.to_string()))?;
+    signature
+        .recover(payload.hash()?)
+        .map_err(|e| SigningError::InvalidSignature(e.to_string()))
+}
@@ -6,6 +6,7 @@ use super::status::TransactionStatus;
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub enum TransactionEvent {
     Started,
+    CheckingPolicy,
     Building,
     Built(TransactionRequest),
     Signing,
@@ -39,6 +40,7 @@ impl From<TransactionEvent> for TransactionStatus {
     fn from(event: TransactionEvent) -> Self {
         match event {
             TransactionEvent::Started => Self::Preparing,
+            TransactionEvent::CheckingPolicy => Self::CheckingPolicy,
             TransactionEvent::Building => Self::Building,
             TransactionEvent::Built(_) => Self::Building,
             TransactionEvent::Signing => Self::Signing { tx: 0.into() },
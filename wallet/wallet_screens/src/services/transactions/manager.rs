@@ -5,10 +5,13 @@ use ethers::{
     providers::{Http, Provider},
     signers::{LocalWallet, Signer},
     types::{
-        transaction::eip2718::TypedTransaction, Address, Transaction, TransactionRequest, H256,
-        U256,
+        transaction::eip2718::TypedTransaction, Address, Bytes, Transaction, TransactionRequest,
+        H256, U256,
     },
 };
+use wallet_api::BroadcastTransaction;
+
+use crate::store::transactions_api;
 
 pub struct TransactionManager {
     provider: Provider<Http>,
@@ -36,14 +39,25 @@ impl TransactionManager {
         Ok(())
     }
 
+    pub fn provider(&self) -> &Provider<Http> {
+        &self.provider
+    }
+
+    pub fn address(&self) -> Option<Address> {
+        self.wallet.as_ref().map(|wallet| wallet.address())
+    }
+
+    /// Builds a transaction sending `amount` to `to`, calling it with `data`
+    /// when that is not empty.
     pub async fn build_transaction(
         &self,
         to: Address,
         amount: U256,
+        data: Bytes,
+        nonce: U256,
     ) -> Result<TransactionRequest, Box<dyn Error>> {
         if let Some(wallet) = &self.wallet {
             let from = wallet.address();
-            let nonce = self.provider.get_transaction_count(from, None).await?;
             let gas_price = self.provider.get_gas_price().await?;
 
             // Add gas estimate
@@ -51,6 +65,7 @@ impl TransactionManager {
                 .to(to)
                 .from(from)
                 .value(amount)
+                .data(data)
                 .nonce(nonce)
                 .gas_price(gas_price)
                 .chain_id(self.chain_id);
@@ -63,11 +78,22 @@ impl TransactionManager {
         }
     }
 
+    /// Signs a transaction. A recipient given as an ENS name is resolved first,
+    /// so that the signed transaction and the returned one agree on it.
     pub async fn sign_transaction(
         &self,
-        tx: TransactionRequest,
+        mut tx: TransactionRequest,
     ) -> Result<Transaction, Box<dyn Error>> {
         if let Some(wallet) = &self.wallet {
+            if let Some(NameOrAddress::Name(name)) = &tx.to {
+                let address = self
+                    .provider
+                    .resolve_name(name)
+                    .await
+                    .map_err(|e| format!("Could not resolve {}: {}", name, e))?;
+                tx.to = Some(NameOrAddress::Address(address));
+            }
+
             let typed_tx = TypedTransaction::Legacy(tx);
             let signature = wallet.sign_transaction(&typed_tx).await?;
             let signed_tx = Transaction {
@@ -77,10 +103,7 @@ impl TransactionManager {
                 block_number: None,
                 transaction_index: None,
                 from: wallet.address(),
-                to: typed_tx.to().cloned().and_then(|addr| match addr {
-                    NameOrAddress::Address(a) => Some(a),
-                    _ => None,
-                }),
+                to: typed_tx.to_addr().copied(),
                 value: *typed_tx.value().unwrap_or(&U256::zero()),
                 gas_price: typed_tx.gas_price(),
                 gas: *typed_tx.gas().unwrap_or(&U256::zero()),
@@ -101,10 +124,14 @@ impl TransactionManager {
         }
     }
 
+    /// Submits a signed transaction through the server, which records it and
+    /// relays it to the node.
     pub async fn submit_transaction(&self, tx: Transaction) -> Result<H256, Box<dyn Error>> {
-        let rlp_bytes = tx.rlp();
-        let pending_tx = self.provider.send_raw_transaction(rlp_bytes).await?;
-        Ok(pending_tx.tx_hash())
+        let result = transactions_api::broadcast_transaction(&BroadcastTransaction {
+            raw_transaction: tx.rlp().to_string(),
+        })
+        .await?;
+        Ok(H256::from_str(&result.tx_hash)?)
     }
 
     pub async fn get_transaction_status(
@@ -1,9 +1,13 @@
 pub mod events;
 pub mod manager;
+pub mod nonce;
+pub mod pending;
 pub mod status;
 pub mod worker;
 
 pub use events::TransactionEvent;
 pub use manager::TransactionManager;
+pub use nonce::NonceManager;
+pub use pending::PendingTransaction;
 pub use status::TransactionStatus;
 pub use worker::TransactionWorker;
@@ -0,0 +1,63 @@
+use std::{cell::RefCell, collections::HashMap, error::Error, rc::Rc};
+
+use ethers::{
+    prelude::*,
+    providers::{Http, Provider},
+    types::{Address, BlockNumber, U256},
+};
+
+/// Hands out nonces for outgoing transactions.
+///
+/// The node only reports the number of transactions it has seen, so two sends
+/// issued back-to-back would both be assigned the same nonce. The manager keeps
+/// track of the nonces it has handed out locally and always returns the larger
+/// of the node's pending count and the next local nonce.
+#[derive(Clone, Default)]
+pub struct NonceManager {
+    next_nonces: Rc<RefCell<HashMap<Address, U256>>>,
+}
+
+impl NonceManager {
+    pub async fn next_nonce(
+        &self,
+        provider: &Provider<Http>,
+        address: Address,
+    ) -> Result<U256, Box<dyn Error>> {
+        let chain_nonce = provider
+            .get_transaction_count(address, Some(BlockNumber::Pending.into()))
+            .await?;
+
+        let mut next_nonces = self.next_nonces.borrow_mut();
+        let local_nonce = next_nonces.get(&address).copied().unwrap_or_default();
+        let nonce = chain_nonce.max(local_nonce);
+        next_nonces.insert(address, nonce + 1);
+
+        Ok(nonce)
+    }
+
+    /// Gives back a nonce that was never broadcast, so the next send can reuse
+    /// it instead of leaving a gap that would block every later transaction.
+    pub fn release(&self, address: Address, nonce: U256) {
+        let mut next_nonces = self.next_nonces.borrow_mut();
+        if next_nonces.get(&address) == Some(&(nonce + 1)) {
+            next_nonces.insert(address, nonce);
+        }
+    }
+
+    /// Forgets the local count for `address` so the next nonce is taken from
+    /// the node again.
+    pub fn reset(&self, address: Address) {
+        self.next_nonces.borrow_mut().remove(&address);
+    }
+}
+
+impl PartialEq for NonceManager {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.next_nonces, &other.next_nonces)
+    }
+}
+
+pub fn is_nonce_too_low(error: &str) -> bool {
+    let error = error.to_lowercase();
+    error.contains("nonce too low") || error.contains("nonce has already been used")
+}
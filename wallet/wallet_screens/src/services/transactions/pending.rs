@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use ethers::types::{Address, Bytes, H256, U256};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use super::status::TransactionStatus;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct PendingTransaction {
+    pub id: Uuid,
+    /// Account the transaction is sent from.
+    #[serde(default)]
+    pub from: String,
+    pub to: Address,
+    pub amount: U256,
+    /// Calldata of a contract call; empty for plain transfers.
+    #[serde(default)]
+    pub data: Bytes,
+    pub nonce: Option<U256>,
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+    /// Hash of the transaction once it was submitted; kept when it fails later.
+    #[serde(default)]
+    pub tx_hash: Option<H256>,
+    pub status: TransactionStatus,
+    pub created_at: DateTime<Utc>,
+    /// Run of a scheduled payment the transaction pays.
+    #[serde(default)]
+    pub scheduled_run: Option<Uuid>,
+}
+
+impl PendingTransaction {
+    pub fn new(from: String, to: Address, amount: U256) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            from,
+            to,
+            amount,
+            data: Bytes::new(),
+            nonce: None,
+            chain_id: None,
+            tx_hash: None,
+            status: TransactionStatus::Preparing,
+            created_at: Utc::now(),
+            scheduled_run: None,
+        }
+    }
+}
@@ -7,6 +7,7 @@ use serde::{Deserialize, Serialize};
 pub enum TransactionStatus {
     None,
     Preparing,
+    CheckingPolicy,
     Building,
     Signing { tx: U256 },
     Submitting { tx_hash: Option<H256> },
@@ -53,6 +54,7 @@ impl fmt::Display for TransactionStatus {
         match self {
             TransactionStatus::None => write!(f, "No transaction"),
             TransactionStatus::Preparing => write!(f, "Preparing transaction"),
+            TransactionStatus::CheckingPolicy => write!(f, "Checking spending policy"),
             TransactionStatus::Building => write!(f, "Building transaction"),
             TransactionStatus::Signing { tx } => write!(f, "Signing transaction {}", tx),
             TransactionStatus::Submitting { tx_hash } => match tx_hash {
@@ -1,42 +1,115 @@
 use std::{error::Error, sync::mpsc::Sender};
 
-use ethers::types::{Address, H256, U256};
+use ethers::types::{Address, Bytes, H256, U256};
 use gloo_timers::future::TimeoutFuture;
+use wallet_api::{
+    address::checksum_address,
+    amount::{to_decimal, ETHER_DECIMALS},
+    SpendCheck,
+};
 
-use super::{events::TransactionEvent, manager::TransactionManager};
+use super::{
+    events::TransactionEvent,
+    manager::TransactionManager,
+    nonce::{self, NonceManager},
+};
+use crate::store::policies_api;
+
+const MAX_NONCE_RETRIES: usize = 3;
 
 pub struct TransactionWorker {
     manager: TransactionManager,
+    nonce_manager: NonceManager,
     event_sender: Sender<TransactionEvent>,
 }
 
 impl TransactionWorker {
     pub async fn new(
         rpc_url: &str,
+        nonce_manager: NonceManager,
         event_sender: Sender<TransactionEvent>,
     ) -> Result<Self, Box<dyn Error>> {
         let manager = TransactionManager::new(rpc_url).await?;
 
         Ok(Self {
             manager,
+            nonce_manager,
             event_sender,
         })
     }
 
+    /// Sends `amount` to `to`. Contract calls pass their calldata as `data`,
+    /// plain transfers leave it empty.
     pub async fn process_transaction(
         &mut self,
         to: Address,
         amount: U256,
+        data: Bytes,
         private_key: &str,
     ) -> Result<(), Box<dyn Error>> {
         self.event_sender.send(TransactionEvent::Started)?;
 
         // Unlock wallet
         self.manager.unlock_wallet(private_key)?;
+        let from = self.manager.address().ok_or("Wallet not unlocked")?;
+
+        // The server holds the user's spending policy and must approve the
+        // send before anything is signed
+        self.event_sender.send(TransactionEvent::CheckingPolicy)?;
+        policies_api::check_spend(&SpendCheck {
+            address: checksum_address(&from),
+            recipient: checksum_address(&to),
+            amount: to_decimal(amount, ETHER_DECIMALS)?,
+            contract_call: !data.is_empty(),
+        })
+        .await?;
+
+        let mut attempt = 0;
+        let tx_hash = loop {
+            attempt += 1;
+            let nonce = self
+                .nonce_manager
+                .next_nonce(self.manager.provider(), from)
+                .await?;
+
+            match self
+                .build_sign_and_submit(to, amount, data.clone(), nonce)
+                .await
+            {
+                Ok(tx_hash) => break tx_hash,
+                Err(e)
+                    if nonce::is_nonce_too_low(&e.to_string()) && attempt < MAX_NONCE_RETRIES =>
+                {
+                    // The nonce was consumed outside of this session (another tab or
+                    // device), so resynchronise with the node and try again.
+                    self.nonce_manager.reset(from);
+                }
+                Err(e) => {
+                    self.nonce_manager.release(from, nonce);
+                    return Err(e);
+                }
+            }
+        };
+
+        // Monitor transaction
+        self.monitor_transaction(tx_hash).await?;
 
+        Ok(())
+    }
+
+    async fn build_sign_and_submit(
+        &self,
+        to: Address,
+        amount: U256,
+        data: Bytes,
+        nonce: U256,
+    ) -> Result<H256, Box<dyn Error>> {
         // Build transaction
         self.event_sender.send(TransactionEvent::Building)?;
-        let tx_request = self.manager.build_transaction(to, amount).await?;
+        let tx_request = self
+            .manager
+            .build_transaction(to, amount, data, nonce)
+            .await?;
         self.event_sender
             .send(TransactionEvent::Built(tx_request.clone()))?;
 
@@ -51,10 +124,7 @@ impl TransactionWorker {
         self.event_sender
             .send(TransactionEvent::Submitted(tx_hash))?;
 
-        // Monitor transaction
-        self.monitor_transaction(tx_hash).await?;
-
-        Ok(())
+        Ok(tx_hash)
     }
 
     async fn monitor_transaction(&self, tx_hash: H256) -> Result<(), Box<dyn Error>> {
@@ -0,0 +1,118 @@
+//! Client for the `/api/v1/wallet/accounts` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use uuid::Uuid;
+use wallet_api::{Account, CreateAccount, UpdateAccount, UpgradeAccountKey};
+
+#[cfg(target_arch = "wasm32")]
+const ACCOUNTS_URL: &str = "http://localhost:8080/api/v1/wallet/accounts";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_accounts() -> Result<Vec<Account>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_accounts() -> Result<Vec<Account>, anyhow::Error> {
+    let res = Client::new()
+        .get(ACCOUNTS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load accounts"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_account(_account: &CreateAccount) -> Result<Account, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_account(account: &CreateAccount) -> Result<Account, anyhow::Error> {
+    let res = Client::new()
+        .post(ACCOUNTS_URL)
+        .json(account)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to create account"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn update_account(_id: Uuid, _account: &UpdateAccount) -> Result<Account, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn update_account(id: Uuid, account: &UpdateAccount) -> Result<Account, anyhow::Error> {
+    let res = Client::new()
+        .patch(format!("{}/{}", ACCOUNTS_URL, id))
+        .json(account)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to update account"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn upgrade_account_key(
+    _id: Uuid,
+    _key: &UpgradeAccountKey,
+) -> Result<Account, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn upgrade_account_key(
+    id: Uuid,
+    key: &UpgradeAccountKey,
+) -> Result<Account, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/upgrade-key", ACCOUNTS_URL, id))
+        .json(key)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to upgrade account key"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn delete_account(_id: Uuid) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn delete_account(id: Uuid) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .delete(format!("{}/{}", ACCOUNTS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Failed to delete account"))
+    }
+}
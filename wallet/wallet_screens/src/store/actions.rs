@@ -1,44 +1,234 @@
-use std::rc::Rc;
+use std::{rc::Rc, sync::mpsc::TryRecvError};
 
-use base64::{engine::general_purpose, Engine as _};
-use ethers::types::{Address, U256};
+use auth_siwe_api::SiweAddress;
+use ethers::types::{Address, Bytes, U256};
 use gloo_console as console;
-use rust_decimal::Decimal;
+use uuid::Uuid;
 use wallet_api::{
-    transaction::Transaction,
-    wallet::{Wallet, WalletError},
+    account::{Account, CustodialSend, CustodyStatus, UpdateAccount, UpgradeAccountKey},
+    address::parse_address,
+    amount::{to_decimal, ETHER_DECIMALS},
+    contact::{Contact, CreateContact, UpdateContact},
+    contract::{CreateSavedContract, SavedContract},
+    ens::normalize_name,
+    notification::Notification,
+    payment_request::{CreatePaymentRequest, PaymentRequest},
+    policy::{AllowedRecipient, CreateAllowedRecipient, PolicyOverview, SpendingPolicy},
+    scheduled_payment::{CreateScheduledPayment, ScheduledPayment, ScheduledPaymentRun},
+    transaction::{
+        RecordTransaction, Transaction, TransactionExportQuery, TransactionPage, TransactionQuery,
+    },
+    treasury::{
+        CreateTreasury, CreateTreasuryProposal, TreasuryAuditEntry, TreasuryOverview,
+        TreasuryProposal,
+    },
+    wallet::{
+        BalanceHistory, BalanceHistoryQuery, BalancePeriod, Wallet, WalletBalance, WalletError,
+    },
+    EnsResolution, WalletEvent,
 };
 use yewdux::prelude::*;
 
 use super::state::WalletStore;
 use crate::{
     services::{
-        crypto::decrypt,
-        transactions::{TransactionEvent, TransactionStatus, TransactionWorker},
+        crypto::{decrypt, CryptoError},
+        signing::{sign, SignPayload},
+        transactions::{
+            PendingTransaction, TransactionEvent, TransactionStatus, TransactionWorker,
+        },
+    },
+    store::{
+        spawn_act_on_treasury_proposal, spawn_add_account, spawn_add_allowed_recipient,
+        spawn_add_contact, spawn_add_custodial_account, spawn_cancel_payment_request,
+        spawn_cancel_policy_change, spawn_cancel_scheduled_payment, spawn_create_payment_request,
+        spawn_create_scheduled_payment, spawn_create_treasury, spawn_export_transactions,
+        spawn_generate_wallet, spawn_import_keystore, spawn_import_private_key,
+        spawn_link_sign_in_address, spawn_load_accounts, spawn_load_balance_history,
+        spawn_load_contacts, spawn_load_contracts, spawn_load_custody_status,
+        spawn_load_notifications, spawn_load_payment_requests, spawn_load_scheduled_payments,
+        spawn_load_sign_in_addresses, spawn_load_spending_policy, spawn_load_transactions,
+        spawn_load_treasuries, spawn_load_treasury, spawn_lookup_ens_names,
+        spawn_mark_all_notifications_read, spawn_mark_notification_read,
+        spawn_propose_treasury_send, spawn_record_transaction, spawn_refresh_balance,
+        spawn_remove_account, spawn_remove_allowed_recipient, spawn_remove_contact,
+        spawn_remove_contract, spawn_resolve_ens_name, spawn_save_contract, spawn_send_custodial,
+        spawn_skip_scheduled_run, spawn_submit_scheduled_run, spawn_subscribe_events,
+        spawn_unlink_sign_in_address, spawn_update_account, spawn_update_contact,
+        spawn_update_spending_policy, spawn_upgrade_account_key, wallet_load_save::SaveableWallet,
     },
-    store::{spawn_generate_wallet, spawn_refresh_balance, wallet_load_save::SaveableWallet},
 };
 
 pub enum WalletAction {
     GenerateWallet {
         mnemonic: String,
+        /// Optional BIP-39 passphrase. It is never stored.
+        passphrase: Option<String>,
+        derivation_path: String,
+        password: String,
+    },
+    ImportPrivateKey {
+        private_key: String,
+        password: String,
+    },
+    ImportKeystore {
+        keystore: String,
+        keystore_password: String,
         password: String,
     },
     LoadWallet,
+    LoadAccounts,
+    SetAccounts(Vec<Account>),
+    SelectAccount(Uuid),
+    AddAccount {
+        label: String,
+        password: String,
+        /// BIP-39 passphrase the wallet was set up with, if any.
+        passphrase: Option<String>,
+    },
+    RenameAccount {
+        id: Uuid,
+        label: String,
+    },
+    /// Creates an account whose key the server holds.
+    AddCustodialAccount {
+        label: String,
+    },
+    LoadCustodyStatus,
+    SetCustodyStatus(CustodyStatus),
+    RemoveAccount(Uuid),
+    AccountSaved(Account),
+    AccountRemoved(Uuid),
+    LoadContacts,
+    SetContacts(Vec<Contact>),
+    AddContact(CreateContact),
+    EditContact {
+        id: Uuid,
+        update: UpdateContact,
+    },
+    RemoveContact(Uuid),
+    ContactSaved(Contact),
+    ContactRemoved(Uuid),
+    LoadContracts,
+    SetContracts(Vec<SavedContract>),
+    /// Saves a contract, replacing the one saved for the same address.
+    SaveContract(CreateSavedContract),
+    RemoveContract(Uuid),
+    ContractSaved(SavedContract),
+    ContractRemoved(Uuid),
+    /// Loads the scheduled payments and their recent runs.
+    LoadScheduledPayments,
+    SetScheduledPayments(Vec<ScheduledPayment>),
+    SetScheduledRuns(Vec<ScheduledPaymentRun>),
+    CreateScheduledPayment(CreateScheduledPayment),
+    CancelScheduledPayment(Uuid),
+    ScheduledPaymentSaved(ScheduledPayment),
+    /// Signs and sends the payment of a run waiting for approval, from the
+    /// run's account.
+    ApproveScheduledRun {
+        id: Uuid,
+        password: String,
+    },
+    SkipScheduledRun(Uuid),
+    ScheduledRunSaved(ScheduledPaymentRun),
+    LoadTreasuries,
+    SetTreasuries(Vec<TreasuryOverview>),
+    CreateTreasury(CreateTreasury),
+    TreasurySaved(TreasuryOverview),
+    /// Loads the proposals and audit trail of a treasury.
+    OpenTreasury(Uuid),
+    SetTreasuryProposals(Vec<TreasuryProposal>),
+    SetTreasuryAudit(Vec<TreasuryAuditEntry>),
+    ProposeTreasurySend {
+        treasury_id: Uuid,
+        proposal: CreateTreasuryProposal,
+    },
+    /// Records the current user's approval or rejection of a proposal.
+    VoteTreasuryProposal {
+        id: Uuid,
+        approve: bool,
+    },
+    /// Signs and broadcasts an approved proposal with the treasury's key.
+    ExecuteTreasuryProposal(Uuid),
+    TreasuryProposalSaved(TreasuryProposal),
+    LoadPaymentRequests,
+    SetPaymentRequests(Vec<PaymentRequest>),
+    CreatePaymentRequest(CreatePaymentRequest),
+    CancelPaymentRequest(Uuid),
+    PaymentRequestSaved(PaymentRequest),
+    LoadSpendingPolicy,
+    SetSpendingPolicy(PolicyOverview),
+    /// Requests a policy change, which applies after the cooling-off period.
+    UpdateSpendingPolicy(SpendingPolicy),
+    CancelPolicyChange,
+    AddAllowedRecipient(CreateAllowedRecipient),
+    RemoveAllowedRecipient(Uuid),
+    AllowedRecipientSaved(AllowedRecipient),
+    AllowedRecipientRemoved(Uuid),
+    LoadSignInAddresses,
+    SetSignInAddresses(Vec<SiweAddress>),
+    /// Links the selected account's address for Sign-In With Ethereum, which
+    /// needs its key to sign the message.
+    LinkSignInAddress {
+        password: String,
+    },
+    UnlinkSignInAddress(String),
+    SignInAddressLinked(SiweAddress),
+    SignInAddressUnlinked(String),
+    /// Resolves an ENS name to an address, unless it was resolved before.
+    ResolveEnsName(String),
+    EnsNameResolved {
+        name: String,
+        address: Result<String, String>,
+    },
+    /// Looks up the ENS names of addresses that have not been looked up yet.
+    LookupEnsNames(Vec<String>),
+    EnsNamesFound(Vec<EnsResolution>),
+    /// Signs a message or typed data with the selected account's key.
+    SignMessage {
+        payload: SignPayload,
+        password: String,
+    },
+    ClearSignature,
+    /// Sends `amount` to `to`, calling it with `data` if that is not empty.
     SendTransaction {
         to: Address,
         amount: U256,
+        data: Bytes,
         password: String,
     },
-    UpdateTransactionStatus(TransactionStatus),
+    UpdateTransactionStatus {
+        id: Uuid,
+        status: TransactionStatus,
+    },
+    DismissTransaction(Uuid),
     RefreshBalance,
+    /// Reloads the current page of transactions.
     RefreshTransactions,
+    /// Loads the page of transactions the query asks for.
+    QueryTransactions(TransactionQuery),
+    /// Downloads a statement of the user's transactions.
+    ExportTransactions(TransactionExportQuery),
     SetWallet(Wallet),
     RemoveWallet,
     SetError(WalletError),
-    UpdateBalance(Decimal),
-    UpdateTransactions(Vec<Transaction>),
-    AddTransactions(Vec<Transaction>),
+    UpdateBalance(WalletBalance),
+    /// Loads the balance history of the selected account.
+    LoadBalanceHistory(BalancePeriod),
+    SetBalanceHistory(BalanceHistory),
+    ReceiveEvent(WalletEvent),
+    LoadNotifications,
+    SetNotifications(Vec<Notification>),
+    MarkNotificationRead(Uuid),
+    MarkAllNotificationsRead,
+    NotificationSaved(Notification),
+    /// The toasts for `pending_toasts` were shown.
+    ToastsShown,
+    SetTransactionPage {
+        query: TransactionQuery,
+        page: TransactionPage,
+    },
+    TransactionSaved(Transaction),
     ClearErrorMessage,
 }
 
@@ -50,35 +240,559 @@ impl Reducer<WalletStore> for WalletAction {
             WalletAction::ClearErrorMessage => {
                 new_state.last_error = None;
             }
-            WalletAction::GenerateWallet { mnemonic, password } => {
-                console::log!(format!("Loading wallet with mnemonic: {}", mnemonic));
+            WalletAction::GenerateWallet {
+                mnemonic,
+                passphrase,
+                derivation_path,
+                password,
+            } => {
+                console::log!(format!("Loading wallet at {}", derivation_path));
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_generate_wallet(
+                        mnemonic,
+                        passphrase,
+                        derivation_path,
+                        password,
+                        dispatch.clone(),
+                    );
+                }
+            }
+            WalletAction::ImportPrivateKey {
+                private_key,
+                password,
+            } => {
+                console::log!("Importing wallet from private key");
                 if let Some(dispatch) = state.dispatcher.as_ref() {
-                    spawn_generate_wallet(mnemonic, password, dispatch.clone());
+                    spawn_import_private_key(private_key, password, dispatch.clone());
+                }
+            }
+            WalletAction::ImportKeystore {
+                keystore,
+                keystore_password,
+                password,
+            } => {
+                console::log!("Importing wallet from keystore file");
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_import_keystore(keystore, keystore_password, password, dispatch.clone());
                 }
             }
             WalletAction::LoadWallet => {
                 console::log!("Loading wallet");
                 if let Some(dispatch) = state.dispatcher.as_ref() {
+                    // The event stream is rejected until the user has logged in
+                    spawn_subscribe_events(dispatch.clone());
                     let dispatch = dispatch.clone();
                     wasm_bindgen_futures::spawn_local(async move {
                         if let Ok(wallet) = Wallet::load().await {
                             dispatch.apply(super::actions::WalletAction::SetWallet(wallet));
+                            dispatch.apply(super::actions::WalletAction::LoadAccounts);
+                            dispatch.apply(super::actions::WalletAction::LoadNotifications);
+                            dispatch.apply(super::actions::WalletAction::LoadContacts);
                         } else {
                             dispatch.apply(super::actions::WalletAction::RemoveWallet);
                         }
                     });
                 }
             }
+            WalletAction::LoadAccounts => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_accounts(dispatch.clone());
+                }
+            }
+            WalletAction::SetAccounts(accounts) => {
+                new_state.accounts = accounts;
+                if !new_state
+                    .accounts
+                    .iter()
+                    .any(|account| Some(account.id) == new_state.selected_account)
+                {
+                    new_state.selected_account =
+                        new_state.accounts.first().map(|account| account.id);
+                }
+            }
+            WalletAction::SelectAccount(id) => {
+                if let Some(account) = new_state.accounts.iter().find(|account| account.id == id) {
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        spawn_refresh_balance(account.address.clone(), dispatch.clone());
+                    }
+                    new_state.selected_account = Some(id);
+                }
+            }
+            WalletAction::AddAccount {
+                label,
+                password,
+                passphrase,
+            } => {
+                if let (Some(wallet), Some(dispatch)) = (&state.wallet, state.dispatcher.as_ref()) {
+                    spawn_add_account(
+                        wallet.clone(),
+                        state.accounts.clone(),
+                        label,
+                        password,
+                        passphrase,
+                        dispatch.clone(),
+                    );
+                }
+            }
+            WalletAction::RenameAccount { id, label } => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_update_account(
+                        id,
+                        UpdateAccount { label: Some(label) },
+                        dispatch.clone(),
+                    );
+                }
+            }
+            WalletAction::AddCustodialAccount { label } => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_add_custodial_account(label, dispatch.clone());
+                }
+            }
+            WalletAction::LoadCustodyStatus => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_custody_status(dispatch.clone());
+                }
+            }
+            WalletAction::SetCustodyStatus(status) => {
+                new_state.custody = status;
+            }
+            WalletAction::RemoveAccount(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_remove_account(id, dispatch.clone());
+                }
+            }
+            WalletAction::AccountSaved(account) => {
+                match new_state
+                    .accounts
+                    .iter_mut()
+                    .find(|existing| existing.id == account.id)
+                {
+                    Some(existing) => *existing = account,
+                    None => new_state.accounts.push(account),
+                }
+            }
+            WalletAction::AccountRemoved(id) => {
+                new_state.accounts.retain(|account| account.id != id);
+                if new_state.selected_account == Some(id) {
+                    new_state.selected_account =
+                        new_state.accounts.first().map(|account| account.id);
+                }
+            }
+            WalletAction::LoadContacts => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_contacts(dispatch.clone());
+                }
+            }
+            WalletAction::SetContacts(contacts) => {
+                new_state.contacts = contacts;
+            }
+            WalletAction::AddContact(contact) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_add_contact(contact, dispatch.clone());
+                }
+            }
+            WalletAction::EditContact { id, update } => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_update_contact(id, update, dispatch.clone());
+                }
+            }
+            WalletAction::RemoveContact(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_remove_contact(id, dispatch.clone());
+                }
+            }
+            WalletAction::ContactSaved(contact) => {
+                match new_state
+                    .contacts
+                    .iter_mut()
+                    .find(|existing| existing.id == contact.id)
+                {
+                    Some(existing) => *existing = contact,
+                    None => {
+                        new_state.contacts.push(contact);
+                        new_state
+                            .contacts
+                            .sort_by_key(|contact| contact.name.to_lowercase());
+                    }
+                }
+            }
+            WalletAction::ContactRemoved(id) => {
+                new_state.contacts.retain(|contact| contact.id != id);
+            }
+            WalletAction::LoadContracts => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_contracts(dispatch.clone());
+                }
+            }
+            WalletAction::SetContracts(contracts) => {
+                new_state.contracts = contracts;
+            }
+            WalletAction::SaveContract(contract) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_save_contract(contract, dispatch.clone());
+                }
+            }
+            WalletAction::RemoveContract(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_remove_contract(id, dispatch.clone());
+                }
+            }
+            WalletAction::ContractSaved(contract) => {
+                new_state.contracts.retain(|saved| saved.id != contract.id);
+                new_state.contracts.push(contract);
+                new_state
+                    .contracts
+                    .sort_by_key(|contract| contract.name.to_lowercase());
+            }
+            WalletAction::ContractRemoved(id) => {
+                new_state.contracts.retain(|contract| contract.id != id);
+            }
+            WalletAction::LoadScheduledPayments => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_scheduled_payments(dispatch.clone());
+                }
+            }
+            WalletAction::SetScheduledPayments(payments) => {
+                new_state.scheduled_payments = payments;
+            }
+            WalletAction::SetScheduledRuns(runs) => {
+                new_state.scheduled_runs = runs;
+            }
+            WalletAction::CreateScheduledPayment(payment) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_create_scheduled_payment(payment, dispatch.clone());
+                }
+            }
+            WalletAction::CancelScheduledPayment(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_cancel_scheduled_payment(id, dispatch.clone());
+                }
+            }
+            WalletAction::ScheduledPaymentSaved(payment) => {
+                match new_state
+                    .scheduled_payments
+                    .iter_mut()
+                    .find(|saved| saved.id == payment.id)
+                {
+                    Some(saved) => *saved = payment,
+                    None => new_state.scheduled_payments.insert(0, payment),
+                }
+            }
+            WalletAction::ApproveScheduledRun { id, password } => {
+                let Some(run) = state
+                    .scheduled_runs
+                    .iter()
+                    .find(|run| run.id == id && run.is_awaiting_approval())
+                else {
+                    return Rc::new(new_state);
+                };
+                let (to, amount, data) = match run.transaction() {
+                    Ok(transaction) => transaction,
+                    Err(e) => {
+                        new_state.last_error = Some(WalletError {
+                            message: e.to_string(),
+                            timestamp: chrono::Utc::now(),
+                        });
+                        return Rc::new(new_state);
+                    }
+                };
+
+                // The run is paid like any other send, only from its own
+                // account rather than the selected one
+                let selected_account = new_state.selected_account;
+                let pending_count = new_state.pending_transactions.len();
+                new_state.selected_account = Some(run.account_id);
+                let sent = WalletAction::SendTransaction {
+                    to,
+                    amount,
+                    data,
+                    password,
+                }
+                .apply(Rc::new(new_state));
+                let mut new_state = (*sent).clone();
+                new_state.selected_account = selected_account;
+                if new_state.pending_transactions.len() > pending_count {
+                    if let Some(pending) = new_state.pending_transactions.last_mut() {
+                        pending.scheduled_run = Some(id);
+                    }
+                }
+                return Rc::new(new_state);
+            }
+            WalletAction::SkipScheduledRun(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_skip_scheduled_run(id, dispatch.clone());
+                }
+            }
+            WalletAction::ScheduledRunSaved(run) => save_scheduled_run(&mut new_state, run),
+            WalletAction::LoadTreasuries => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_treasuries(dispatch.clone());
+                }
+            }
+            WalletAction::SetTreasuries(treasuries) => {
+                new_state.treasuries = treasuries;
+            }
+            WalletAction::CreateTreasury(treasury) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_create_treasury(treasury, dispatch.clone());
+                }
+            }
+            WalletAction::TreasurySaved(overview) => {
+                new_state
+                    .treasuries
+                    .retain(|saved| saved.treasury.id != overview.treasury.id);
+                new_state.treasuries.push(overview);
+                new_state
+                    .treasuries
+                    .sort_by_key(|overview| overview.treasury.name.to_lowercase());
+            }
+            WalletAction::OpenTreasury(id) => {
+                new_state.treasury_proposals = vec![];
+                new_state.treasury_audit = vec![];
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_treasury(id, dispatch.clone());
+                }
+            }
+            WalletAction::SetTreasuryProposals(proposals) => {
+                new_state.treasury_proposals = proposals;
+            }
+            WalletAction::SetTreasuryAudit(entries) => {
+                new_state.treasury_audit = entries;
+            }
+            WalletAction::ProposeTreasurySend {
+                treasury_id,
+                proposal,
+            } => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_propose_treasury_send(treasury_id, proposal, dispatch.clone());
+                }
+            }
+            WalletAction::VoteTreasuryProposal { id, approve } => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    let action = if approve { "approve" } else { "reject" };
+                    spawn_act_on_treasury_proposal(id, action, dispatch.clone());
+                }
+            }
+            WalletAction::ExecuteTreasuryProposal(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_act_on_treasury_proposal(id, "execute", dispatch.clone());
+                }
+            }
+            WalletAction::TreasuryProposalSaved(proposal) => {
+                match new_state
+                    .treasury_proposals
+                    .iter_mut()
+                    .find(|saved| saved.id == proposal.id)
+                {
+                    Some(saved) => *saved = proposal,
+                    None => new_state.treasury_proposals.insert(0, proposal),
+                }
+            }
+            WalletAction::LoadPaymentRequests => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_payment_requests(dispatch.clone());
+                }
+            }
+            WalletAction::SetPaymentRequests(requests) => {
+                new_state.payment_requests = requests;
+            }
+            WalletAction::CreatePaymentRequest(request) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_create_payment_request(request, dispatch.clone());
+                }
+            }
+            WalletAction::CancelPaymentRequest(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_cancel_payment_request(id, dispatch.clone());
+                }
+            }
+            WalletAction::PaymentRequestSaved(request) => {
+                save_payment_request(&mut new_state, request);
+            }
+            WalletAction::LoadSpendingPolicy => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_spending_policy(dispatch.clone());
+                }
+            }
+            WalletAction::SetSpendingPolicy(overview) => {
+                new_state.spending_policy = Some(overview);
+            }
+            WalletAction::UpdateSpendingPolicy(policy) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_update_spending_policy(policy, dispatch.clone());
+                }
+            }
+            WalletAction::CancelPolicyChange => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_cancel_policy_change(dispatch.clone());
+                }
+            }
+            WalletAction::AddAllowedRecipient(recipient) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_add_allowed_recipient(recipient, dispatch.clone());
+                }
+            }
+            WalletAction::RemoveAllowedRecipient(id) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_remove_allowed_recipient(id, dispatch.clone());
+                }
+            }
+            WalletAction::AllowedRecipientSaved(recipient) => {
+                if let Some(overview) = new_state.spending_policy.as_mut() {
+                    overview.recipients.retain(|saved| saved.id != recipient.id);
+                    overview.recipients.push(recipient);
+                }
+            }
+            WalletAction::AllowedRecipientRemoved(id) => {
+                if let Some(overview) = new_state.spending_policy.as_mut() {
+                    overview.recipients.retain(|recipient| recipient.id != id);
+                }
+            }
+            WalletAction::LoadSignInAddresses => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_sign_in_addresses(dispatch.clone());
+                }
+            }
+            WalletAction::SetSignInAddresses(addresses) => {
+                new_state.sign_in_addresses = addresses;
+            }
+            WalletAction::LinkSignInAddress { password } => {
+                if let (Some(account), Some(dispatch)) =
+                    (state.selected_account(), state.dispatcher.as_ref())
+                {
+                    let unlocked = parse_address(&account.address)
+                        .map_err(|e| e.to_string())
+                        .and_then(|address| {
+                            unlock_key(&state, &mut new_state, account, &password, dispatch)
+                                .map(|private_key| (address, private_key))
+                                .map_err(|e| e.to_string())
+                        });
+                    match unlocked {
+                        Ok((address, private_key)) => {
+                            spawn_link_sign_in_address(address, private_key, dispatch.clone())
+                        }
+                        Err(message) => {
+                            new_state.last_error = Some(WalletError {
+                                message,
+                                timestamp: chrono::Utc::now(),
+                            })
+                        }
+                    }
+                }
+            }
+            WalletAction::UnlinkSignInAddress(address) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_unlink_sign_in_address(address, dispatch.clone());
+                }
+            }
+            WalletAction::SignInAddressLinked(address) => {
+                new_state
+                    .sign_in_addresses
+                    .retain(|linked| linked.address != address.address);
+                new_state.sign_in_addresses.push(address);
+                new_state.last_error = None;
+            }
+            WalletAction::SignInAddressUnlinked(address) => {
+                new_state
+                    .sign_in_addresses
+                    .retain(|linked| linked.address != address);
+            }
+            WalletAction::ResolveEnsName(name) => {
+                let name = normalize_name(&name);
+                if !state.ens_addresses.contains_key(&name) {
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        spawn_resolve_ens_name(name, dispatch.clone());
+                    }
+                }
+            }
+            WalletAction::EnsNameResolved { name, address } => {
+                new_state.ens_addresses.insert(name, address);
+            }
+            WalletAction::LookupEnsNames(addresses) => {
+                let mut unknown: Vec<String> = addresses
+                    .iter()
+                    .map(|address| address.to_lowercase())
+                    .filter(|address| !state.ens_names.contains_key(address))
+                    .collect();
+                unknown.sort();
+                unknown.dedup();
+                if !unknown.is_empty() {
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        for address in &unknown {
+                            new_state.ens_names.insert(address.clone(), None);
+                        }
+                        spawn_lookup_ens_names(unknown, dispatch.clone());
+                    }
+                }
+            }
+            WalletAction::EnsNamesFound(resolutions) => {
+                for resolution in resolutions {
+                    new_state
+                        .ens_names
+                        .insert(resolution.address.to_lowercase(), Some(resolution.name));
+                }
+            }
+            WalletAction::SignMessage { payload, password } => {
+                new_state.signature = match (state.selected_account(), state.dispatcher.as_ref()) {
+                    (Some(account), _) if account.custodial => {
+                        Some(Err("Custodial accounts cannot sign messages".to_string()))
+                    }
+                    (Some(account), Some(dispatch)) => Some(
+                        unlock_key(&state, &mut new_state, account, &password, dispatch)
+                            .map_err(|e| e.to_string())
+                            .and_then(|private_key| {
+                                sign(&private_key, &payload).map_err(|e| e.to_string())
+                            }),
+                    ),
+                    _ => None,
+                };
+            }
+            WalletAction::ClearSignature => {
+                new_state.signature = None;
+            }
             WalletAction::SendTransaction {
                 to,
                 amount,
+                data,
                 password,
             } => {
                 console::log!(format!(
                     "Sending transaction to {} with amount {}",
                     to, amount
                 ));
-                if let Some(wallet) = &new_state.wallet {
+                if let Some(account) = state.selected_account().filter(|account| account.custodial)
+                {
+                    // The server signs for custodial accounts, so there is no key to unlock
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        let mut pending =
+                            PendingTransaction::new(account.address.clone(), to, amount);
+                        pending.status = TransactionStatus::Submitting { tx_hash: None };
+                        match to_decimal(amount, ETHER_DECIMALS) {
+                            // The server only signs plain transfers
+                            Ok(_) if !data.is_empty() => {
+                                pending.data = data;
+                                pending.status = TransactionStatus::Failed {
+                                    error: "Custodial accounts cannot call contracts".to_string(),
+                                }
+                            }
+                            Ok(amount) => spawn_send_custodial(
+                                pending.id,
+                                CustodialSend {
+                                    account_id: account.id,
+                                    recipient: format!("{:?}", to),
+                                    amount,
+                                },
+                                dispatch.clone(),
+                            ),
+                            Err(e) => {
+                                pending.status = TransactionStatus::Failed {
+                                    error: e.to_string(),
+                                }
+                            }
+                        }
+                        new_state.pending_transactions.push(pending);
+                    }
+                } else if let Some(account) = state.selected_account() {
                     if let Some(dispatch) = state.dispatcher.as_ref() {
                         use std::sync::mpsc::channel;
 
@@ -86,24 +800,25 @@ impl Reducer<WalletStore> for WalletAction {
 
                         let (tx, rx) = channel::<TransactionEvent>();
                         let dispatch_clone = dispatch.clone();
+                        let mut pending =
+                            PendingTransaction::new(account.address.clone(), to, amount);
+                        pending.data = data.clone();
+                        let id = pending.id;
 
-                        // Decrypt private key
-                        let encrypted_pk =
-                            match general_purpose::STANDARD.decode(&wallet.encrypted_private_key) {
-                                Ok(pk) => pk,
-                                Err(e) => {
-                                    console::error!(
-                                        "{}",
-                                        format!("Failed to decode private key: {:#?}", e)
-                                    );
-                                    return Rc::new(new_state);
-                                }
-                            };
-
-                        let private_key = match decrypt(&encrypted_pk, &password, &wallet.salt) {
-                            Ok(pk) => pk,
+                        let private_key = match unlock_key(
+                            &state,
+                            &mut new_state,
+                            account,
+                            &password,
+                            dispatch,
+                        ) {
+                            Ok(private_key) => private_key,
                             Err(e) => {
-                                console::error!("Failed to decrypt private key: {}", e);
+                                console::error!("Failed to decrypt private key: {}", e.to_string());
+                                pending.status = TransactionStatus::Failed {
+                                    error: e.to_string(),
+                                };
+                                new_state.pending_transactions.push(pending);
                                 return Rc::new(new_state);
                             }
                         };
@@ -114,136 +829,491 @@ impl Reducer<WalletStore> for WalletAction {
                             loop {
                                 match rx.try_recv() {
                                     Ok(event) => {
-                                        let status: TransactionStatus = event.clone().into();
-                                        dispatch_clone.reduce_mut(|store| {
-                                            store.transaction_status = status.clone();
-                                        });
-
-                                        if status.is_complete() {
-                                            // Add transaction to list when complete
-                                            match event {
-                                                TransactionEvent::Confirmed {
-                                                    tx_hash,
-                                                    block_number: _,
-                                                } => {
-                                                    let tx = Transaction {
-                                                        id: tx_hash.to_string(),
-                                                        recipient: to.to_string(),
-                                                        amount,
-                                                        date: chrono::Utc::now(),
-                                                    };
-                                                    dispatch_clone.apply(
-                                                        WalletAction::AddTransactions(vec![tx]),
-                                                    );
-                                                }
-                                                TransactionEvent::Failed(error) => {
-                                                    console::error!(
-                                                        "Transaction failed: {}",
-                                                        error
-                                                    );
-                                                    let tx = Transaction {
-                                                        id: "failed".to_string(),
-                                                        recipient: to.to_string(),
-                                                        amount,
-                                                        date: chrono::Utc::now(),
-                                                    };
-                                                    dispatch_clone.apply(
-                                                        WalletAction::AddTransactions(vec![tx]),
-                                                    );
-                                                }
-                                                _ => {}
+                                        match &event {
+                                            TransactionEvent::Built(request) => {
+                                                let nonce = request.nonce;
+                                                dispatch_clone.reduce_mut(|store| {
+                                                    if let Some(pending) = store
+                                                        .pending_transactions
+                                                        .iter_mut()
+                                                        .find(|pending| pending.id == id)
+                                                    {
+                                                        pending.nonce = nonce;
+                                                    }
+                                                });
                                             }
+                                            TransactionEvent::Signed(signed) => {
+                                                let chain_id = signed
+                                                    .chain_id
+                                                    .map(|chain_id| chain_id.as_u64());
+                                                dispatch_clone.reduce_mut(|store| {
+                                                    if let Some(pending) = store
+                                                        .pending_transactions
+                                                        .iter_mut()
+                                                        .find(|pending| pending.id == id)
+                                                    {
+                                                        pending.chain_id = chain_id;
+                                                    }
+                                                });
+                                            }
+                                            _ => {}
+                                        }
+
+                                        let status: TransactionStatus = event.into();
+                                        let is_complete = status.is_complete();
+                                        dispatch_clone.apply(
+                                            WalletAction::UpdateTransactionStatus { id, status },
+                                        );
+                                        if is_complete {
                                             break;
                                         }
                                     }
-                                    Err(_) => {
+                                    Err(TryRecvError::Empty) => {
                                         TimeoutFuture::new(100).await;
                                     }
+                                    Err(TryRecvError::Disconnected) => break,
                                 }
                             }
                         });
 
                         let dispatch_clone = dispatch.clone();
+                        let nonce_manager = state.nonce_manager.clone();
                         spawn_local(async move {
-                            if let Ok(mut worker) =
-                                TransactionWorker::new("http://localhost:8545", tx).await
+                            match TransactionWorker::new("http://localhost:8545", nonce_manager, tx)
+                                .await
                             {
-                                if let Err(e) =
-                                    worker.process_transaction(to, amount, &private_key).await
-                                {
-                                    let failed_tx = TransactionStatus::Failed {
-                                        error: e.to_string(),
-                                    };
-                                    dispatch_clone
-                                        .apply(WalletAction::UpdateTransactionStatus(failed_tx));
-                                    console::error!(format!(
-                                        "Transaction failed: {}",
-                                        e.to_string()
-                                    ));
+                                Ok(mut worker) => {
+                                    if let Err(e) = worker
+                                        .process_transaction(to, amount, data, &private_key)
+                                        .await
+                                    {
+                                        let failed_tx = TransactionStatus::Failed {
+                                            error: e.to_string(),
+                                        };
+                                        dispatch_clone.apply(
+                                            WalletAction::UpdateTransactionStatus {
+                                                id,
+                                                status: failed_tx,
+                                            },
+                                        );
+                                        console::error!(format!(
+                                            "Transaction failed: {}",
+                                            e.to_string()
+                                        ));
+                                    }
+                                }
+                                Err(e) => {
+                                    dispatch_clone.apply(WalletAction::UpdateTransactionStatus {
+                                        id,
+                                        status: TransactionStatus::Failed {
+                                            error: e.to_string(),
+                                        },
+                                    });
                                 }
                             }
                         });
 
-                        new_state.transaction_status = TransactionStatus::Preparing;
+                        new_state.pending_transactions.push(pending);
                     } else {
                         console::error!("No dispatcher found");
                     }
                 }
             }
-            WalletAction::UpdateTransactionStatus(status) => {
-                if status == TransactionStatus::None {
-                    new_state.transaction_status = TransactionStatus::None;
-                } else {
-                    match new_state.transaction_status {
-                        TransactionStatus::Confirmed { .. } | TransactionStatus::Failed { .. } => {
-                            console::error!(format!(
-                                "Attempted to set status on completed transaction: {:#?}",
-                                status
-                            ));
+            WalletAction::UpdateTransactionStatus { id, status } => {
+                match new_state
+                    .pending_transactions
+                    .iter_mut()
+                    .find(|pending| pending.id == id)
+                {
+                    Some(pending) if pending.status.is_complete() => {
+                        console::error!(format!(
+                            "Attempted to set status on completed transaction: {:#?}",
+                            status
+                        ));
+                    }
+                    Some(pending) => {
+                        if let Some(tx_hash) = status.get_tx_hash() {
+                            // A send paying a scheduled payment marks its run
+                            // as submitted once it has a hash
+                            if let (Some(run), None, Some(dispatch)) = (
+                                pending.scheduled_run,
+                                pending.tx_hash,
+                                state.dispatcher.as_ref(),
+                            ) {
+                                spawn_submit_scheduled_run(
+                                    run,
+                                    format!("{:?}", tx_hash),
+                                    dispatch.clone(),
+                                );
+                            }
+                            pending.tx_hash = Some(tx_hash);
                         }
-                        _ => {
-                            new_state.transaction_status = status;
+                        pending.status = status;
+                        // The server records sends as it relays them, and the app
+                        // records how they finish
+                        if pending.status.is_complete() {
+                            if let Some(dispatch) = state.dispatcher.as_ref() {
+                                spawn_record_transaction(record_of(pending), dispatch.clone());
+                            }
                         }
                     }
+                    None => {
+                        console::error!(format!("Unknown transaction: {}", id));
+                    }
                 }
             }
+            WalletAction::DismissTransaction(id) => {
+                new_state
+                    .pending_transactions
+                    .retain(|pending| pending.id != id || !pending.status.is_complete());
+            }
             WalletAction::RefreshBalance => {
                 console::log!("Refreshing balance");
-                if let Some(wallet) = &new_state.wallet {
+                if let Some(account) = state.selected_account() {
                     if let Some(dispatch) = state.dispatcher.as_ref() {
-                        let address = wallet.address.clone();
+                        let address = account.address.clone();
                         spawn_refresh_balance(address, dispatch.clone());
                     }
                 }
             }
             WalletAction::RefreshTransactions => {
                 console::log!("Refreshing transactions");
-                // TODO: Placeholder for future backend that indexes transactions
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_transactions(state.transaction_query.clone(), dispatch.clone());
+                }
+            }
+            WalletAction::QueryTransactions(query) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    new_state.transaction_query = query.clone();
+                    spawn_load_transactions(query, dispatch.clone());
+                }
+            }
+            WalletAction::ExportTransactions(query) => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_export_transactions(query, dispatch.clone());
+                }
             }
             WalletAction::SetWallet(wallet) => {
                 new_state.wallet = Some(wallet);
             }
             WalletAction::RemoveWallet => {
                 new_state.wallet = None;
+                new_state.accounts = vec![];
+                new_state.selected_account = None;
                 new_state.transactions = vec![];
-                new_state.transaction_status = TransactionStatus::None;
+                new_state.transactions_total = 0;
+                new_state.transactions_next = None;
+                new_state.transactions_previous = None;
+                new_state.pending_transactions = vec![];
+                new_state.contacts = vec![];
+                new_state.contracts = vec![];
+                new_state.balance_history = None;
+                new_state.scheduled_payments = vec![];
+                new_state.scheduled_runs = vec![];
+                new_state.treasuries = vec![];
+                new_state.treasury_proposals = vec![];
+                new_state.treasury_audit = vec![];
+                new_state.payment_requests = vec![];
+                new_state.spending_policy = None;
+                new_state.notifications = vec![];
+                new_state.pending_toasts = vec![];
             }
             WalletAction::SetError(error) => {
                 console::error!(format!("Setting error: {:?}", error.message));
                 new_state.last_error = Some(error);
             }
             WalletAction::UpdateBalance(balance) => {
-                if let Some(wallet) = new_state.wallet.as_mut() {
-                    wallet.balance = balance;
+                update_balance(&mut new_state, balance);
+            }
+            WalletAction::LoadBalanceHistory(period) => {
+                if let Some(account) = state.selected_account() {
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        let query = BalanceHistoryQuery {
+                            address: Some(account.address.clone()),
+                            period,
+                        };
+                        spawn_load_balance_history(query, dispatch.clone());
+                    }
                 }
             }
-            WalletAction::UpdateTransactions(transactions) => {
-                new_state.transactions = transactions;
+            WalletAction::SetBalanceHistory(history) => {
+                new_state.balance_history = Some(history);
             }
-            WalletAction::AddTransactions(transactions) => {
-                new_state.transactions.extend(transactions);
+            WalletAction::ReceiveEvent(event) => match event {
+                WalletEvent::BalanceChanged(balance) => update_balance(&mut new_state, balance),
+                WalletEvent::Notification(notification) => {
+                    if !new_state
+                        .notifications
+                        .iter()
+                        .any(|stored| stored.id == notification.id)
+                    {
+                        new_state.notifications.insert(0, notification.clone());
+                        new_state.pending_toasts.push(notification);
+                    }
+                }
+                WalletEvent::PaymentRequestPaid(request) => {
+                    save_payment_request(&mut new_state, request)
+                }
+                WalletEvent::TransactionRecorded(transaction) => {
+                    for run in new_state
+                        .scheduled_runs
+                        .iter_mut()
+                        .filter(|run| run.tx_hash.is_some() && run.tx_hash == transaction.tx_hash)
+                    {
+                        run.transaction_status = Some(transaction.status.clone());
+                    }
+                    // Sends the app did not sign itself, such as custodial
+                    // ones, only learn that they failed from the server
+                    if transaction.status == Transaction::FAILED {
+                        for pending in new_state.pending_transactions.iter_mut().filter(|pending| {
+                            !pending.status.is_complete()
+                                && pending.status.get_tx_hash().is_some_and(|hash| {
+                                    Some(format!("{:?}", hash)) == transaction.tx_hash
+                                })
+                        }) {
+                            pending.status = TransactionStatus::Failed {
+                                error: transaction
+                                    .error
+                                    .clone()
+                                    .unwrap_or_else(|| "Transaction failed".to_string()),
+                            };
+                        }
+                    }
+                    save_transaction(&mut new_state, transaction)
+                }
+                WalletEvent::ScheduledPaymentRun(run) => {
+                    // A run that just came due moved its payment on to the
+                    // next run
+                    let is_new = !new_state
+                        .scheduled_runs
+                        .iter()
+                        .any(|saved| saved.id == run.id);
+                    save_scheduled_run(&mut new_state, run);
+                    if is_new {
+                        if let Some(dispatch) = state.dispatcher.as_ref() {
+                            spawn_load_scheduled_payments(dispatch.clone());
+                        }
+                    }
+                }
+                WalletEvent::TransactionConfirmed {
+                    tx_hash,
+                    block_number,
+                    ..
+                } => {
+                    for pending in new_state.pending_transactions.iter_mut().filter(|pending| {
+                        !pending.status.is_complete()
+                            && pending
+                                .status
+                                .get_tx_hash()
+                                .is_some_and(|hash| format!("{:?}", hash) == tx_hash)
+                    }) {
+                        pending.status = TransactionStatus::Confirmed {
+                            tx_hash: pending.status.get_tx_hash().unwrap_or_default(),
+                            block_number,
+                        };
+                    }
+                }
+            },
+            WalletAction::LoadNotifications => {
+                if let Some(dispatch) = state.dispatcher.as_ref() {
+                    spawn_load_notifications(dispatch.clone());
+                }
+            }
+            WalletAction::SetNotifications(notifications) => {
+                new_state.notifications = notifications;
+            }
+            WalletAction::MarkNotificationRead(id) => {
+                if let Some(notification) = new_state
+                    .notifications
+                    .iter_mut()
+                    .find(|notification| notification.id == id && !notification.is_read())
+                {
+                    notification.read_at = Some(chrono::Utc::now());
+                    if let Some(dispatch) = state.dispatcher.as_ref() {
+                        spawn_mark_notification_read(id, dispatch.clone());
+                    }
+                }
+            }
+            WalletAction::MarkAllNotificationsRead => {
+                let now = chrono::Utc::now();
+                for notification in new_state
+                    .notifications
+                    .iter_mut()
+                    .filter(|notification| !notification.is_read())
+                {
+                    notification.read_at = Some(now);
+                }
+                spawn_mark_all_notifications_read();
+            }
+            WalletAction::NotificationSaved(notification) => {
+                if let Some(stored) = new_state
+                    .notifications
+                    .iter_mut()
+                    .find(|stored| stored.id == notification.id)
+                {
+                    *stored = notification;
+                }
+            }
+            WalletAction::ToastsShown => {
+                new_state.pending_toasts.clear();
+            }
+            WalletAction::SetTransactionPage { query, page } => {
+                // Pages of queries made before the current one are stale
+                if query == state.transaction_query {
+                    new_state.transactions = page.transactions;
+                    new_state.transactions_total = page.total;
+                    new_state.transactions_next = page.next;
+                    new_state.transactions_previous = page.previous;
+                }
+            }
+            WalletAction::TransactionSaved(transaction) => {
+                save_transaction(&mut new_state, transaction);
             }
         }
         Rc::new(new_state)
     }
 }
+
+/// Decrypts the private key of `account`. Keys in the legacy format were
+/// encrypted with the wallet's salt, and are upgraded to the current keystore
+/// format both here and on the server.
+fn unlock_key(
+    state: &WalletStore,
+    new_state: &mut WalletStore,
+    account: &Account,
+    password: &str,
+    dispatch: &Dispatch<WalletStore>,
+) -> Result<String, CryptoError> {
+    let legacy_salt = state
+        .wallet
+        .as_ref()
+        .map(|wallet| wallet.salt.clone())
+        .unwrap_or_default();
+    let unlocked = decrypt(&account.encrypted_private_key, password, &legacy_salt)?;
+    if let Some(upgraded) = unlocked.upgraded {
+        // The server only takes an upgraded key signed by the key itself
+        let signature = match sign(
+            &unlocked.private_key,
+            &SignPayload::Message(upgraded.clone()),
+        ) {
+            Ok(signature) => signature,
+            Err(e) => {
+                console::error!(format!("Failed to sign upgraded key: {}", e));
+                return Ok(unlocked.private_key);
+            }
+        };
+        console::log!("Upgrading private key to the current keystore format");
+        if let Some(wallet) = new_state
+            .wallet
+            .as_mut()
+            .filter(|wallet| wallet.address == account.address)
+        {
+            wallet.encrypted_private_key = upgraded.clone();
+            wallet.salt = Vec::new();
+        }
+        if let Some(stored) = new_state
+            .accounts
+            .iter_mut()
+            .find(|stored| stored.id == account.id)
+        {
+            stored.encrypted_private_key = upgraded.clone();
+        }
+        spawn_upgrade_account_key(
+            account.id,
+            UpgradeAccountKey {
+                encrypted_private_key: upgraded,
+                signature,
+            },
+            dispatch.clone(),
+        );
+    }
+    Ok(unlocked.private_key)
+}
+
+/// Replaces the stored copy of `run` when its status changed, or adds a run
+/// that just came due as the newest one.
+fn save_scheduled_run(state: &mut WalletStore, run: ScheduledPaymentRun) {
+    match state
+        .scheduled_runs
+        .iter_mut()
+        .find(|existing| existing.id == run.id)
+    {
+        Some(existing) => *existing = run,
+        None => state.scheduled_runs.insert(0, run),
+    }
+}
+
+/// Replaces the stored copy of `request`, or adds it as the newest one.
+fn save_payment_request(state: &mut WalletStore, request: PaymentRequest) {
+    match state
+        .payment_requests
+        .iter_mut()
+        .find(|existing| existing.id == request.id)
+    {
+        Some(existing) => *existing = request,
+        None => state.payment_requests.insert(0, request),
+    }
+}
+
+/// Replaces the stored copy of `transaction`. A new one is added as the
+/// newest when the first page of all transactions is shown.
+fn save_transaction(state: &mut WalletStore, transaction: Transaction) {
+    match state
+        .transactions
+        .iter_mut()
+        .find(|existing| existing.id == transaction.id)
+    {
+        Some(existing) => *existing = transaction,
+        None if state.transaction_query == state.transaction_query.first_page()
+            && !state.transaction_query.is_filtered() =>
+        {
+            state.transactions.insert(0, transaction);
+            state.transactions_total += 1;
+        }
+        None => {}
+    }
+}
+
+/// What the backend records of a send the app made.
+fn record_of(pending: &PendingTransaction) -> RecordTransaction {
+    let (status, error) = match &pending.status {
+        TransactionStatus::Confirmed { .. } => (Transaction::CONFIRMED, None),
+        TransactionStatus::Failed { error } => (Transaction::FAILED, Some(error.clone())),
+        _ => (Transaction::PENDING, None),
+    };
+    let block_number = match &pending.status {
+        TransactionStatus::Confirmed { block_number, .. } => Some(*block_number as i64),
+        _ => None,
+    };
+    RecordTransaction {
+        address: pending.from.clone(),
+        tx_hash: pending.tx_hash.map(|tx_hash| format!("{:?}", tx_hash)),
+        recipient: format!("{:?}", pending.to),
+        amount: to_decimal(pending.amount, ETHER_DECIMALS).unwrap_or_default(),
+        status: status.to_string(),
+        nonce: pending.nonce.map(|nonce| nonce.as_u64() as i64),
+        block_number,
+        chain_id: pending.chain_id.map(|chain_id| chain_id as i64),
+        error: error.map(|error| error.chars().take(500).collect()),
+    }
+}
+
+/// Applies a balance read by the backend to the wallet and its accounts.
+fn update_balance(state: &mut WalletStore, balance: WalletBalance) {
+    if let Some(wallet) = state
+        .wallet
+        .as_mut()
+        .filter(|wallet| wallet.address == balance.address)
+    {
+        wallet.balance = balance.balance;
+        wallet.balance_synced_at = balance.synced_at;
+    }
+    for account in state
+        .accounts
+        .iter_mut()
+        .filter(|account| account.address == balance.address)
+    {
+        account.balance = balance.balance;
+        account.balance_synced_at = balance.synced_at;
+    }
+}
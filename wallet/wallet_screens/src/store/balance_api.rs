@@ -0,0 +1,56 @@
+//! Client for `GET /api/v1/wallet/balance` and its history.
+
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use wallet_api::{BalanceHistory, BalanceHistoryQuery, WalletBalance};
+
+#[cfg(target_arch = "wasm32")]
+const BALANCE_URL: &str = "http://localhost:8080/api/v1/wallet/balance";
+#[cfg(target_arch = "wasm32")]
+const BALANCE_HISTORY_URL: &str = "http://localhost:8080/api/v1/wallet/balance/history";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_balance(_address: &str, _refresh: bool) -> Result<WalletBalance, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn get_balance(address: &str, refresh: bool) -> Result<WalletBalance, anyhow::Error> {
+    let res = Client::new()
+        .get(BALANCE_URL)
+        .query(&[("address", address), ("refresh", &refresh.to_string())])
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load balance"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_balance_history(
+    _query: &BalanceHistoryQuery,
+) -> Result<BalanceHistory, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn get_balance_history(
+    query: &BalanceHistoryQuery,
+) -> Result<BalanceHistory, anyhow::Error> {
+    let res = Client::new()
+        .get(BALANCE_HISTORY_URL)
+        .query(query)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load balance history"))
+    }
+}
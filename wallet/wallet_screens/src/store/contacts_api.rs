@@ -0,0 +1,100 @@
+//! Client for the `/api/v1/wallet/contacts` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use uuid::Uuid;
+use wallet_api::{Contact, CreateContact, UpdateContact};
+
+#[cfg(target_arch = "wasm32")]
+const CONTACTS_URL: &str = "http://localhost:8080/api/v1/wallet/contacts";
+
+/// Reads the saved contact, or the server's reason for rejecting it.
+#[cfg(target_arch = "wasm32")]
+async fn saved_contact(res: Response, fallback: &str) -> Result<Contact, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_contacts() -> Result<Vec<Contact>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_contacts() -> Result<Vec<Contact>, anyhow::Error> {
+    let res = Client::new()
+        .get(CONTACTS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load contacts"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_contact(_contact: &CreateContact) -> Result<Contact, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_contact(contact: &CreateContact) -> Result<Contact, anyhow::Error> {
+    let res = Client::new()
+        .post(CONTACTS_URL)
+        .json(contact)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    saved_contact(res, "Failed to save contact").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn update_contact(_id: Uuid, _contact: &UpdateContact) -> Result<Contact, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn update_contact(id: Uuid, contact: &UpdateContact) -> Result<Contact, anyhow::Error> {
+    let res = Client::new()
+        .patch(format!("{}/{}", CONTACTS_URL, id))
+        .json(contact)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    saved_contact(res, "Failed to update contact").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn delete_contact(_id: Uuid) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn delete_contact(id: Uuid) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .delete(format!("{}/{}", CONTACTS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Failed to delete contact"))
+    }
+}
@@ -0,0 +1,79 @@
+//! Client for the `/api/v1/wallet/contracts` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use uuid::Uuid;
+use wallet_api::{CreateSavedContract, SavedContract};
+
+#[cfg(target_arch = "wasm32")]
+const CONTRACTS_URL: &str = "http://localhost:8080/api/v1/wallet/contracts";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_contracts() -> Result<Vec<SavedContract>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_contracts() -> Result<Vec<SavedContract>, anyhow::Error> {
+    let res = Client::new()
+        .get(CONTRACTS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load contracts"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn save_contract(
+    _contract: &CreateSavedContract,
+) -> Result<SavedContract, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn save_contract(contract: &CreateSavedContract) -> Result<SavedContract, anyhow::Error> {
+    let res = Client::new()
+        .post(CONTRACTS_URL)
+        .json(contract)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to save contract".to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn delete_contract(_id: Uuid) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn delete_contract(id: Uuid) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .delete(format!("{}/{}", CONTRACTS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Failed to delete contract"))
+    }
+}
@@ -0,0 +1,84 @@
+//! Client for the `/api/v1/wallet/custody` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use wallet_api::{Account, BroadcastResult, CreateCustodialAccount, CustodialSend, CustodyStatus};
+
+#[cfg(target_arch = "wasm32")]
+const CUSTODY_URL: &str = "http://localhost:8080/api/v1/wallet/custody";
+
+/// Reads a successful response, or the server's reason for refusing the
+/// request.
+#[cfg(target_arch = "wasm32")]
+async fn read<T: serde::de::DeserializeOwned>(
+    res: Response,
+    fallback: &str,
+) -> Result<T, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_custody_status() -> Result<CustodyStatus, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn get_custody_status() -> Result<CustodyStatus, anyhow::Error> {
+    let res = Client::new()
+        .get(CUSTODY_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load custody status").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_custodial_account(
+    _account: &CreateCustodialAccount,
+) -> Result<Account, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_custodial_account(
+    account: &CreateCustodialAccount,
+) -> Result<Account, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/accounts", CUSTODY_URL))
+        .json(account)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to create custodial account").await
+}
+
+/// Has the server sign and relay a send from a custodial account.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn send_custodial(_send: &CustodialSend) -> Result<BroadcastResult, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn send_custodial(send: &CustodialSend) -> Result<BroadcastResult, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/send", CUSTODY_URL))
+        .json(send)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to send transaction").await
+}
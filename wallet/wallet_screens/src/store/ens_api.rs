@@ -0,0 +1,56 @@
+//! Client for the `/api/v1/wallet/ens` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use wallet_api::EnsResolution;
+
+#[cfg(target_arch = "wasm32")]
+const ENS_URL: &str = "http://localhost:8080/api/v1/wallet/ens";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn resolve_name(_name: &str) -> Result<EnsResolution, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn resolve_name(name: &str) -> Result<EnsResolution, anyhow::Error> {
+    let res = Client::new()
+        .get(format!("{}/resolve", ENS_URL))
+        .query(&[("name", name)])
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| format!("Failed to resolve {}", name));
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn lookup_addresses(_addresses: &[String]) -> Result<Vec<EnsResolution>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn lookup_addresses(addresses: &[String]) -> Result<Vec<EnsResolution>, anyhow::Error> {
+    let res = Client::new()
+        .get(format!("{}/lookup", ENS_URL))
+        .query(&[("addresses", addresses.join(","))])
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to look up ENS names"))
+    }
+}
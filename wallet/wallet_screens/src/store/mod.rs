@@ -1,9 +1,38 @@
+mod accounts_api;
 mod actions;
+mod balance_api;
+mod contacts_api;
+mod contracts_api;
+mod custody_api;
+mod ens_api;
+mod notifications_api;
 mod operations;
+mod payment_requests_api;
+pub(crate) mod policies_api;
+mod scheduled_payments_api;
 mod state;
+pub(crate) mod transactions_api;
+mod treasury_api;
 mod wallet_load_save;
 
 pub use actions::WalletAction;
-use operations::{spawn_generate_wallet, spawn_refresh_balance};
+use operations::{
+    spawn_act_on_treasury_proposal, spawn_add_account, spawn_add_allowed_recipient,
+    spawn_add_contact, spawn_add_custodial_account, spawn_cancel_payment_request,
+    spawn_cancel_policy_change, spawn_cancel_scheduled_payment, spawn_create_payment_request,
+    spawn_create_scheduled_payment, spawn_create_treasury, spawn_export_transactions,
+    spawn_generate_wallet, spawn_import_keystore, spawn_import_private_key,
+    spawn_link_sign_in_address, spawn_load_accounts, spawn_load_balance_history,
+    spawn_load_contacts, spawn_load_contracts, spawn_load_custody_status, spawn_load_notifications,
+    spawn_load_payment_requests, spawn_load_scheduled_payments, spawn_load_sign_in_addresses,
+    spawn_load_spending_policy, spawn_load_transactions, spawn_load_treasuries,
+    spawn_load_treasury, spawn_lookup_ens_names, spawn_mark_all_notifications_read,
+    spawn_mark_notification_read, spawn_propose_treasury_send, spawn_record_transaction,
+    spawn_refresh_balance, spawn_remove_account, spawn_remove_allowed_recipient,
+    spawn_remove_contact, spawn_remove_contract, spawn_resolve_ens_name, spawn_save_contract,
+    spawn_send_custodial, spawn_skip_scheduled_run, spawn_submit_scheduled_run,
+    spawn_subscribe_events, spawn_unlink_sign_in_address, spawn_update_account,
+    spawn_update_contact, spawn_update_spending_policy, spawn_upgrade_account_key,
+};
 pub use state::WalletStore;
 pub use wallet_load_save::SaveableWallet;
@@ -0,0 +1,69 @@
+//! Client for the `/api/v1/wallet/notifications` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use uuid::Uuid;
+use wallet_api::Notification;
+
+#[cfg(target_arch = "wasm32")]
+const NOTIFICATIONS_URL: &str = "http://localhost:8080/api/v1/wallet/notifications";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_notifications() -> Result<Vec<Notification>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_notifications() -> Result<Vec<Notification>, anyhow::Error> {
+    let res = Client::new()
+        .get(NOTIFICATIONS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load notifications"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn mark_read(_id: Uuid) -> Result<Notification, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn mark_read(id: Uuid) -> Result<Notification, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/read", NOTIFICATIONS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to mark notification as read"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn mark_all_read() -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn mark_all_read() -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/read", NOTIFICATIONS_URL))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Failed to mark notifications as read"))
+    }
+}
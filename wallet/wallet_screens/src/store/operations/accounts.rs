@@ -0,0 +1,168 @@
+use chrono::Utc;
+use ethers::{signers::Signer, utils::hash_message};
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{
+    account::{Account, CreateAccount, UpdateAccount, UpgradeAccountKey},
+    wallet::{Wallet, WalletError},
+};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::{
+    super::{accounts_api, actions::WalletAction, state::WalletStore},
+    load_wallet::encrypt_private_key,
+};
+use crate::services::{
+    crypto::decrypt,
+    hd_wallet::{derivation_path, derive_signer, DEFAULT_DERIVATION_PATH_PREFIX},
+};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_accounts(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match accounts_api::list_accounts().await {
+            Ok(accounts) => dispatch.apply(WalletAction::SetAccounts(accounts)),
+            Err(e) => set_error(&dispatch, format!("Failed to load accounts: {}", e)),
+        }
+    });
+}
+
+/// Derives the next unused account from the wallet's recovery phrase.
+fn derive_next_account(
+    wallet: &Wallet,
+    accounts: &[Account],
+    label: String,
+    password: &str,
+    passphrase: Option<&str>,
+) -> Result<CreateAccount, String> {
+    let encrypted_mnemonic = wallet.encrypted_mnemonic.as_ref().ok_or_else(|| {
+        "New accounts can only be derived from wallets set up with a recovery phrase".to_string()
+    })?;
+    let mnemonic = decrypt(encrypted_mnemonic, password, &wallet.salt)
+        .map_err(|e| e.to_string())?
+        .private_key;
+
+    let wallet_path = wallet
+        .derivation_path
+        .clone()
+        .unwrap_or_else(|| derivation_path(DEFAULT_DERIVATION_PATH_PREFIX, 0));
+    let prefix = wallet_path
+        .rsplit_once('/')
+        .map(|(prefix, _)| prefix)
+        .unwrap_or(DEFAULT_DERIVATION_PATH_PREFIX);
+
+    // A wrong BIP-39 passphrase silently derives a different wallet, so check it
+    // against the wallet's own address first.
+    let primary = derive_signer(&mnemonic, passphrase, &wallet_path).map_err(|e| e.to_string())?;
+    if !format!("{:?}", primary.address()).eq_ignore_ascii_case(&wallet.address) {
+        return Err("The passphrase does not match this wallet".to_string());
+    }
+
+    let index = accounts
+        .iter()
+        .filter_map(|account| account.derivation_index)
+        .max()
+        .map_or(0, |index| index + 1);
+    let signer = derive_signer(
+        &mnemonic,
+        passphrase,
+        &derivation_path(prefix, index as u32),
+    )
+    .map_err(|e| e.to_string())?;
+
+    let encrypted_private_key =
+        encrypt_private_key(&signer, password).map_err(|e| e.to_string())?;
+    let address = format!("{:?}", signer.address());
+    // The server only adds an account signed by the account's own key
+    let message = CreateAccount::message(wallet.user_id, &address, &encrypted_private_key);
+    let signature = signer
+        .sign_hash(hash_message(message))
+        .map_err(|e| e.to_string())?;
+
+    Ok(CreateAccount {
+        label,
+        derivation_index: Some(index),
+        encrypted_private_key,
+        address,
+        signature: format!("0x{}", signature),
+    })
+}
+
+pub fn spawn_add_account(
+    wallet: Wallet,
+    accounts: Vec<Account>,
+    label: String,
+    password: String,
+    passphrase: Option<String>,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        let account = match derive_next_account(
+            &wallet,
+            &accounts,
+            label,
+            &password,
+            passphrase.as_deref(),
+        ) {
+            Ok(account) => account,
+            Err(e) => {
+                set_error(&dispatch, format!("Failed to add account: {}", e));
+                return;
+            }
+        };
+
+        match accounts_api::create_account(&account).await {
+            Ok(account) => {
+                let id = account.id;
+                dispatch.apply(WalletAction::AccountSaved(account));
+                dispatch.apply(WalletAction::SelectAccount(id));
+            }
+            Err(e) => set_error(&dispatch, format!("Failed to add account: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_update_account(id: Uuid, update: UpdateAccount, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match accounts_api::update_account(id, &update).await {
+            Ok(account) => dispatch.apply(WalletAction::AccountSaved(account)),
+            Err(e) => {
+                console::error!(format!("Failed to update account {}: {}", id, e));
+                set_error(&dispatch, format!("Failed to update account: {}", e));
+            }
+        }
+    });
+}
+
+/// Stores a key upgraded to the current keystore format. Failures are only
+/// logged: the
+/// previous key still decrypts with the same password, so the upgrade is simply
+/// retried the next time the key is used.
+pub fn spawn_upgrade_account_key(
+    id: Uuid,
+    key: UpgradeAccountKey,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match accounts_api::upgrade_account_key(id, &key).await {
+            Ok(account) => dispatch.apply(WalletAction::AccountSaved(account)),
+            Err(e) => console::error!(format!("Failed to upgrade key of account {}: {}", id, e)),
+        }
+    });
+}
+
+pub fn spawn_remove_account(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match accounts_api::delete_account(id).await {
+            Ok(()) => dispatch.apply(WalletAction::AccountRemoved(id)),
+            Err(e) => set_error(&dispatch, format!("Failed to remove account: {}", e)),
+        }
+    });
+}
@@ -0,0 +1,51 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateContact, UpdateContact};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, contacts_api, state::WalletStore};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_contacts(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contacts_api::list_contacts().await {
+            Ok(contacts) => dispatch.apply(WalletAction::SetContacts(contacts)),
+            Err(e) => console::error!(format!("Failed to load contacts: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_add_contact(contact: CreateContact, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contacts_api::create_contact(&contact).await {
+            Ok(contact) => dispatch.apply(WalletAction::ContactSaved(contact)),
+            Err(e) => set_error(&dispatch, format!("Failed to save contact: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_update_contact(id: Uuid, update: UpdateContact, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contacts_api::update_contact(id, &update).await {
+            Ok(contact) => dispatch.apply(WalletAction::ContactSaved(contact)),
+            Err(e) => set_error(&dispatch, format!("Failed to update contact: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_remove_contact(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contacts_api::delete_contact(id).await {
+            Ok(()) => dispatch.apply(WalletAction::ContactRemoved(id)),
+            Err(e) => set_error(&dispatch, format!("Failed to remove contact: {}", e)),
+        }
+    });
+}
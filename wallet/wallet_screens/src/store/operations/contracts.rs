@@ -0,0 +1,42 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateSavedContract};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, contracts_api, state::WalletStore};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_contracts(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contracts_api::list_contracts().await {
+            Ok(contracts) => dispatch.apply(WalletAction::SetContracts(contracts)),
+            Err(e) => console::error!(format!("Failed to load contracts: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_save_contract(contract: CreateSavedContract, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contracts_api::save_contract(&contract).await {
+            Ok(contract) => dispatch.apply(WalletAction::ContractSaved(contract)),
+            Err(e) => set_error(&dispatch, format!("Failed to save contract: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_remove_contract(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match contracts_api::delete_contract(id).await {
+            Ok(()) => dispatch.apply(WalletAction::ContractRemoved(id)),
+            Err(e) => set_error(&dispatch, format!("Failed to remove contract: {}", e)),
+        }
+    });
+}
@@ -0,0 +1,60 @@
+use chrono::Utc;
+use ethers::types::H256;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateCustodialAccount, CustodialSend};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, custody_api, state::WalletStore};
+use crate::services::transactions::TransactionStatus;
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_custody_status(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match custody_api::get_custody_status().await {
+            Ok(status) => dispatch.apply(WalletAction::SetCustodyStatus(status)),
+            Err(e) => console::error!(format!("Failed to load custody status: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_add_custodial_account(label: String, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match custody_api::create_custodial_account(&CreateCustodialAccount { label }).await {
+            Ok(account) => {
+                let id = account.id;
+                dispatch.apply(WalletAction::AccountSaved(account));
+                dispatch.apply(WalletAction::SelectAccount(id));
+            }
+            Err(e) => set_error(&dispatch, format!("Failed to add account: {}", e)),
+        }
+    });
+}
+
+/// Has the server sign and relay the send tracked as pending transaction
+/// `id`. The chain watcher reports when it is mined.
+pub fn spawn_send_custodial(id: Uuid, send: CustodialSend, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        let status = match custody_api::send_custodial(&send).await {
+            Ok(result) => match result.tx_hash.parse::<H256>() {
+                Ok(tx_hash) => TransactionStatus::Submitting {
+                    tx_hash: Some(tx_hash),
+                },
+                Err(e) => TransactionStatus::Failed {
+                    error: e.to_string(),
+                },
+            },
+            Err(e) => TransactionStatus::Failed {
+                error: e.to_string(),
+            },
+        };
+        dispatch.apply(WalletAction::UpdateTransactionStatus { id, status });
+    });
+}
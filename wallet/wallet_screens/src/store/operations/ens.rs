@@ -0,0 +1,29 @@
+use gloo_console as console;
+use wallet_api::ens::MAX_LOOKUP_ADDRESSES;
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, ens_api, state::WalletStore};
+
+pub fn spawn_resolve_ens_name(name: String, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        let address = ens_api::resolve_name(&name)
+            .await
+            .map(|resolution| resolution.address)
+            .map_err(|e| e.to_string());
+        dispatch.apply(WalletAction::EnsNameResolved { name, address });
+    });
+}
+
+/// Looks up the names of `addresses` in batches the backend accepts. Failed
+/// batches are only logged; those addresses are shown without a name.
+pub fn spawn_lookup_ens_names(addresses: Vec<String>, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        for batch in addresses.chunks(MAX_LOOKUP_ADDRESSES) {
+            match ens_api::lookup_addresses(batch).await {
+                Ok(resolutions) => dispatch.apply(WalletAction::EnsNamesFound(resolutions)),
+                Err(e) => console::error!(format!("Failed to look up ENS names: {}", e)),
+            }
+        }
+    });
+}
@@ -0,0 +1,63 @@
+use chrono::Utc;
+use ethers::signers::LocalWallet;
+use gloo_console as console;
+use wallet_api::wallet::{Wallet, WalletError};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::{
+    super::{actions::WalletAction, state::WalletStore},
+    load_wallet::{activate_wallet, encrypt_wallet},
+};
+use crate::services::crypto::{CryptoError, Web3Keystore};
+
+pub fn spawn_import_private_key(
+    private_key: String,
+    password: String,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        let private_key = private_key.trim();
+        let result = private_key
+            .parse::<LocalWallet>()
+            .map_err(|_| CryptoError::Malformed("not a valid private key".to_string()))
+            .and_then(|signer| encrypt_wallet(&signer, None, None, &password));
+
+        import_result(result, dispatch).await;
+    });
+}
+
+pub fn spawn_import_keystore(
+    keystore: String,
+    keystore_password: String,
+    password: String,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        let result = Web3Keystore::from_json(&keystore)
+            .and_then(|keystore| keystore.decrypt(&keystore_password))
+            .and_then(|private_key| {
+                LocalWallet::from_bytes(&private_key)
+                    .map_err(|e| CryptoError::Malformed(e.to_string()))
+            })
+            .and_then(|signer| encrypt_wallet(&signer, None, None, &password));
+
+        import_result(result, dispatch).await;
+    });
+}
+
+async fn import_result(result: Result<Wallet, CryptoError>, dispatch: Dispatch<WalletStore>) {
+    match result {
+        Ok(wallet) => {
+            console::log!(format!("Imported wallet {}", wallet.address));
+            activate_wallet(wallet, &dispatch).await;
+        }
+        Err(e) => {
+            console::error!(format!("Failed to import wallet: {}", e));
+            dispatch.apply(WalletAction::SetError(WalletError {
+                message: format!("Failed to import wallet: {}", e),
+                timestamp: Utc::now(),
+            }));
+        }
+    }
+}
@@ -1,56 +1,120 @@
-use base64::{engine::general_purpose, Engine as _};
 use chrono::Utc;
-use ethers::{
-    prelude::*,
-    signers::{coins_bip39::English, MnemonicBuilder, Signer},
-};
+use ethers::signers::{LocalWallet, Signer};
 use gloo_console as console;
-use rand::{thread_rng, Rng};
 use rust_decimal::Decimal;
 use uuid::Uuid;
 use wallet_api::wallet::{Wallet, WalletError};
 use wasm_bindgen_futures::spawn_local;
 use yewdux::prelude::*;
 
-use super::super::{actions::WalletAction, state::WalletStore};
-use crate::services::crypto::encrypt;
+use super::super::{
+    actions::WalletAction,
+    state::WalletStore,
+    wallet_load_save::{SaveWalletError, SaveableWallet},
+};
+use crate::services::{
+    crypto::{encrypt, CryptoError},
+    hd_wallet::{derive_signer, normalize_phrase},
+};
+
+pub(super) fn encrypt_private_key(
+    signer: &LocalWallet,
+    password: &str,
+) -> Result<String, CryptoError> {
+    let unencrypted_pk = signer
+        .signer()
+        .to_bytes()
+        .iter()
+        .map(|&i| format!("{:02X}", i))
+        .collect::<Vec<String>>()
+        .join("");
 
-pub fn spawn_generate_wallet(mnemonic: String, password: String, dispatch: Dispatch<WalletStore>) {
+    encrypt(&unencrypted_pk, password)
+}
+
+/// Builds the stored wallet for `signer`, encrypting its private key and, for
+/// wallets derived from a recovery phrase, the phrase under `password`.
+pub(super) fn encrypt_wallet(
+    signer: &LocalWallet,
+    mnemonic: Option<&str>,
+    derivation_path: Option<String>,
+    password: &str,
+) -> Result<Wallet, CryptoError> {
+    let encrypted_private_key = encrypt_private_key(signer, password)?;
+    let encrypted_mnemonic = mnemonic
+        .map(|mnemonic| encrypt(mnemonic, password))
+        .transpose()?;
+
+    Ok(Wallet {
+        id: Uuid::new_v4(),
+        user_id: Uuid::new_v4(), // TODO: Replace with actual user_id
+        encrypted_private_key,
+        encrypted_mnemonic,
+        derivation_path,
+        address: format!("{:?}", signer.address()),
+        balance: Decimal::from(0),
+        // The KDF salt is part of the keystore envelope
+        salt: Vec::new(),
+        token_decimals: 18,
+        created_at: Some(Utc::now()),
+        updated_at: Some(Utc::now()),
+        balance_synced_at: None,
+    })
+}
+
+/// Makes `wallet` the current wallet. The wallet is saved before its accounts
+/// are loaded, because the backend creates the first account on save. A user
+/// who already has a wallet replaces its key instead, which the backend only
+/// allows shortly after logging in.
+pub(super) async fn activate_wallet(wallet: Wallet, dispatch: &Dispatch<WalletStore>) {
+    let saved = match wallet.create().await {
+        Err(SaveWalletError::Conflict(_)) => wallet.rotate_key().await,
+        result => result,
+    };
+
+    match saved {
+        Ok(wallet) => {
+            dispatch.apply(WalletAction::SetWallet(wallet));
+            dispatch.apply(WalletAction::LoadAccounts);
+        }
+        Err(e) => {
+            console::error!(format!("Failed to save wallet: {}", e));
+            dispatch.apply(WalletAction::SetError(WalletError {
+                message: format!("Failed to save wallet: {}", e),
+                timestamp: Utc::now(),
+            }));
+        }
+    }
+}
+
+pub fn spawn_generate_wallet(
+    mnemonic: String,
+    passphrase: Option<String>,
+    derivation_path: String,
+    password: String,
+    dispatch: Dispatch<WalletStore>,
+) {
     spawn_local(async move {
-        let mnemonic = mnemonic.trim().to_string();
-        let wallet = MnemonicBuilder::<English>::default()
-            .phrase(mnemonic.as_str())
-            .build();
+        let wallet = derive_signer(&mnemonic, passphrase.as_deref(), &derivation_path);
 
         match wallet {
-            Ok(wallet) => {
-                let address = wallet.address();
-                let unencrypted_pk = wallet
-                    .signer()
-                    .to_bytes()
-                    .iter()
-                    .map(|&i| format!("{:02X}", i))
-                    .collect::<Vec<String>>()
-                    .join("");
-
-                let mut salt = [0u8; 16];
-                thread_rng().fill(&mut salt);
-
-                let encrypted_pk = encrypt(unencrypted_pk, &password, &salt);
-                let new_wallet = Wallet {
-                    id: Uuid::new_v4(),
-                    user_id: Uuid::new_v4(), // TODO: Replace with actual user_id
-                    encrypted_private_key: general_purpose::STANDARD.encode(encrypted_pk.clone()),
-                    address: format!("{:?}", address),
-                    balance: Decimal::from(0),
-                    salt: salt.to_vec(),
-                    token_decimals: 18,
-                    created_at: Some(Utc::now()),
-                    updated_at: Some(Utc::now()),
-                };
-                dispatch.apply(WalletAction::SetWallet(new_wallet));
-                console::log!("Wallet loaded successfully");
-            }
+            Ok(wallet) => match encrypt_wallet(
+                &wallet,
+                Some(&normalize_phrase(&mnemonic)),
+                Some(derivation_path),
+                &password,
+            ) {
+                Ok(new_wallet) => {
+                    activate_wallet(new_wallet, &dispatch).await;
+                    console::log!("Wallet loaded successfully");
+                }
+                Err(e) => {
+                    dispatch.apply(WalletAction::SetError(WalletError {
+                        message: format!("Failed to encrypt private key: {}", e),
+                        timestamp: Utc::now(),
+                    }));
+                }
+            },
             Err(e) => {
                 console::error!(format!("Failed setting the wallet up: {}", e));
                 dispatch.apply(WalletAction::SetError(WalletError {
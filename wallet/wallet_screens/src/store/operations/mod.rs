@@ -1,5 +1,55 @@
+mod accounts;
+mod contacts;
+mod contracts;
+mod custody;
+mod ens;
+mod import_wallet;
 mod load_wallet;
+mod notifications;
+mod payment_requests;
+mod policies;
 mod refresh_balance;
+mod scheduled_payments;
+mod sign_in;
+mod subscribe_events;
+mod transactions;
+mod treasury;
 
+pub use accounts::{
+    spawn_add_account, spawn_load_accounts, spawn_remove_account, spawn_update_account,
+    spawn_upgrade_account_key,
+};
+pub use contacts::{
+    spawn_add_contact, spawn_load_contacts, spawn_remove_contact, spawn_update_contact,
+};
+pub use contracts::{spawn_load_contracts, spawn_remove_contract, spawn_save_contract};
+pub use custody::{spawn_add_custodial_account, spawn_load_custody_status, spawn_send_custodial};
+pub use ens::{spawn_lookup_ens_names, spawn_resolve_ens_name};
+pub use import_wallet::{spawn_import_keystore, spawn_import_private_key};
 pub use load_wallet::spawn_generate_wallet;
-pub use refresh_balance::spawn_refresh_balance;
+pub use notifications::{
+    spawn_load_notifications, spawn_mark_all_notifications_read, spawn_mark_notification_read,
+};
+pub use payment_requests::{
+    spawn_cancel_payment_request, spawn_create_payment_request, spawn_load_payment_requests,
+};
+pub use policies::{
+    spawn_add_allowed_recipient, spawn_cancel_policy_change, spawn_load_spending_policy,
+    spawn_remove_allowed_recipient, spawn_update_spending_policy,
+};
+pub use refresh_balance::{spawn_load_balance_history, spawn_refresh_balance};
+pub use scheduled_payments::{
+    spawn_cancel_scheduled_payment, spawn_create_scheduled_payment, spawn_load_scheduled_payments,
+    spawn_skip_scheduled_run, spawn_submit_scheduled_run,
+};
+pub use sign_in::{
+    spawn_link_sign_in_address, spawn_load_sign_in_addresses, spawn_unlink_sign_in_address,
+};
+pub use subscribe_events::spawn_subscribe_events;
+pub use transactions::{
+    spawn_export_transactions, spawn_load_transactions, spawn_record_transaction,
+};
+pub use treasury::{
+    spawn_act_on_treasury_proposal, spawn_create_treasury, spawn_load_treasuries,
+    spawn_load_treasury, spawn_propose_treasury_send,
+};
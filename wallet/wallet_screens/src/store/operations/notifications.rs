@@ -0,0 +1,35 @@
+use gloo_console as console;
+use uuid::Uuid;
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, notifications_api, state::WalletStore};
+
+pub fn spawn_load_notifications(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match notifications_api::list_notifications().await {
+            Ok(notifications) => dispatch.apply(WalletAction::SetNotifications(notifications)),
+            Err(e) => console::error!(format!("Failed to load notifications: {}", e)),
+        }
+    });
+}
+
+// Notifications are marked as read in the store right away, so failures are
+// only logged; they show up as unread again on the next load.
+
+pub fn spawn_mark_notification_read(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match notifications_api::mark_read(id).await {
+            Ok(notification) => dispatch.apply(WalletAction::NotificationSaved(notification)),
+            Err(e) => console::error!(format!("Failed to mark notification {} as read: {}", id, e)),
+        }
+    });
+}
+
+pub fn spawn_mark_all_notifications_read() {
+    spawn_local(async move {
+        if let Err(e) = notifications_api::mark_all_read().await {
+            console::error!(format!("Failed to mark notifications as read: {}", e));
+        }
+    });
+}
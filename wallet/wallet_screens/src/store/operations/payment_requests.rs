@@ -0,0 +1,51 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreatePaymentRequest};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, payment_requests_api, state::WalletStore};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_payment_requests(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match payment_requests_api::list_payment_requests().await {
+            Ok(requests) => dispatch.apply(WalletAction::SetPaymentRequests(requests)),
+            Err(e) => console::error!(format!("Failed to load payment requests: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_create_payment_request(
+    request: CreatePaymentRequest,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match payment_requests_api::create_payment_request(&request).await {
+            Ok(request) => dispatch.apply(WalletAction::PaymentRequestSaved(request)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to create payment request: {}", e),
+            ),
+        }
+    });
+}
+
+pub fn spawn_cancel_payment_request(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match payment_requests_api::cancel_payment_request(id).await {
+            Ok(request) => dispatch.apply(WalletAction::PaymentRequestSaved(request)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to cancel payment request: {}", e),
+            ),
+        }
+    });
+}
@@ -0,0 +1,66 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateAllowedRecipient, SpendingPolicy};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, policies_api, state::WalletStore};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_spending_policy(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match policies_api::get_policy().await {
+            Ok(overview) => dispatch.apply(WalletAction::SetSpendingPolicy(overview)),
+            Err(e) => console::error!(format!("Failed to load spending policy: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_update_spending_policy(policy: SpendingPolicy, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match policies_api::update_policy(&policy).await {
+            Ok(overview) => dispatch.apply(WalletAction::SetSpendingPolicy(overview)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to change spending policy: {}", e),
+            ),
+        }
+    });
+}
+
+pub fn spawn_cancel_policy_change(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match policies_api::cancel_policy_change().await {
+            Ok(overview) => dispatch.apply(WalletAction::SetSpendingPolicy(overview)),
+            Err(e) => set_error(&dispatch, format!("Failed to cancel policy change: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_add_allowed_recipient(
+    recipient: CreateAllowedRecipient,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match policies_api::add_allowed_recipient(&recipient).await {
+            Ok(recipient) => dispatch.apply(WalletAction::AllowedRecipientSaved(recipient)),
+            Err(e) => set_error(&dispatch, format!("Failed to allow-list recipient: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_remove_allowed_recipient(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match policies_api::remove_allowed_recipient(id).await {
+            Ok(()) => dispatch.apply(WalletAction::AllowedRecipientRemoved(id)),
+            Err(e) => set_error(&dispatch, format!("Failed to remove recipient: {}", e)),
+        }
+    });
+}
@@ -1,31 +1,19 @@
 use chrono::Utc;
-use ethers::{
-    prelude::*,
-    providers::{Http, Provider},
-};
 use gloo_console as console;
-use rust_decimal::{prelude::FromPrimitive, Decimal};
-use wallet_api::wallet::WalletError;
+use wallet_api::{wallet::WalletError, BalanceHistoryQuery};
 use wasm_bindgen_futures::spawn_local;
 use yewdux::prelude::*;
 
-use super::super::{actions::WalletAction, state::WalletStore};
+use super::super::{actions::WalletAction, balance_api, state::WalletStore};
 
+/// Asks the backend for a fresh balance. The backend rate-limits reads from the
+/// chain, so this is cheap to call whenever an account is shown.
 pub fn spawn_refresh_balance(address: String, dispatch: Dispatch<WalletStore>) {
     spawn_local(async move {
-        let provider = Provider::<Http>::try_from("http://localhost:8545")
-            .expect("could not instantiate HTTP Provider");
-        match provider
-            .get_balance(address.parse::<Address>().unwrap(), None)
-            .await
-        {
+        match balance_api::get_balance(&address, true).await {
             Ok(balance) => {
-                console::log!(format!("Balance: {}", balance));
-                let balance_eth =
-                    Decimal::from_u128(balance.as_u128()).unwrap() / Decimal::from(10u64.pow(18));
-                console::log!(format!("Balance: {}", balance_eth));
-                dispatch.apply(WalletAction::UpdateBalance(balance_eth));
-                console::log!(format!("Balance updated: {} ETH", balance_eth));
+                console::log!(format!("Balance updated: {} ETH", balance.balance));
+                dispatch.apply(WalletAction::UpdateBalance(balance));
             }
             Err(e) => {
                 dispatch.apply(WalletAction::SetError(WalletError {
@@ -36,3 +24,17 @@ pub fn spawn_refresh_balance(address: String, dispatch: Dispatch<WalletStore>) {
         }
     });
 }
+
+pub fn spawn_load_balance_history(query: BalanceHistoryQuery, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match balance_api::get_balance_history(&query).await {
+            Ok(history) => dispatch.apply(WalletAction::SetBalanceHistory(history)),
+            Err(e) => {
+                dispatch.apply(WalletAction::SetError(WalletError {
+                    message: format!("Failed to load balance history: {}", e),
+                    timestamp: Utc::now(),
+                }));
+            }
+        }
+    });
+}
@@ -0,0 +1,78 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateScheduledPayment, SubmitScheduledPaymentRun};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, scheduled_payments_api, state::WalletStore};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+/// Loads the user's scheduled payments and their recent runs.
+pub fn spawn_load_scheduled_payments(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match scheduled_payments_api::list_scheduled_payments().await {
+            Ok(payments) => dispatch.apply(WalletAction::SetScheduledPayments(payments)),
+            Err(e) => console::error!(format!("Failed to load scheduled payments: {}", e)),
+        }
+        match scheduled_payments_api::list_runs().await {
+            Ok(runs) => dispatch.apply(WalletAction::SetScheduledRuns(runs)),
+            Err(e) => console::error!(format!("Failed to load scheduled payment runs: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_create_scheduled_payment(
+    payment: CreateScheduledPayment,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match scheduled_payments_api::create_scheduled_payment(&payment).await {
+            Ok(payment) => dispatch.apply(WalletAction::ScheduledPaymentSaved(payment)),
+            Err(e) => set_error(&dispatch, format!("Failed to schedule payment: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_cancel_scheduled_payment(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match scheduled_payments_api::cancel_scheduled_payment(id).await {
+            Ok(payment) => dispatch.apply(WalletAction::ScheduledPaymentSaved(payment)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to cancel scheduled payment: {}", e),
+            ),
+        }
+    });
+}
+
+/// Tells the server which send the app signed for a run.
+pub fn spawn_submit_scheduled_run(id: Uuid, tx_hash: String, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match scheduled_payments_api::submit_run(id, &SubmitScheduledPaymentRun { tx_hash }).await {
+            Ok(run) => dispatch.apply(WalletAction::ScheduledRunSaved(run)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to record scheduled payment: {}", e),
+            ),
+        }
+    });
+}
+
+pub fn spawn_skip_scheduled_run(id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match scheduled_payments_api::skip_run(id).await {
+            Ok(run) => dispatch.apply(WalletAction::ScheduledRunSaved(run)),
+            Err(e) => set_error(
+                &dispatch,
+                format!("Failed to skip scheduled payment: {}", e),
+            ),
+        }
+    });
+}
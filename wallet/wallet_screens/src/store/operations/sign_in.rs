@@ -0,0 +1,60 @@
+use auth_siwe_screens::api as siwe_api;
+use chrono::Utc;
+use ethers::types::Address;
+use gloo_console as console;
+use wallet_api::wallet::WalletError;
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, state::WalletStore};
+use crate::services::signing::{sign, SignPayload};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+pub fn spawn_load_sign_in_addresses(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match siwe_api::list_addresses().await {
+            Ok(addresses) => dispatch.apply(WalletAction::SetSignInAddresses(addresses)),
+            Err(e) => console::error!(format!("Failed to load sign-in addresses: {}", e)),
+        }
+    });
+}
+
+/// Signs a Sign-In With Ethereum message with the account's key, which proves
+/// to the server that the user holds it, and links the address.
+pub fn spawn_link_sign_in_address(
+    address: Address,
+    private_key: String,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        let linked = async {
+            let nonce = siwe_api::get_nonce().await?;
+            let origin = web_sys::window()
+                .and_then(|window| window.location().origin().ok())
+                .unwrap_or_default();
+            let message = siwe_api::sign_in_message(&nonce, address, origin).to_string();
+            let signature = sign(&private_key, &SignPayload::Message(message.clone()))?;
+            siwe_api::link_address(&auth_siwe_api::AuthSiweSignIn { message, signature }).await
+        }
+        .await;
+        match linked {
+            Ok(address) => dispatch.apply(WalletAction::SignInAddressLinked(address)),
+            Err(e) => set_error(&dispatch, format!("Failed to link address: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_unlink_sign_in_address(address: String, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match siwe_api::unlink_address(&address).await {
+            Ok(()) => dispatch.apply(WalletAction::SignInAddressUnlinked(address)),
+            Err(e) => set_error(&dispatch, format!("Failed to unlink address: {}", e)),
+        }
+    });
+}
@@ -0,0 +1,136 @@
+//! Subscription to `GET /api/v1/wallet/events`. The browser's `EventSource`
+//! reconnects by itself after network errors; when it gives up, for instance
+//! because the session expired, a new one is opened with an increasing delay.
+
+#[cfg(target_arch = "wasm32")]
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+#[cfg(target_arch = "wasm32")]
+use gloo_console as console;
+#[cfg(target_arch = "wasm32")]
+use gloo_timers::callback::Timeout;
+#[cfg(target_arch = "wasm32")]
+use wallet_api::WalletEvent;
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen::{closure::Closure, JsCast};
+#[cfg(target_arch = "wasm32")]
+use web_sys::{Event, EventSource, EventSourceInit, MessageEvent};
+use yewdux::prelude::*;
+
+#[cfg(target_arch = "wasm32")]
+use super::super::actions::WalletAction;
+use super::super::state::WalletStore;
+
+#[cfg(target_arch = "wasm32")]
+const EVENTS_URL: &str = "http://localhost:8080/api/v1/wallet/events";
+#[cfg(target_arch = "wasm32")]
+const INITIAL_RETRY_DELAY_MS: u32 = 1_000;
+#[cfg(target_arch = "wasm32")]
+const MAX_RETRY_DELAY_MS: u32 = 60_000;
+
+#[cfg(target_arch = "wasm32")]
+struct Subscription {
+    source: EventSource,
+    _on_open: Closure<dyn FnMut(Event)>,
+    _on_message: Closure<dyn FnMut(MessageEvent)>,
+    _on_error: Closure<dyn FnMut(Event)>,
+}
+
+#[cfg(target_arch = "wasm32")]
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        self.source.close();
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+thread_local! {
+    static SUBSCRIPTION: RefCell<Option<Subscription>> = const { RefCell::new(None) };
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn spawn_subscribe_events(_dispatch: Dispatch<WalletStore>) {}
+
+/// Subscribes the store to the wallet's events, replacing any previous
+/// subscription.
+#[cfg(target_arch = "wasm32")]
+pub fn spawn_subscribe_events(dispatch: Dispatch<WalletStore>) {
+    connect(dispatch, INITIAL_RETRY_DELAY_MS);
+}
+
+#[cfg(target_arch = "wasm32")]
+fn connect(dispatch: Dispatch<WalletStore>, retry_delay: u32) {
+    let init = EventSourceInit::new();
+    init.set_with_credentials(true);
+    let source = match EventSource::new_with_event_source_init_dict(EVENTS_URL, &init) {
+        Ok(source) => source,
+        Err(e) => {
+            console::error!("Failed to subscribe to wallet events", e);
+            return;
+        }
+    };
+
+    // Events sent while disconnected are lost, so catch up on the balance
+    let opened = Rc::new(Cell::new(false));
+    let on_open = {
+        let dispatch = dispatch.clone();
+        let opened = opened.clone();
+        Closure::<dyn FnMut(Event)>::new(move |_| {
+            opened.set(true);
+            dispatch.apply(WalletAction::RefreshBalance);
+        })
+    };
+
+    let on_message = {
+        let dispatch = dispatch.clone();
+        Closure::<dyn FnMut(MessageEvent)>::new(move |message: MessageEvent| {
+            let Some(data) = message.data().as_string() else {
+                return;
+            };
+            match serde_json::from_str::<WalletEvent>(&data) {
+                Ok(event) => dispatch.apply(WalletAction::ReceiveEvent(event)),
+                Err(e) => console::error!(format!("Malformed wallet event: {}", e)),
+            }
+        })
+    };
+
+    let on_error = {
+        let source = source.clone();
+        Closure::<dyn FnMut(Event)>::new(move |_| {
+            if source.ready_state() != EventSource::CLOSED {
+                return;
+            }
+            // Back off only while connecting keeps failing
+            let retry_delay = if opened.get() {
+                INITIAL_RETRY_DELAY_MS
+            } else {
+                retry_delay
+            };
+            console::warn!(format!(
+                "Wallet events disconnected, reconnecting in {} ms",
+                retry_delay
+            ));
+            let dispatch = dispatch.clone();
+            Timeout::new(retry_delay, move || {
+                connect(dispatch, (retry_delay * 2).min(MAX_RETRY_DELAY_MS));
+            })
+            .forget();
+        })
+    };
+
+    source.set_onopen(Some(on_open.as_ref().unchecked_ref()));
+    source.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    source.set_onerror(Some(on_error.as_ref().unchecked_ref()));
+
+    SUBSCRIPTION.with(|subscription| {
+        subscription.replace(Some(Subscription {
+            source,
+            _on_open: on_open,
+            _on_message: on_message,
+            _on_error: on_error,
+        }));
+    });
+}
@@ -0,0 +1,73 @@
+use chrono::Utc;
+use gloo_console as console;
+use wallet_api::{
+    wallet::WalletError, RecordTransaction, TransactionExportQuery, TransactionQuery,
+};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, state::WalletStore, transactions_api};
+use crate::services::browser::download_text;
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+/// Name of the downloaded statement, e.g. `transactions-2024-01-01-to-2024-03-31.csv`.
+fn export_filename(query: &TransactionExportQuery) -> String {
+    let from = query
+        .from
+        .map(|from| from.to_string())
+        .unwrap_or_else(|| "start".to_string());
+    let to = query
+        .to
+        .unwrap_or_else(|| Utc::now().date_naive())
+        .to_string();
+    format!(
+        "transactions-{}-to-{}.{}",
+        from,
+        to,
+        query.format.extension()
+    )
+}
+
+pub fn spawn_load_transactions(query: TransactionQuery, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match transactions_api::query_transactions(&query).await {
+            Ok(page) => dispatch.apply(WalletAction::SetTransactionPage { query, page }),
+            Err(e) => set_error(&dispatch, format!("Failed to load transactions: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_record_transaction(transaction: RecordTransaction, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match transactions_api::record_transaction(&transaction).await {
+            Ok(transaction) => dispatch.apply(WalletAction::TransactionSaved(transaction)),
+            Err(e) => console::error!(format!("Failed to record transaction: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_export_transactions(query: TransactionExportQuery, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match transactions_api::export_transactions(&query).await {
+            Ok(contents) => {
+                if let Err(e) = download_text(
+                    &export_filename(&query),
+                    &contents,
+                    query.format.content_type(),
+                ) {
+                    set_error(
+                        &dispatch,
+                        format!("Failed to download transactions: {:?}", e),
+                    );
+                }
+            }
+            Err(e) => set_error(&dispatch, format!("Failed to export transactions: {}", e)),
+        }
+    });
+}
@@ -0,0 +1,85 @@
+use chrono::Utc;
+use gloo_console as console;
+use uuid::Uuid;
+use wallet_api::{wallet::WalletError, CreateTreasury, CreateTreasuryProposal};
+use wasm_bindgen_futures::spawn_local;
+use yewdux::prelude::*;
+
+use super::super::{actions::WalletAction, state::WalletStore, treasury_api};
+
+fn set_error(dispatch: &Dispatch<WalletStore>, message: String) {
+    dispatch.apply(WalletAction::SetError(WalletError {
+        message,
+        timestamp: Utc::now(),
+    }));
+}
+
+/// Reloads a treasury's audit trail, which every change adds to.
+async fn load_audit_log(treasury_id: Uuid, dispatch: &Dispatch<WalletStore>) {
+    match treasury_api::audit_log(treasury_id).await {
+        Ok(entries) => dispatch.apply(WalletAction::SetTreasuryAudit(entries)),
+        Err(e) => console::error!(format!("Failed to load treasury audit trail: {}", e)),
+    }
+}
+
+pub fn spawn_load_treasuries(dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match treasury_api::list_treasuries().await {
+            Ok(treasuries) => dispatch.apply(WalletAction::SetTreasuries(treasuries)),
+            Err(e) => console::error!(format!("Failed to load treasuries: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_create_treasury(treasury: CreateTreasury, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match treasury_api::create_treasury(&treasury).await {
+            Ok(treasury) => dispatch.apply(WalletAction::TreasurySaved(treasury)),
+            Err(e) => set_error(&dispatch, format!("Failed to create treasury: {}", e)),
+        }
+    });
+}
+
+pub fn spawn_load_treasury(treasury_id: Uuid, dispatch: Dispatch<WalletStore>) {
+    spawn_local(async move {
+        match treasury_api::list_proposals(treasury_id).await {
+            Ok(proposals) => dispatch.apply(WalletAction::SetTreasuryProposals(proposals)),
+            Err(e) => set_error(&dispatch, format!("Failed to load proposals: {}", e)),
+        }
+        load_audit_log(treasury_id, &dispatch).await;
+    });
+}
+
+pub fn spawn_propose_treasury_send(
+    treasury_id: Uuid,
+    proposal: CreateTreasuryProposal,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match treasury_api::create_proposal(treasury_id, &proposal).await {
+            Ok(proposal) => {
+                dispatch.apply(WalletAction::TreasuryProposalSaved(proposal));
+                load_audit_log(treasury_id, &dispatch).await;
+            }
+            Err(e) => set_error(&dispatch, format!("Failed to propose send: {}", e)),
+        }
+    });
+}
+
+/// Approves, rejects or executes a proposal.
+pub fn spawn_act_on_treasury_proposal(
+    id: Uuid,
+    action: &'static str,
+    dispatch: Dispatch<WalletStore>,
+) {
+    spawn_local(async move {
+        match treasury_api::act_on_proposal(id, action).await {
+            Ok(proposal) => {
+                let treasury_id = proposal.treasury_id;
+                dispatch.apply(WalletAction::TreasuryProposalSaved(proposal));
+                load_audit_log(treasury_id, &dispatch).await;
+            }
+            Err(e) => set_error(&dispatch, format!("Failed to {} proposal: {}", action, e)),
+        }
+    });
+}
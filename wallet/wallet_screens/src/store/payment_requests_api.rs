@@ -0,0 +1,83 @@
+//! Client for the `/api/v1/wallet/payment-requests` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use uuid::Uuid;
+use wallet_api::{CreatePaymentRequest, PaymentRequest};
+
+#[cfg(target_arch = "wasm32")]
+const PAYMENT_REQUESTS_URL: &str = "http://localhost:8080/api/v1/wallet/payment-requests";
+
+/// Reads the saved request, or the server's reason for rejecting it.
+#[cfg(target_arch = "wasm32")]
+async fn saved_request(res: Response, fallback: &str) -> Result<PaymentRequest, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_payment_requests() -> Result<Vec<PaymentRequest>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_payment_requests() -> Result<Vec<PaymentRequest>, anyhow::Error> {
+    let res = Client::new()
+        .get(PAYMENT_REQUESTS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(res.json().await?)
+    } else {
+        Err(anyhow::anyhow!("Failed to load payment requests"))
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_payment_request(
+    _request: &CreatePaymentRequest,
+) -> Result<PaymentRequest, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_payment_request(
+    request: &CreatePaymentRequest,
+) -> Result<PaymentRequest, anyhow::Error> {
+    let res = Client::new()
+        .post(PAYMENT_REQUESTS_URL)
+        .json(request)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    saved_request(res, "Failed to create payment request").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn cancel_payment_request(_id: Uuid) -> Result<PaymentRequest, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn cancel_payment_request(id: Uuid) -> Result<PaymentRequest, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/cancel", PAYMENT_REQUESTS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    saved_request(res, "Failed to cancel payment request").await
+}
@@ -0,0 +1,149 @@
+//! Client for the `/api/v1/wallet/policy` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use uuid::Uuid;
+use wallet_api::{
+    AllowedRecipient, CreateAllowedRecipient, PolicyOverview, SpendCheck, SpendingPolicy,
+};
+
+#[cfg(target_arch = "wasm32")]
+const POLICY_URL: &str = "http://localhost:8080/api/v1/wallet/policy";
+
+/// Reads a successful response, or the server's reason for rejecting the
+/// request, such as an expired login or a policy violation.
+#[cfg(target_arch = "wasm32")]
+async fn read<T: serde::de::DeserializeOwned>(
+    res: Response,
+    fallback: &str,
+) -> Result<T, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+    Err(rejection(res, fallback).await)
+}
+
+#[cfg(target_arch = "wasm32")]
+async fn rejection(res: Response, fallback: &str) -> anyhow::Error {
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    anyhow::anyhow!(message)
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn get_policy() -> Result<PolicyOverview, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn get_policy() -> Result<PolicyOverview, anyhow::Error> {
+    let res = Client::new()
+        .get(POLICY_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load spending policy").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn update_policy(_policy: &SpendingPolicy) -> Result<PolicyOverview, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn update_policy(policy: &SpendingPolicy) -> Result<PolicyOverview, anyhow::Error> {
+    let res = Client::new()
+        .put(POLICY_URL)
+        .json(policy)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to update spending policy").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn cancel_policy_change() -> Result<PolicyOverview, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn cancel_policy_change() -> Result<PolicyOverview, anyhow::Error> {
+    let res = Client::new()
+        .delete(format!("{}/pending", POLICY_URL))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to cancel policy change").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn add_allowed_recipient(
+    _recipient: &CreateAllowedRecipient,
+) -> Result<AllowedRecipient, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn add_allowed_recipient(
+    recipient: &CreateAllowedRecipient,
+) -> Result<AllowedRecipient, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/recipients", POLICY_URL))
+        .json(recipient)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to allow-list recipient").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn remove_allowed_recipient(_id: Uuid) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn remove_allowed_recipient(id: Uuid) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .delete(format!("{}/recipients/{}", POLICY_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(rejection(res, "Failed to remove recipient").await)
+    }
+}
+
+/// Asks the server whether the user's spending policy allows a send. The
+/// error carries the reason when it does not.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn check_spend(_send: &SpendCheck) -> Result<(), anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn check_spend(send: &SpendCheck) -> Result<(), anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/check", POLICY_URL))
+        .json(send)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        Ok(())
+    } else {
+        Err(rejection(res, "Failed to check spending policy").await)
+    }
+}
@@ -0,0 +1,143 @@
+//! Client for the `/api/v1/wallet/scheduled-payments` and
+//! `/api/v1/wallet/scheduled-payment-runs` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use uuid::Uuid;
+use wallet_api::{
+    CreateScheduledPayment, ScheduledPayment, ScheduledPaymentRun, SubmitScheduledPaymentRun,
+};
+
+#[cfg(target_arch = "wasm32")]
+const SCHEDULED_PAYMENTS_URL: &str = "http://localhost:8080/api/v1/wallet/scheduled-payments";
+#[cfg(target_arch = "wasm32")]
+const RUNS_URL: &str = "http://localhost:8080/api/v1/wallet/scheduled-payment-runs";
+
+/// Reads a successful response, or the server's reason for rejecting the
+/// request, such as a schedule that runs too often.
+#[cfg(target_arch = "wasm32")]
+async fn read<T: serde::de::DeserializeOwned>(
+    res: Response,
+    fallback: &str,
+) -> Result<T, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_scheduled_payments() -> Result<Vec<ScheduledPayment>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_scheduled_payments() -> Result<Vec<ScheduledPayment>, anyhow::Error> {
+    let res = Client::new()
+        .get(SCHEDULED_PAYMENTS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load scheduled payments").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_scheduled_payment(
+    _payment: &CreateScheduledPayment,
+) -> Result<ScheduledPayment, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_scheduled_payment(
+    payment: &CreateScheduledPayment,
+) -> Result<ScheduledPayment, anyhow::Error> {
+    let res = Client::new()
+        .post(SCHEDULED_PAYMENTS_URL)
+        .json(payment)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to schedule payment").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn cancel_scheduled_payment(_id: Uuid) -> Result<ScheduledPayment, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn cancel_scheduled_payment(id: Uuid) -> Result<ScheduledPayment, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/cancel", SCHEDULED_PAYMENTS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to cancel scheduled payment").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_runs() -> Result<Vec<ScheduledPaymentRun>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_runs() -> Result<Vec<ScheduledPaymentRun>, anyhow::Error> {
+    let res = Client::new()
+        .get(RUNS_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load scheduled payment runs").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn submit_run(
+    _id: Uuid,
+    _submit: &SubmitScheduledPaymentRun,
+) -> Result<ScheduledPaymentRun, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn submit_run(
+    id: Uuid,
+    submit: &SubmitScheduledPaymentRun,
+) -> Result<ScheduledPaymentRun, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/submit", RUNS_URL, id))
+        .json(submit)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to record scheduled payment").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn skip_run(_id: Uuid) -> Result<ScheduledPaymentRun, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn skip_run(id: Uuid) -> Result<ScheduledPaymentRun, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/skip", RUNS_URL, id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to skip scheduled payment").await
+}
@@ -1,22 +1,109 @@
+use std::collections::HashMap;
+
+use auth_siwe_api::SiweAddress;
 use serde::{Deserialize, Serialize};
+use uuid::Uuid;
 use wallet_api::{
-    transaction::Transaction as ApiTransaction,
-    wallet::{Wallet, WalletError},
+    account::{Account, CustodyStatus},
+    address::same_address,
+    contact::Contact,
+    contract::SavedContract,
+    notification::Notification,
+    payment_request::PaymentRequest,
+    policy::PolicyOverview,
+    scheduled_payment::{ScheduledPayment, ScheduledPaymentRun},
+    transaction::{Transaction as ApiTransaction, TransactionQuery},
+    treasury::{TreasuryAuditEntry, TreasuryOverview, TreasuryProposal},
+    wallet::{BalanceHistory, Wallet, WalletError},
 };
 use yewdux::prelude::*;
 #[cfg(target_arch = "wasm32")]
 use yewdux::{init_listener, storage, Listener};
 
 use crate::{
-    services::transactions::status::TransactionStatus, store::wallet_load_save::SaveableWallet,
+    services::transactions::{NonceManager, PendingTransaction},
+    store::wallet_load_save::SaveableWallet,
 };
 
 #[derive(Clone, PartialEq, Serialize, Deserialize, Default)]
 pub struct WalletStore {
     pub last_error: Option<WalletError>,
     pub wallet: Option<Wallet>,
+    #[serde(default)]
+    pub accounts: Vec<Account>,
+    #[serde(default)]
+    pub selected_account: Option<Uuid>,
+    /// Whether the server can hold keys for custodial accounts.
+    #[serde(skip)]
+    pub custody: CustodyStatus,
+    /// The page of transactions `transaction_query` asked for.
     pub transactions: Vec<ApiTransaction>,
-    pub transaction_status: TransactionStatus,
+    #[serde(skip)]
+    pub transaction_query: TransactionQuery,
+    /// Transactions on all pages of `transaction_query`.
+    #[serde(skip)]
+    pub transactions_total: i64,
+    /// Cursors to the older and newer pages.
+    #[serde(skip)]
+    pub transactions_next: Option<String>,
+    #[serde(skip)]
+    pub transactions_previous: Option<String>,
+    /// Sends of this session. Not kept across reloads, as the nonces they
+    /// reserved in `nonce_manager` are not either; the server's history still
+    /// lists them.
+    #[serde(skip)]
+    pub pending_transactions: Vec<PendingTransaction>,
+    #[serde(default)]
+    pub contacts: Vec<Contact>,
+    /// Loaded when the contracts screen opens, as ABIs can be large.
+    #[serde(skip)]
+    pub contracts: Vec<SavedContract>,
+    /// Newest first.
+    #[serde(default)]
+    pub payment_requests: Vec<PaymentRequest>,
+    /// Loaded from the server each session, as it is the server that
+    /// enforces it.
+    #[serde(skip)]
+    pub spending_policy: Option<PolicyOverview>,
+    /// Balance history of the selected account, for the dashboard chart.
+    #[serde(skip)]
+    pub balance_history: Option<BalanceHistory>,
+    /// Newest first.
+    #[serde(skip)]
+    pub scheduled_payments: Vec<ScheduledPayment>,
+    /// Recent runs of the scheduled payments, newest first.
+    #[serde(skip)]
+    pub scheduled_runs: Vec<ScheduledPaymentRun>,
+    /// Treasuries the user signs for; only loaded for staff.
+    #[serde(skip)]
+    pub treasuries: Vec<TreasuryOverview>,
+    /// Proposals of the treasury open on the treasury screen, newest first.
+    #[serde(skip)]
+    pub treasury_proposals: Vec<TreasuryProposal>,
+    /// Audit trail of the open treasury, newest first.
+    #[serde(skip)]
+    pub treasury_audit: Vec<TreasuryAuditEntry>,
+    /// Addresses the user can sign in with.
+    #[serde(skip)]
+    pub sign_in_addresses: Vec<SiweAddress>,
+    #[serde(default)]
+    pub notifications: Vec<Notification>,
+    /// Notifications received while the app is open that have not been shown
+    /// as a toast yet.
+    #[serde(skip)]
+    pub pending_toasts: Vec<Notification>,
+    /// ENS names of addresses, keyed by lowercase address. `None` while the
+    /// lookup is running or when the address has no name.
+    #[serde(skip)]
+    pub ens_names: HashMap<String, Option<String>>,
+    /// Addresses ENS names resolved to, or why they did not resolve.
+    #[serde(skip)]
+    pub ens_addresses: HashMap<String, Result<String, String>>,
+    /// The last signature made on the sign screen, or why signing failed.
+    #[serde(skip)]
+    pub signature: Option<Result<String, String>>,
+    #[serde(skip)]
+    pub nonce_manager: NonceManager,
     #[serde(skip)]
     pub dispatcher: Option<Dispatch<WalletStore>>,
 }
@@ -29,18 +116,75 @@ impl Listener for StorageListener {
     type Store = WalletStore;
 
     fn on_change(&mut self, _cx: &yewdux::Context, state: std::rc::Rc<Self::Store>) {
+        // The backend copy of the wallet is only written when it is created or
+        // its key is rotated, never as a side effect of local state changes.
         if let Err(err) = storage::save(state.as_ref(), storage::Area::Local) {
             log::error!("Error saving state to storage: {:?}", err);
         }
+    }
+}
 
-        if let Some(wallet) = &state.wallet {
-            let wallet = wallet.clone();
-            wasm_bindgen_futures::spawn_local(async move {
-                if let Err(e) = wallet.save().await {
-                    log::error!("Error saving wallet to backend: {:?}", e);
-                }
-            });
-        }
+impl WalletStore {
+    pub fn unread_notifications(&self) -> usize {
+        self.notifications
+            .iter()
+            .filter(|notification| !notification.is_read())
+            .count()
+    }
+
+    /// The saved contact for an address, if any.
+    pub fn contact_for(&self, address: &str) -> Option<&Contact> {
+        self.contacts
+            .iter()
+            .find(|contact| same_address(&contact.address, address))
+    }
+
+    /// A name for `address`: the contact's name, the label of one of the
+    /// user's own accounts, or its ENS name.
+    pub fn name_for(&self, address: &str) -> Option<&str> {
+        self.contact_for(address)
+            .map(|contact| contact.name.as_str())
+            .or_else(|| {
+                self.accounts
+                    .iter()
+                    .find(|account| same_address(&account.address, address))
+                    .map(|account| account.label.as_str())
+            })
+            .or_else(|| self.ens_name(address))
+    }
+
+    pub fn ens_name(&self, address: &str) -> Option<&str> {
+        self.ens_names
+            .get(&address.to_lowercase())
+            .and_then(|name| name.as_deref())
+    }
+
+    /// Whether the user has dealt with `address` before: it is a contact, one
+    /// of their own accounts, or appears in their transactions.
+    pub fn is_known_recipient(&self, address: &str) -> bool {
+        self.contact_for(address).is_some()
+            || self
+                .accounts
+                .iter()
+                .any(|account| same_address(&account.address, address))
+            || self
+                .transactions
+                .iter()
+                .filter(|tx| tx.is_outgoing())
+                .filter_map(|tx| tx.recipient.as_deref())
+                .any(|recipient| same_address(recipient, address))
+            || self
+                .pending_transactions
+                .iter()
+                .any(|pending| same_address(&format!("{:?}", pending.to), address))
+    }
+
+    /// The account the dashboard and transfer screens operate on. Falls back to
+    /// the first account when none has been picked.
+    pub fn selected_account(&self) -> Option<&Account> {
+        self.selected_account
+            .and_then(|id| self.accounts.iter().find(|account| account.id == id))
+            .or_else(|| self.accounts.first())
     }
 }
 
@@ -51,8 +195,32 @@ impl Store for WalletStore {
         let store = Self {
             last_error: None,
             wallet: None,
+            accounts: Vec::new(),
+            selected_account: None,
+            custody: CustodyStatus::default(),
             transactions: Vec::new(),
-            transaction_status: TransactionStatus::default(),
+            transaction_query: TransactionQuery::default(),
+            transactions_total: 0,
+            transactions_next: None,
+            transactions_previous: None,
+            pending_transactions: Vec::new(),
+            contacts: Vec::new(),
+            contracts: Vec::new(),
+            payment_requests: Vec::new(),
+            spending_policy: None,
+            balance_history: None,
+            scheduled_payments: Vec::new(),
+            scheduled_runs: Vec::new(),
+            treasuries: Vec::new(),
+            treasury_proposals: Vec::new(),
+            treasury_audit: Vec::new(),
+            sign_in_addresses: Vec::new(),
+            notifications: Vec::new(),
+            pending_toasts: Vec::new(),
+            ens_names: HashMap::new(),
+            ens_addresses: HashMap::new(),
+            signature: None,
+            nonce_manager: NonceManager::default(),
             dispatcher: Some(dispatcher.clone()),
         };
 
@@ -60,10 +228,14 @@ impl Store for WalletStore {
         wasm_bindgen_futures::spawn_local(async move {
             if let Ok(wallet) = Wallet::load().await {
                 dispatch.apply(super::actions::WalletAction::SetWallet(wallet));
+                dispatch.apply(super::actions::WalletAction::LoadAccounts);
+                dispatch.apply(super::actions::WalletAction::LoadNotifications);
+                dispatch.apply(super::actions::WalletAction::LoadContacts);
             } else {
                 dispatch.apply(super::actions::WalletAction::RemoveWallet);
             }
         });
+        super::spawn_subscribe_events(dispatcher);
 
         store
     }
@@ -78,8 +250,32 @@ impl Store for WalletStore {
             .unwrap_or(Self {
                 last_error: None,
                 wallet: None,
+                accounts: Vec::new(),
+                selected_account: None,
+                custody: CustodyStatus::default(),
                 transactions: Vec::new(),
-                transaction_status: TransactionStatus::default(),
+                transaction_query: TransactionQuery::default(),
+                transactions_total: 0,
+                transactions_next: None,
+                transactions_previous: None,
+                pending_transactions: Vec::new(),
+                contacts: Vec::new(),
+                contracts: Vec::new(),
+                payment_requests: Vec::new(),
+                spending_policy: None,
+                balance_history: None,
+                scheduled_payments: Vec::new(),
+                scheduled_runs: Vec::new(),
+                treasuries: Vec::new(),
+                treasury_proposals: Vec::new(),
+                treasury_audit: Vec::new(),
+                sign_in_addresses: Vec::new(),
+                notifications: Vec::new(),
+                pending_toasts: Vec::new(),
+                ens_names: HashMap::new(),
+                ens_addresses: HashMap::new(),
+                signature: None,
+                nonce_manager: NonceManager::default(),
                 dispatcher: None,
             });
         let dispatcher = Dispatch::<WalletStore>::new(ctx);
@@ -89,8 +285,12 @@ impl Store for WalletStore {
         wasm_bindgen_futures::spawn_local(async move {
             if let Ok(wallet) = Wallet::load().await {
                 dispatch.apply(super::actions::WalletAction::SetWallet(wallet));
+                dispatch.apply(super::actions::WalletAction::LoadAccounts);
+                dispatch.apply(super::actions::WalletAction::LoadNotifications);
+                dispatch.apply(super::actions::WalletAction::LoadContacts);
             }
         });
+        super::spawn_subscribe_events(dispatcher);
 
         ret
     }
@@ -0,0 +1,142 @@
+//! Client for the `/api/v1/wallet/transactions` endpoints.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::Client;
+use wallet_api::{
+    BroadcastResult, BroadcastTransaction, RecordTransaction, Transaction, TransactionExportQuery,
+    TransactionPage, TransactionQuery,
+};
+
+#[cfg(target_arch = "wasm32")]
+const TRANSACTIONS_URL: &str = "http://localhost:8080/api/v1/wallet/transactions";
+#[cfg(target_arch = "wasm32")]
+const TRANSACTIONS_BROADCAST_URL: &str =
+    "http://localhost:8080/api/v1/wallet/transactions/broadcast";
+#[cfg(target_arch = "wasm32")]
+const TRANSACTIONS_EXPORT_URL: &str = "http://localhost:8080/api/v1/wallet/transactions/export";
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn query_transactions(
+    _query: &TransactionQuery,
+) -> Result<TransactionPage, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn query_transactions(
+    query: &TransactionQuery,
+) -> Result<TransactionPage, anyhow::Error> {
+    let res = Client::new()
+        .get(TRANSACTIONS_URL)
+        .query(query)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to load transactions".to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+/// Records a send made by the app, or its new status.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn record_transaction(
+    _transaction: &RecordTransaction,
+) -> Result<Transaction, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+/// Records a send made by the app, or its new status.
+#[cfg(target_arch = "wasm32")]
+pub async fn record_transaction(
+    transaction: &RecordTransaction,
+) -> Result<Transaction, anyhow::Error> {
+    let res = Client::new()
+        .post(TRANSACTIONS_URL)
+        .json(transaction)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to record transaction".to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+/// Hands a signed transaction to the server, which checks it against the
+/// user's spending policy and relays it to the node.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn broadcast_transaction(
+    _transaction: &BroadcastTransaction,
+) -> Result<BroadcastResult, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+/// Hands a signed transaction to the server, which checks it against the
+/// user's spending policy and relays it to the node.
+#[cfg(target_arch = "wasm32")]
+pub async fn broadcast_transaction(
+    transaction: &BroadcastTransaction,
+) -> Result<BroadcastResult, anyhow::Error> {
+    let res = Client::new()
+        .post(TRANSACTIONS_BROADCAST_URL)
+        .json(transaction)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to broadcast transaction".to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+/// Fetches a statement of the user's transactions as CSV or JSON text.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn export_transactions(_query: &TransactionExportQuery) -> Result<String, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+/// Fetches a statement of the user's transactions as CSV or JSON text.
+#[cfg(target_arch = "wasm32")]
+pub async fn export_transactions(query: &TransactionExportQuery) -> Result<String, anyhow::Error> {
+    let res = Client::new()
+        .get(TRANSACTIONS_EXPORT_URL)
+        .query(query)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    if res.status().is_success() {
+        return Ok(res.text().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to export transactions".to_string());
+    Err(anyhow::anyhow!(message))
+}
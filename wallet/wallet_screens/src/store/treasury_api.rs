@@ -0,0 +1,143 @@
+//! Client for the `/api/v1/wallet/treasuries` and
+//! `/api/v1/wallet/treasury-proposals` endpoints, which only staff can use.
+
+#[cfg(target_arch = "wasm32")]
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
+use uuid::Uuid;
+use wallet_api::{
+    CreateTreasury, CreateTreasuryProposal, TreasuryAuditEntry, TreasuryOverview, TreasuryProposal,
+};
+
+#[cfg(target_arch = "wasm32")]
+const TREASURIES_URL: &str = "http://localhost:8080/api/v1/wallet/treasuries";
+#[cfg(target_arch = "wasm32")]
+const PROPOSALS_URL: &str = "http://localhost:8080/api/v1/wallet/treasury-proposals";
+
+/// Reads a successful response, or the server's reason for rejecting the
+/// request, such as a vote on a proposal that was already decided.
+#[cfg(target_arch = "wasm32")]
+async fn read<T: serde::de::DeserializeOwned>(
+    res: Response,
+    fallback: &str,
+) -> Result<T, anyhow::Error> {
+    if res.status().is_success() {
+        return Ok(res.json().await?);
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| fallback.to_string());
+    Err(anyhow::anyhow!(message))
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_treasuries() -> Result<Vec<TreasuryOverview>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_treasuries() -> Result<Vec<TreasuryOverview>, anyhow::Error> {
+    let res = Client::new()
+        .get(TREASURIES_URL)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load treasuries").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_treasury(
+    _treasury: &CreateTreasury,
+) -> Result<TreasuryOverview, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_treasury(treasury: &CreateTreasury) -> Result<TreasuryOverview, anyhow::Error> {
+    let res = Client::new()
+        .post(TREASURIES_URL)
+        .json(treasury)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to create treasury").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn list_proposals(_treasury_id: Uuid) -> Result<Vec<TreasuryProposal>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn list_proposals(treasury_id: Uuid) -> Result<Vec<TreasuryProposal>, anyhow::Error> {
+    let res = Client::new()
+        .get(format!("{}/{}/proposals", TREASURIES_URL, treasury_id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load proposals").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn create_proposal(
+    _treasury_id: Uuid,
+    _proposal: &CreateTreasuryProposal,
+) -> Result<TreasuryProposal, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn create_proposal(
+    treasury_id: Uuid,
+    proposal: &CreateTreasuryProposal,
+) -> Result<TreasuryProposal, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/proposals", TREASURIES_URL, treasury_id))
+        .json(proposal)
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to propose send").await
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn audit_log(_treasury_id: Uuid) -> Result<Vec<TreasuryAuditEntry>, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub async fn audit_log(treasury_id: Uuid) -> Result<Vec<TreasuryAuditEntry>, anyhow::Error> {
+    let res = Client::new()
+        .get(format!("{}/{}/audit", TREASURIES_URL, treasury_id))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, "Failed to load audit trail").await
+}
+
+/// Posts `action` (`approve`, `reject` or `execute`) for a proposal.
+#[cfg(not(target_arch = "wasm32"))]
+pub async fn act_on_proposal(_id: Uuid, _action: &str) -> Result<TreasuryProposal, anyhow::Error> {
+    Err(anyhow::anyhow!("Not implemented"))
+}
+
+/// Posts `action` (`approve`, `reject` or `execute`) for a proposal.
+#[cfg(target_arch = "wasm32")]
+pub async fn act_on_proposal(id: Uuid, action: &str) -> Result<TreasuryProposal, anyhow::Error> {
+    let res = Client::new()
+        .post(format!("{}/{}/{}", PROPOSALS_URL, id, action))
+        .fetch_credentials_include()
+        .send()
+        .await?;
+
+    read(res, &format!("Failed to {} proposal", action)).await
+}
@@ -1,37 +1,96 @@
 #[cfg(target_arch = "wasm32")]
-use reqwest::Client;
+use app_config::ErrorResponse;
+#[cfg(target_arch = "wasm32")]
+use reqwest::{Client, Response};
 use wallet_api::wallet::Wallet;
+#[cfg(target_arch = "wasm32")]
+use wallet_api::wallet::{CreateWallet, RotateWalletKey};
+
+#[derive(Debug, thiserror::Error)]
+pub enum SaveWalletError {
+    /// The user already has a wallet, which can only be replaced through a
+    /// key rotation.
+    #[error("{0}")]
+    Conflict(String),
+    #[error("{0}")]
+    Rejected(String),
+    #[error(transparent)]
+    Request(#[from] anyhow::Error),
+}
 
 #[async_trait::async_trait(?Send)]
 pub trait SaveableWallet {
-    async fn save(&self) -> Result<(), anyhow::Error>;
+    async fn create(&self) -> Result<Self, SaveWalletError>
+    where
+        Self: Sized;
+    async fn rotate_key(&self) -> Result<Self, SaveWalletError>
+    where
+        Self: Sized;
     async fn load() -> Result<Self, anyhow::Error>
     where
         Self: Sized;
 }
 
+#[cfg(target_arch = "wasm32")]
+async fn saved_wallet(res: Response) -> Result<Wallet, SaveWalletError> {
+    let status = res.status();
+    if status.is_success() {
+        return res
+            .json()
+            .await
+            .map_err(|e| SaveWalletError::Request(e.into()));
+    }
+
+    let message = res
+        .json::<ErrorResponse>()
+        .await
+        .map(|error| error.message)
+        .unwrap_or_else(|_| "Failed to save wallet".to_string());
+    if status == reqwest::StatusCode::CONFLICT {
+        Err(SaveWalletError::Conflict(message))
+    } else {
+        Err(SaveWalletError::Rejected(message))
+    }
+}
+
 #[async_trait::async_trait(?Send)]
 impl SaveableWallet for Wallet {
     #[cfg(not(target_arch = "wasm32"))]
-    async fn save(&self) -> Result<(), anyhow::Error> {
-        Err(anyhow::anyhow!("Not implemented"))
+    async fn create(&self) -> Result<Self, SaveWalletError> {
+        Err(anyhow::anyhow!("Not implemented").into())
     }
 
     #[cfg(target_arch = "wasm32")]
-    async fn save(&self) -> Result<(), anyhow::Error> {
+    async fn create(&self) -> Result<Self, SaveWalletError> {
         let client = Client::new();
         let res = client
-            .put("http://localhost:8080/api/v1/wallet")
-            .json(self)
+            .post("http://localhost:8080/api/v1/wallet")
+            .json(&CreateWallet::from(self))
             .fetch_credentials_include()
             .send()
-            .await?;
+            .await
+            .map_err(|e| SaveWalletError::Request(e.into()))?;
 
-        if res.status().is_success() {
-            Ok(())
-        } else {
-            Err(anyhow::anyhow!("Failed to save wallet"))
-        }
+        saved_wallet(res).await
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    async fn rotate_key(&self) -> Result<Self, SaveWalletError> {
+        Err(anyhow::anyhow!("Not implemented").into())
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    async fn rotate_key(&self) -> Result<Self, SaveWalletError> {
+        let client = Client::new();
+        let res = client
+            .post("http://localhost:8080/api/v1/wallet/rotate-key")
+            .json(&RotateWalletKey::from(self))
+            .fetch_credentials_include()
+            .send()
+            .await
+            .map_err(|e| SaveWalletError::Request(e.into()))?;
+
+        saved_wallet(res).await
     }
 
     #[cfg(not(target_arch = "wasm32"))]